@@ -24,7 +24,7 @@ fn main() {
     );
     emit_env(
         "SYN_SYU_FEATURES",
-        env_value("SYN_SYU_FEATURES").unwrap_or_else(|| "".into()),
+        env_value("SYN_SYU_FEATURES").unwrap_or_else(cargo_features),
     );
 
     for key in [
@@ -57,6 +57,19 @@ fn env_value(key: &str) -> Option<String> {
         .filter(|s| !s.is_empty())
 }
 
+/// Enabled Cargo features, comma-separated (e.g. `aur`).
+fn cargo_features() -> String {
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|name| name.to_lowercase().replace('_', "-"))
+        })
+        .filter(|name| name != "default")
+        .collect();
+    features.sort();
+    features.join(",")
+}
+
 fn git_commit() -> Option<String> {
     Command::new("git")
         .args(["rev-parse", "--short=12", "HEAD"])
@@ -0,0 +1,198 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::annotations
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Load operator annotations (`--annotations`, a TOML or JSON
+    mapping of package name to note) and append them to the
+    matching manifest entries.
+
+  Security / Safety Notes:
+    The file is only read; malformed content fails the run
+    before any backend is queried.
+
+  Dependencies:
+    toml and serde_json for parsing.
+
+  Operational Scope:
+    Loaded at startup, applied once the manifest is built so
+    operator notes sit after the resolution notes.
+
+  Revision History:
+    2026-10-18 COD  Introduced operator annotations.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Operator knowledge is added, never overwrites resolution output
+    - Format errors name the file and the offending key
+============================================================*/
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::error::{Result, SynsyuError};
+use crate::logger::Logger;
+use crate::manifest::ManifestDocument;
+
+/// Prefix marking a note as operator-supplied rather than resolution output.
+pub const NOTE_OPERATOR: &str = "operator:";
+
+/// Package name to the operator's notes (each list keeps its file order).
+pub type Annotations = BTreeMap<String, Vec<String>>;
+
+/// Read and validate an annotations file; `.json` is parsed as JSON, anything else as TOML.
+pub fn load_annotations(path: &Path) -> Result<Annotations> {
+    let text = fs::read_to_string(path).map_err(|err| {
+        SynsyuError::Filesystem(format!(
+            "Failed to read annotations {}: {err}",
+            path.display()
+        ))
+    })?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    parse_annotations(&text, is_json).map_err(|detail| {
+        SynsyuError::Config(format!(
+            "Invalid annotations file {}: {detail}",
+            path.display()
+        ))
+    })
+}
+
+fn parse_annotations(text: &str, is_json: bool) -> std::result::Result<Annotations, String> {
+    let value: Value = if is_json {
+        serde_json::from_str(text).map_err(|err| err.to_string())?
+    } else {
+        let table: toml::Table = toml::from_str(text).map_err(|err| err.to_string())?;
+        serde_json::to_value(table).map_err(|err| err.to_string())?
+    };
+    let Value::Object(map) = value else {
+        return Err("expected a mapping of package name to note".to_string());
+    };
+    let mut annotations = Annotations::new();
+    for (name, value) in map {
+        if name.trim().is_empty() {
+            return Err("package names must not be blank".to_string());
+        }
+        let notes = match value {
+            Value::String(note) => vec![note],
+            Value::Array(items) => items
+                .into_iter()
+                .map(|item| match item {
+                    Value::String(note) => Ok(note),
+                    _ => Err(format!("`{name}`: list entries must be strings")),
+                })
+                .collect::<std::result::Result<_, _>>()?,
+            _ => return Err(format!("`{name}`: expected a string or a list of strings")),
+        };
+        let notes: Vec<String> = notes
+            .into_iter()
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty())
+            .collect();
+        if notes.is_empty() {
+            return Err(format!("`{name}`: note is empty"));
+        }
+        annotations.insert(name, notes);
+    }
+    Ok(annotations)
+}
+
+/// Append each annotation to its entry's notes; returns how many entries were annotated.
+///
+/// Names missing from the manifest are logged (ANNOTATE) and skipped.
+pub fn apply_annotations(
+    document: &mut ManifestDocument,
+    annotations: &Annotations,
+    logger: &Logger,
+) -> usize {
+    let mut applied = 0;
+    for (name, notes) in annotations {
+        match document.packages.get_mut(name) {
+            Some(entry) => {
+                entry
+                    .notes
+                    .extend(notes.iter().map(|note| format!("{NOTE_OPERATOR} {note}")));
+                applied += 1;
+            }
+            None => logger.warn(
+                "ANNOTATE",
+                format!("Annotation for {name} skipped: package not in manifest"),
+            ),
+        }
+    }
+    applied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures::{document, entry};
+    use crate::manifest::{PackageSource, NOTE_AUR_NOT_FOUND};
+
+    #[test]
+    fn annotations_append_after_resolution_notes() {
+        let mut ghost = entry("1.0-1", PackageSource::Unknown, None);
+        ghost.notes.push(NOTE_AUR_NOT_FOUND.to_string());
+        let mut doc = document(vec![
+            ("ghost", ghost),
+            (
+                "bash",
+                entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1")),
+            ),
+        ]);
+        let annotations = parse_annotations(
+            r#"
+ghost = "vendored by the lab; rebuild from /srv/pkgbuilds"
+bash = ["hold until INC-42 closes", "  "]
+retired = "no longer installed"
+"#,
+            false,
+        )
+        .unwrap();
+        let logger = Logger::new(None, false).unwrap();
+        assert_eq!(apply_annotations(&mut doc, &annotations, &logger), 2);
+
+        assert_eq!(
+            doc.packages["ghost"].notes,
+            [
+                NOTE_AUR_NOT_FOUND.to_string(),
+                format!("{NOTE_OPERATOR} vendored by the lab; rebuild from /srv/pkgbuilds"),
+            ]
+        );
+        assert_eq!(
+            doc.packages["bash"].notes,
+            [format!("{NOTE_OPERATOR} hold until INC-42 closes")]
+        );
+        assert!(logger
+            .warnings()
+            .iter()
+            .any(|w| w.starts_with("[ANNOTATE]") && w.contains("retired")));
+    }
+
+    #[test]
+    fn json_and_toml_parse_alike() {
+        let json = parse_annotations(r#"{"zlib": "pinned", "curl": ["a", "b"]}"#, true).unwrap();
+        let toml = parse_annotations("zlib = \"pinned\"\ncurl = [\"a\", \"b\"]\n", false).unwrap();
+        assert_eq!(json, toml);
+    }
+
+    #[test]
+    fn malformed_files_are_rejected() {
+        for (text, is_json, expected) in [
+            ("zlib = 3", false, "`zlib`"),
+            ("zlib = [\"ok\", 1]", false, "list entries"),
+            ("[zlib]\nnote = \"x\"", false, "`zlib`"),
+            ("zlib = \"  \"", false, "empty"),
+            ("[\"zlib\"]", true, "mapping"),
+            ("{\"zlib\": ", true, "EOF"),
+            ("zlib = ", false, ""),
+        ] {
+            let err = parse_annotations(text, is_json).unwrap_err();
+            assert!(err.contains(expected), "{text}: {err}");
+        }
+    }
+}
@@ -0,0 +1,119 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::audit
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Report how long the installed version of each updatable
+    package has been in place, for forensic review.
+
+  Security / Safety Notes:
+    Pure computation over manifest data and a supplied clock.
+
+  Dependencies:
+    chrono for install date parsing.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core when `--audit-changes` is set.
+
+  Revision History:
+    2026-10-18 COD  Introduced installed-age auditing.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Deterministic results against an injected clock
+    - Unparseable dates are omitted, never guessed
+============================================================*/
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::manifest::ManifestDocument;
+
+/// Layouts pacman prints `Install Date` in (C and common English locales), without the zone.
+const INSTALL_DATE_FORMATS: &[&str] = &[
+    "%a %b %e %H:%M:%S %Y",
+    "%a %d %b %Y %I:%M:%S %p",
+    "%a %d %b %Y %H:%M:%S",
+];
+
+/// Set `installed_age_days` on every entry with a pending update and a parseable install date.
+pub fn annotate_installed_age(document: &mut ManifestDocument, now: DateTime<Utc>) {
+    for entry in document.packages.values_mut() {
+        if !entry.update_available {
+            continue;
+        }
+        entry.installed_age_days = entry
+            .install_date
+            .as_deref()
+            .and_then(parse_install_date)
+            .map(|installed| (now - installed).num_days().max(0));
+    }
+}
+
+/// Parse an install date as RFC 3339 or one of pacman's locale layouts (zone treated as UTC).
+pub fn parse_install_date(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(raw) {
+        return Some(date.with_timezone(&Utc));
+    }
+    let candidates = [raw, strip_zone(raw)];
+    for text in candidates {
+        for format in INSTALL_DATE_FORMATS {
+            if let Ok(naive) = NaiveDateTime::parse_from_str(text, format) {
+                return Some(naive.and_utc());
+            }
+        }
+    }
+    None
+}
+
+/// Drop a trailing alphabetic zone abbreviation such as `CEST`.
+fn strip_zone(raw: &str) -> &str {
+    match raw.rsplit_once(' ') {
+        Some((head, zone)) if zone.chars().all(|c| c.is_ascii_alphabetic()) => head,
+        _ => raw,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::{fixtures, PackageSource};
+
+    fn fixed_now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-18T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn pacman_date_layouts_parse() {
+        let expected = DateTime::parse_from_rfc3339("2026-01-05T21:14:09Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        for raw in [
+            "Mon Jan  5 21:14:09 2026",
+            "Mon 05 Jan 2026 09:14:09 PM CET",
+            "Mon 05 Jan 2026 21:14:09 CET",
+            "2026-01-05T21:14:09Z",
+        ] {
+            assert_eq!(parse_install_date(raw), Some(expected), "{raw}");
+        }
+        assert_eq!(parse_install_date("sometime last week"), None);
+    }
+
+    #[test]
+    fn age_is_computed_for_updatable_packages_with_dates() {
+        let mut stale = fixtures::entry("6.10.1-1", PackageSource::Pacman, Some("6.11.2-1"));
+        stale.install_date = Some("Mon 05 Jan 2026 09:14:09 PM CET".into());
+        let mut current = fixtures::entry("5.9-5", PackageSource::Pacman, Some("5.9-5"));
+        current.install_date = Some("2026-10-01T00:00:00Z".into());
+        let undated = fixtures::entry("8.10.1-1", PackageSource::Pacman, Some("8.10.1-2"));
+        let mut document =
+            fixtures::document(vec![("linux", stale), ("zsh", current), ("curl", undated)]);
+
+        annotate_installed_age(&mut document, fixed_now());
+        assert_eq!(document.packages["linux"].installed_age_days, Some(285));
+        assert_eq!(document.packages["zsh"].installed_age_days, None);
+        assert_eq!(document.packages["curl"].installed_age_days, None);
+    }
+}
@@ -9,10 +9,14 @@
 
   Security / Safety Notes:
     Performs read-only HTTPS requests to the public AUR API.
-    No credentials are transmitted.
+    Only operator-supplied proxy credentials are transmitted,
+    and they are never echoed in diagnostics. A pinned
+    certificate is checked in the TLS handshake, before any
+    request (or `Authorization` header) is sent.
 
   Dependencies:
-    reqwest for HTTP, serde for response parsing.
+    reqwest for HTTP, rustls for pinned connections, serde for
+    response parsing.
 
   Operational Scope:
     Supplies candidate versions for packages absent from the
@@ -20,6 +24,21 @@
 
   Revision History:
     2024-11-04 COD  Implemented asynchronous AUR client.
+    2026-10-18 COD  Added environment and explicit proxy support.
+    2026-10-18 COD  Optional raw RPC response capture.
+    2026-10-18 COD  Optional certificate pinning for the AUR host.
+    2026-10-18 COD  Configurable retry backoff strategies.
+    2026-10-18 COD  Capped RPC response bodies (aur.max_response_bytes).
+    2026-10-18 COD  Rejected malformed AUR versions (AURBADVER).
+    2026-10-18 COD  Deferred chunk retries to a second pass.
+    2026-10-18 COD  Flagged tarball-derived sizes as estimated.
+    2026-10-18 COD  Seeded retry jitter (`aur.backoff_jitter`).
+    2026-10-18 COD  Captured NumVotes/Popularity from info results.
+    2026-10-18 COD  Skipped undecodable results individually (AURPARSE).
+    2026-10-18 COD  Captured LastModified and the OutOfDate flag.
+    2026-10-18 COD  Persistent lookup cache with TTL (aur.cache_ttl_secs).
+    2026-10-18 COD  Captured Depends/MakeDepends/CheckDepends.
+    2026-10-18 COD  Enforced the certificate pin during the handshake.
   ------------------------------------------------------------
   SSE Principles Observed:
     - Defensive retry logic with exponential backoff
@@ -28,32 +47,84 @@
 ============================================================*/
 
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 
-use reqwest::header::CONTENT_LENGTH;
-use reqwest::StatusCode;
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use reqwest::header::{
+    HeaderMap, HeaderValue, ACCEPT_ENCODING, ACCEPT_RANGES, AUTHORIZATION, CONTENT_LENGTH,
+    CONTENT_RANGE, CONTENT_TYPE, DATE, RANGE,
+};
+use reqwest::{NoProxy, Proxy, StatusCode, Url};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tokio::task::JoinSet;
 use tokio::time::sleep;
 use urlencoding::encode;
 
-use crate::config::AurConfig;
+use crate::cache;
+use crate::config::{web_base_url, AurConfig, BackoffStrategy};
 use crate::error::{Result, SynsyuError};
-use crate::package_info::VersionInfo;
+use crate::logger::Logger;
+use crate::package_info::{AurDependencies, VersionInfo};
+use crate::raw_dump::{capture, RawDump};
+use crate::rng::{entropy_seed, SeededRng};
 
 /// Client for interacting with the AUR RPC API.
+#[derive(Clone)]
 pub struct AurClient {
     client: reqwest::Client,
     base_url: String,
     max_args: usize,
     max_retries: usize,
+    max_parallel: usize,
+    max_response_bytes: usize,
+    backoff: Backoff,
+    raw_dump: Option<Arc<RawDump>>,
+    cache: Option<AurCache>,
 }
 
 impl AurClient {
     /// Construct a new client from configuration.
+    ///
+    /// Without an explicit `aur.proxy`, reqwest's system proxy detection
+    /// applies and `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY`/`NO_PROXY` are
+    /// honored from the environment.
     pub fn new(config: &AurConfig) -> Result<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(config.timeout))
-            .user_agent("Syn-Syu-Core/0.13 (linux)")
+        Self::with_builder(config, reqwest::Client::builder())
+    }
+
+    fn with_builder(config: &AurConfig, builder: reqwest::ClientBuilder) -> Result<Self> {
+        let pinned_cert = config
+            .pinned_cert_sha256
+            .as_deref()
+            .map(normalize_pin)
+            .transpose()?;
+        let mut builder = PoolSettings::from_config(config).apply(
+            builder
+                .timeout(Duration::from_secs(config.timeout))
+                .user_agent("Syn-Syu-Core/0.13 (linux)"),
+        );
+        if let Some(pin) = pinned_cert {
+            // A pin only means something over TLS; plain HTTP requests are refused outright.
+            builder = builder
+                .use_preconfigured_tls(pinned_tls_config(pin)?)
+                .https_only(true);
+        }
+        if let Some(proxy) = build_proxy(config)? {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(headers) = auth_headers(config)? {
+            builder = builder.default_headers(headers);
+        }
+        let client = builder
             .build()
             .map_err(|err| SynsyuError::Network(format!("Failed to build HTTP client: {err}")))?;
 
@@ -62,67 +133,300 @@ impl AurClient {
             base_url: config.base_url.trim_end_matches('/').to_string(),
             max_args: config.max_args.max(1),
             max_retries: config.max_retries.max(1),
+            max_parallel: config.max_parallel_requests.max(1),
+            max_response_bytes: config.max_response_bytes.max(1),
+            backoff: Backoff::from_config(config),
+            raw_dump: None,
+            cache: None,
         })
     }
 
+    /// Draw retry jitter from `seed` so backoff delays are reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.backoff = self.backoff.with_seed(seed);
+        self
+    }
+
+    /// Write every RPC response body into `dump` before decoding it.
+    pub fn with_raw_dump(mut self, dump: Option<Arc<RawDump>>) -> Self {
+        self.raw_dump = dump;
+        self
+    }
+
+    /// Serve lookups younger than the cache TTL from `cache` instead of the RPC.
+    pub fn with_cache(mut self, cache: Option<AurCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
     /// Fetch version information for the provided packages.
+    ///
+    /// With a cache attached, only packages without a fresh cached result are
+    /// requested; their results replace the cached ones. Names the AUR does not
+    /// know are never cached, so they are asked for again on every run.
     pub async fn fetch_versions(
         &self,
         packages: &[String],
+        logger: &Logger,
     ) -> Result<HashMap<String, VersionInfo>> {
+        let Some(cache) = &self.cache else {
+            return self.query_versions(packages, logger).await;
+        };
+        let now = Utc::now();
+        let mut stored: CachedLookups = cache::read_json(&cache.path, logger).unwrap_or_default();
         let mut versions = HashMap::new();
+        let mut stale = Vec::new();
+        for name in packages {
+            match stored.entries.get(name) {
+                Some(cached) if !cache.refresh && cache.is_fresh(cached, now) => {
+                    versions.insert(name.clone(), cached.info.clone());
+                }
+                _ => stale.push(name.clone()),
+            }
+        }
+        logger.debug(
+            "AURCACHE",
+            format!(
+                "{} of {} AUR lookup(s) served from {}",
+                versions.len(),
+                packages.len(),
+                cache.path.display()
+            ),
+        );
+        if stale.is_empty() {
+            return Ok(versions);
+        }
 
-        for chunk in packages.chunks(self.max_args) {
-            let url = self.compose_url(chunk);
-            let mut attempt = 0;
-            loop {
-                let response = self.client.get(&url).send().await.map_err(|err| {
-                    SynsyuError::Network(format!("AUR request to {url} failed: {err}"))
-                })?;
+        let fetched = self.query_versions(&stale, logger).await?;
+        // Expired entries and packages that have since left the AUR are dropped.
+        stored
+            .entries
+            .retain(|name, cached| cache.is_fresh(cached, now) && !stale.contains(name));
+        for (name, info) in &fetched {
+            stored.entries.insert(
+                name.clone(),
+                CachedVersion {
+                    fetched_at: now,
+                    info: info.clone(),
+                },
+            );
+        }
+        if let Err(err) = cache::write_json_atomic(&cache.path, &stored) {
+            logger.warn("AURCACHE", format!("AUR lookup cache not updated: {err}"));
+        }
+        versions.extend(fetched);
+        Ok(versions)
+    }
 
-                if response.status() == StatusCode::OK {
-                    let payload = response.json::<AurResponse>().await.map_err(|err| {
-                        SynsyuError::Serialization(format!("Failed to decode AUR response: {err}"))
-                    })?;
+    /// Request version information for `packages` from the RPC.
+    ///
+    /// Results that do not decode (e.g. a missing `Version`) are skipped with an
+    /// `AURPARSE` warning, leaving the rest of their chunk intact. Results whose
+    /// `Version` is not a sane `[epoch:]pkgver-pkgrel` are dropped with an
+    /// `AURBADVER` warning, so the package is treated as absent from the AUR.
+    async fn query_versions(
+        &self,
+        packages: &[String],
+        logger: &Logger,
+    ) -> Result<HashMap<String, VersionInfo>> {
+        let chunks: Vec<Vec<String>> = packages
+            .chunks(self.max_args)
+            .map(<[String]>::to_vec)
+            .collect();
+        let mut entries = Vec::new();
 
-                    if let Some(error) = payload.error {
-                        return Err(SynsyuError::Network(format!(
-                            "AUR responded with error for {url}: {error}"
-                        )));
-                    }
+        // First pass: one attempt per chunk, so no task sleeps through a backoff
+        // while holding one of the parallel request slots.
+        let mut failed = Vec::new();
+        for group in chunks.chunks(self.max_parallel) {
+            let mut tasks = JoinSet::new();
+            for chunk in group {
+                let client = self.clone();
+                let chunk = chunk.clone();
+                tasks.spawn(async move {
+                    let attempt = client.attempt_chunk(&chunk).await;
+                    (chunk, attempt)
+                });
+            }
+            while let Some(joined) = tasks.join_next().await {
+                let (chunk, attempt) = joined.map_err(|err| {
+                    SynsyuError::Runtime(format!("AUR request task failed: {err}"))
+                })?;
+                match attempt? {
+                    ChunkAttempt::Done(batch) => entries.extend(batch),
+                    ChunkAttempt::Retry(status) => failed.push((chunk, status)),
+                }
+            }
+        }
 
-                    for entry in payload.results.into_iter() {
-                        let download_size = match (entry.compressed_size, entry.url_path.as_deref())
-                        {
-                            (Some(size), _) => Some(size),
-                            (None, Some(path)) => self.fetch_tarball_size(path).await,
-                            (None, None) => None,
-                        };
-                        let installed_size = entry.installed_size;
-                        versions.insert(
-                            entry.name,
-                            VersionInfo::new(entry.version, download_size, installed_size),
-                        );
-                    }
-                    break;
-                } else {
-                    attempt += 1;
-                    if attempt >= self.max_retries {
-                        return Err(SynsyuError::Network(format!(
-                            "AUR request {url} failed with status {} after {attempt} retries",
-                            response.status()
-                        )));
-                    }
-                    let exponent = (attempt as u32).min(8);
-                    let backoff = Duration::from_millis(200_u64.saturating_mul(1_u64 << exponent));
-                    sleep(backoff).await;
+        // Second pass: only the failed chunks, now with backoff between attempts.
+        if !failed.is_empty() {
+            logger.debug(
+                "AURRETRY",
+                format!(
+                    "{} of {} AUR request(s) failed on the first pass; retrying",
+                    failed.len(),
+                    chunks.len()
+                ),
+            );
+        }
+        for group in failed.chunks(self.max_parallel) {
+            let mut tasks = JoinSet::new();
+            for (chunk, status) in group {
+                let client = self.clone();
+                let (chunk, status) = (chunk.clone(), *status);
+                tasks.spawn(async move { client.retry_chunk(&chunk, status).await });
+            }
+            while let Some(joined) = tasks.join_next().await {
+                let batch = joined.map_err(|err| {
+                    SynsyuError::Runtime(format!("AUR request task failed: {err}"))
+                })??;
+                entries.extend(batch);
+            }
+        }
+
+        let mut versions = HashMap::new();
+        for raw in entries {
+            let entry = match AurEntry::deserialize(&raw) {
+                Ok(entry) => entry,
+                Err(err) => {
+                    let name = raw
+                        .get("Name")
+                        .and_then(Value::as_str)
+                        .unwrap_or("<unnamed>");
+                    logger.warn(
+                        "AURPARSE",
+                        format!("{name}: skipping malformed AUR result: {err}"),
+                    );
+                    continue;
                 }
+            };
+            if !is_sane_aur_version(&entry.version) {
+                logger.warn(
+                    "AURBADVER",
+                    format!(
+                        "{}: ignoring malformed AUR version {:?}",
+                        entry.name, entry.version
+                    ),
+                );
+                continue;
             }
+            // The snapshot tarball only approximates what will be downloaded and built.
+            let (download_size, size_estimated) =
+                match (entry.compressed_size, entry.url_path.as_deref()) {
+                    (Some(size), _) => (Some(size), false),
+                    (None, Some(path)) => {
+                        let size = self.fetch_tarball_size(path).await;
+                        (size, size.is_some())
+                    }
+                    (None, None) => (None, false),
+                };
+            let installed_size = entry.installed_size;
+            let dependencies = AurDependencies {
+                depends: entry.depends.unwrap_or_default(),
+                make_depends: entry.make_depends.unwrap_or_default(),
+                check_depends: entry.check_depends.unwrap_or_default(),
+            };
+            versions.insert(
+                entry.name,
+                VersionInfo::new(entry.version, download_size, installed_size)
+                    .with_size_estimated(size_estimated)
+                    .with_url_path(entry.url_path)
+                    .with_maintainer(entry.maintainer)
+                    .with_popularity(entry.num_votes, entry.popularity)
+                    .with_released_at(entry.last_modified.and_then(from_unix))
+                    .with_out_of_date(entry.out_of_date.and_then(from_unix))
+                    .with_aur_dependencies(dependencies),
+            );
         }
 
         Ok(versions)
     }
 
+    /// Issue a single info request without size resolution; returns the result count.
+    pub async fn probe(&self, packages: &[String]) -> Result<usize> {
+        Ok(self.query_chunk(packages).await?.len())
+    }
+
+    /// Run one RPC info request with retry and the configured backoff.
+    async fn query_chunk(&self, chunk: &[String]) -> Result<Vec<Value>> {
+        match self.attempt_chunk(chunk).await? {
+            ChunkAttempt::Done(entries) => Ok(entries),
+            ChunkAttempt::Retry(status) => self.retry_chunk(chunk, status).await,
+        }
+    }
+
+    /// Retry a chunk whose first attempt was answered with `status`, backing off between attempts.
+    async fn retry_chunk(&self, chunk: &[String], mut status: StatusCode) -> Result<Vec<Value>> {
+        let mut attempt = 1;
+        while attempt < self.max_retries {
+            sleep(self.backoff.delay(attempt)).await;
+            match self.attempt_chunk(chunk).await? {
+                ChunkAttempt::Done(entries) => return Ok(entries),
+                ChunkAttempt::Retry(next) => status = next,
+            }
+            attempt += 1;
+        }
+        Err(SynsyuError::Network(format!(
+            "AUR request {} failed with status {status} after {attempt} retries",
+            self.compose_url(chunk)
+        )))
+    }
+
+    /// Issue a single RPC info request; a non-200 status is reported for retrying.
+    async fn attempt_chunk(&self, chunk: &[String]) -> Result<ChunkAttempt> {
+        let url = self.compose_url(chunk);
+        let response = self.client.get(&url).send().await.map_err(|err| {
+            SynsyuError::Network(format!(
+                "AUR request to {url} failed: {}",
+                error_chain(&err)
+            ))
+        })?;
+
+        if response.status() != StatusCode::OK {
+            return Ok(ChunkAttempt::Retry(response.status()));
+        }
+        let body = self.read_capped(response, chunk.len()).await?;
+        capture(self.raw_dump.as_deref(), "aur-rpc", "json", &body)?;
+        let payload = serde_json::from_slice::<AurResponse>(&body).map_err(|err| {
+            SynsyuError::Serialization(format!("Failed to decode AUR response: {err}"))
+        })?;
+
+        if let Some(error) = payload.error {
+            return Err(SynsyuError::Network(format!(
+                "AUR responded with error for {url}: {error}"
+            )));
+        }
+        Ok(ChunkAttempt::Done(payload.results))
+    }
+
+    /// Read a response body, aborting once it exceeds `aur.max_response_bytes`.
+    async fn read_capped(&self, mut response: reqwest::Response, args: usize) -> Result<Vec<u8>> {
+        let limit = self.max_response_bytes;
+        let oversized = || {
+            SynsyuError::Serialization(format!(
+                "AUR response for {args} package(s) exceeds aur.max_response_bytes ({limit} bytes); \
+                 lower aur.max_args to split the request"
+            ))
+        };
+        if content_length(response.headers()).is_some_and(|length| length > limit as u64) {
+            return Err(oversized());
+        }
+        let mut body = Vec::new();
+        while let Some(piece) = response.chunk().await.map_err(|err| {
+            SynsyuError::Network(format!(
+                "AUR response from {} unreadable: {err}",
+                response.url()
+            ))
+        })? {
+            if body.len() + piece.len() > limit {
+                return Err(oversized());
+            }
+            body.extend_from_slice(&piece);
+        }
+        Ok(body)
+    }
+
     fn compose_url(&self, packages: &[String]) -> String {
         let mut url = format!("{}?v=5&type=info", self.base_url);
         for pkg in packages {
@@ -133,39 +437,429 @@ impl AurClient {
     }
 
     fn aur_base_url(&self) -> String {
-        // Trim trailing /rpc to derive the host root for tarball fetches.
-        let mut base = self.base_url.trim_end_matches('/').to_string();
-        if let Some(idx) = base.rfind("/rpc") {
-            base.truncate(idx);
-        }
-        base
+        web_base_url(&self.base_url)
     }
 
+    /// Server clock from the `Date` header of a HEAD request to the RPC endpoint.
+    pub async fn server_date(&self) -> Result<Option<DateTime<Utc>>> {
+        let response = self
+            .client
+            .head(&self.base_url)
+            .send()
+            .await
+            .map_err(|err| {
+                SynsyuError::Network(format!("AUR clock probe failed: {}", error_chain(&err)))
+            })?;
+        Ok(response
+            .headers()
+            .get(DATE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+            .map(|date| date.with_timezone(&Utc)))
+    }
+
+    /// Resolve the snapshot tarball size, validating that the resource really is a tarball.
+    ///
+    /// Requests identity encoding so `Content-Length` reflects the bytes on the wire. When the
+    /// length is absent but the server advertises byte ranges, a one-byte ranged GET confirms
+    /// the resource and recovers the total from `Content-Range`.
     async fn fetch_tarball_size(&self, path: &str) -> Option<u64> {
         let url = if path.starts_with("http://") || path.starts_with("https://") {
             path.to_string()
         } else {
             format!("{}{}", self.aur_base_url(), path)
         };
-        let response = self.client.head(url).send().await.ok()?;
-        if !response.status().is_success() {
+        let response = self
+            .client
+            .head(&url)
+            .header(ACCEPT_ENCODING, "identity")
+            .send()
+            .await
+            .ok()?;
+        if !response.status().is_success() || !is_tarball(response.headers()) {
             return None;
         }
-        response
-            .headers()
-            .get(CONTENT_LENGTH)
-            .and_then(|value| value.to_str().ok())
-            .and_then(|value| value.parse::<u64>().ok())
+        if let Some(length) = content_length(response.headers()) {
+            return Some(length);
+        }
+        if !accepts_byte_ranges(response.headers()) {
+            return None;
+        }
+
+        let probe = self
+            .client
+            .get(&url)
+            .header(ACCEPT_ENCODING, "identity")
+            .header(RANGE, "bytes=0-0")
+            .send()
+            .await
+            .ok()?;
+        if probe.status() != StatusCode::PARTIAL_CONTENT || !is_tarball(probe.headers()) {
+            return None;
+        }
+        content_range_total(probe.headers())
+    }
+}
+
+/// Outcome of a single RPC attempt for one chunk.
+enum ChunkAttempt {
+    /// Raw results; each is decoded on its own so one malformed entry spares the rest.
+    Done(Vec<Value>),
+    /// Non-200 status; the chunk may be retried.
+    Retry(StatusCode),
+}
+
+/// AUR lookups persisted between runs, consulted by [`AurClient::fetch_versions`].
+#[derive(Debug, Clone)]
+pub struct AurCache {
+    path: PathBuf,
+    ttl_secs: u64,
+    refresh: bool,
+}
+
+impl AurCache {
+    /// Cache stored at `path` whose entries stay fresh for `ttl_secs`.
+    pub fn new(path: PathBuf, ttl_secs: u64) -> Self {
+        Self {
+            path,
+            ttl_secs,
+            refresh: false,
+        }
+    }
+
+    /// Ignore cached results and refetch every package, still updating the file.
+    pub fn refreshing(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// A result fetched in the future (the clock went back) counts as stale.
+    fn is_fresh(&self, cached: &CachedVersion, now: DateTime<Utc>) -> bool {
+        u64::try_from((now - cached.fetched_at).num_seconds()).is_ok_and(|age| age < self.ttl_secs)
+    }
+}
+
+/// Location of the AUR lookup cache within the cache directory.
+pub fn aur_cache_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("aur_versions.json")
+}
+
+/// On-disk layout of the AUR lookup cache.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CachedLookups {
+    entries: HashMap<String, CachedVersion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedVersion {
+    fetched_at: DateTime<Utc>,
+    info: VersionInfo,
+}
+
+/// Canonical form of a configured pin: lowercase hex, `:` separators and whitespace removed.
+fn normalize_pin(raw: &str) -> Result<String> {
+    let pin: String = raw
+        .chars()
+        .filter(|c| *c != ':' && !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    if pin.len() != 64 || !pin.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(SynsyuError::Config(format!(
+            "aur.pinned_cert_sha256 must be 64 hex digits, got `{raw}`"
+        )));
+    }
+    Ok(pin)
+}
+
+/// TLS settings that accept exactly the certificate whose SHA-256 is `pin`.
+fn pinned_tls_config(pin: String) -> Result<rustls::ClientConfig> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let mut tls = rustls::ClientConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .map_err(|err| {
+            SynsyuError::Config(format!("TLS setup for certificate pinning failed: {err}"))
+        })?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(PinnedCertVerifier { pin, provider }))
+        .with_no_client_auth();
+    tls.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(tls)
+}
+
+/// Handshake-time pin check. The pin stands in for CA and hostname validation:
+/// only the pinned certificate is accepted, and the handshake signature proves
+/// the server holds its key. A mismatch aborts before any request is sent.
+#[derive(Debug)]
+struct PinnedCertVerifier {
+    /// Lowercase hex SHA-256 of the expected leaf certificate (DER).
+    pin: String,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        let actual = hex_digest(end_entity.as_ref());
+        if actual != self.pin {
+            return Err(rustls::Error::General(format!(
+                "AUR certificate for {} does not match the pinned SHA-256 (expected {}, got {actual})",
+                server_name.to_str(),
+                self.pin
+            )));
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+/// `err` followed by its causes; reqwest hides TLS failures (such as a pin mismatch) in the chain.
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut text = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        let cause_text = cause.to_string();
+        if !text.contains(&cause_text) {
+            text.push_str(": ");
+            text.push_str(&cause_text);
+        }
+        source = cause.source();
+    }
+    text
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Permissive `[epoch:]pkgver-pkgrel` check: pkgver may not contain `:`, `-`, `/` or whitespace.
+fn is_sane_aur_version(version: &str) -> bool {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN
+        .get_or_init(|| {
+            Regex::new(r"^(?:[0-9]+:)?[A-Za-z0-9._+~]+-[0-9]+(?:\.[0-9]+)?$")
+                .expect("AUR version pattern is valid")
+        })
+        .is_match(version)
+}
+
+fn is_tarball(headers: &HeaderMap) -> bool {
+    let Some(value) = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    let mime = value
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    matches!(
+        mime.as_str(),
+        "application/x-gzip" | "application/gzip" | "application/octet-stream"
+    )
+}
+
+fn accepts_byte_ranges(headers: &HeaderMap) -> bool {
+    headers
+        .get(ACCEPT_RANGES)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| {
+            v.split(',')
+                .any(|unit| unit.trim().eq_ignore_ascii_case("bytes"))
+        })
+        .unwrap_or(false)
+}
+
+fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+}
+
+/// Extract the complete length from `Content-Range: bytes 0-0/12345`.
+fn content_range_total(headers: &HeaderMap) -> Option<u64> {
+    let value = headers.get(CONTENT_RANGE)?.to_str().ok()?;
+    let (_, total) = value.trim().strip_prefix("bytes")?.rsplit_once('/')?;
+    total.trim().parse::<u64>().ok()
+}
+
+/// Connection reuse toward the AUR host; HTTP/2 is negotiated via ALPN when offered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PoolSettings {
+    max_idle_per_host: usize,
+    idle_timeout: Option<Duration>,
+}
+
+impl PoolSettings {
+    fn from_config(config: &AurConfig) -> Self {
+        Self {
+            max_idle_per_host: config.pool_max_idle_per_host,
+            // Zero keeps idle connections until the server closes them.
+            idle_timeout: (config.pool_idle_timeout_secs > 0)
+                .then(|| Duration::from_secs(config.pool_idle_timeout_secs)),
+        }
+    }
+
+    fn apply(self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        builder
+            .pool_max_idle_per_host(self.max_idle_per_host)
+            .pool_idle_timeout(self.idle_timeout)
+    }
+}
+
+/// Retry delay schedule from `aur.backoff_*`.
+#[derive(Debug, Clone)]
+struct Backoff {
+    strategy: BackoffStrategy,
+    base: Duration,
+    ceiling: Duration,
+    jitter: f64,
+    /// Shared by clones so concurrent chunks draw distinct jitter.
+    rng: Arc<Mutex<SeededRng>>,
+}
+
+impl Backoff {
+    fn from_config(config: &AurConfig) -> Self {
+        Self {
+            strategy: config.backoff_strategy,
+            base: Duration::from_millis(config.backoff_base_ms),
+            ceiling: Duration::from_millis(config.backoff_max_ms),
+            jitter: config.backoff_jitter.clamp(0.0, 1.0),
+            rng: Arc::new(Mutex::new(SeededRng::new(entropy_seed()))),
+        }
+    }
+
+    fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Arc::new(Mutex::new(SeededRng::new(seed)));
+        self
+    }
+
+    /// Delay before retrying after failed attempt number `attempt` (1-based).
+    fn delay(&self, attempt: usize) -> Duration {
+        let attempt = u32::try_from(attempt).unwrap_or(u32::MAX);
+        let delay = match self.strategy {
+            BackoffStrategy::Exponential => self.base.saturating_mul(2_u32.saturating_pow(attempt)),
+            BackoffStrategy::Linear => self.base.saturating_mul(attempt),
+            BackoffStrategy::Constant => self.base,
+        }
+        .min(self.ceiling);
+        if self.jitter == 0.0 {
+            return delay;
+        }
+        let draw = self.rng.lock().map(|mut rng| rng.next_f64()).unwrap_or(0.0);
+        delay.mul_f64(1.0 - self.jitter * draw)
     }
 }
 
+/// Translate `aur.proxy*` settings into an explicit proxy, validating the URL.
+fn build_proxy(config: &AurConfig) -> Result<Option<Proxy>> {
+    let Some(raw) = config
+        .proxy
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+    else {
+        return Ok(None);
+    };
+
+    let url = Url::parse(raw)
+        .map_err(|err| SynsyuError::Config(format!("Invalid aur.proxy URL: {err}")))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(SynsyuError::Config(format!(
+            "Unsupported aur.proxy scheme `{}`; expected http or https",
+            url.scheme()
+        )));
+    }
+    if url.host_str().map(str::is_empty).unwrap_or(true) {
+        return Err(SynsyuError::Config(
+            "aur.proxy URL is missing a host".into(),
+        ));
+    }
+
+    let mut proxy = Proxy::all(url)
+        .map_err(|err| SynsyuError::Config(format!("Invalid aur.proxy URL: {err}")))?;
+    match (&config.proxy_username, &config.proxy_password) {
+        (Some(user), password) => {
+            proxy = proxy.basic_auth(user, password.as_deref().unwrap_or(""));
+        }
+        (None, Some(_)) => {
+            return Err(SynsyuError::Config(
+                "aur.proxy_password requires aur.proxy_username".into(),
+            ));
+        }
+        (None, None) => {}
+    }
+    let no_proxy = match config.no_proxy.as_deref() {
+        Some(list) => NoProxy::from_string(list),
+        None => NoProxy::from_env(),
+    };
+    Ok(Some(proxy.no_proxy(no_proxy)))
+}
+
+/// `Authorization: Bearer <aur.token>`, marked sensitive so reqwest never prints it.
+fn auth_headers(config: &AurConfig) -> Result<Option<HeaderMap>> {
+    let Some(token) = config.token() else {
+        return Ok(None);
+    };
+    // The error deliberately omits the value.
+    let mut value = HeaderValue::from_str(&format!("Bearer {token}")).map_err(|_| {
+        SynsyuError::Config("aur.token contains characters not allowed in an HTTP header".into())
+    })?;
+    value.set_sensitive(true);
+    let mut headers = HeaderMap::new();
+    headers.insert(AUTHORIZATION, value);
+    Ok(Some(headers))
+}
+
 #[derive(Debug, Deserialize)]
 struct AurResponse {
     #[serde(rename = "resultcount")]
     #[allow(dead_code)]
     pub result_count: Option<u32>,
     #[serde(default)]
-    pub results: Vec<AurEntry>,
+    pub results: Vec<Value>,
     #[serde(rename = "error")]
     pub error: Option<String>,
 }
@@ -182,6 +876,30 @@ struct AurEntry {
     pub compressed_size: Option<u64>,
     #[serde(rename = "InstalledSize")]
     pub installed_size: Option<u64>,
+    /// `null` when the package is orphaned.
+    #[serde(rename = "Maintainer", default)]
+    pub maintainer: Option<String>,
+    #[serde(rename = "NumVotes", default)]
+    pub num_votes: Option<u64>,
+    #[serde(rename = "Popularity", default)]
+    pub popularity: Option<f64>,
+    /// Unix time of the last package base update.
+    #[serde(rename = "LastModified", default)]
+    pub last_modified: Option<i64>,
+    /// Unix time the package was flagged out of date; `null` when not flagged.
+    #[serde(rename = "OutOfDate", default)]
+    pub out_of_date: Option<i64>,
+    /// The dependency arrays are omitted when empty.
+    #[serde(rename = "Depends", default)]
+    pub depends: Option<Vec<String>>,
+    #[serde(rename = "MakeDepends", default)]
+    pub make_depends: Option<Vec<String>>,
+    #[serde(rename = "CheckDepends", default)]
+    pub check_depends: Option<Vec<String>>,
+}
+
+fn from_unix(secs: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(secs, 0)
 }
 
 /// Placeholder for future expansion (e.g., changelog retrieval).
@@ -190,3 +908,760 @@ pub async fn fetch_future_metadata(_packages: &[String]) -> Result<()> {
     // Future hook: integrate changelog or plugin metadata.
     Ok(())
 }
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one canned response per connection, returning the captured request heads.
+    pub async fn mock_server(
+        responses: Vec<String>,
+    ) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = tokio::spawn(async move {
+            let mut heads = Vec::new();
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 4096];
+                let mut head = Vec::new();
+                while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    head.extend_from_slice(&buf[..n]);
+                }
+                socket.write_all(response.as_bytes()).await.unwrap();
+                heads.push(String::from_utf8_lossy(&head).into_owned());
+            }
+            heads
+        });
+        (format!("http://{addr}"), handle)
+    }
+
+    /// Answer every connection with the same response until the test runtime ends.
+    pub async fn mock_server_forever(response: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    break;
+                };
+                let response = response.clone();
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 4096];
+                    let mut head = Vec::new();
+                    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match socket.read(&mut buf).await {
+                            Ok(0) | Err(_) => break,
+                            Ok(n) => head.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    /// Canned RPC response with the given JSON body.
+    pub fn json_response(body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::{json_response, mock_server};
+    use super::*;
+
+    /// Answer a single RPC lookup with an empty result set.
+    async fn mock_proxy() -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let body = r#"{"resultcount":0,"results":[],"type":"multiinfo"}"#;
+        mock_server(vec![json_response(body)]).await
+    }
+
+    fn tarball_client(base: &str) -> AurClient {
+        let config = AurConfig {
+            base_url: format!("{base}/rpc/"),
+            ..AurConfig::default()
+        };
+        AurClient::new(&config).expect("client should build")
+    }
+
+    fn backoff_sequence(strategy: BackoffStrategy) -> Vec<u128> {
+        let config = AurConfig {
+            backoff_strategy: strategy,
+            backoff_base_ms: 100,
+            backoff_max_ms: 1_000,
+            ..AurConfig::default()
+        };
+        let backoff = Backoff::from_config(&config);
+        (1..=6)
+            .map(|attempt| backoff.delay(attempt).as_millis())
+            .collect()
+    }
+
+    #[test]
+    fn backoff_strategies_produce_expected_delays() {
+        assert_eq!(
+            backoff_sequence(BackoffStrategy::Exponential),
+            vec![200, 400, 800, 1_000, 1_000, 1_000]
+        );
+        assert_eq!(
+            backoff_sequence(BackoffStrategy::Linear),
+            vec![100, 200, 300, 400, 500, 600]
+        );
+        assert_eq!(backoff_sequence(BackoffStrategy::Constant), vec![100; 6]);
+    }
+
+    #[test]
+    fn same_seed_reproduces_jittered_backoff() {
+        let config = AurConfig {
+            backoff_jitter: 0.5,
+            ..AurConfig::default()
+        };
+        let run = |seed| {
+            let backoff = Backoff::from_config(&config).with_seed(seed);
+            (1..=8)
+                .map(|attempt| backoff.delay(attempt))
+                .collect::<Vec<_>>()
+        };
+        let first = run(7);
+        assert_eq!(first, run(7));
+        assert_ne!(first, run(8));
+        let plain = Backoff::from_config(&AurConfig::default());
+        for (attempt, delay) in (1..=8).zip(&first) {
+            let full = plain.delay(attempt);
+            assert!(
+                *delay <= full && *delay >= full / 2,
+                "{delay:?} vs {full:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn default_backoff_matches_the_historic_schedule() {
+        let backoff = Backoff::from_config(&AurConfig::default());
+        let delays: Vec<u128> = [1, 2, 8, 9, 30]
+            .iter()
+            .map(|attempt| backoff.delay(*attempt).as_millis())
+            .collect();
+        assert_eq!(delays, vec![400, 800, 51_200, 51_200, 51_200]);
+    }
+
+    #[test]
+    fn pool_settings_follow_config() {
+        let config = AurConfig {
+            pool_max_idle_per_host: 2,
+            pool_idle_timeout_secs: 30,
+            ..AurConfig::default()
+        };
+        assert_eq!(
+            PoolSettings::from_config(&config),
+            PoolSettings {
+                max_idle_per_host: 2,
+                idle_timeout: Some(Duration::from_secs(30)),
+            }
+        );
+        AurClient::new(&config).expect("client builds with pool settings");
+
+        let unbounded = AurConfig {
+            pool_idle_timeout_secs: 0,
+            ..AurConfig::default()
+        };
+        assert_eq!(PoolSettings::from_config(&unbounded).idle_timeout, None);
+    }
+
+    fn head_response(headers: &str) -> String {
+        format!("HTTP/1.1 200 OK\r\n{headers}Connection: close\r\n\r\n")
+    }
+
+    #[tokio::test]
+    async fn tarball_size_uses_content_length_for_tarballs() {
+        let (base, handle) = mock_server(vec![head_response(
+            "Content-Type: application/x-gzip\r\nContent-Length: 2048\r\n",
+        )])
+        .await;
+        let size = tarball_client(&base)
+            .fetch_tarball_size("/cgit/aur.git/snapshot/foo.tar.gz")
+            .await;
+        assert_eq!(size, Some(2048));
+        let heads = handle.await.unwrap();
+        assert!(heads[0].starts_with("HEAD /cgit/aur.git/snapshot/foo.tar.gz"));
+        assert!(heads[0]
+            .to_ascii_lowercase()
+            .contains("accept-encoding: identity"));
+    }
+
+    #[tokio::test]
+    async fn tarball_size_rejects_non_tarball_content_type() {
+        let (base, _handle) = mock_server(vec![head_response(
+            "Content-Type: text/html; charset=utf-8\r\nContent-Length: 512\r\n",
+        )])
+        .await;
+        let size = tarball_client(&base)
+            .fetch_tarball_size("/foo.tar.gz")
+            .await;
+        assert_eq!(size, None);
+    }
+
+    #[tokio::test]
+    async fn tarball_size_rejects_missing_content_type() {
+        let (base, _handle) = mock_server(vec![head_response("Content-Length: 512\r\n")]).await;
+        let size = tarball_client(&base)
+            .fetch_tarball_size("/foo.tar.gz")
+            .await;
+        assert_eq!(size, None);
+    }
+
+    #[tokio::test]
+    async fn tarball_size_probes_range_when_length_missing() {
+        let (base, handle) = mock_server(vec![
+            head_response("Content-Type: application/octet-stream\r\nAccept-Ranges: bytes\r\n"),
+            "HTTP/1.1 206 Partial Content\r\nContent-Type: application/octet-stream\r\nContent-Range: bytes 0-0/4096\r\nContent-Length: 1\r\nConnection: close\r\n\r\nx".to_string(),
+        ])
+        .await;
+        let size = tarball_client(&base)
+            .fetch_tarball_size("/foo.tar.gz")
+            .await;
+        assert_eq!(size, Some(4096));
+        let heads = handle.await.unwrap();
+        assert_eq!(heads.len(), 2);
+        assert!(heads[1].starts_with("GET /foo.tar.gz"));
+        assert!(heads[1].to_ascii_lowercase().contains("range: bytes=0-0"));
+    }
+
+    #[tokio::test]
+    async fn tarball_size_is_unknown_without_length_or_ranges() {
+        let (base, handle) =
+            mock_server(vec![head_response("Content-Type: application/x-gzip\r\n")]).await;
+        let size = tarball_client(&base)
+            .fetch_tarball_size("/foo.tar.gz")
+            .await;
+        assert_eq!(size, None);
+        assert_eq!(handle.await.unwrap().len(), 1, "no ranged probe expected");
+    }
+
+    #[tokio::test]
+    async fn configured_token_is_sent_as_bearer_authorization() {
+        let body = r#"{"resultcount":0,"results":[],"type":"multiinfo"}"#;
+        let (base, handle) = mock_server(vec![json_response(body)]).await;
+        let config = AurConfig {
+            base_url: format!("{base}/rpc/"),
+            token: Some("tok-abc123".into()),
+            ..AurConfig::default()
+        };
+        let client = AurClient::new(&config).expect("client should build");
+        client
+            .fetch_versions(&["syn-syu".to_string()], &Logger::new(None, false).unwrap())
+            .await
+            .expect("request should succeed");
+
+        let head = handle.await.unwrap().remove(0).to_ascii_lowercase();
+        assert!(head.contains("authorization: bearer tok-abc123\r\n"));
+
+        let bad = AurConfig {
+            token: Some("line\nbreak".into()),
+            ..AurConfig::default()
+        };
+        match AurClient::new(&bad) {
+            Err(SynsyuError::Config(message)) => assert!(!message.contains("break")),
+            other => panic!("expected a config error, got {:?}", other.err()),
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_flow_through_configured_proxy() {
+        let (proxy_url, handle) = mock_proxy().await;
+        let config = AurConfig {
+            base_url: "http://aur.example.invalid/rpc/".into(),
+            proxy: Some(proxy_url),
+            proxy_username: Some("operator".into()),
+            proxy_password: Some("secret".into()),
+            no_proxy: Some(String::new()),
+            ..AurConfig::default()
+        };
+        let client = AurClient::new(&config).expect("client should build");
+        let versions = client
+            .fetch_versions(&["syn-syu".to_string()], &Logger::new(None, false).unwrap())
+            .await
+            .expect("proxied request should succeed");
+        assert!(versions.is_empty());
+
+        let head = handle.await.unwrap().remove(0);
+        assert!(
+            head.starts_with("GET http://aur.example.invalid/rpc?v=5&type=info&arg[]=syn-syu"),
+            "proxy should receive absolute-form request, got: {head}"
+        );
+        assert!(
+            head.lines().any(|line| {
+                line.to_ascii_lowercase()
+                    .starts_with("proxy-authorization:")
+                    && line.ends_with("Basic b3BlcmF0b3I6c2VjcmV0")
+            }),
+            "proxy credentials should be forwarded"
+        );
+    }
+
+    #[tokio::test]
+    async fn maintainer_is_captured_and_null_means_orphaned() {
+        let body = r#"{"resultcount":2,"type":"multiinfo","results":[
+            {"Name":"paru","Version":"2.0.4-1","Maintainer":"Morganamilo","CompressedSize":10},
+            {"Name":"old-tool","Version":"0.3-1","Maintainer":null,"CompressedSize":10}]}"#;
+        let (base, _handle) = mock_server(vec![json_response(body)]).await;
+        let versions = tarball_client(&base)
+            .fetch_versions(
+                &["paru".to_string(), "old-tool".to_string()],
+                &Logger::new(None, false).unwrap(),
+            )
+            .await
+            .expect("lookup succeeds");
+        assert_eq!(versions["paru"].maintainer.as_deref(), Some("Morganamilo"));
+        assert_eq!(versions["old-tool"].maintainer, None);
+    }
+
+    #[tokio::test]
+    async fn malformed_results_are_skipped_without_losing_the_chunk() {
+        let body = r#"{"resultcount":4,"type":"multiinfo","results":[
+            {"Name":"paru","Version":"2.0.4-1","CompressedSize":10},
+            {"Name":"broken","CompressedSize":10},
+            {"Version":"1.0-1","CompressedSize":10},
+            {"Name":"yay","Version":"12.4.2-1","CompressedSize":"big"},
+            {"Name":"rua","Version":"0.19.10-1","CompressedSize":10}]}"#;
+        let (base, _handle) = mock_server(vec![json_response(body)]).await;
+        let logger = Logger::new(None, false).unwrap();
+        let versions = tarball_client(&base)
+            .fetch_versions(&["paru", "broken", "yay", "rua"].map(String::from), &logger)
+            .await
+            .expect("one bad entry must not fail the chunk");
+        let mut names: Vec<&str> = versions.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["paru", "rua"]);
+        let warnings = logger.warnings();
+        let parse: Vec<&String> = warnings
+            .iter()
+            .filter(|w| w.starts_with("[AURPARSE]"))
+            .collect();
+        assert_eq!(parse.len(), 3, "{warnings:?}");
+        assert!(parse[0].contains("broken") && parse[0].contains("Version"));
+        assert!(parse[1].contains("<unnamed>"));
+        assert!(parse[2].contains("yay"));
+    }
+
+    #[tokio::test]
+    async fn votes_and_popularity_are_captured() {
+        let body = r#"{"resultcount":2,"type":"multiinfo","results":[
+            {"Name":"paru","Version":"2.0.4-1","Maintainer":"Morganamilo","NumVotes":1203,"Popularity":28.41,"CompressedSize":10},
+            {"Name":"niche","Version":"0.1-1","Maintainer":"me","CompressedSize":10}]}"#;
+        let (base, _handle) = mock_server(vec![json_response(body)]).await;
+        let versions = tarball_client(&base)
+            .fetch_versions(
+                &["paru".to_string(), "niche".to_string()],
+                &Logger::new(None, false).unwrap(),
+            )
+            .await
+            .expect("lookup succeeds");
+        assert_eq!(versions["paru"].aur_votes, Some(1203));
+        assert_eq!(versions["paru"].aur_popularity, Some(28.41));
+        assert_eq!(versions["niche"].aur_votes, None);
+        assert_eq!(versions["niche"].aur_popularity, None);
+    }
+
+    #[tokio::test]
+    async fn cached_lookups_inside_the_ttl_skip_the_rpc() {
+        let dir = std::env::temp_dir().join(format!("synsyu-aur-cache-{}", std::process::id()));
+        let path = aur_cache_path(&dir);
+        let now = Utc::now();
+        let cached = |version: &str, age_secs: i64| CachedVersion {
+            fetched_at: now - chrono::Duration::seconds(age_secs),
+            info: VersionInfo::new(version.into(), Some(10), None),
+        };
+        let seeded = CachedLookups {
+            entries: HashMap::from([
+                ("yay".to_string(), cached("12.4.1-1", 60)),
+                ("paru".to_string(), cached("2.0.3-1", 7_200)),
+            ]),
+        };
+        cache::write_json_atomic(&path, &seeded).unwrap();
+        let logger = Logger::new(None, false).unwrap();
+        let names = ["yay".to_string(), "paru".to_string()];
+
+        let body = r#"{"resultcount":1,"results":[{"Name":"paru","Version":"2.0.4-1","Maintainer":"Morganamilo","CompressedSize":10}]}"#;
+        let (base, handle) = mock_server(vec![json_response(body)]).await;
+        let client = tarball_client(&base).with_cache(Some(AurCache::new(path.clone(), 3_600)));
+        let versions = client.fetch_versions(&names, &logger).await.unwrap();
+        assert_eq!(
+            versions["yay"].version, "12.4.1-1",
+            "fresh entry served from cache"
+        );
+        assert_eq!(versions["paru"].version, "2.0.4-1", "stale entry refetched");
+        let heads = handle.await.unwrap();
+        assert!(heads[0].contains("paru") && !heads[0].contains("yay"));
+        let stored: CachedLookups = cache::read_json(&path, &logger).unwrap();
+        assert_eq!(stored.entries["paru"].info.version, "2.0.4-1");
+        assert!(stored.entries["paru"].fetched_at >= now);
+
+        // --refresh-cache asks for everything again and drops what the AUR no longer has.
+        let (base, handle) = mock_server(vec![json_response(body)]).await;
+        let client = tarball_client(&base)
+            .with_cache(Some(AurCache::new(path.clone(), 3_600).refreshing(true)));
+        let versions = client.fetch_versions(&names, &logger).await.unwrap();
+        assert!(!versions.contains_key("yay"));
+        let heads = handle.await.unwrap();
+        assert!(heads[0].contains("paru") && heads[0].contains("yay"));
+        let stored: CachedLookups = cache::read_json(&path, &logger).unwrap();
+        assert_eq!(stored.entries.keys().collect::<Vec<_>>(), ["paru"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn last_modified_and_out_of_date_are_captured() {
+        let body = r#"{"resultcount":2,"type":"multiinfo","results":[
+            {"Name":"stale","Version":"1.0-1","LastModified":1760745600,"OutOfDate":1760832000,"CompressedSize":10},
+            {"Name":"fresh","Version":"2.0-1","LastModified":1760745600,"OutOfDate":null,"CompressedSize":10}]}"#;
+        let (base, _handle) = mock_server(vec![json_response(body)]).await;
+        let versions = tarball_client(&base)
+            .fetch_versions(
+                &["stale".to_string(), "fresh".to_string()],
+                &Logger::new(None, false).unwrap(),
+            )
+            .await
+            .expect("lookup succeeds");
+        let stale = &versions["stale"];
+        assert_eq!(
+            stale.released_at.map(|at| at.to_rfc3339()).as_deref(),
+            Some("2025-10-18T00:00:00+00:00")
+        );
+        assert_eq!(
+            stale
+                .flagged_out_of_date
+                .map(|at| at.to_rfc3339())
+                .as_deref(),
+            Some("2025-10-19T00:00:00+00:00")
+        );
+        assert!(versions["fresh"].released_at.is_some());
+        assert_eq!(versions["fresh"].flagged_out_of_date, None);
+    }
+
+    #[tokio::test]
+    async fn dependency_arrays_are_captured_and_may_be_absent() {
+        let body = r#"{"resultcount":2,"type":"multiinfo","results":[
+            {"Name":"paru","Version":"2.0.4-1","CompressedSize":10,
+             "Depends":["git","pacman>=6.1"],"MakeDepends":["cargo"],"CheckDepends":null},
+            {"Name":"bare","Version":"1.0-1","CompressedSize":10}]}"#;
+        let (base, _handle) = mock_server(vec![json_response(body)]).await;
+        let versions = tarball_client(&base)
+            .fetch_versions(
+                &["paru".to_string(), "bare".to_string()],
+                &Logger::new(None, false).unwrap(),
+            )
+            .await
+            .expect("lookup succeeds");
+        assert_eq!(
+            versions["paru"].aur_dependencies,
+            Some(AurDependencies {
+                depends: vec!["git".into(), "pacman>=6.1".into()],
+                make_depends: vec!["cargo".into()],
+                check_depends: Vec::new(),
+            })
+        );
+        assert_eq!(
+            versions["bare"].aur_dependencies,
+            Some(AurDependencies::default())
+        );
+    }
+
+    #[test]
+    fn invalid_proxy_url_is_rejected() {
+        for bad in [
+            "not a url",
+            "ftp://proxy.example:21",
+            "socks5://proxy.example",
+        ] {
+            let config = AurConfig {
+                proxy: Some(bad.into()),
+                ..AurConfig::default()
+            };
+            assert!(
+                matches!(AurClient::new(&config), Err(SynsyuError::Config(_))),
+                "expected {bad} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn password_without_username_is_rejected() {
+        let config = AurConfig {
+            proxy: Some("http://proxy.example:3128".into()),
+            proxy_password: Some("secret".into()),
+            ..AurConfig::default()
+        };
+        assert!(matches!(
+            AurClient::new(&config),
+            Err(SynsyuError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn raw_dump_captures_rpc_bodies_verbatim() {
+        let body = r#"{"resultcount":1,"results":[{"Name":"yay","Version":"12.4.2-1","CompressedSize":9000}],"type":"multiinfo"}"#;
+        let (base, _server) = mock_server(vec![json_response(body)]).await;
+        let dir = std::env::temp_dir().join(format!("synsyu-aur-rawdump-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let dump = Arc::new(RawDump::create(&dir).unwrap());
+        let client = tarball_client(&base).with_raw_dump(Some(dump));
+
+        let versions = client
+            .fetch_versions(&["yay".to_string()], &Logger::new(None, false).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(versions["yay"].version, "12.4.2-1");
+        let captured = std::fs::read_to_string(dir.join("001-aur-rpc.json")).unwrap();
+        assert_eq!(captured, body);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    const TLS_CERT: &[u8] = include_bytes!("../testdata/aur-tls-cert.pem");
+    const TLS_KEY: &[u8] = include_bytes!("../testdata/aur-tls-key.pem");
+    const TLS_CERT_SHA256: &str =
+        "37:58:A2:FA:97:17:00:DF:3E:9A:45:B5:5D:C3:1A:00:F8:73:E2:82:C8:A9:98:DF:BE:BA:31:1E:E4:87:52:A2";
+
+    /// HTTPS variant of `mock_server_forever` presenting the fixture certificate;
+    /// also returns how many request heads it received.
+    async fn tls_server(response: String) -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio_native_tls::native_tls::{Identity, TlsAcceptor};
+
+        let identity = Identity::from_pkcs8(TLS_CERT, TLS_KEY).expect("fixture identity");
+        let acceptor = tokio_native_tls::TlsAcceptor::from(TlsAcceptor::new(identity).unwrap());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let heads = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let seen = Arc::clone(&heads);
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                let response = response.clone();
+                let seen = Arc::clone(&seen);
+                tokio::spawn(async move {
+                    let Ok(mut stream) = acceptor.accept(socket).await else {
+                        return;
+                    };
+                    let mut buf = vec![0u8; 4096];
+                    let mut head = Vec::new();
+                    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match stream.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => head.extend_from_slice(&buf[..n]),
+                        }
+                    }
+                    seen.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+        (format!("https://{addr}"), heads)
+    }
+
+    fn pinned_client(base: &str, pin: &str) -> AurClient {
+        let config = AurConfig {
+            base_url: format!("{base}/rpc/"),
+            max_retries: 1,
+            pinned_cert_sha256: Some(pin.to_string()),
+            ..AurConfig::default()
+        };
+        let root = reqwest::Certificate::from_pem(TLS_CERT).unwrap();
+        AurClient::with_builder(
+            &config,
+            reqwest::Client::builder().add_root_certificate(root),
+        )
+        .expect("client should build")
+    }
+
+    #[tokio::test]
+    async fn matching_certificate_pin_is_accepted() {
+        let body = r#"{"resultcount":1,"results":[{"Name":"yay","Version":"12.4.2-1","CompressedSize":1}],"type":"multiinfo"}"#;
+        let (base, heads) = tls_server(json_response(body)).await;
+        let client = pinned_client(&base, TLS_CERT_SHA256);
+        let versions = client
+            .fetch_versions(&["yay".to_string()], &Logger::new(None, false).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(versions["yay"].version, "12.4.2-1");
+        assert_eq!(heads.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn mismatching_certificate_pin_is_rejected() {
+        let body = r#"{"resultcount":0,"results":[],"type":"multiinfo"}"#;
+        let (base, heads) = tls_server(json_response(body)).await;
+        let client = pinned_client(&base, &"ab".repeat(32));
+        let err = client
+            .fetch_versions(&["yay".to_string()], &Logger::new(None, false).unwrap())
+            .await
+            .expect_err("pin mismatch must fail");
+        assert!(matches!(err, SynsyuError::Network(_)));
+        assert!(
+            err.to_string()
+                .contains("does not match the pinned SHA-256"),
+            "{err}"
+        );
+        // The handshake failed, so neither the package list nor any token left the client.
+        assert_eq!(heads.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn malformed_pin_is_a_config_error() {
+        let config = AurConfig {
+            pinned_cert_sha256: Some("not-a-digest".into()),
+            ..AurConfig::default()
+        };
+        assert!(matches!(
+            AurClient::new(&config),
+            Err(SynsyuError::Config(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn oversized_responses_are_capped() {
+        let results: Vec<String> = (0..200)
+            .map(|i| format!(r#"{{"Name":"pkg{i}","Version":"1.0-1"}}"#))
+            .collect();
+        let body = format!(
+            r#"{{"resultcount":200,"type":"multiinfo","results":[{}]}}"#,
+            results.join(",")
+        );
+        // One response advertises its length, the other streams until close.
+        let streamed = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n{body}"
+        );
+        let (base, _handle) = mock_server(vec![json_response(&body), streamed]).await;
+        let config = AurConfig {
+            base_url: base,
+            max_response_bytes: 1024,
+            ..AurConfig::default()
+        };
+        let client = AurClient::new(&config).unwrap();
+        for _ in 0..2 {
+            let err = client
+                .fetch_versions(&["pkg0".to_string()], &Logger::new(None, false).unwrap())
+                .await
+                .unwrap_err();
+            assert!(matches!(err, SynsyuError::Serialization(_)), "{err}");
+            assert!(err.to_string().contains("max_args"));
+        }
+    }
+
+    #[tokio::test]
+    async fn malformed_aur_versions_are_dropped() {
+        let body = r#"{"resultcount":2,"type":"multiinfo","results":[
+            {"Name":"paru","Version":"1:2.0.4-1.1","Maintainer":"Morganamilo","CompressedSize":10},
+            {"Name":"broken","Version":"Error: upstream unavailable","Maintainer":"x","CompressedSize":10}]}"#;
+        let (base, _handle) = mock_server(vec![json_response(body)]).await;
+        let versions = tarball_client(&base)
+            .fetch_versions(
+                &["paru".to_string(), "broken".to_string()],
+                &Logger::new(None, false).unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(versions["paru"].version, "1:2.0.4-1.1");
+        assert!(!versions.contains_key("broken"));
+
+        for bad in [
+            "",
+            "1.0",
+            "1.0-",
+            "a:1.0-1",
+            "1.0-1 beta",
+            "1/0-1",
+            "<html>-1",
+        ] {
+            assert!(!is_sane_aur_version(bad), "{bad:?}");
+        }
+        for good in ["12.4.2-1", "r123.abc1234-2", "2.0_rc1+git~x-1"] {
+            assert!(is_sane_aur_version(good), "{good:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_chunks_are_retried_after_the_first_pass() {
+        use std::sync::Mutex;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::<String>::new()));
+        let seen = Arc::clone(&requests);
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    break;
+                };
+                let mut buf = vec![0u8; 4096];
+                let mut head = Vec::new();
+                while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match socket.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => head.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let head = String::from_utf8_lossy(&head).into_owned();
+                let name = head
+                    .split("arg[]=")
+                    .nth(1)
+                    .and_then(|rest| rest.split([' ', '&']).next())
+                    .unwrap_or_default()
+                    .to_string();
+                let first_try = {
+                    let mut seen = seen.lock().unwrap();
+                    let first = !seen.contains(&name);
+                    seen.push(name.clone());
+                    first
+                };
+                let response = if name == "beta" && first_try {
+                    "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string()
+                } else {
+                    json_response(&format!(
+                        r#"{{"resultcount":1,"type":"multiinfo","results":[{{"Name":"{name}","Version":"1.0-1","Maintainer":"me","CompressedSize":1}}]}}"#
+                    ))
+                };
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let config = AurConfig {
+            base_url: format!("http://{addr}/rpc/"),
+            max_args: 1,
+            max_parallel_requests: 1,
+            max_retries: 3,
+            backoff_base_ms: 1,
+            ..AurConfig::default()
+        };
+        let names: Vec<String> = ["alpha", "beta", "gamma"].map(String::from).into();
+        let versions = AurClient::new(&config)
+            .unwrap()
+            .fetch_versions(&names, &Logger::new(None, false).unwrap())
+            .await
+            .expect("beta succeeds on its second attempt");
+
+        assert_eq!(versions.len(), 3);
+        assert_eq!(versions["beta"].version, "1.0-1");
+        // beta's retry waits until every chunk had its first attempt.
+        assert_eq!(
+            *requests.lock().unwrap(),
+            ["alpha", "beta", "gamma", "beta"]
+        );
+    }
+}
@@ -0,0 +1,429 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::bench
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Measure backend latency (pacman -Si and the AUR RPC) at
+    several batch sizes and recommend tuning values.
+
+  Security / Safety Notes:
+    Issues read-only queries only; AUR probes honor the same
+    proxy and retry configuration as manifest generation.
+
+  Dependencies:
+    tokio for timing and concurrent probes.
+
+  Operational Scope:
+    Invoked via `synsyu_core bench` when operators tune
+    `core.si_chunk_size`, `aur.max_args`, and
+    `aur.max_parallel_requests`.
+
+  Revision History:
+    2026-10-18 COD  Introduced backend latency bench.
+    2026-10-18 COD  Split repo and foreign packages by `pacman -Qm`.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Probes reuse production backends unchanged
+    - Deterministic recommendation rules
+    - Explicit skip reporting when a backend is unavailable
+============================================================*/
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use clap::{ArgAction, Args};
+use serde::Serialize;
+use tokio::task::JoinSet;
+
+#[cfg(feature = "aur")]
+use crate::aur::AurClient;
+use crate::config::SynsyuConfig;
+use crate::error::{Result, SynsyuError};
+use crate::manifest::{source_from_repo, PackageSource};
+use crate::output::outln;
+use crate::pacman::{
+    enumerate_foreign_packages, enumerate_installed_packages, query_repo_versions, RetryPolicy,
+};
+
+/// Higher parallelism must beat the best lower setting by this fraction to be recommended.
+const PARALLEL_GAIN_THRESHOLD: f64 = 0.10;
+
+#[derive(Debug, Args, Clone)]
+pub struct BenchCommand {
+    /// Configuration file path; repeat to layer overrides (later files win).
+    #[arg(long, value_name = "PATH", action = ArgAction::Append)]
+    pub config: Vec<PathBuf>,
+    /// Calls issued per configuration.
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    pub rounds: usize,
+    /// `pacman -Si` chunk sizes to probe (repeatable).
+    #[arg(long = "si-chunk", value_name = "SIZE", action = ArgAction::Append)]
+    pub si_chunks: Vec<usize>,
+    /// AUR RPC batch sizes to probe (repeatable).
+    #[arg(long = "aur-batch", value_name = "SIZE", action = ArgAction::Append)]
+    pub aur_batches: Vec<usize>,
+    /// AUR parallelism levels to probe (repeatable).
+    #[arg(long = "aur-parallel", value_name = "N", action = ArgAction::Append)]
+    pub aur_parallel: Vec<usize>,
+    /// Skip AUR probes.
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub offline: bool,
+    /// Emit JSON output.
+    #[arg(long, action = ArgAction::SetTrue)]
+    pub json: bool,
+}
+
+/// Timing for one batch-size/parallelism configuration.
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchSample {
+    pub backend: &'static str,
+    pub batch: usize,
+    pub parallel: usize,
+    pub calls: usize,
+    pub packages: usize,
+    pub elapsed_ms: f64,
+}
+
+impl BenchSample {
+    pub fn ms_per_package(&self) -> f64 {
+        if self.packages == 0 {
+            f64::INFINITY
+        } else {
+            self.elapsed_ms / self.packages as f64
+        }
+    }
+}
+
+/// Tuning values derived from the samples; `None` when the backend was not probed.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct BenchRecommendation {
+    pub si_chunk_size: Option<usize>,
+    pub max_args: Option<usize>,
+    pub max_parallel_requests: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct BenchReport {
+    pub samples: Vec<BenchSample>,
+    pub skipped: Vec<String>,
+    pub recommendation: BenchRecommendation,
+}
+
+impl BenchCommand {
+    pub async fn execute(&self, config: &SynsyuConfig) -> Result<BenchReport> {
+        let foreign = enumerate_foreign_packages().await?;
+        let installed =
+            enumerate_installed_packages(&foreign, RetryPolicy::NONE, None, None).await?;
+        let (repo, foreign): (Vec<_>, Vec<_>) = installed
+            .into_iter()
+            .partition(|pkg| source_from_repo(pkg.repository.as_deref()) == PackageSource::Pacman);
+        let repo: Vec<String> = repo.into_iter().map(|pkg| pkg.name).collect();
+        let foreign: Vec<String> = foreign.into_iter().map(|pkg| pkg.name).collect();
+
+        let mut report = BenchReport::default();
+        let si_chunks = or_default(&self.si_chunks, &[16, 32, 64, 128]);
+        if repo.is_empty() {
+            report
+                .skipped
+                .push("pacman: no repository packages installed".into());
+        } else {
+            for &chunk in &si_chunks {
+                let sample =
+                    time_batches("pacman", &repo, chunk, 1, self.rounds, |names| async move {
+                        query_repo_versions(&names, names.len(), 1, RetryPolicy::NONE, None, None)
+                            .await
+                            .map(|found| found.len())
+                    })
+                    .await?;
+                report.samples.push(sample);
+            }
+        }
+
+        if self.offline {
+            report.skipped.push("aur: offline".into());
+        } else if foreign.is_empty() {
+            report
+                .skipped
+                .push("aur: no foreign packages installed".into());
+        } else {
+            self.bench_aur_sources(config, &foreign, &mut report)
+                .await?;
+        }
+
+        report.recommendation = recommend(&report.samples);
+        Ok(report)
+    }
+}
+
+impl BenchCommand {
+    #[cfg(feature = "aur")]
+    async fn bench_aur_sources(
+        &self,
+        config: &SynsyuConfig,
+        foreign: &[String],
+        report: &mut BenchReport,
+    ) -> Result<()> {
+        let mut client = AurClient::new(&config.aur)?;
+        if let Some(seed) = config.seed {
+            client = client.with_seed(seed);
+        }
+        let batches = or_default(&self.aur_batches, &[10, 25, 50, 100]);
+        let parallel = or_default(&self.aur_parallel, &[1, 2, 4, 8]);
+        bench_aur(&client, foreign, &batches, &parallel, self.rounds, report).await
+    }
+
+    #[cfg(not(feature = "aur"))]
+    async fn bench_aur_sources(
+        &self,
+        _config: &SynsyuConfig,
+        _foreign: &[String],
+        report: &mut BenchReport,
+    ) -> Result<()> {
+        report
+            .skipped
+            .push("aur: built without the `aur` feature".into());
+        Ok(())
+    }
+}
+
+/// Probe AUR batch sizes serially, then parallelism levels at the fastest batch size.
+#[cfg(feature = "aur")]
+async fn bench_aur(
+    client: &AurClient,
+    names: &[String],
+    batches: &[usize],
+    parallel: &[usize],
+    rounds: usize,
+    report: &mut BenchReport,
+) -> Result<()> {
+    let mut serial = Vec::new();
+    for &batch in batches {
+        let client = client.clone();
+        let sample = time_batches("aur", names, batch, 1, rounds, move |chunk| {
+            let client = client.clone();
+            async move { client.probe(&chunk).await }
+        })
+        .await?;
+        serial.push(sample);
+    }
+    let best_batch = fastest(serial.iter()).map(|s| s.batch);
+    report.samples.extend(serial);
+
+    if let Some(batch) = best_batch {
+        for &level in parallel.iter().filter(|&&p| p > 1) {
+            let client = client.clone();
+            let sample = time_batches("aur", names, batch, level, rounds, move |chunk| {
+                let client = client.clone();
+                async move { client.probe(&chunk).await }
+            })
+            .await?;
+            report.samples.push(sample);
+        }
+    }
+    Ok(())
+}
+
+/// Time `rounds` waves of `parallel` concurrent calls, each carrying `batch` names.
+///
+/// Names are drawn cyclically so small inventories still exercise large batches.
+async fn time_batches<F, Fut>(
+    backend: &'static str,
+    names: &[String],
+    batch: usize,
+    parallel: usize,
+    rounds: usize,
+    probe: F,
+) -> Result<BenchSample>
+where
+    F: Fn(Vec<String>) -> Fut,
+    Fut: Future<Output = Result<usize>> + Send + 'static,
+{
+    let batch = batch.max(1);
+    let parallel = parallel.max(1);
+    let mut cursor = names.iter().cycle();
+    let mut calls = 0usize;
+    let started = Instant::now();
+    for _ in 0..rounds.max(1) {
+        let mut tasks = JoinSet::new();
+        for _ in 0..parallel {
+            let chunk: Vec<String> = cursor.by_ref().take(batch).cloned().collect();
+            tasks.spawn(probe(chunk));
+        }
+        while let Some(joined) = tasks.join_next().await {
+            joined.map_err(|err| SynsyuError::Runtime(format!("Bench probe failed: {err}")))??;
+            calls += 1;
+        }
+    }
+    Ok(BenchSample {
+        backend,
+        batch,
+        parallel,
+        calls,
+        packages: calls * batch,
+        elapsed_ms: started.elapsed().as_secs_f64() * 1000.0,
+    })
+}
+
+fn or_default(values: &[usize], fallback: &[usize]) -> Vec<usize> {
+    let mut list: Vec<usize> = if values.is_empty() {
+        fallback.to_vec()
+    } else {
+        values.iter().copied().filter(|v| *v > 0).collect()
+    };
+    list.sort_unstable();
+    list.dedup();
+    list
+}
+
+/// Lowest per-package latency; ties favor the larger batch.
+fn fastest<'a>(samples: impl Iterator<Item = &'a BenchSample>) -> Option<&'a BenchSample> {
+    samples.min_by(|a, b| {
+        a.ms_per_package()
+            .total_cmp(&b.ms_per_package())
+            .then_with(|| b.batch.cmp(&a.batch))
+    })
+}
+
+/// Derive tuning values from the collected samples.
+pub fn recommend(samples: &[BenchSample]) -> BenchRecommendation {
+    let si_chunk_size = fastest(samples.iter().filter(|s| s.backend == "pacman")).map(|s| s.batch);
+    let max_args = fastest(
+        samples
+            .iter()
+            .filter(|s| s.backend == "aur" && s.parallel == 1),
+    )
+    .map(|s| s.batch);
+
+    let mut levels: Vec<&BenchSample> = samples
+        .iter()
+        .filter(|s| s.backend == "aur" && Some(s.batch) == max_args)
+        .collect();
+    levels.sort_by_key(|s| s.parallel);
+    let mut chosen: Option<&BenchSample> = None;
+    for sample in levels {
+        match chosen {
+            Some(best)
+                if sample.ms_per_package()
+                    >= best.ms_per_package() * (1.0 - PARALLEL_GAIN_THRESHOLD) => {}
+            _ => chosen = Some(sample),
+        }
+    }
+
+    BenchRecommendation {
+        si_chunk_size,
+        max_args,
+        max_parallel_requests: chosen.map(|s| s.parallel),
+    }
+}
+
+/// Render the report for terminal output.
+pub fn print_report(report: &BenchReport) -> Result<()> {
+    for sample in &report.samples {
+        outln!(
+            "{:<6} batch={:<4} parallel={:<2} calls={:<3} {:>9.1} ms total {:>8.2} ms/pkg",
+            sample.backend,
+            sample.batch,
+            sample.parallel,
+            sample.calls,
+            sample.elapsed_ms,
+            sample.ms_per_package()
+        );
+    }
+    for reason in &report.skipped {
+        outln!("skipped: {reason}");
+    }
+    outln!();
+    outln!("Recommended:");
+    let rec = &report.recommendation;
+    let show = |value: Option<usize>| value.map_or("n/a".to_string(), |v| v.to_string());
+    outln!("  core.si_chunk_size         = {}", show(rec.si_chunk_size));
+    outln!("  aur.max_args               = {}", show(rec.max_args));
+    outln!(
+        "  aur.max_parallel_requests  = {}",
+        show(rec.max_parallel_requests)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "aur")]
+    use crate::aur::test_support::{json_response, mock_server_forever};
+    #[cfg(feature = "aur")]
+    use crate::config::AurConfig;
+    use std::time::Duration;
+
+    fn names(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("pkg{i}")).collect()
+    }
+
+    #[tokio::test]
+    async fn fake_pacman_runner_favors_amortized_chunks() {
+        // Fixed per-call overhead dominates, so larger chunks win.
+        let pool = names(40);
+        let mut samples = Vec::new();
+        for chunk in [4, 16] {
+            let sample = time_batches("pacman", &pool, chunk, 1, 2, |names| async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(names.len())
+            })
+            .await
+            .expect("fake runner succeeds");
+            assert_eq!(sample.calls, 2);
+            assert_eq!(sample.packages, 2 * chunk);
+            samples.push(sample);
+        }
+        assert_eq!(recommend(&samples).si_chunk_size, Some(16));
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    async fn bench_against_mock_aur_emits_recommendations() {
+        let base = mock_server_forever(json_response(
+            r#"{"resultcount":0,"results":[],"type":"multiinfo"}"#,
+        ))
+        .await;
+        let client = AurClient::new(&AurConfig {
+            base_url: format!("{base}/rpc/"),
+            ..AurConfig::default()
+        })
+        .expect("client should build");
+
+        let mut report = BenchReport::default();
+        bench_aur(&client, &names(12), &[5, 10], &[1, 2], 2, &mut report)
+            .await
+            .expect("bench completes");
+        report.recommendation = recommend(&report.samples);
+
+        assert_eq!(
+            report.samples.iter().filter(|s| s.backend == "aur").count(),
+            3,
+            "two serial batch probes plus one parallel probe"
+        );
+        let rec = &report.recommendation;
+        assert!(matches!(rec.max_args, Some(5) | Some(10)));
+        assert!(matches!(rec.max_parallel_requests, Some(1) | Some(2)));
+        assert_eq!(rec.si_chunk_size, None);
+    }
+
+    #[test]
+    fn parallelism_requires_meaningful_gain() {
+        let sample = |parallel, elapsed_ms| BenchSample {
+            backend: "aur",
+            batch: 50,
+            parallel,
+            calls: 1,
+            packages: 100,
+            elapsed_ms,
+        };
+        let rec = recommend(&[sample(1, 100.0), sample(2, 95.0), sample(4, 40.0)]);
+        assert_eq!(rec.max_args, Some(50));
+        assert_eq!(rec.max_parallel_requests, Some(4));
+
+        let rec = recommend(&[sample(1, 100.0), sample(2, 95.0)]);
+        assert_eq!(rec.max_parallel_requests, Some(1));
+    }
+}
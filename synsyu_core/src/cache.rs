@@ -0,0 +1,373 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::cache
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Centralise crash-safe persistence for cache files carried
+    between Syn-Syu-Core runs.
+
+  Security / Safety Notes:
+    Cache files are written with private permissions via a
+    temp-file-and-rename sequence so readers never observe a
+    partially written file.
+
+  Dependencies:
+    serde_json for (de)serialization.
+
+  Operational Scope:
+    Used by every module that keeps state in the cache
+    directory (run history, lookup caches, metrics), and by
+    `cache prune` to reclaim space from it.
+
+  Revision History:
+    2026-10-18 COD  Introduced atomic cache persistence.
+    2026-10-18 COD  Added age/size based pruning.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Atomic replace semantics for on-disk state
+    - Corrupt caches are discarded with a warning, never fatal
+    - Explicit error paths for persistence failures
+============================================================*/
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::{Result, SynsyuError};
+use crate::logger::Logger;
+
+/// Serialize `value` as pretty JSON and atomically replace `path` with it.
+pub fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let bytes = serde_json::to_vec_pretty(value).map_err(|err| {
+        SynsyuError::Serialization(format!(
+            "Failed to serialize cache {}: {err}",
+            path.display()
+        ))
+    })?;
+    write_atomic(path, &bytes)
+}
+
+/// Write `bytes` to a sibling temp file, fsync it, and rename it over `path`.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent).map_err(|err| {
+        SynsyuError::Filesystem(format!(
+            "Failed to create cache directory {}: {err}",
+            parent.display()
+        ))
+    })?;
+
+    let temp = temp_path(path);
+    let result = write_temp(&temp, bytes).and_then(|_| {
+        fs::rename(&temp, path).map_err(|err| {
+            SynsyuError::Filesystem(format!("Failed to replace cache {}: {err}", path.display()))
+        })
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+        return result;
+    }
+
+    // Persist the rename itself; best effort since not every filesystem supports it.
+    if let Ok(dir) = File::open(parent) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// Load a JSON cache; missing files yield `None`, corrupt files are removed with a WARN.
+pub fn read_json<T: DeserializeOwned>(path: &Path, logger: &Logger) -> Option<T> {
+    let contents = match fs::read(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(err) => {
+            logger.warn(
+                "CACHE",
+                format!("Unable to read cache {}: {err}", path.display()),
+            );
+            return None;
+        }
+    };
+    match serde_json::from_slice(&contents) {
+        Ok(value) => Some(value),
+        Err(err) => {
+            logger.warn(
+                "CACHE",
+                format!("Discarding corrupt cache {}: {err}", path.display()),
+            );
+            if let Err(err) = fs::remove_file(path) {
+                logger.warn(
+                    "CACHE",
+                    format!("Failed to remove corrupt cache {}: {err}", path.display()),
+                );
+            }
+            None
+        }
+    }
+}
+
+/// Outcome of a `cache prune` pass.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PruneReport {
+    pub removed: Vec<PathBuf>,
+    pub bytes_reclaimed: u64,
+    /// Temp files of a writer that is still running, left alone.
+    pub skipped_in_flight: usize,
+}
+
+/// Parse an age such as `90s`, `45m`, `12h`, `30d` or `2w` (bare numbers are seconds).
+pub fn parse_age(text: &str) -> std::result::Result<Duration, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (digits, unit) = text.split_at(split);
+    let value: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid duration '{text}' (expected e.g. 30d, 12h, 45m)"))?;
+    let scale = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        "w" => 60 * 60 * 24 * 7,
+        other => return Err(format!("unknown duration unit '{other}' in '{text}'")),
+    };
+    value
+        .checked_mul(scale)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("duration '{text}' is out of range"))
+}
+
+/// Remove cache files older than `older_than`, then the least recently
+/// modified ones until the directory holds at most `max_size` bytes.
+///
+/// Temp files belonging to a live writer are never touched, so a concurrent
+/// `write_atomic` always finds its file in place for the final rename.
+pub fn prune(
+    dir: &Path,
+    older_than: Option<Duration>,
+    max_size: Option<u64>,
+    now: SystemTime,
+) -> Result<PruneReport> {
+    let mut report = PruneReport::default();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(report),
+        Err(err) => {
+            return Err(SynsyuError::Filesystem(format!(
+                "Failed to read cache directory {}: {err}",
+                dir.display()
+            )))
+        }
+    };
+
+    let mut files: Vec<(SystemTime, PathBuf, u64)> = Vec::new();
+    for entry in entries.flatten() {
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        if writer_alive(&entry.file_name().to_string_lossy()) {
+            report.skipped_in_flight += 1;
+            continue;
+        }
+        files.push((meta.modified().unwrap_or(now), entry.path(), meta.len()));
+    }
+    files.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+    let cutoff = older_than.and_then(|age| now.checked_sub(age));
+    let mut total: u64 = files.iter().map(|(_, _, size)| *size).sum();
+    for (mtime, path, size) in files {
+        let stale = cutoff.is_some_and(|cutoff| mtime < cutoff);
+        let over_budget = max_size.is_some_and(|limit| total > limit);
+        if !stale && !over_budget {
+            continue;
+        }
+        match fs::remove_file(&path) {
+            Ok(()) => {}
+            // Replaced or removed underneath us; nothing left to reclaim.
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(SynsyuError::Filesystem(format!(
+                    "Failed to remove cache file {}: {err}",
+                    path.display()
+                )))
+            }
+        }
+        total -= size;
+        report.bytes_reclaimed += size;
+        report.removed.push(path);
+    }
+    Ok(report)
+}
+
+/// Whether `name` is a `<file>.tmp.<pid>` temp file whose writer is still running.
+fn writer_alive(name: &str) -> bool {
+    let Some((_, pid)) = name.rsplit_once(".tmp.") else {
+        return false;
+    };
+    let Ok(pid) = pid.parse::<u32>() else {
+        return false;
+    };
+    pid == std::process::id() || Path::new("/proc").join(pid.to_string()).exists()
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".tmp.{}", std::process::id()));
+    path.with_file_name(name)
+}
+
+fn write_temp(temp: &Path, bytes: &[u8]) -> Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(temp).map_err(|err| {
+        SynsyuError::Filesystem(format!(
+            "Failed to create cache temp file {}: {err}",
+            temp.display()
+        ))
+    })?;
+    file.write_all(bytes)
+        .and_then(|_| file.sync_all())
+        .map_err(|err| {
+            SynsyuError::Filesystem(format!(
+                "Failed to write cache temp file {}: {err}",
+                temp.display()
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("synsyu-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn atomic_write_round_trips_without_leftovers() {
+        let dir = scratch("roundtrip");
+        let path = dir.join("versions.json");
+        let mut value = BTreeMap::new();
+        value.insert("paru".to_string(), "2.0.4-1".to_string());
+        write_json_atomic(&path, &value).expect("write succeeds");
+        write_json_atomic(&path, &value).expect("overwrite succeeds");
+
+        let logger = Logger::new(None, false).unwrap();
+        let loaded: BTreeMap<String, String> = read_json(&path, &logger).expect("cache loads");
+        assert_eq!(loaded, value);
+        let entries: Vec<_> = fs::read_dir(&dir).unwrap().flatten().collect();
+        assert_eq!(entries.len(), 1, "temp files must not linger");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn truncated_cache_is_discarded() {
+        let dir = scratch("truncated");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("versions.json");
+        fs::write(&path, br#"{"paru": "2.0."#).unwrap();
+
+        let logger = Logger::new(None, false).unwrap();
+        let loaded: Option<BTreeMap<String, String>> = read_json(&path, &logger);
+        assert!(loaded.is_none());
+        assert!(!path.exists(), "corrupt cache should be removed");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    fn seed(dir: &Path, name: &str, size: usize, age: Duration, now: SystemTime) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, vec![b'x'; size]).unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(now - age)
+            .unwrap();
+        path
+    }
+
+    #[test]
+    fn prune_removes_old_entries_then_least_recent_over_budget() {
+        let dir = scratch("prune");
+        fs::create_dir_all(&dir).unwrap();
+        let now = SystemTime::now();
+        let day = Duration::from_secs(60 * 60 * 24);
+        let ancient = seed(&dir, "history.json", 100, day * 40, now);
+        let older = seed(&dir, "aur.json", 300, day * 5, now);
+        let newer = seed(&dir, "metrics.json", 200, day * 2, now);
+        let fresh = seed(&dir, "versions.json", 400, Duration::from_secs(60), now);
+
+        let report = prune(&dir, Some(day * 30), None, now).unwrap();
+        assert_eq!(report.removed, vec![ancient.clone()]);
+        assert_eq!(report.bytes_reclaimed, 100);
+
+        // 900 bytes remain; trimming to 650 drops the oldest entry only.
+        let report = prune(&dir, None, Some(650), now).unwrap();
+        assert_eq!(report.removed, vec![older.clone()]);
+        assert_eq!(report.bytes_reclaimed, 300);
+        assert!(!ancient.exists() && !older.exists());
+        assert!(newer.exists() && fresh.exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn prune_leaves_live_writer_temp_files_alone() {
+        let dir = scratch("prune-inflight");
+        fs::create_dir_all(&dir).unwrap();
+        let now = SystemTime::now();
+        let old = Duration::from_secs(60 * 60 * 24 * 90);
+        let live = seed(
+            &dir,
+            &format!("versions.json.tmp.{}", std::process::id()),
+            50,
+            old,
+            now,
+        );
+        // Far above any pid_max, so no process owns it.
+        let abandoned = seed(&dir, "versions.json.tmp.4294967295", 50, old, now);
+
+        let report = prune(&dir, Some(Duration::from_secs(1)), Some(0), now).unwrap();
+        assert_eq!(report.removed, vec![abandoned]);
+        assert_eq!(report.skipped_in_flight, 1);
+        assert!(live.exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn ages_parse_with_units() {
+        assert_eq!(parse_age("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_age("45m"), Ok(Duration::from_secs(45 * 60)));
+        assert_eq!(parse_age("30d"), Ok(Duration::from_secs(30 * 86_400)));
+        assert!(parse_age("3y").is_err());
+        assert!(parse_age("d").is_err());
+    }
+
+    #[test]
+    fn missing_cache_is_quietly_absent() {
+        let dir = scratch("missing");
+        let logger = Logger::new(None, false).unwrap();
+        let loaded: Option<BTreeMap<String, String>> = read_json(&dir.join("none.json"), &logger);
+        assert!(loaded.is_none());
+    }
+}
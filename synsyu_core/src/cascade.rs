@@ -0,0 +1,132 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::cascade
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Derive the blast radius of each pending update: installed
+    packages that depend on it, directly or transitively.
+
+  Security / Safety Notes:
+    Pure graph traversal over data already collected from
+    `pacman -Qi`; no I/O performed in this module.
+
+  Dependencies:
+    None beyond std.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core when `--cascade` is set.
+
+  Revision History:
+    2026-10-18 COD  Introduced dependency cascade report.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Deterministic, sorted output
+    - Cycle-safe traversal
+============================================================*/
+
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use crate::manifest::ManifestDocument;
+use crate::pacman::InstalledPackage;
+
+/// Reverse-dependency edges: package name -> installed packages requiring it.
+pub fn reverse_dependencies(packages: &[InstalledPackage]) -> HashMap<&str, &[String]> {
+    packages
+        .iter()
+        .map(|pkg| (pkg.name.as_str(), pkg.required_by.as_slice()))
+        .collect()
+}
+
+/// Every installed package reachable through `Required By` edges from `name`.
+pub fn affected_dependents(graph: &HashMap<&str, &[String]>, name: &str) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    let mut queue = VecDeque::from([name]);
+    while let Some(current) = queue.pop_front() {
+        for dependent in graph.get(current).copied().unwrap_or_default() {
+            if dependent != name && seen.insert(dependent.clone()) {
+                queue.push_back(dependent.as_str());
+            }
+        }
+    }
+    seen.into_iter().collect()
+}
+
+/// Fill `affected_dependents` on every entry with a pending update.
+pub fn annotate_cascade(document: &mut ManifestDocument, packages: &[InstalledPackage]) {
+    let graph = reverse_dependencies(packages);
+    for (name, entry) in document.packages.iter_mut() {
+        if entry.update_available {
+            entry.affected_dependents = Some(affected_dependents(&graph, name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::{fixtures, PackageSource};
+
+    fn package(name: &str, required_by: &[&str]) -> InstalledPackage {
+        InstalledPackage {
+            name: name.into(),
+            version: "1.0-1".into(),
+            repository: Some("core".into()),
+            installed_size: None,
+            install_date: None,
+            validated_by: None,
+            package_hash: None,
+            required_by: required_by.iter().map(|s| s.to_string()).collect(),
+            provides: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn base_package_update_reaches_transitive_dependents() {
+        let installed = vec![
+            package("glibc", &["bash", "openssl", "zlib"]),
+            package("openssl", &["curl", "python"]),
+            package("zlib", &["curl", "glibc"]),
+            package("curl", &["pacman"]),
+            package("bash", &[]),
+            package("python", &[]),
+            package("pacman", &[]),
+            package("vim", &[]),
+        ];
+        let mut document = fixtures::document(vec![
+            (
+                "glibc",
+                fixtures::entry("2.40-1", PackageSource::Pacman, Some("2.40-2")),
+            ),
+            (
+                "curl",
+                fixtures::entry("8.10.1-1", PackageSource::Pacman, Some("8.10.1-2")),
+            ),
+            (
+                "vim",
+                fixtures::entry("9.1.0-1", PackageSource::Pacman, Some("9.1.0-1")),
+            ),
+        ]);
+
+        annotate_cascade(&mut document, &installed);
+        assert_eq!(
+            document.packages["glibc"].affected_dependents.as_deref(),
+            Some(
+                &[
+                    "bash".to_string(),
+                    "curl".into(),
+                    "openssl".into(),
+                    "pacman".into(),
+                    "python".into(),
+                    "zlib".into(),
+                ][..]
+            )
+        );
+        assert_eq!(
+            document.packages["curl"].affected_dependents.as_deref(),
+            Some(&["pacman".to_string()][..])
+        );
+        assert_eq!(document.packages["vim"].affected_dependents, None);
+    }
+}
@@ -0,0 +1,130 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::checkpoint
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Persist resolved manifest entries while a run progresses so
+    an interrupted run can `--resume` without re-resolving them.
+
+  Security / Safety Notes:
+    The `.partial` file sits beside the manifest and is replaced
+    atomically through the cache persistence helpers.
+
+  Dependencies:
+    crate::cache for atomic JSON writes and tolerant reads.
+
+  Operational Scope:
+    Fed by manifest construction; cleared once the run
+    completes.
+
+  Revision History:
+    2026-10-18 COD  Introduced resumable run checkpoints.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Readers never observe a half-written checkpoint
+    - Stale entries (installed version changed) are re-resolved
+============================================================*/
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{read_json, write_json_atomic};
+use crate::error::Result;
+use crate::logger::Logger;
+use crate::manifest::ManifestEntry;
+
+/// Resolved entries between checkpoint writes.
+pub const CHECKPOINT_EVERY: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct PartialManifest {
+    entries: BTreeMap<String, ManifestEntry>,
+}
+
+/// Progress of a manifest run, flushed to `<manifest>.partial`.
+#[derive(Debug)]
+pub struct Checkpoint {
+    path: PathBuf,
+    every: usize,
+    partial: PartialManifest,
+    pending: usize,
+}
+
+impl Checkpoint {
+    /// Checkpoint file kept beside `manifest`.
+    pub fn partial_path(manifest: &Path) -> PathBuf {
+        let mut name = manifest.as_os_str().to_owned();
+        name.push(".partial");
+        PathBuf::from(name)
+    }
+
+    /// Open the checkpoint; with `resume` earlier entries are loaded, otherwise any stale file is dropped.
+    pub fn start(path: PathBuf, every: usize, resume: bool, logger: &Logger) -> Self {
+        let partial = if resume {
+            let loaded: PartialManifest = read_json(&path, logger).unwrap_or_default();
+            logger.info(
+                "RESUME",
+                format!(
+                    "Loaded {} resolved entries from {}",
+                    loaded.entries.len(),
+                    path.display()
+                ),
+            );
+            loaded
+        } else {
+            let _ = fs::remove_file(&path);
+            PartialManifest::default()
+        };
+        Self {
+            path,
+            every: every.max(1),
+            partial,
+            pending: 0,
+        }
+    }
+
+    /// Entry resolved by an earlier run, if the installed version is unchanged.
+    pub fn resolved(&self, name: &str, installed_version: &str) -> Option<ManifestEntry> {
+        self.partial
+            .entries
+            .get(name)
+            .filter(|entry| entry.installed_version == installed_version)
+            .cloned()
+    }
+
+    /// Remember a freshly resolved entry, flushing every `every` records.
+    pub fn record(&mut self, name: &str, entry: &ManifestEntry) -> Result<()> {
+        self.partial.entries.insert(name.to_string(), entry.clone());
+        self.pending += 1;
+        if self.pending >= self.every {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Atomically write all entries recorded so far.
+    pub fn flush(&mut self) -> Result<()> {
+        if self.pending == 0 {
+            return Ok(());
+        }
+        write_json_atomic(&self.path, &self.partial)?;
+        self.pending = 0;
+        Ok(())
+    }
+
+    /// Remove the checkpoint once the run has completed.
+    pub fn clear(self, logger: &Logger) {
+        if let Err(err) = fs::remove_file(&self.path) {
+            if err.kind() != std::io::ErrorKind::NotFound {
+                logger.warn(
+                    "RESUME",
+                    format!("Failed to remove checkpoint {}: {err}", self.path.display()),
+                );
+            }
+        }
+    }
+}
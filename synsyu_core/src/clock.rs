@@ -0,0 +1,110 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::clock
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Detect a skewed system clock by comparing it with the AUR
+    server's `Date` header, since manifest and log timestamps
+    depend on it.
+
+  Security / Safety Notes:
+    Issues a single HEAD request; no local state is modified.
+
+  Dependencies:
+    chrono for time arithmetic.
+
+  Operational Scope:
+    Invoked at Syn-Syu-Core startup when `core.clock_skew_check`
+    is enabled and the run is not offline.
+
+  Revision History:
+    2026-10-18 COD  Introduced clock skew detection.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Advisory only: failures never abort a run
+    - Injected clock for deterministic tests
+============================================================*/
+
+use chrono::{DateTime, Utc};
+
+use crate::aur::AurClient;
+use crate::logger::Logger;
+
+/// Measure local-minus-server skew in seconds; WARN `CLOCKSKEW` above `threshold_secs`.
+pub async fn check_clock_skew(
+    client: &AurClient,
+    now: DateTime<Utc>,
+    threshold_secs: u64,
+    logger: &Logger,
+) -> Option<i64> {
+    let server = match client.server_date().await {
+        Ok(Some(server)) => server,
+        Ok(None) => {
+            logger.debug("CLOCKSKEW", "AUR response carried no usable Date header.");
+            return None;
+        }
+        Err(err) => {
+            logger.debug("CLOCKSKEW", format!("Clock check skipped: {err}"));
+            return None;
+        }
+    };
+    let skew = (now - server).num_seconds();
+    if skew.unsigned_abs() > threshold_secs {
+        logger.warn(
+            "CLOCKSKEW",
+            format!(
+                "System clock differs from AUR server by {skew}s (now={}, server={}); timestamps may be misleading",
+                now.to_rfc3339(),
+                server.to_rfc3339()
+            ),
+        );
+    }
+    Some(skew)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aur::test_support::mock_server;
+    use crate::config::AurConfig;
+
+    fn client(base: &str) -> AurClient {
+        AurClient::new(&AurConfig {
+            base_url: format!("{base}/rpc/"),
+            ..AurConfig::default()
+        })
+        .unwrap()
+    }
+
+    fn dated(date: &str) -> String {
+        format!("HTTP/1.1 200 OK\r\nDate: {date}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+    }
+
+    #[tokio::test]
+    async fn distant_server_date_is_reported_as_skew() {
+        let (base, handle) = mock_server(vec![dated("Sun, 18 Oct 2026 10:00:00 GMT")]).await;
+        let now = DateTime::parse_from_rfc3339("2026-10-18T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let logger = Logger::new(None, false).unwrap();
+
+        let skew = check_clock_skew(&client(&base), now, 300, &logger).await;
+        assert_eq!(skew, Some(7200));
+        let heads = handle.await.unwrap();
+        assert!(heads[0].starts_with("HEAD /rpc "), "{}", heads[0]);
+    }
+
+    #[tokio::test]
+    async fn missing_date_header_yields_no_measurement() {
+        let (base, _handle) = mock_server(vec![
+            "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+        ])
+        .await;
+        let logger = Logger::new(None, false).unwrap();
+        assert_eq!(
+            check_clock_skew(&client(&base), Utc::now(), 300, &logger).await,
+            None
+        );
+    }
+}
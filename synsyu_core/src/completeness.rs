@@ -0,0 +1,105 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::completeness
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Track lookups that were skipped or failed during a manifest
+    run so `--require-complete` can refuse a partial result.
+
+  Security / Safety Notes:
+    Records descriptions only; no I/O performed in this module.
+
+  Dependencies:
+    crate::manifest for the resolved package sources.
+
+  Operational Scope:
+    Fed by Syn-Syu-Core while resolving candidates; enforced
+    before any manifest or report is emitted.
+
+  Revision History:
+    2026-10-18 COD  Introduced run completeness tracking.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Degraded lookups stay visible instead of silently shrinking
+    - Every gap is enumerated, not just counted
+============================================================*/
+
+use crate::error::{Result, SynsyuError};
+use crate::logger::Logger;
+use crate::manifest::{ManifestDocument, PackageSource};
+
+/// Gaps that leave a manifest short of a full picture of the system.
+#[derive(Debug, Default)]
+pub struct Completeness {
+    gaps: Vec<String>,
+}
+
+impl Completeness {
+    /// Record one incomplete lookup.
+    pub fn record(&mut self, gap: impl Into<String>) {
+        self.gaps.push(gap.into());
+    }
+
+    #[cfg(test)]
+    pub fn gaps(&self) -> &[String] {
+        &self.gaps
+    }
+
+    /// Record packages whose source could not be determined.
+    pub fn record_unknown_sources(&mut self, document: &ManifestDocument) {
+        let unknown: Vec<&str> = document
+            .packages
+            .iter()
+            .filter(|(_, entry)| entry.source == PackageSource::Unknown)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if !unknown.is_empty() {
+            self.record(format!(
+                "source unknown for {} package(s): {}",
+                unknown.len(),
+                unknown.join(", ")
+            ));
+        }
+    }
+
+    /// Log every gap; fail with [`SynsyuError::Incomplete`] when `require` is set.
+    pub fn enforce(&self, require: bool, logger: &Logger) -> Result<()> {
+        for gap in &self.gaps {
+            logger.warn("INCOMPLETE", gap);
+        }
+        if require && !self.gaps.is_empty() {
+            return Err(SynsyuError::Incomplete(self.gaps.clone()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures;
+
+    #[test]
+    fn unknown_sources_are_enumerated_and_enforced() {
+        let document = fixtures::document(vec![
+            ("zsh", fixtures::entry("5.9-5", PackageSource::Pacman, None)),
+            (
+                "mystery",
+                fixtures::entry("1.0-1", PackageSource::Unknown, None),
+            ),
+        ]);
+        let logger = Logger::new(None, false).unwrap();
+        let mut completeness = Completeness::default();
+        completeness.record_unknown_sources(&document);
+
+        assert_eq!(
+            completeness.gaps(),
+            ["source unknown for 1 package(s): mystery"]
+        );
+        assert!(completeness.enforce(false, &logger).is_ok());
+        let err = completeness.enforce(true, &logger).unwrap_err();
+        assert!(matches!(err, SynsyuError::Incomplete(_)));
+        assert!(err.to_string().contains("mystery"));
+    }
+}
@@ -20,6 +20,27 @@
 
   Revision History:
     2024-11-04 COD  Authored configuration subsystem.
+    2026-10-18 COD  Layered multiple config files with deep merge.
+    2026-10-18 COD  Recorded loaded config paths and their SHA-256.
+    2026-10-18 COD  Added duplicate installed entry policy.
+    2026-10-18 COD  Added per-package resolution timeout.
+    2026-10-18 COD  Added `paths.style` for metadata and log paths.
+    2026-10-18 COD  Added `aur.token` (also from SYNSYU_AUR_TOKEN).
+    2026-10-18 COD  Added `core.pacman_db_lock` preflight path.
+    2026-10-18 COD  Added `aur.backoff_jitter` and the run seed.
+    2026-10-18 COD  Added `core.size_skew_percent` (SIZESKEW).
+    2026-10-18 COD  Added `core.reboot_required` patterns.
+    2026-10-18 COD  Added `core.expected_repo` (REPOMISS).
+    2026-10-18 COD  Added `core.vercmp_external`.
+    2026-10-18 COD  Added the `[stability]` policy.
+    2026-10-18 COD  Added `aur.cache_ttl_secs` for the AUR lookup cache.
+    2026-10-18 COD  Added `core.pacman_conf_path` (IgnorePkg/IgnoreGroup).
+    2026-10-18 COD  Added `core.si_max_parallel` for concurrent -Si chunks.
+    2026-10-18 COD  Added `logging.format` (text or JSON lines).
+    2026-10-18 COD  Added the `[pacman]` retry settings.
+    2026-10-18 COD  `+key` appends extend the defaults; key/+key clash.
+    2026-10-18 COD  Replaced `core.mirrorlist_path` with
+                    `core.sync_db_directory`.
   ------------------------------------------------------------
   SSE Principles Observed:
     - Explicit defaults with documented precedence
@@ -27,22 +48,27 @@
     - Deterministic error reporting with context
 ============================================================*/
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use dirs::config_dir;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::error::{Result, SynsyuError};
+use crate::rng::{parse_seed_env, SEED_ENV};
 
 /// Top-level configuration for Syn-Syu-Core.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SynsyuConfig {
     #[serde(default)]
     pub aur: AurConfig,
     #[serde(default)]
     pub core: CoreConfig,
     #[serde(default)]
+    pub pacman: PacmanConfig,
+    #[serde(default)]
     pub helpers: HelperConfig,
     #[serde(default)]
     pub space: SpaceConfig,
@@ -56,65 +82,81 @@ pub struct SynsyuConfig {
     pub safety: SafetyConfig,
     #[serde(default)]
     pub clean: CleanConfig,
+    #[serde(default)]
+    pub manifest: ManifestConfig,
+    #[serde(default)]
+    pub paths: PathsConfig,
+    #[serde(default)]
+    pub stability: StabilityConfig,
+    /// Files this configuration was loaded from; `None` for pure defaults.
+    #[serde(skip)]
+    pub source: Option<ConfigSource>,
+    /// Seed for randomized behavior (`SYNSYU_SEED`, overridden by `--seed`).
+    #[serde(skip)]
+    pub seed: Option<u64>,
+}
+
+/// Provenance of a loaded configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigSource {
+    /// Layer paths in load order, joined with ` + `.
+    pub path: String,
+    /// SHA-256 over the bytes of every layer, in load order.
+    pub sha256: String,
 }
 
 impl SynsyuConfig {
-    /// Load configuration, applying defaults and overriding with file contents if present.
-    pub fn load_from_optional_path(path: Option<&Path>) -> Result<Self> {
-        let mut config = SynsyuConfig::default();
-        if let Some(path) = path {
-            if path.exists() {
-                ensure_secure_permissions(path)?;
-                let contents = fs::read_to_string(path).map_err(|err| {
-                    SynsyuError::Filesystem(format!(
-                        "Failed to read configuration {}: {err}",
-                        path.display()
-                    ))
-                })?;
-                let external: SynsyuConfig = toml::from_str(&contents).map_err(|err| {
-                    SynsyuError::Config(format!(
-                        "Failed to parse configuration {}: {err}",
-                        path.display()
-                    ))
-                })?;
-                config.merge(external);
-            } else {
-                return Err(SynsyuError::Config(format!(
-                    "Configuration file {} does not exist",
-                    path.display()
-                )));
-            }
-        } else if let Some(default_path) = default_config_path() {
-            if default_path.exists() {
-                ensure_secure_permissions(&default_path)?;
-                let contents = fs::read_to_string(&default_path).map_err(|err| {
-                    SynsyuError::Filesystem(format!(
-                        "Failed to read configuration {}: {err}",
-                        default_path.display()
-                    ))
-                })?;
-                let external: SynsyuConfig = toml::from_str(&contents).map_err(|err| {
-                    SynsyuError::Config(format!(
-                        "Failed to parse configuration {}: {err}",
-                        default_path.display()
-                    ))
-                })?;
-                config.merge(external);
-            }
-        }
+    /// Load configuration layers in order, later files overriding earlier ones.
+    ///
+    /// With no explicit paths the default config file is used when present.
+    pub fn load_layers(paths: &[PathBuf]) -> Result<Self> {
+        let mut config = Self::load_files(paths)?;
+        config
+            .aur
+            .apply_token_env(std::env::var(AUR_TOKEN_ENV).ok());
+        config.seed = parse_seed_env(std::env::var(SEED_ENV).ok())?;
         Ok(config)
     }
 
-    fn merge(&mut self, other: SynsyuConfig) {
-        self.aur = other.aur;
-        self.core = other.core;
-        self.helpers = other.helpers;
-        self.space = other.space;
-        self.applications = other.applications;
-        self.logging = other.logging;
-        self.snapshots = other.snapshots;
-        self.safety = other.safety;
-        self.clean = other.clean;
+    fn load_files(paths: &[PathBuf]) -> Result<Self> {
+        let mut layers = paths.to_vec();
+        if layers.is_empty() {
+            match default_config_path() {
+                Some(default_path) if default_path.exists() => layers.push(default_path),
+                _ => return Ok(SynsyuConfig::default()),
+            }
+        } else if let Some(missing) = layers.iter().find(|path| !path.exists()) {
+            return Err(SynsyuError::Config(format!(
+                "Configuration file {} does not exist",
+                missing.display()
+            )));
+        }
+
+        // Seeded with the defaults so a `+key` append extends the built-in list.
+        let mut merged = toml::Table::try_from(SynsyuConfig::default()).map_err(|err| {
+            SynsyuError::Config(format!("Failed to serialize default configuration: {err}"))
+        })?;
+        let mut hasher = Sha256::new();
+        for path in &layers {
+            merge_tables(&mut merged, read_layer(path, &mut hasher)?).map_err(|key| {
+                SynsyuError::Config(format!(
+                    "Configuration {} both replaces and appends to `{key}`; use `key` or `+key`",
+                    path.display()
+                ))
+            })?;
+        }
+        let sources: Vec<String> = layers.iter().map(|p| p.display().to_string()).collect();
+        let mut config: SynsyuConfig = toml::Value::Table(merged).try_into().map_err(|err| {
+            SynsyuError::Config(format!(
+                "Failed to parse configuration {}: {err}",
+                sources.join(" + ")
+            ))
+        })?;
+        config.source = Some(ConfigSource {
+            path: sources.join(" + "),
+            sha256: format!("{:x}", hasher.finalize()),
+        });
+        Ok(config)
     }
 
     /// Manifest path resolved from configuration.
@@ -131,6 +173,15 @@ impl SynsyuConfig {
             .unwrap_or_else(default_log_dir)
     }
 
+    /// Cache directory for state carried between runs.
+    pub fn cache_dir(&self) -> PathBuf {
+        self.core
+            .cache_directory
+            .as_ref()
+            .map(|p| PathBuf::from(p.as_str()))
+            .unwrap_or_else(default_cache_dir)
+    }
+
     /// Preferred helper priority order.
     #[allow(dead_code)]
     pub fn helper_priority(&self) -> &[String] {
@@ -143,6 +194,7 @@ impl SynsyuConfig {
     }
 
     /// Policy for handling low space relative to the configured buffer.
+    #[allow(dead_code)]
     pub fn space_policy(&self) -> SpacePolicy {
         self.space.policy
     }
@@ -162,16 +214,20 @@ impl SynsyuConfig {
         ConfigReport {
             manifest_path: self.manifest_path(),
             log_directory: self.log_dir(),
+            cache_directory: self.cache_dir(),
             helper_priority: self.helpers.priority.clone(),
             helper_default: self.helpers.default.clone(),
             space_min_free_bytes: self.space.min_free_bytes(),
             space_policy: self.space.policy.to_string(),
             batch_size: self.core.batch_size,
+            source_policy: self.core.source_policy.to_string(),
             applications_flatpak: self.applications.flatpak,
             applications_fwupd: self.applications.fwupd,
             log_level: self.logging.level.clone(),
             log_retention_days: self.logging.retention_days,
             log_retention_megabytes: self.logging.retention_megabytes,
+            log_naming: self.logging.naming.to_string(),
+            log_format: self.logging.format.to_string(),
             snapshots_enabled: self.snapshots.enabled,
             snapshot_pre_command: self.snapshots.pre_command.clone(),
             snapshot_post_command: self.snapshots.post_command.clone(),
@@ -181,28 +237,14 @@ impl SynsyuConfig {
             clean_keep_versions: self.clean.keep_versions,
             clean_remove_orphans: self.clean.remove_orphans,
             clean_check_pacnew: self.clean.check_pacnew,
-        }
-    }
-}
-
-impl Default for SynsyuConfig {
-    fn default() -> Self {
-        Self {
-            aur: AurConfig::default(),
-            core: CoreConfig::default(),
-            helpers: HelperConfig::default(),
-            space: SpaceConfig::default(),
-            applications: ApplicationsConfig::default(),
-            logging: LoggingConfig::default(),
-            snapshots: SnapshotsConfig::default(),
-            safety: SafetyConfig::default(),
-            clean: CleanConfig::default(),
+            aur_token: self.aur.token().map(|_| REDACTED.to_string()),
         }
     }
 }
 
 /// Configuration options for AUR interactions.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(not(feature = "aur"), allow(dead_code))]
 pub struct AurConfig {
     #[serde(default = "AurConfig::default_base_url")]
     pub base_url: String,
@@ -212,6 +254,51 @@ pub struct AurConfig {
     pub max_retries: usize,
     #[serde(default = "AurConfig::default_timeout_seconds")]
     pub timeout: u64,
+    #[serde(default = "AurConfig::default_max_parallel_requests")]
+    pub max_parallel_requests: usize,
+    /// Idle keep-alive connections retained per AUR host.
+    #[serde(default = "AurConfig::default_pool_max_idle_per_host")]
+    pub pool_max_idle_per_host: usize,
+    /// Seconds an idle connection is kept for reuse (0 = until the server closes it).
+    #[serde(default = "AurConfig::default_pool_idle_timeout_secs")]
+    pub pool_idle_timeout_secs: u64,
+    /// Explicit proxy URL; overrides HTTP_PROXY/HTTPS_PROXY from the environment.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    #[serde(default)]
+    pub proxy_username: Option<String>,
+    #[serde(default)]
+    pub proxy_password: Option<String>,
+    /// Comma-separated hosts that bypass the explicit proxy (NO_PROXY syntax).
+    #[serde(default)]
+    pub no_proxy: Option<String>,
+    /// Delay growth between retries of a failed RPC request.
+    #[serde(default)]
+    pub backoff_strategy: BackoffStrategy,
+    /// First retry delay (exponential/linear step, or the constant delay).
+    #[serde(default = "AurConfig::default_backoff_base_ms")]
+    pub backoff_base_ms: u64,
+    /// Upper bound on any single retry delay.
+    #[serde(default = "AurConfig::default_backoff_max_ms")]
+    pub backoff_max_ms: u64,
+    /// Fraction (0.0–1.0) by which each retry delay is randomly shortened; 0 disables jitter.
+    #[serde(default)]
+    pub backoff_jitter: f64,
+    /// Largest RPC response body accepted per chunk; bigger bodies fail the chunk.
+    #[serde(default = "AurConfig::default_max_response_bytes")]
+    pub max_response_bytes: usize,
+    /// SHA-256 (hex) of the AUR server's DER certificate; handshakes presenting any other certificate fail.
+    #[serde(default)]
+    pub pinned_cert_sha256: Option<String>,
+    /// Sent as `Authorization: Bearer <token>`; `SYNSYU_AUR_TOKEN` overrides it. Never logged.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Seconds a cached AUR lookup is reused before it is requested again (0 disables the cache).
+    #[serde(default = "AurConfig::default_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+    /// Refetch every lookup this run, ignoring cached results (`--refresh-cache`).
+    #[serde(skip)]
+    pub refresh_cache: bool,
 }
 
 impl AurConfig {
@@ -227,6 +314,58 @@ impl AurConfig {
     fn default_timeout_seconds() -> u64 {
         10
     }
+    fn default_max_parallel_requests() -> usize {
+        4
+    }
+    fn default_pool_max_idle_per_host() -> usize {
+        8
+    }
+    fn default_pool_idle_timeout_secs() -> u64 {
+        90
+    }
+    fn default_backoff_base_ms() -> u64 {
+        200
+    }
+    fn default_backoff_max_ms() -> u64 {
+        51_200
+    }
+    fn default_max_response_bytes() -> usize {
+        8 * 1024 * 1024
+    }
+    fn default_cache_ttl_secs() -> u64 {
+        600
+    }
+
+    /// Let a non-empty `SYNSYU_AUR_TOKEN` value replace the configured token.
+    fn apply_token_env(&mut self, value: Option<String>) {
+        if let Some(token) = value.filter(|token| !token.trim().is_empty()) {
+            self.token = Some(token);
+        }
+    }
+
+    /// Configured token, ignoring blank values.
+    pub fn token(&self) -> Option<&str> {
+        self.token
+            .as_deref()
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+    }
+}
+
+/// Environment variable supplying `aur.token`.
+pub const AUR_TOKEN_ENV: &str = "SYNSYU_AUR_TOKEN";
+
+/// Placeholder shown wherever a secret value would otherwise appear.
+pub const REDACTED: &str = "<redacted>";
+
+/// Derive the AUR web root (used for snapshot URLs) from the RPC endpoint.
+pub fn web_base_url(rpc_base: &str) -> String {
+    // Trim trailing /rpc to derive the host root for tarball fetches.
+    let mut base = rpc_base.trim_end_matches('/').to_string();
+    if let Some(idx) = base.rfind("/rpc") {
+        base.truncate(idx);
+    }
+    base
 }
 
 impl Default for AurConfig {
@@ -236,20 +375,108 @@ impl Default for AurConfig {
             max_args: Self::default_max_args(),
             max_retries: Self::default_max_retries(),
             timeout: Self::default_timeout_seconds(),
+            max_parallel_requests: Self::default_max_parallel_requests(),
+            pool_max_idle_per_host: Self::default_pool_max_idle_per_host(),
+            pool_idle_timeout_secs: Self::default_pool_idle_timeout_secs(),
+            proxy: None,
+            proxy_username: None,
+            proxy_password: None,
+            no_proxy: None,
+            backoff_strategy: BackoffStrategy::default(),
+            backoff_base_ms: Self::default_backoff_base_ms(),
+            backoff_max_ms: Self::default_backoff_max_ms(),
+            backoff_jitter: 0.0,
+            max_response_bytes: Self::default_max_response_bytes(),
+            pinned_cert_sha256: None,
+            token: None,
+            cache_ttl_secs: Self::default_cache_ttl_secs(),
+            refresh_cache: false,
         }
     }
 }
 
+/// How AUR retry delays grow with each attempt.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackoffStrategy {
+    /// `base * 2^attempt`.
+    #[default]
+    Exponential,
+    /// `base * attempt`.
+    Linear,
+    /// `base` every time.
+    Constant,
+}
+
 /// Configuration for core runtime.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CoreConfig {
     #[serde(default = "CoreConfig::default_manifest_path")]
     pub manifest_path: String,
     #[serde(default)]
     pub log_directory: Option<String>,
+    #[serde(default)]
+    pub cache_directory: Option<String>,
+    /// pacman package cache inspected by `--check-pkg-cache`.
+    #[serde(default = "CoreConfig::default_pkg_cache_directory")]
+    pub pkg_cache_directory: String,
+    /// pacman sync databases, read for the package file names in `download_url`.
+    #[serde(default = "CoreConfig::default_sync_db_directory")]
+    pub sync_db_directory: String,
+    /// pacman.conf whose `IgnorePkg`/`IgnoreGroup` hold packages back.
+    #[serde(default = "CoreConfig::default_pacman_conf_path")]
+    pub pacman_conf_path: String,
+    /// pacman database lock; its presence means a transaction is in progress.
+    #[serde(default = "CoreConfig::default_pacman_db_lock")]
+    pub pacman_db_lock: String,
     #[serde(default = "CoreConfig::default_batch_size")]
     #[allow(dead_code)]
     pub batch_size: usize,
+    /// Packages per `pacman -Si` invocation.
+    #[serde(default = "CoreConfig::default_si_chunk_size")]
+    pub si_chunk_size: usize,
+    /// `pacman -Si` invocations allowed to run at once.
+    #[serde(default = "CoreConfig::default_si_max_parallel")]
+    pub si_max_parallel: usize,
+    /// Which source wins when both the repos and the AUR offer a candidate.
+    #[serde(default)]
+    pub source_policy: SourcePolicy,
+    /// Packages kept in every manifest, even outside a `--package` allowlist.
+    #[serde(default)]
+    pub always_include: Vec<String>,
+    /// Packages left out of every manifest; wins over `always_include`.
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// Packages that must come from the official repositories; never looked up in the AUR.
+    #[serde(default)]
+    pub expected_repo: Vec<String>,
+    /// Per-package source forced regardless of policy (`name = "aur"|"repo"|"local"`).
+    #[serde(default)]
+    pub source_override: BTreeMap<String, SourceOverride>,
+    /// Fold version strings to lowercase before comparing; deviates from strict vercmp.
+    #[serde(default)]
+    pub version_case_fold: bool,
+    /// Spawn the `vercmp` binary per comparison instead of the built-in port.
+    #[serde(default)]
+    pub vercmp_external: bool,
+    /// Compare the system clock with the AUR server's `Date` header at startup.
+    #[serde(default)]
+    pub clock_skew_check: bool,
+    #[serde(default = "CoreConfig::default_clock_skew_threshold_secs")]
+    #[cfg_attr(not(feature = "aur"), allow(dead_code))]
+    pub clock_skew_threshold_secs: u64,
+    /// Which entry survives when `pacman -Qi` lists a name more than once.
+    #[serde(default)]
+    pub duplicate_installed: DuplicatePolicy,
+    /// Longest a single package may take to resolve (seconds, 0 = unlimited).
+    #[serde(default = "CoreConfig::default_package_timeout_secs")]
+    pub package_timeout_secs: u64,
+    /// Installed-size difference (percent) tolerated between equal installed and candidate versions (0 = off).
+    #[serde(default = "CoreConfig::default_size_skew_percent")]
+    pub size_skew_percent: f64,
+    /// Glob patterns of packages whose update needs a reboot to take effect.
+    #[serde(default = "CoreConfig::default_reboot_required")]
+    pub reboot_required: Vec<String>,
 }
 
 impl CoreConfig {
@@ -263,9 +490,51 @@ impl CoreConfig {
             .into_owned()
     }
 
+    fn default_pkg_cache_directory() -> String {
+        "/var/cache/pacman/pkg".to_string()
+    }
+
+    fn default_sync_db_directory() -> String {
+        "/var/lib/pacman/sync".to_string()
+    }
+
+    fn default_pacman_conf_path() -> String {
+        "/etc/pacman.conf".to_string()
+    }
+
+    fn default_pacman_db_lock() -> String {
+        "/var/lib/pacman/db.lck".to_string()
+    }
+
     fn default_batch_size() -> usize {
         10
     }
+
+    fn default_si_chunk_size() -> usize {
+        64
+    }
+
+    fn default_si_max_parallel() -> usize {
+        4
+    }
+
+    fn default_clock_skew_threshold_secs() -> u64 {
+        300
+    }
+
+    fn default_package_timeout_secs() -> u64 {
+        120
+    }
+
+    fn default_size_skew_percent() -> f64 {
+        10.0
+    }
+
+    fn default_reboot_required() -> Vec<String> {
+        ["linux", "linux-lts", "systemd", "*-firmware"]
+            .map(String::from)
+            .to_vec()
+    }
 }
 
 impl Default for CoreConfig {
@@ -273,13 +542,98 @@ impl Default for CoreConfig {
         Self {
             manifest_path: Self::default_manifest_path(),
             log_directory: None,
+            cache_directory: None,
+            pkg_cache_directory: Self::default_pkg_cache_directory(),
+            sync_db_directory: Self::default_sync_db_directory(),
+            pacman_conf_path: Self::default_pacman_conf_path(),
+            pacman_db_lock: Self::default_pacman_db_lock(),
             batch_size: Self::default_batch_size(),
+            si_chunk_size: Self::default_si_chunk_size(),
+            si_max_parallel: Self::default_si_max_parallel(),
+            source_policy: SourcePolicy::default(),
+            always_include: Vec::new(),
+            ignore: Vec::new(),
+            expected_repo: Vec::new(),
+            source_override: BTreeMap::new(),
+            version_case_fold: false,
+            vercmp_external: false,
+            clock_skew_check: false,
+            clock_skew_threshold_secs: Self::default_clock_skew_threshold_secs(),
+            duplicate_installed: DuplicatePolicy::default(),
+            package_timeout_secs: Self::default_package_timeout_secs(),
+            size_skew_percent: Self::default_size_skew_percent(),
+            reboot_required: Self::default_reboot_required(),
+        }
+    }
+}
+
+/// Resolution for a package name listed more than once by `pacman -Qi`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePolicy {
+    /// Keep the entry with the highest version.
+    #[default]
+    KeepHighest,
+    /// Keep the entry pacman listed first.
+    KeepFirst,
+}
+
+impl std::fmt::Display for DuplicatePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DuplicatePolicy::KeepHighest => write!(f, "keep_highest"),
+            DuplicatePolicy::KeepFirst => write!(f, "keep_first"),
+        }
+    }
+}
+
+/// Tiebreak between repository and AUR candidates for the same package.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SourcePolicy {
+    /// Use the repo candidate unless the AUR is strictly newer.
+    #[default]
+    #[serde(rename = "prefer_repo")]
+    Repo,
+    /// Use the AUR candidate whenever the AUR has one; repos are the fallback.
+    #[serde(rename = "prefer_aur")]
+    Aur,
+    /// Use whichever candidate is newer; ties keep the installed source.
+    #[serde(rename = "prefer_newer")]
+    Newer,
+}
+
+impl std::fmt::Display for SourcePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourcePolicy::Repo => write!(f, "prefer_repo"),
+            SourcePolicy::Aur => write!(f, "prefer_aur"),
+            SourcePolicy::Newer => write!(f, "prefer_newer"),
+        }
+    }
+}
+
+/// Source a single package is pinned to by `core.source_override`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceOverride {
+    Aur,
+    Repo,
+    /// Locally built; never offered an update.
+    Local,
+}
+
+impl std::fmt::Display for SourceOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceOverride::Aur => write!(f, "aur"),
+            SourceOverride::Repo => write!(f, "repo"),
+            SourceOverride::Local => write!(f, "local"),
         }
     }
 }
 
 /// Disk space requirements.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SpaceConfig {
     #[serde(default = "SpaceConfig::default_min_free_gb")]
     pub min_free_gb: f64,
@@ -314,7 +668,7 @@ impl Default for SpaceConfig {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum SpacePolicy {
     Warn,
@@ -331,7 +685,7 @@ impl std::fmt::Display for SpacePolicy {
 }
 
 /// Preferred helper prioritization.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct HelperConfig {
     #[serde(default = "HelperConfig::default_priority")]
     #[allow(dead_code)]
@@ -360,28 +714,121 @@ impl Default for HelperConfig {
     }
 }
 
-/// Application metadata collection toggles.
-#[derive(Debug, Deserialize, Clone)]
-pub struct ApplicationsConfig {
+/// Output file handling for the manifest (and the core log).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ManifestConfig {
+    /// A manifest or log path naming a directory gets a timestamped file inside it.
     #[serde(default)]
-    pub flatpak: bool,
+    pub dir_generates_named_file: bool,
+    /// Record `config_path` and `config_sha256` in the manifest metadata.
+    #[serde(default = "ManifestConfig::default_record_config")]
+    pub record_config: bool,
+    /// Refuse to write a manifest whose target path is a symlink.
     #[serde(default)]
-    pub fwupd: bool,
+    pub no_symlink: bool,
+    /// Directory every manifest write must resolve into (symlinks followed).
+    #[serde(default)]
+    pub allowed_base: Option<String>,
+}
+
+impl ManifestConfig {
+    fn default_record_config() -> bool {
+        true
+    }
 }
 
-impl Default for ApplicationsConfig {
+impl Default for ManifestConfig {
     fn default() -> Self {
         Self {
-            flatpak: false,
-            fwupd: false,
+            dir_generates_named_file: false,
+            record_config: Self::default_record_config(),
+            no_symlink: false,
+            allowed_base: None,
         }
     }
 }
 
+/// Thresholds a pending update must meet to count as stable.
+///
+/// Zero disables a numeric threshold; AUR-only checks ignore repo candidates.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StabilityConfig {
+    /// Minimum days since the candidate was built (repo) or last modified (AUR).
+    #[serde(default)]
+    pub min_age_days: u64,
+    /// Minimum AUR `NumVotes`.
+    #[serde(default)]
+    pub min_aur_votes: u64,
+    /// Minimum AUR `Popularity`.
+    #[serde(default)]
+    pub min_aur_popularity: f64,
+    /// AUR candidates flagged out of date are unstable.
+    #[serde(default = "StabilityConfig::default_reject_out_of_date")]
+    pub reject_out_of_date: bool,
+    /// AUR candidates without a maintainer are unstable.
+    #[serde(default = "StabilityConfig::default_require_maintainer")]
+    pub require_maintainer: bool,
+}
+
+impl StabilityConfig {
+    fn default_reject_out_of_date() -> bool {
+        true
+    }
+
+    fn default_require_maintainer() -> bool {
+        true
+    }
+}
+
+impl Default for StabilityConfig {
+    fn default() -> Self {
+        Self {
+            min_age_days: 0,
+            min_aur_votes: 0,
+            min_aur_popularity: 0.0,
+            reject_out_of_date: Self::default_reject_out_of_date(),
+            require_maintainer: Self::default_require_maintainer(),
+        }
+    }
+}
+
+/// How paths appear in manifest metadata and log messages.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PathsConfig {
+    #[serde(default)]
+    pub style: PathStyle,
+    /// Prefix stripped under `relative`; defaults to the home directory.
+    #[serde(default)]
+    pub base: Option<String>,
+}
+
+/// Rendering of paths under `paths.style`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+    /// Paths as given.
+    #[default]
+    Absolute,
+    /// Paths under `paths.base` made relative to it.
+    Relative,
+    /// The home directory prefix replaced by `~`.
+    Redacted,
+}
+
+/// Application metadata collection toggles.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ApplicationsConfig {
+    #[serde(default)]
+    pub flatpak: bool,
+    #[serde(default)]
+    pub fwupd: bool,
+}
+
 /// Logging preferences.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LoggingConfig {
     #[serde(default)]
+    #[allow(dead_code)]
     pub directory: Option<String>,
     #[serde(default)]
     pub level: Option<String>,
@@ -389,6 +836,21 @@ pub struct LoggingConfig {
     pub retention_days: Option<u64>,
     #[serde(default)]
     pub retention_megabytes: Option<u64>,
+    #[serde(default)]
+    pub naming: LogNaming,
+    /// Encoding of log entries, in the file and on stderr.
+    #[serde(default)]
+    pub format: LogFormat,
+}
+
+impl LoggingConfig {
+    /// File name of the core session log.
+    pub fn core_log_name(&self, session_stamp: &str) -> String {
+        match self.naming {
+            LogNaming::Timestamped => format!("core_{session_stamp}.log"),
+            LogNaming::Fixed => FIXED_CORE_LOG.to_string(),
+        }
+    }
 }
 
 impl Default for LoggingConfig {
@@ -398,12 +860,57 @@ impl Default for LoggingConfig {
             level: Some("info".to_string()),
             retention_days: None,
             retention_megabytes: None,
+            naming: LogNaming::default(),
+            format: LogFormat::default(),
+        }
+    }
+}
+
+/// Encoding of each log entry (`logging.format`, `--log-format`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// `timestamp [LEVEL] [CODE] message`.
+    #[default]
+    Text,
+    /// One JSON object per line with `timestamp`, `level`, `code`, and `message`.
+    Json,
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogFormat::Text => write!(f, "text"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Stable core log name used with `naming = "fixed"`.
+pub const FIXED_CORE_LOG: &str = "core.log";
+
+/// How core log files are named.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogNaming {
+    /// One `core_<stamp>.log` per session.
+    #[default]
+    Timestamped,
+    /// A single `core.log` left to external rotation (e.g. logrotate).
+    Fixed,
+}
+
+impl std::fmt::Display for LogNaming {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogNaming::Timestamped => write!(f, "timestamped"),
+            LogNaming::Fixed => write!(f, "fixed"),
         }
     }
 }
 
 /// Snapshot hooks configuration.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SnapshotsConfig {
     #[serde(default)]
     pub enabled: bool,
@@ -415,19 +922,38 @@ pub struct SnapshotsConfig {
     pub require_success: bool,
 }
 
-impl Default for SnapshotsConfig {
+/// pacman invocation tuning.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PacmanConfig {
+    /// Tries per `pacman -Qi`/`-Si` call when it fails transiently (db lock held); 1 disables retries.
+    #[serde(default = "PacmanConfig::default_retry_attempts")]
+    pub retry_attempts: u32,
+    /// Delay before the first retry; doubled for each further one.
+    #[serde(default = "PacmanConfig::default_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+}
+
+impl PacmanConfig {
+    fn default_retry_attempts() -> u32 {
+        3
+    }
+
+    fn default_retry_delay_ms() -> u64 {
+        500
+    }
+}
+
+impl Default for PacmanConfig {
     fn default() -> Self {
         Self {
-            enabled: false,
-            pre_command: None,
-            post_command: None,
-            require_success: false,
+            retry_attempts: Self::default_retry_attempts(),
+            retry_delay_ms: Self::default_retry_delay_ms(),
         }
     }
 }
 
 /// Safety tuning.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SafetyConfig {
     #[serde(default = "SafetyConfig::default_disk_check")]
     pub disk_check: bool,
@@ -451,7 +977,7 @@ impl Default for SafetyConfig {
 }
 
 /// Cache/cleanup preferences.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CleanConfig {
     #[serde(default = "CleanConfig::default_keep_versions")]
     pub keep_versions: u64,
@@ -485,16 +1011,20 @@ impl Default for CleanConfig {
 pub struct ConfigReport {
     pub manifest_path: PathBuf,
     pub log_directory: PathBuf,
+    pub cache_directory: PathBuf,
     pub helper_priority: Vec<String>,
     pub helper_default: Option<String>,
     pub space_min_free_bytes: u64,
     pub space_policy: String,
     pub batch_size: usize,
+    pub source_policy: String,
     pub applications_flatpak: bool,
     pub applications_fwupd: bool,
     pub log_level: Option<String>,
     pub log_retention_days: Option<u64>,
     pub log_retention_megabytes: Option<u64>,
+    pub log_naming: String,
+    pub log_format: String,
     pub snapshots_enabled: bool,
     pub snapshot_pre_command: Option<String>,
     pub snapshot_post_command: Option<String>,
@@ -504,6 +1034,8 @@ pub struct ConfigReport {
     pub clean_keep_versions: u64,
     pub clean_remove_orphans: bool,
     pub clean_check_pacnew: bool,
+    /// `<redacted>` when a token is configured; the value itself is never reported.
+    pub aur_token: Option<String>,
 }
 
 fn default_config_path() -> Option<PathBuf> {
@@ -517,6 +1049,72 @@ fn default_log_dir() -> PathBuf {
         .join("logs")
 }
 
+fn default_cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| {
+            PathBuf::from(std::env::var("HOME").unwrap_or_else(|_| ".".into())).join(".cache")
+        })
+        .join("syn-syu")
+}
+
+fn read_layer(path: &Path, hasher: &mut Sha256) -> Result<toml::Table> {
+    ensure_secure_permissions(path)?;
+    let contents = fs::read_to_string(path).map_err(|err| {
+        SynsyuError::Filesystem(format!(
+            "Failed to read configuration {}: {err}",
+            path.display()
+        ))
+    })?;
+    hasher.update(contents.as_bytes());
+    contents.parse::<toml::Table>().map_err(|err| {
+        SynsyuError::Config(format!(
+            "Failed to parse configuration {}: {err}",
+            path.display()
+        ))
+    })
+}
+
+/// Deep-merge `overlay` into `base`: tables merge key-by-key, other values replace,
+/// and a `+key` array appends to `key` instead of replacing it.
+///
+/// Fails with the dotted key when one table sets both `key` and `+key`.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) -> std::result::Result<(), String> {
+    if let Some(key) = overlay.keys().find_map(|key| {
+        key.strip_prefix('+')
+            .filter(|target| overlay.contains_key(*target))
+    }) {
+        return Err(key.to_string());
+    }
+    for (key, value) in overlay {
+        if let (Some(target), toml::Value::Array(items)) = (key.strip_prefix('+'), &value) {
+            match base.get_mut(target) {
+                Some(toml::Value::Array(existing)) => existing.extend(items.iter().cloned()),
+                _ => {
+                    base.insert(target.to_string(), value);
+                }
+            }
+            continue;
+        }
+        match value {
+            toml::Value::Table(table) => {
+                let slot = base
+                    .entry(key.clone())
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+                if !slot.is_table() {
+                    *slot = toml::Value::Table(toml::Table::new());
+                }
+                if let toml::Value::Table(existing) = slot {
+                    merge_tables(existing, table).map_err(|inner| format!("{key}.{inner}"))?;
+                }
+            }
+            other => {
+                base.insert(key, other);
+            }
+        }
+    }
+    Ok(())
+}
+
 fn ensure_secure_permissions(path: &Path) -> Result<()> {
     let metadata = fs::metadata(path).map_err(|err| {
         SynsyuError::Filesystem(format!(
@@ -554,3 +1152,129 @@ fn ensure_secure_permissions(path: &Path) -> Result<()> {
     })?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn core_log_name_follows_naming_policy() {
+        let mut logging = LoggingConfig::default();
+        let stamp = "2026-10-18_09-30-00";
+        assert_eq!(logging.core_log_name(stamp), "core_2026-10-18_09-30-00.log");
+
+        logging.naming = LogNaming::Fixed;
+        assert_eq!(logging.core_log_name(stamp), "core.log");
+    }
+
+    #[test]
+    fn naming_is_read_from_logging_section() {
+        let config: SynsyuConfig = toml::from_str("[logging]\nnaming = \"fixed\"\n").unwrap();
+        assert_eq!(config.logging.naming, LogNaming::Fixed);
+        let config: SynsyuConfig = toml::from_str("").unwrap();
+        assert_eq!(config.logging.naming, LogNaming::Timestamped);
+    }
+
+    #[test]
+    fn aur_token_comes_from_env_and_is_masked_in_report() {
+        let mut config: SynsyuConfig =
+            toml::from_str("[aur]\ntoken = \"file-token-123\"\n").unwrap();
+        config.aur.apply_token_env(Some("  ".into()));
+        assert_eq!(config.aur.token(), Some("file-token-123"));
+        config.aur.apply_token_env(Some("env-token-456".into()));
+        assert_eq!(config.aur.token(), Some("env-token-456"));
+
+        let dump = serde_json::to_string(&config.to_report()).unwrap();
+        assert!(dump.contains(r#""aur_token":"<redacted>""#));
+        assert!(!dump.contains("token-456"));
+        assert!(SynsyuConfig::default().to_report().aur_token.is_none());
+    }
+
+    fn layer(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn layered_files_deep_merge_scalars_and_lists() {
+        let dir = std::env::temp_dir().join(format!("synsyu-config-layers-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let base = layer(
+            &dir,
+            "base.toml",
+            "[aur]\nmax_args = 20\ntimeout = 30\n\n[helpers]\npriority = [\"paru\", \"yay\"]\n\n[clean]\nkeep_versions = 5\n",
+        );
+        let host = layer(
+            &dir,
+            "host.toml",
+            "[aur]\ntimeout = 5\n\n[helpers]\n\"+priority\" = [\"pikaur\"]\n",
+        );
+        let config = SynsyuConfig::load_layers(&[base.clone(), host.clone()]).unwrap();
+        assert_eq!(config.aur.max_args, 20);
+        assert_eq!(config.aur.timeout, 5);
+        assert_eq!(config.clean.keep_versions, 5);
+        assert_eq!(config.helpers.priority, vec!["paru", "yay", "pikaur"]);
+
+        assert_eq!(
+            config.source.as_ref().map(|source| source.path.as_str()),
+            Some(format!("{} + {}", base.display(), host.display()).as_str())
+        );
+
+        let replace = layer(&dir, "replace.toml", "[helpers]\npriority = [\"yay\"]\n");
+        let config = SynsyuConfig::load_layers(&[base, host, replace]).unwrap();
+        assert_eq!(config.helpers.priority, vec!["yay"]);
+        assert_eq!(config.aur.timeout, 5);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn single_layer_append_extends_the_defaults() {
+        let dir = std::env::temp_dir().join(format!("synsyu-config-append-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let only = layer(
+            &dir,
+            "only.toml",
+            "[helpers]\n\"+priority\" = [\"pikaur\"]\n",
+        );
+        let config = SynsyuConfig::load_layers(&[only]).unwrap();
+        let mut expected = HelperConfig::default().priority;
+        expected.push("pikaur".to_string());
+        assert_eq!(config.helpers.priority, expected);
+
+        let both = layer(
+            &dir,
+            "both.toml",
+            "[helpers]\npriority = [\"yay\"]\n\"+priority\" = [\"pikaur\"]\n",
+        );
+        let err = SynsyuConfig::load_layers(&[both]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("both replaces and appends to `helpers.priority`"),
+            "{err}"
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn loaded_config_records_path_and_sha256() {
+        let dir = std::env::temp_dir().join(format!("synsyu-config-source-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let contents = "[core]\nsi_chunk_size = 32\n";
+        let path = layer(&dir, "config.toml", contents);
+
+        let config = SynsyuConfig::load_layers(std::slice::from_ref(&path)).unwrap();
+        let source = config.source.expect("a loaded file is recorded");
+        assert_eq!(source.path, path.display().to_string());
+        assert_eq!(
+            source.sha256,
+            format!("{:x}", Sha256::digest(fs::read(&path).unwrap()))
+        );
+        assert_eq!(source.sha256.len(), 64);
+        assert!(SynsyuConfig::default().source.is_none());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
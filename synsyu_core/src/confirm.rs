@@ -0,0 +1,133 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::confirm
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Show pending updates grouped by repository and ask the
+    operator to confirm before the manifest is written.
+
+  Security / Safety Notes:
+    Refuses to prompt without a terminal on stdin so unattended
+    runs fail fast instead of hanging.
+
+  Dependencies:
+    crate::report for the repository grouping.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core under `--interactive`, between
+    manifest construction and the manifest write.
+
+  Revision History:
+    2026-10-18 COD  Introduced interactive write confirmation.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Anything but an explicit "yes" declines
+    - Prompts go to stderr, keeping stdout machine-readable
+============================================================*/
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use crate::error::{Result, SynsyuError};
+use crate::manifest::ManifestDocument;
+use crate::report::updates_by_repo_view;
+
+/// Print the pending updates and require a `y`/`yes` answer before `manifest` is written.
+pub fn confirm_write(
+    document: &ManifestDocument,
+    manifest: &Path,
+    mut input: impl BufRead,
+    mut prompt: impl Write,
+    interactive_stdin: bool,
+) -> Result<()> {
+    if !interactive_stdin {
+        return Err(SynsyuError::Config(
+            "--interactive needs a terminal on stdin; rerun without it for unattended use".into(),
+        ));
+    }
+    write!(prompt, "{}", updates_by_repo_view(document))?;
+    for (name, entry) in document
+        .packages
+        .iter()
+        .filter(|(_, entry)| entry.update_available)
+    {
+        writeln!(
+            prompt,
+            "  [{}] {name} {} -> {}",
+            entry.update_repo(),
+            entry.installed_version,
+            entry.newer_version.as_deref().unwrap_or("?")
+        )?;
+    }
+    write!(prompt, "Write manifest to {}? [y/N] ", manifest.display())?;
+    prompt.flush()?;
+
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    match answer.trim().to_ascii_lowercase().as_str() {
+        "y" | "yes" => Ok(()),
+        _ => Err(SynsyuError::Declined(format!(
+            "manifest not written to {}",
+            manifest.display()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::{fixtures, PackageSource};
+
+    fn document() -> ManifestDocument {
+        fixtures::document(vec![(
+            "curl",
+            fixtures::entry("8.10.1-1", PackageSource::Pacman, Some("8.10.1-2")),
+        )])
+    }
+
+    #[test]
+    fn yes_confirms_and_summary_lists_updates() {
+        let mut prompt = Vec::new();
+        confirm_write(
+            &document(),
+            Path::new("/tmp/manifest.json"),
+            &b"Yes\n"[..],
+            &mut prompt,
+            true,
+        )
+        .unwrap();
+        let prompt = String::from_utf8(prompt).unwrap();
+        assert!(prompt.contains("curl 8.10.1-1 -> 8.10.1-2"));
+        assert!(prompt.ends_with("[y/N] "));
+    }
+
+    #[test]
+    fn anything_else_declines_with_distinct_exit_code() {
+        for answer in [&b"no\n"[..], b"\n", b""] {
+            let err = confirm_write(
+                &document(),
+                Path::new("/tmp/manifest.json"),
+                answer,
+                Vec::new(),
+                true,
+            )
+            .unwrap_err();
+            assert!(matches!(err, SynsyuError::Declined(_)));
+            assert_eq!(err.exit_code(), std::process::ExitCode::from(70));
+        }
+    }
+
+    #[test]
+    fn non_tty_stdin_is_refused_without_reading() {
+        let err = confirm_write(
+            &document(),
+            Path::new("/tmp/manifest.json"),
+            &b"yes\n"[..],
+            Vec::new(),
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(err, SynsyuError::Config(_)));
+    }
+}
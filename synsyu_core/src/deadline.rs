@@ -0,0 +1,286 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::deadline
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Enforce the `--max-runtime` wall-clock cap over a whole
+    run and salvage the entries resolved before it expired.
+
+  Security / Safety Notes:
+    The salvaged manifest is written with the regular manifest
+    writer (private permissions) and flagged `truncated`.
+
+  Dependencies:
+    tokio::time for the cap.
+
+  Operational Scope:
+    Wraps the core run in `main`; the manifest builder reports
+    each resolved entry into the shared progress record.
+
+  Revision History:
+    2026-10-18 COD  Introduced the run-wide wall-clock cap.
+    2026-10-18 COD  Applied the manifest symlink guard to truncated writes.
+    2026-10-18 COD  Wrote truncated manifests in the run's --format.
+    2026-10-18 COD  Never overwrite a manifest the run already wrote.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - The cap holds wherever time is spent (network or subprocess)
+    - Partial results are labelled, never passed off as complete
+============================================================*/
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::error::{Result, SynsyuError};
+use crate::logger::Logger;
+use crate::manifest::{
+    write_manifest, ManifestDocument, ManifestEntry, ManifestFormat, SymlinkGuard,
+};
+
+/// Entries resolved so far, shared between the run and the cap.
+#[derive(Clone, Default)]
+pub struct RunProgress {
+    inner: Arc<Mutex<ProgressState>>,
+}
+
+impl std::fmt::Debug for RunProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunProgress").finish_non_exhaustive()
+    }
+}
+
+#[derive(Default)]
+struct ProgressState {
+    /// Where a truncated manifest goes; `None` when the run writes no manifest.
+    manifest_path: Option<PathBuf>,
+    format: ManifestFormat,
+    guard: SymlinkGuard,
+    logger: Option<Arc<Logger>>,
+    expected: usize,
+    entries: BTreeMap<String, ManifestEntry>,
+    /// The complete manifest is on disk; a late cap must not replace it.
+    written: bool,
+}
+
+impl RunProgress {
+    /// Register the run's manifest target and logger once they are known.
+    pub fn attach(
+        &self,
+        manifest_path: Option<PathBuf>,
+        format: ManifestFormat,
+        guard: SymlinkGuard,
+        logger: Arc<Logger>,
+    ) {
+        if let Ok(mut state) = self.inner.lock() {
+            state.manifest_path = manifest_path;
+            state.format = format;
+            state.guard = guard;
+            state.logger = Some(logger);
+        }
+    }
+
+    /// Number of packages the run set out to resolve.
+    pub fn expect(&self, packages: usize) {
+        if let Ok(mut state) = self.inner.lock() {
+            state.expected = packages;
+        }
+    }
+
+    pub fn record(&self, name: &str, entry: &ManifestEntry) {
+        if let Ok(mut state) = self.inner.lock() {
+            state.entries.insert(name.to_string(), entry.clone());
+        }
+    }
+
+    /// Note that the complete manifest was written; the cap then leaves it alone.
+    pub fn mark_written(&self) {
+        if let Ok(mut state) = self.inner.lock() {
+            state.written = true;
+        }
+    }
+
+    /// Write what was resolved as a `truncated` manifest and build the exit error.
+    fn salvage(&self, limit: Duration) -> SynsyuError {
+        let Ok(state) = self.inner.lock() else {
+            return SynsyuError::RuntimeExceeded(format!(
+                "stopped after {}s; progress unavailable",
+                limit.as_secs()
+            ));
+        };
+        let resolved = state.entries.len();
+        let mut detail = format!(
+            "stopped after {}s with {resolved} of {} package(s) resolved",
+            limit.as_secs(),
+            state.expected
+        );
+        if let Some(path) = state.manifest_path.as_ref().filter(|_| state.written) {
+            detail.push_str(&format!(
+                "; complete manifest already written to {}",
+                path.display()
+            ));
+        } else if let Some(path) = &state.manifest_path {
+            let mut document = ManifestDocument::from_entries(state.entries.clone());
+            document.metadata.truncated = true;
+            match write_manifest(&document, path, state.format, &state.guard) {
+                Ok(()) => detail.push_str(&format!(
+                    "; truncated manifest written to {}",
+                    path.display()
+                )),
+                Err(err) => detail.push_str(&format!("; truncated manifest not written: {err}")),
+            }
+        }
+        if let Some(logger) = &state.logger {
+            // Each log line is flushed as written, so this is the last thing on disk.
+            logger.error("RUNTIME", format!("--max-runtime exceeded: {detail}"));
+        }
+        SynsyuError::RuntimeExceeded(detail)
+    }
+}
+
+/// Run `work` under the wall-clock cap; zero or `None` means no cap.
+pub async fn run_capped<F>(
+    limit: Option<Duration>,
+    progress: &RunProgress,
+    work: F,
+) -> Result<ExitCode>
+where
+    F: Future<Output = Result<ExitCode>>,
+{
+    match limit.filter(|limit| !limit.is_zero()) {
+        None => work.await,
+        Some(limit) => match tokio::time::timeout(limit, work).await {
+            Ok(result) => result,
+            Err(_) => Err(progress.salvage(limit)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures::entry;
+    use crate::manifest::PackageSource;
+
+    #[tokio::test]
+    async fn slow_backend_hits_cap_and_leaves_truncated_manifest() {
+        let dir = std::env::temp_dir().join(format!("synsyu-maxruntime-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let manifest = dir.join("manifest.json");
+        let log = dir.join("core.log");
+        let logger = Arc::new(Logger::new(Some(log.clone()), false).unwrap());
+
+        let progress = RunProgress::default();
+        progress.attach(
+            Some(manifest.clone()),
+            ManifestFormat::Json,
+            SymlinkGuard::default(),
+            Arc::clone(&logger),
+        );
+        progress.expect(3);
+        let backend = async {
+            progress.record(
+                "bash",
+                &entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1")),
+            );
+            progress.record(
+                "paru",
+                &entry("2.0.4-1", PackageSource::Aur, Some("2.0.4-1")),
+            );
+            // Third package: a backend that never answers.
+            std::future::pending::<()>().await;
+            Ok(ExitCode::SUCCESS)
+        };
+        let result = run_capped(Some(Duration::from_millis(50)), &progress, backend).await;
+        let err = result.unwrap_err();
+        assert!(matches!(err, SynsyuError::RuntimeExceeded(_)));
+        assert_eq!(err.exit_code(), ExitCode::from(61));
+        assert!(
+            err.to_string().contains("2 of 3 package(s) resolved"),
+            "{err}"
+        );
+
+        let written: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&manifest).unwrap()).unwrap();
+        assert_eq!(written["metadata"]["truncated"], true);
+        assert_eq!(written["metadata"]["total_packages"], 2);
+        assert_eq!(written["metadata"]["updates_available"], 1);
+        assert!(written["packages"]["paru"].is_object());
+        let log_text = std::fs::read_to_string(&log).unwrap();
+        assert!(log_text.contains("[RUNTIME] --max-runtime exceeded"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn cap_after_the_manifest_write_keeps_the_complete_manifest() {
+        let dir =
+            std::env::temp_dir().join(format!("synsyu-maxruntime-late-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let manifest = dir.join("manifest.json");
+        let logger = Arc::new(Logger::new(None, false).unwrap());
+
+        let progress = RunProgress::default();
+        progress.attach(
+            Some(manifest.clone()),
+            ManifestFormat::Json,
+            SymlinkGuard::default(),
+            logger,
+        );
+        progress.expect(2);
+        let run = async {
+            let mut entries = BTreeMap::new();
+            for (name, entry) in [
+                (
+                    "bash",
+                    entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1")),
+                ),
+                ("zsh", entry("5.9-5", PackageSource::Pacman, Some("5.9-5"))),
+            ] {
+                progress.record(name, &entry);
+                entries.insert(name.to_string(), entry);
+            }
+            let document = ManifestDocument::from_entries(entries);
+            write_manifest(
+                &document,
+                &manifest,
+                ManifestFormat::Json,
+                &SymlinkGuard::default(),
+            )?;
+            progress.mark_written();
+            // Post-write work (history, reports) that outlives the cap.
+            std::future::pending::<()>().await;
+            Ok(ExitCode::SUCCESS)
+        };
+        let err = run_capped(Some(Duration::from_millis(50)), &progress, run)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("complete manifest already written"),
+            "{err}"
+        );
+
+        let written: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&manifest).unwrap()).unwrap();
+        assert_eq!(written["metadata"]["truncated"], false);
+        assert_eq!(written["metadata"]["total_packages"], 2);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn work_finishing_in_time_is_untouched() {
+        let progress = RunProgress::default();
+        let result = run_capped(Some(Duration::from_secs(5)), &progress, async {
+            Ok(ExitCode::from(3))
+        })
+        .await;
+        assert_eq!(result.unwrap(), ExitCode::from(3));
+        let uncapped = run_capped(None, &progress, async { Ok(ExitCode::SUCCESS) }).await;
+        assert_eq!(uncapped.unwrap(), ExitCode::SUCCESS);
+    }
+}
@@ -0,0 +1,242 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::dep_graph
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Render the installed dependency graph as Graphviz DOT
+    (`--export-graph`) so operators can see why the upgrade
+    plan is ordered the way it is.
+
+  Security / Safety Notes:
+    Pure transformation over `pacman -Qi` data; node names are
+    quoted and escaped, so no package name can inject DOT.
+
+  Dependencies:
+    None beyond std.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core after the manifest is built. Edges
+    come from the `Required By` lists also used by cascade and
+    upgrade_plan.
+
+  Revision History:
+    2026-10-18 COD  Introduced DOT export of the dependency graph.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Deterministic output: nodes and edges sorted by name
+    - Cycle edges are marked, never dropped
+============================================================*/
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::manifest::ManifestDocument;
+use crate::pacman::InstalledPackage;
+
+/// Fill colour of packages with a pending update.
+const UPDATE_FILL: &str = "#ffd27f";
+/// Colour of edges that lie on a dependency cycle.
+const CYCLE_COLOR: &str = "red";
+
+/// DOT digraph with an edge from every package to each installed package it depends on.
+///
+/// Packages with a pending update are filled and labelled `from -> to`; edges inside a
+/// dependency cycle are drawn in red.
+pub fn render_dot(document: &ManifestDocument, installed: &[InstalledPackage]) -> String {
+    let mut depends_on: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    let mut nodes: BTreeSet<&str> = BTreeSet::new();
+    for package in installed {
+        nodes.insert(package.name.as_str());
+        for dependent in &package.required_by {
+            nodes.insert(dependent.as_str());
+            depends_on
+                .entry(dependent.as_str())
+                .or_default()
+                .insert(package.name.as_str());
+        }
+    }
+    let component = components(&nodes, &depends_on);
+
+    let mut dot = String::from("digraph synsyu {\n    rankdir=LR;\n    node [shape=box];\n");
+    for &node in &nodes {
+        match document.packages.get(node).filter(|e| e.update_available) {
+            Some(entry) => {
+                let to = entry
+                    .newer_version
+                    .as_deref()
+                    .or(entry.available_version.as_deref())
+                    .unwrap_or("?");
+                let label = format!("{node}\n{} -> {to}", entry.installed_version);
+                let _ = writeln!(
+                    dot,
+                    "    {} [style=filled, fillcolor=\"{UPDATE_FILL}\", label={}];",
+                    quote(node),
+                    quote(&label)
+                );
+            }
+            None => {
+                let _ = writeln!(dot, "    {};", quote(node));
+            }
+        }
+    }
+    for (&from, targets) in &depends_on {
+        for &to in targets {
+            // Both ends in one strongly connected component (or a self-loop): a cycle edge.
+            let style = if component[from] == component[to] {
+                format!(" [color={CYCLE_COLOR}]")
+            } else {
+                String::new()
+            };
+            let _ = writeln!(dot, "    {} -> {}{style};", quote(from), quote(to));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Strongly connected component id of every node (Kosaraju, iterative).
+fn components<'a>(
+    nodes: &BTreeSet<&'a str>,
+    edges: &BTreeMap<&'a str, BTreeSet<&'a str>>,
+) -> BTreeMap<&'a str, usize> {
+    let successors = |node: &str| edges.get(node).into_iter().flatten().copied();
+    let mut finished = Vec::with_capacity(nodes.len());
+    let mut visited = BTreeSet::new();
+    for &start in nodes {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut stack = vec![(start, successors(start))];
+        while let Some((node, pending)) = stack.last_mut() {
+            let node = *node;
+            match pending.next() {
+                Some(next) => {
+                    if visited.insert(next) {
+                        stack.push((next, successors(next)));
+                    }
+                }
+                None => {
+                    finished.push(node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    let mut reversed: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (&from, targets) in edges {
+        for &to in targets {
+            reversed.entry(to).or_default().push(from);
+        }
+    }
+    let mut component = BTreeMap::new();
+    for (id, &root) in finished.iter().rev().enumerate() {
+        if component.contains_key(root) {
+            continue;
+        }
+        component.insert(root, id);
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            for &prev in reversed.get(node).into_iter().flatten() {
+                if !component.contains_key(prev) {
+                    component.insert(prev, id);
+                    stack.push(prev);
+                }
+            }
+        }
+    }
+    component
+}
+
+/// Quoted DOT identifier.
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            other => quoted.push(other),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures::{document, entry};
+    use crate::manifest::PackageSource;
+
+    fn package(name: &str, required_by: &[&str]) -> InstalledPackage {
+        InstalledPackage {
+            name: name.into(),
+            version: "1.0-1".into(),
+            repository: Some("core".into()),
+            installed_size: None,
+            install_date: None,
+            validated_by: None,
+            package_hash: None,
+            required_by: required_by.iter().map(|s| s.to_string()).collect(),
+            provides: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn dot_lists_nodes_edges_updates_and_cycles() {
+        // curl and openssl depend on glibc; python and python-pip depend on each other.
+        let installed = vec![
+            package("glibc", &["curl", "openssl"]),
+            package("openssl", &["curl"]),
+            package("curl", &[]),
+            package("python", &["python-pip"]),
+            package("python-pip", &["python"]),
+        ];
+        let doc = document(vec![
+            (
+                "glibc",
+                entry("2.40-1", PackageSource::Pacman, Some("2.40-2")),
+            ),
+            (
+                "curl",
+                entry("8.10-1", PackageSource::Pacman, Some("8.10-1")),
+            ),
+        ]);
+        let dot = render_dot(&doc, &installed);
+        let lines: Vec<&str> = dot.lines().map(str::trim).collect();
+
+        assert_eq!(lines[0], "digraph synsyu {");
+        assert_eq!(lines.last(), Some(&"}"));
+        for node in [
+            "\"curl\";",
+            "\"openssl\";",
+            "\"python\";",
+            "\"python-pip\";",
+        ] {
+            assert!(lines.contains(&node), "missing {node}\n{dot}");
+        }
+        assert!(lines.contains(
+            &"\"glibc\" [style=filled, fillcolor=\"#ffd27f\", label=\"glibc\\n2.40-1 -> 2.40-2\"];"
+        ));
+        for edge in [
+            "\"curl\" -> \"glibc\";",
+            "\"curl\" -> \"openssl\";",
+            "\"openssl\" -> \"glibc\";",
+            "\"python\" -> \"python-pip\" [color=red];",
+            "\"python-pip\" -> \"python\" [color=red];",
+        ] {
+            assert!(lines.contains(&edge), "missing {edge}\n{dot}");
+        }
+        assert_eq!(lines.iter().filter(|l| l.contains("\" -> \"")).count(), 5);
+        assert_eq!(dot, render_dot(&doc, &installed));
+    }
+
+    #[test]
+    fn names_are_escaped() {
+        assert_eq!(quote("a\"b\\c"), "\"a\\\"b\\\\c\"");
+    }
+}
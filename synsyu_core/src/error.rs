@@ -20,6 +20,11 @@
 
   Revision History:
     2024-11-04 COD  Established shared error definitions.
+    2026-10-18 COD  Added Incomplete for `--require-complete`.
+    2026-10-18 COD  Added Declined for `--interactive`.
+    2026-10-18 COD  Added ActiveTransaction for `--require-no-active-txn`.
+    2026-10-18 COD  Added RuntimeExceeded for `--max-runtime`.
+    2026-10-18 COD  Added ExpectedRepoMissing for `--strict-expected-repo`.
   ------------------------------------------------------------
   SSE Principles Observed:
     - Explicit error taxonomy with actionable context
@@ -48,7 +53,12 @@ pub enum SynsyuError {
     },
     #[error("Configuration: {0}")]
     Config(String),
+    #[error("Configuration: requested packages not installed: {}", .0.join(", "))]
+    MissingPackages(Vec<String>),
+    #[error("Configuration: expected repository packages missing from the sync databases: {}", .0.join(", "))]
+    ExpectedRepoMissing(Vec<String>),
     #[error("Network: {0}")]
+    #[cfg_attr(not(feature = "aur"), allow(dead_code))]
     Network(String),
     #[error("Serialization: {0}")]
     Serialization(String),
@@ -56,6 +66,14 @@ pub enum SynsyuError {
     Filesystem(String),
     #[error("Runtime: {0}")]
     Runtime(String),
+    #[error("Incomplete run: {}", .0.join("; "))]
+    Incomplete(Vec<String>),
+    #[error("Declined by operator: {0}")]
+    Declined(String),
+    #[error("Run time limit exceeded: {0}")]
+    RuntimeExceeded(String),
+    #[error("pacman transaction in progress: lock {0} exists")]
+    ActiveTransaction(String),
     #[error(transparent)]
     Io(#[from] io::Error),
 }
@@ -67,10 +85,16 @@ impl SynsyuError {
             SynsyuError::CommandMissing { .. } => ExitCode::from(10),
             SynsyuError::CommandFailure { .. } => ExitCode::from(11),
             SynsyuError::Config(_) => ExitCode::from(20),
+            SynsyuError::MissingPackages(_) => ExitCode::from(21),
+            SynsyuError::ExpectedRepoMissing(_) => ExitCode::from(22),
             SynsyuError::Network(_) => ExitCode::from(30),
             SynsyuError::Serialization(_) => ExitCode::from(31),
             SynsyuError::Filesystem(_) => ExitCode::from(40),
             SynsyuError::Runtime(_) => ExitCode::from(50),
+            SynsyuError::Incomplete(_) => ExitCode::from(60),
+            SynsyuError::RuntimeExceeded(_) => ExitCode::from(61),
+            SynsyuError::Declined(_) => ExitCode::from(70),
+            SynsyuError::ActiveTransaction(_) => ExitCode::from(75),
             SynsyuError::Io(_) => ExitCode::from(41),
         }
     }
@@ -1,9 +1,9 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tokio::process::Command;
 
 use crate::logger::Logger;
 
-#[derive(Debug, Serialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct FlatpakState {
     pub enabled: bool,
     pub installed_count: usize,
@@ -12,7 +12,7 @@ pub struct FlatpakState {
     pub updates: Vec<FlatpakUpdate>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FlatpakApp {
     pub application: String,
     pub version: String,
@@ -20,7 +20,7 @@ pub struct FlatpakApp {
     pub origin: String,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FlatpakUpdate {
     pub application: String,
     pub branch: String,
@@ -93,7 +93,7 @@ async fn capture_installed() -> Option<Vec<FlatpakApp>> {
         if parts.is_empty() {
             continue;
         }
-        let application = parts.get(0).unwrap_or(&"").trim().to_string();
+        let application = parts.first().unwrap_or(&"").trim().to_string();
         if application.is_empty() {
             continue;
         }
@@ -133,7 +133,7 @@ async fn capture_updates() -> Option<Vec<FlatpakUpdate>> {
         if parts.is_empty() {
             continue;
         }
-        let application = parts.get(0).unwrap_or(&"").trim().to_string();
+        let application = parts.first().unwrap_or(&"").trim().to_string();
         if application.is_empty() {
             continue;
         }
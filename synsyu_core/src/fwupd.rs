@@ -5,13 +5,14 @@ use crate::logger::Logger;
 
 #[derive(Debug, Deserialize)]
 struct FwupdJson {
-    #[serde(default)]
-    Devices: Vec<FwupdDeviceRaw>,
+    #[serde(rename = "Devices", default)]
+    devices_upper: Vec<FwupdDeviceRaw>,
     #[serde(default)]
     devices: Vec<FwupdDeviceRaw>,
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 struct FwupdDeviceRaw {
     #[serde(rename = "Id")]
     id: Option<String>,
@@ -44,6 +45,7 @@ struct FwupdDeviceRaw {
 }
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 struct FwupdReleaseRaw {
     #[serde(rename = "Version")]
     version: Option<String>,
@@ -65,7 +67,7 @@ struct FwupdReleaseRaw {
     signed: Option<bool>,
 }
 
-#[derive(Debug, serde::Serialize, Clone)]
+#[derive(Debug, serde::Serialize, Deserialize, Clone)]
 pub struct FwupdRelease {
     pub version: String,
     pub summary: String,
@@ -73,7 +75,7 @@ pub struct FwupdRelease {
     pub trust: String,
 }
 
-#[derive(Debug, serde::Serialize, Clone)]
+#[derive(Debug, serde::Serialize, Deserialize, Clone)]
 pub struct FwupdDevice {
     pub device: String,
     pub name: String,
@@ -84,7 +86,7 @@ pub struct FwupdDevice {
     pub releases: Vec<FwupdRelease>,
 }
 
-#[derive(Debug, serde::Serialize, Clone)]
+#[derive(Debug, serde::Serialize, Deserialize, Clone)]
 pub struct FwupdState {
     pub enabled: bool,
     pub device_count: usize,
@@ -93,7 +95,7 @@ pub struct FwupdState {
     pub updates: Vec<FwupdUpdate>,
 }
 
-#[derive(Debug, serde::Serialize, Clone)]
+#[derive(Debug, serde::Serialize, Deserialize, Clone)]
 pub struct FwupdUpdate {
     pub device: String,
     pub name: String,
@@ -130,8 +132,8 @@ pub async fn collect_fwupd(logger: &Logger, include_updates: bool) -> Result<Opt
     let parsed: FwupdJson = serde_json::from_slice(&output.stdout).map_err(|err| {
         SynsyuError::Serialization(format!("Failed to parse fwupd JSON output: {err}"))
     })?;
-    let devices_raw = if !parsed.Devices.is_empty() {
-        parsed.Devices
+    let devices_raw = if !parsed.devices_upper.is_empty() {
+        parsed.devices_upper
     } else {
         parsed.devices
     };
@@ -143,18 +145,15 @@ pub async fn collect_fwupd(logger: &Logger, include_updates: bool) -> Result<Opt
             .or(raw.device_id)
             .unwrap_or_else(|| "unknown".to_string());
         let name = raw.name.clone().unwrap_or_else(|| device_id.clone());
-        let installed = raw
-            .version
-            .or(raw.version_bootloader)
-            .unwrap_or_else(String::new);
-        let summary = raw.summary.or(raw.description).unwrap_or_else(String::new);
+        let installed = raw.version.or(raw.version_bootloader).unwrap_or_default();
+        let summary = raw.summary.or(raw.description).unwrap_or_default();
 
         let checksum = truncate_hash(select_checksum(
             raw.checksum,
             raw.checksums,
             raw.checksums_lower,
         ));
-        let trust = join_trust(raw.trust_flags, raw.trust_flags_lower).unwrap_or_else(String::new);
+        let trust = join_trust(raw.trust_flags, raw.trust_flags_lower).unwrap_or_default();
 
         // Manifest should reflect current firmware state; exclude pending release data.
         let releases = Vec::new();
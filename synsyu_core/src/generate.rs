@@ -0,0 +1,1093 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::generate
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    The manifest pipeline shared by the CLI and library callers:
+    enumerate, select, look up candidates, resolve, and annotate.
+    `generate_manifest` is its one-call programmatic entry.
+
+  Security / Safety Notes:
+    Runs pacman and (with the `aur` feature) the AUR RPC.
+    Writes no manifest and logs nowhere unless the caller
+    supplies a logger, a checkpoint path, or enables the cache.
+
+  Dependencies:
+    crate::pacman, crate::aur, crate::manifest, crate::selection.
+
+  Operational Scope:
+    Called by Syn-Syu-Core's main run and by downstream crates
+    embedding manifest generation.
+
+  Revision History:
+    2026-10-18 COD  Moved candidate lookup out of the binary;
+                    added generate_manifest.
+    2026-10-18 COD  AUR client build failures degrade the lookup.
+    2026-10-18 COD  Moved selection and post-processing out of the
+                    binary so the CLI runs this same pipeline.
+    2026-10-18 COD  Added narrows_selection for run history.
+    2026-10-18 COD  Sample estimates follow every update withdrawal.
+    2026-10-18 COD  Download URLs from pacman.conf servers and the
+                    sync databases.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Lookup failures degrade to missing candidates, logged
+    - Same pipeline for CLI and library callers
+============================================================*/
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use clap::ValueEnum;
+
+use crate::annotations::{self, Annotations};
+#[cfg(feature = "aur")]
+use crate::aur::{aur_cache_path, AurCache, AurClient};
+use crate::checkpoint::{Checkpoint, CHECKPOINT_EVERY};
+#[cfg(feature = "aur")]
+use crate::clock;
+use crate::completeness::Completeness;
+use crate::config::{SourceOverride, SynsyuConfig};
+use crate::deadline::RunProgress;
+use crate::error::{Result, SynsyuError};
+use crate::flatpak::collect_flatpak;
+use crate::fwupd::collect_fwupd;
+use crate::logger::Logger;
+use crate::manifest::{
+    self, build_manifest, Candidates, ManifestDocument, PackageSource, ResolveOptions,
+    VersionCompare,
+};
+use crate::package_info::VersionInfo;
+use crate::pacman::{
+    check_active_transaction, dedupe_installed, enumerate_foreign_packages,
+    enumerate_installed_packages, query_repo_versions, InstalledPackage, RetryPolicy,
+    VercmpBackend,
+};
+use crate::paths::PathFormatter;
+use crate::pkg_cache::{check_pkg_cache, PkgCacheSummary};
+use crate::raw_dump::RawDump;
+use crate::selection::{
+    apply_always_and_ignore, apply_limit, apply_sample, filter_packages, LimitSort,
+    MissingPackages, Sample,
+};
+use crate::{audit, cascade, integrity, mirror, new_deps, pacman_conf, stability};
+
+/// Inputs to [`generate`] and [`generate_manifest`]; each field mirrors a CLI flag.
+#[derive(Clone)]
+pub struct ManifestOptions {
+    /// Effective configuration, e.g. from `SynsyuConfig::load_layers` or `Default`.
+    pub config: SynsyuConfig,
+    /// Installed packages to resolve; empty resolves every installed package.
+    pub packages: Vec<String>,
+    /// Packages added to `core.always_include`.
+    pub always: Vec<String>,
+    /// Packages added to `core.ignore`.
+    pub ignore: Vec<String>,
+    /// Reaction to `packages` that are not installed.
+    pub missing_packages: MissingPackages,
+    /// Resolve a random sample of the selection, seeded by `config.seed`.
+    pub sample: Option<Sample>,
+    /// Resolve at most this many packages, kept in `limit_by` order.
+    pub limit: Option<usize>,
+    pub limit_by: LimitSort,
+    /// Skip AUR lookups and the clock check; foreign packages then stay `local`.
+    pub offline: bool,
+    /// Read and refresh the on-disk AUR lookup cache (`aur.cache_ttl_secs`).
+    pub aur_cache: bool,
+    /// Hold back pacman.conf `IgnorePkg`/`IgnoreGroup` packages (`core.pacman_conf_path`).
+    pub respect_ignore: bool,
+    /// Fail instead of logging REPOMISS for `core.expected_repo` packages outside the repos.
+    pub strict_expected_repo: bool,
+    /// Fail instead of warning while a pacman transaction holds the db lock.
+    pub require_no_active_txn: bool,
+    /// Fail if any lookup was skipped or failed.
+    pub require_complete: bool,
+    /// Record the dependencies of AUR candidates.
+    pub with_deps: bool,
+    /// Withhold updates that fail the `[stability]` policy.
+    pub only_stable: bool,
+    /// Withhold updates for orphaned AUR packages.
+    pub skip_orphaned_aur: bool,
+    /// Withhold updates for AUR packages flagged out of date.
+    pub skip_out_of_date: bool,
+    /// Warn (LOWPOP) for AUR candidates below this popularity.
+    pub min_aur_popularity: Option<f64>,
+    /// Withdraw pkgrel-only rebuilds from the update set.
+    pub ignore_pkgrel_only: bool,
+    /// List installed dependents of each pending update.
+    pub cascade: bool,
+    /// Mark candidates already present in `core.pkg_cache_directory`.
+    pub check_pkg_cache: bool,
+    /// Record how long each updatable package's installed version has been in place.
+    pub audit_changes: bool,
+    /// Verify installed files of update candidates with `pacman -Qkk`.
+    pub integrity_check: bool,
+    /// Collect Flatpak state even when `applications.flatpak` is off.
+    pub with_flatpak: bool,
+    /// Collect fwupd state even when `applications.fwupd` is off.
+    pub with_fwupd: bool,
+    /// Operator notes appended to matching entries.
+    pub annotations: Option<Annotations>,
+    /// Receives raw pacman output and AUR responses.
+    pub raw_dump: Option<Arc<RawDump>>,
+    /// Checkpoint file for resolved entries; `None` keeps no checkpoint.
+    pub checkpoint: Option<PathBuf>,
+    /// Reuse entries from an existing checkpoint.
+    pub resume: bool,
+    /// Shared record of resolved entries for the `--max-runtime` cap.
+    pub progress: Option<RunProgress>,
+    /// Receives progress and warnings; without one nothing is logged.
+    pub logger: Option<Arc<Logger>>,
+}
+
+impl ManifestOptions {
+    /// Resolve every installed package with `config`, online, without cache or logging.
+    pub fn new(config: SynsyuConfig) -> Self {
+        Self {
+            config,
+            packages: Vec::new(),
+            always: Vec::new(),
+            ignore: Vec::new(),
+            missing_packages: MissingPackages::default(),
+            sample: None,
+            limit: None,
+            limit_by: LimitSort::default(),
+            offline: false,
+            aur_cache: false,
+            respect_ignore: true,
+            strict_expected_repo: false,
+            require_no_active_txn: false,
+            require_complete: false,
+            with_deps: false,
+            only_stable: false,
+            skip_orphaned_aur: false,
+            skip_out_of_date: false,
+            min_aur_popularity: None,
+            ignore_pkgrel_only: false,
+            cascade: false,
+            check_pkg_cache: false,
+            audit_changes: false,
+            integrity_check: false,
+            with_flatpak: false,
+            with_fwupd: false,
+            annotations: None,
+            raw_dump: None,
+            checkpoint: None,
+            resume: false,
+            progress: None,
+            logger: None,
+        }
+    }
+
+    /// Whether the run resolves only part of the installed set, so its
+    /// results should be merged into run history rather than replace it.
+    pub fn narrows_selection(&self) -> bool {
+        !self.packages.is_empty()
+            || !self.always.is_empty()
+            || !self.ignore.is_empty()
+            || self.sample.is_some()
+            || self.limit.is_some()
+    }
+}
+
+/// A resolved manifest and what the caller needs to finish the run.
+pub struct Generation {
+    pub document: ManifestDocument,
+    /// Every installed package, for reports built on the dependency graph.
+    pub installed: Vec<InstalledPackage>,
+    /// The run's checkpoint; clear it once the manifest is safely written.
+    pub checkpoint: Option<Checkpoint>,
+}
+
+/// Enumerate installed packages, look up their candidates, and resolve a manifest.
+///
+/// Nothing is written: the document is returned for the caller to inspect or
+/// hand to `manifest::write_manifest`, and any checkpoint is cleared. Lookup
+/// failures degrade to missing candidates, reported as warnings on the
+/// supplied logger.
+pub async fn generate_manifest(options: ManifestOptions) -> Result<ManifestDocument> {
+    let logger = options
+        .logger
+        .clone()
+        .unwrap_or_else(|| Arc::new(Logger::silent()));
+    let generation = generate(options).await?;
+    if let Some(checkpoint) = generation.checkpoint {
+        checkpoint.clear(&logger);
+    }
+    Ok(generation.document)
+}
+
+/// Run the full pipeline: enumerate, select, look up, resolve, and annotate.
+pub async fn generate(options: ManifestOptions) -> Result<Generation> {
+    let logger = options
+        .logger
+        .clone()
+        .unwrap_or_else(|| Arc::new(Logger::silent()));
+    let mut config = options.config;
+    if !options.aur_cache {
+        config.aur.cache_ttl_secs = 0;
+    }
+    let raw_dump = options.raw_dump;
+
+    let clock_skew = if config.core.clock_skew_check && !options.offline {
+        probe_clock_skew(&config, &logger).await
+    } else {
+        None
+    };
+    check_active_transaction(
+        Path::new(&config.core.pacman_db_lock),
+        options.require_no_active_txn,
+        &logger,
+    )?;
+
+    let mut completeness = Completeness::default();
+    let (foreign, mut installed) =
+        enumerate_packages(&config, raw_dump.as_deref(), &mut completeness, &logger).await?;
+    let aur_lookup = classify_aur_packages(
+        &mut installed,
+        &foreign,
+        &config,
+        options.offline,
+        raw_dump.clone(),
+        &mut completeness,
+        &logger,
+    )
+    .await?;
+    logger.info(
+        "PACKAGES",
+        format!("Detected {} installed packages", installed.len()),
+    );
+
+    let selected = filter_packages(
+        &mut installed,
+        &options.packages,
+        options.missing_packages,
+        &logger,
+    )?;
+    let always: Vec<String> = config
+        .core
+        .always_include
+        .iter()
+        .chain(&options.always)
+        .cloned()
+        .collect();
+    let ignore: Vec<String> = config
+        .core
+        .ignore
+        .iter()
+        .chain(&options.ignore)
+        .cloned()
+        .collect();
+    let selected = apply_always_and_ignore(&installed, selected, &always, &ignore, &logger);
+    let population = selected.len();
+    let sample_seed = config.seed.unwrap_or_default();
+    let selected = match options.sample {
+        Some(sample) => {
+            let size = sample.size(population);
+            logger.info(
+                "SAMPLE",
+                format!("Resolving a random sample of {size} of {population} packages (seed {sample_seed})"),
+            );
+            apply_sample(selected, size, sample_seed)
+        }
+        None => selected,
+    };
+    let total_available = selected.len();
+    let selected = match options.limit {
+        Some(limit) if limit < selected.len() => {
+            logger.info(
+                "LIMIT",
+                format!(
+                    "--limit keeps {limit} of {total_available} packages (by {})",
+                    options
+                        .limit_by
+                        .to_possible_value()
+                        .map_or_else(String::new, |value| value.get_name().to_string())
+                ),
+            );
+            apply_limit(selected, limit, options.limit_by)
+        }
+        _ => selected,
+    };
+    // Consumers expect a manifest file every run, so an empty selection still writes one.
+    if selected.is_empty() {
+        logger.warn(
+            "EMPTY",
+            "No packages selected for manifest generation; the manifest will list no packages",
+        );
+    }
+
+    let candidates = Candidates {
+        repo: resolve_repo_candidates(
+            &selected,
+            &config,
+            options.strict_expected_repo,
+            raw_dump.as_deref(),
+            &mut completeness,
+            &logger,
+        )
+        .await?,
+        aur: aur_lookup.versions,
+        aur_not_found: aur_lookup.not_found,
+    };
+    if config.core.version_case_fold {
+        logger.warn(
+            "CASEFOLD",
+            "core.version_case_fold is enabled; versions are compared case-insensitively, not as strict vercmp.",
+        );
+    }
+    let mut checkpoint = options
+        .checkpoint
+        .map(|path| Checkpoint::start(path, CHECKPOINT_EVERY, options.resume, &logger));
+    let ignore_rules = options
+        .respect_ignore
+        .then(|| pacman_conf::load_ignore_rules(Path::new(&config.core.pacman_conf_path), &logger));
+    let resolve = ResolveOptions {
+        compare: VersionCompare {
+            case_fold: config.core.version_case_fold,
+            backend: vercmp_backend(&config),
+        },
+        package_timeout: Duration::from_secs(config.core.package_timeout_secs),
+        progress: options.progress.as_ref(),
+        ignore: ignore_rules.as_ref(),
+        with_deps: options.with_deps,
+        ..ResolveOptions::new(config.core.source_policy, &config.core.source_override)
+    };
+    if let Some(progress) = &options.progress {
+        progress.expect(selected.len());
+    }
+    let mut document = build_manifest(
+        &selected,
+        &candidates,
+        &resolve,
+        checkpoint.as_mut(),
+        &logger,
+    )
+    .await?;
+    document.record_limit(total_available);
+    completeness.record_unknown_sources(&document);
+    completeness.enforce(options.require_complete, &logger)?;
+
+    document.flag_size_skew(config.core.size_skew_percent, &logger);
+    if let Some(annotations) = &options.annotations {
+        let applied = annotations::apply_annotations(&mut document, annotations, &logger);
+        logger.info(
+            "ANNOTATE",
+            format!("Operator annotations applied to {applied} package(s)"),
+        );
+    }
+    if let Some(minimum) = options.min_aur_popularity {
+        document.flag_low_popularity(minimum, &logger);
+    }
+    if options.skip_orphaned_aur {
+        document.skip_orphaned_aur();
+    }
+    if options.skip_out_of_date {
+        document.skip_out_of_date();
+    }
+    let unstable = stability::assess_stability(&mut document, &config.stability, Utc::now());
+    if unstable > 0 {
+        logger.info(
+            "STABILITY",
+            format!("{unstable} pending update(s) fail the stability policy"),
+        );
+    }
+    if options.only_stable {
+        document.skip_unstable();
+    }
+    if options.ignore_pkgrel_only {
+        document.ignore_pkgrel_only();
+    }
+    // Extrapolate from the update set left after every withdrawal.
+    if let Some(sample) = options.sample {
+        document.record_sample(population, sample.percent(), sample_seed);
+    }
+    let reboots = document.flag_reboot_required(&config.core.reboot_required);
+    if reboots > 0 {
+        logger.info(
+            "REBOOT",
+            format!("{reboots} pending update(s) will require a reboot"),
+        );
+    }
+    if options.cascade {
+        cascade::annotate_cascade(&mut document, &installed);
+    }
+    let new_packages =
+        new_deps::annotate_new_dependencies(&mut document, &installed, &candidates.repo);
+    if !new_packages.is_empty() {
+        let names: Vec<String> = new_packages.iter().cloned().collect();
+        let resolved = query_repo_versions(
+            &names,
+            config.core.si_chunk_size,
+            config.core.si_max_parallel,
+            RetryPolicy::from_config(&config.pacman),
+            raw_dump.as_deref(),
+            Some(&logger),
+        )
+        .await
+        .unwrap_or_else(|err| {
+            logger.warn(
+                "NEWDEPS",
+                format!("Sizes of new dependencies unavailable: {err}"),
+            );
+            HashMap::new()
+        });
+        new_deps::record_new_packages(&mut document, &new_packages, &resolved);
+        logger.info(
+            "NEWDEPS",
+            format!(
+                "Pending updates would newly install {} package(s): {}",
+                new_packages.len(),
+                names.join(", ")
+            ),
+        );
+    }
+    let servers = mirror::load_repo_servers(Path::new(&config.core.pacman_conf_path), &logger);
+    mirror::annotate_download_urls(
+        &mut document,
+        &candidates.repo,
+        &servers,
+        Path::new(&config.core.sync_db_directory),
+        std::env::consts::ARCH,
+        &logger,
+    );
+
+    if options.with_flatpak || config.flatpak_enabled() {
+        match collect_flatpak(&logger).await {
+            Some(flatpak) => {
+                document.applications.flatpak = Some(flatpak);
+            }
+            None => logger.warn(
+                "FLATPAK",
+                "Flatpak state unavailable; proceeding without flatpak data.",
+            ),
+        }
+    }
+    if options.with_fwupd || config.fwupd_enabled() {
+        match collect_fwupd(&logger, true).await {
+            Ok(Some(fwupd)) => {
+                document.applications.fwupd = Some(fwupd);
+            }
+            Ok(None) => logger.warn(
+                "FWUPD",
+                "Firmware state unavailable; proceeding without fwupd data.",
+            ),
+            Err(err) => logger.warn("FWUPD", format!("Firmware capture failed: {err}")),
+        }
+    }
+
+    document.refresh_application_metadata();
+    document.metadata.clock_skew_secs = clock_skew;
+    let path_style = PathFormatter::from_config(&config.paths);
+    if let Some(source) = config
+        .source
+        .as_ref()
+        .filter(|_| config.manifest.record_config)
+    {
+        document.metadata.config_path = Some(path_style.rewrite(&source.path));
+        document.metadata.config_sha256 = Some(source.sha256.clone());
+    }
+
+    if options.check_pkg_cache {
+        let cache_dir = PathBuf::from(&config.core.pkg_cache_directory);
+        document.metadata.pkg_cache =
+            check_pkg_cache(&mut document, &cache_dir, &logger).map(|summary| PkgCacheSummary {
+                directory: path_style.format_path(&cache_dir),
+                ..summary
+            });
+        if let Some(summary) = &document.metadata.pkg_cache {
+            logger.info(
+                "PKGCACHE",
+                format!(
+                    "checked={} cached={} mismatched={} remaining_bytes={}",
+                    summary.checked,
+                    summary.cached,
+                    summary.mismatched,
+                    summary.download_bytes_remaining
+                ),
+            );
+        }
+    }
+    if options.audit_changes {
+        audit::annotate_installed_age(&mut document, Utc::now());
+    }
+    if options.integrity_check {
+        integrity::check_integrity(&mut document, config.core.si_chunk_size, &logger).await;
+    }
+
+    Ok(Generation {
+        document,
+        installed,
+        checkpoint,
+    })
+}
+
+/// Foreign package names and the deduplicated installed set.
+pub async fn enumerate_packages(
+    config: &SynsyuConfig,
+    raw_dump: Option<&RawDump>,
+    completeness: &mut Completeness,
+    logger: &Logger,
+) -> Result<(HashSet<String>, Vec<InstalledPackage>)> {
+    let backend = vercmp_backend(config);
+    if backend == VercmpBackend::External {
+        logger.debug(
+            "VERCMP",
+            "Comparing versions with the external vercmp binary.",
+        );
+    }
+    // Only foreign packages are looked up in the AUR.
+    let foreign = detect_foreign_packages(completeness, logger).await;
+    let installed = dedupe_installed(
+        enumerate_installed_packages(
+            &foreign,
+            RetryPolicy::from_config(&config.pacman),
+            raw_dump,
+            Some(logger),
+        )
+        .await?,
+        config.core.duplicate_installed,
+        backend,
+        logger,
+    )
+    .await;
+    Ok((foreign, installed))
+}
+
+fn vercmp_backend(config: &SynsyuConfig) -> VercmpBackend {
+    if config.core.vercmp_external {
+        VercmpBackend::External
+    } else {
+        VercmpBackend::Native
+    }
+}
+
+/// Compare the local clock with the AUR server's `Date` header.
+#[cfg(feature = "aur")]
+async fn probe_clock_skew(config: &SynsyuConfig, logger: &Logger) -> Option<i64> {
+    let client = match AurClient::new(&config.aur) {
+        Ok(client) => client,
+        Err(err) => {
+            logger.warn("CLOCKSKEW", format!("Clock check skipped: {err}"));
+            return None;
+        }
+    };
+    clock::check_clock_skew(
+        &client,
+        Utc::now(),
+        config.core.clock_skew_threshold_secs,
+        logger,
+    )
+    .await
+}
+
+#[cfg(not(feature = "aur"))]
+async fn probe_clock_skew(_config: &SynsyuConfig, logger: &Logger) -> Option<i64> {
+    logger.warn(
+        "CLOCKSKEW",
+        format!("Clock check skipped: {}", aur_not_built()),
+    );
+    None
+}
+
+/// Foreign (`pacman -Qm`) package names; a failed detection leaves none to look up in the AUR.
+pub async fn detect_foreign_packages(
+    completeness: &mut Completeness,
+    logger: &Logger,
+) -> HashSet<String> {
+    enumerate_foreign_packages().await.unwrap_or_else(|err| {
+        logger.warn(
+            "FOREIGN",
+            format!("Foreign package detection failed; skipping AUR lookups: {err}"),
+        );
+        completeness.record(format!("foreign package detection failed: {err}"));
+        HashSet::new()
+    })
+}
+
+/// Look up sync-database candidates for repo and AUR packages; failures degrade to no candidates.
+pub async fn resolve_repo_candidates(
+    packages: &[InstalledPackage],
+    config: &SynsyuConfig,
+    strict_expected_repo: bool,
+    raw_dump: Option<&RawDump>,
+    completeness: &mut Completeness,
+    logger: &Logger,
+) -> Result<HashMap<String, VersionInfo>> {
+    let core = &config.core;
+    let names: Vec<String> = packages
+        .iter()
+        .filter(|pkg| {
+            // AUR packages are included so the source policy can weigh a repo counterpart.
+            matches!(
+                manifest::source_from_repo(pkg.repository.as_deref()),
+                PackageSource::Pacman | PackageSource::Aur
+            ) || core.source_override.get(&pkg.name) == Some(&SourceOverride::Repo)
+                || core.expected_repo.contains(&pkg.name)
+        })
+        .map(|pkg| pkg.name.clone())
+        .collect();
+    match query_repo_versions(
+        &names,
+        core.si_chunk_size,
+        core.si_max_parallel,
+        RetryPolicy::from_config(&config.pacman),
+        raw_dump,
+        Some(logger),
+    )
+    .await
+    {
+        Ok(versions) => {
+            check_expected_repo(
+                &core.expected_repo,
+                packages,
+                &versions,
+                strict_expected_repo,
+                logger,
+            )?;
+            Ok(versions)
+        }
+        Err(err) => {
+            logger.warn(
+                "REPO",
+                format!("Repository candidate lookup skipped: {err}"),
+            );
+            completeness.record(format!(
+                "repo lookup failed for {} package(s): {err}",
+                names.len()
+            ));
+            Ok(HashMap::new())
+        }
+    }
+}
+
+/// Log `REPOMISS` for selected `expected` packages the sync databases do not carry;
+/// with `strict`, fail instead. Returns the missing names.
+pub fn check_expected_repo(
+    expected: &[String],
+    packages: &[InstalledPackage],
+    repo: &HashMap<String, VersionInfo>,
+    strict: bool,
+    logger: &Logger,
+) -> Result<Vec<String>> {
+    let mut missing: Vec<String> = packages
+        .iter()
+        .filter(|pkg| expected.contains(&pkg.name) && !repo.contains_key(&pkg.name))
+        .map(|pkg| pkg.name.clone())
+        .collect();
+    missing.sort();
+    for name in &missing {
+        logger.error(
+            "REPOMISS",
+            format!(
+                "{name} is listed in core.expected_repo but no sync repository carries it; \
+                 not looked up in the AUR"
+            ),
+        );
+    }
+    if strict && !missing.is_empty() {
+        return Err(SynsyuError::ExpectedRepoMissing(missing));
+    }
+    Ok(missing)
+}
+
+/// Query the AUR RPC; callers degrade on any error, including a client that fails to build.
+#[cfg(feature = "aur")]
+async fn fetch_aur_versions(
+    names: &[String],
+    config: &SynsyuConfig,
+    raw_dump: Option<Arc<RawDump>>,
+    logger: &Logger,
+) -> Result<HashMap<String, VersionInfo>> {
+    let cache = (config.aur.cache_ttl_secs > 0).then(|| {
+        AurCache::new(
+            aur_cache_path(&config.cache_dir()),
+            config.aur.cache_ttl_secs,
+        )
+        .refreshing(config.aur.refresh_cache)
+    });
+    let mut client = AurClient::new(&config.aur)?
+        .with_raw_dump(raw_dump)
+        .with_cache(cache);
+    if let Some(seed) = config.seed {
+        client = client.with_seed(seed);
+    }
+    client.fetch_versions(names, logger).await
+}
+
+#[cfg(not(feature = "aur"))]
+async fn fetch_aur_versions(
+    _names: &[String],
+    _config: &SynsyuConfig,
+    _raw_dump: Option<Arc<RawDump>>,
+    _logger: &Logger,
+) -> Result<HashMap<String, VersionInfo>> {
+    Err(aur_not_built())
+}
+
+/// Error for AUR operations requested from a build without the `aur` feature.
+#[cfg(not(feature = "aur"))]
+pub fn aur_not_built() -> SynsyuError {
+    SynsyuError::Config(
+        "AUR support is not compiled into this build; rebuild with `--features aur`".into(),
+    )
+}
+
+/// AUR results for foreign packages, plus names the AUR answered without.
+#[derive(Debug, Default)]
+pub struct AurLookup {
+    pub versions: HashMap<String, VersionInfo>,
+    /// Queried successfully but absent from the results (genuinely not in the AUR).
+    pub not_found: HashSet<String>,
+}
+
+/// Look up `foreign` packages (and `source_override = "aur"` ones) in the AUR.
+///
+/// Foreign packages the AUR carries are reclassified as `aur`; those it does not
+/// know stay `local`. Repo packages are never sent to the AUR, even when their
+/// `-Si` lookup failed.
+pub async fn classify_aur_packages(
+    packages: &mut [InstalledPackage],
+    foreign: &HashSet<String>,
+    config: &SynsyuConfig,
+    offline: bool,
+    raw_dump: Option<Arc<RawDump>>,
+    completeness: &mut Completeness,
+    logger: &Logger,
+) -> Result<AurLookup> {
+    let mut candidates = Vec::new();
+    for pkg in packages.iter() {
+        if foreign.contains(&pkg.name)
+            || config.core.source_override.get(&pkg.name) == Some(&SourceOverride::Aur)
+        {
+            // Expected repo packages must not silently fall through to the AUR.
+            if !config.core.expected_repo.contains(&pkg.name) {
+                candidates.push(pkg.name.clone());
+            }
+        }
+    }
+    if candidates.is_empty() {
+        return Ok(AurLookup::default());
+    }
+    if offline {
+        logger.info("AUR", "Offline flag set; skipping AUR origin detection.");
+        completeness.record(format!(
+            "AUR lookups disabled (offline) with {} foreign package(s): {}",
+            candidates.len(),
+            candidates.join(", ")
+        ));
+        return Ok(AurLookup::default());
+    }
+    match fetch_aur_versions(&candidates, config, raw_dump, logger).await {
+        Ok(found) => {
+            let not_found: HashSet<String> = candidates
+                .iter()
+                .filter(|name| !found.contains_key(*name))
+                .cloned()
+                .collect();
+            for name in candidates.iter().filter(|name| not_found.contains(*name)) {
+                logger.debug("AURMISS", format!("{name}: not found in AUR"));
+            }
+            let mut updated = 0usize;
+            for pkg in packages
+                .iter_mut()
+                .filter(|pkg| foreign.contains(&pkg.name))
+            {
+                if found.contains_key(&pkg.name) {
+                    pkg.repository = Some("aur".to_string());
+                    updated += 1;
+                } else if not_found.contains(&pkg.name) {
+                    pkg.repository = Some("local".to_string());
+                }
+            }
+            if updated == 0 {
+                logger.info("AUR", "No AUR matches found for foreign packages.");
+            } else {
+                logger.info("AUR", format!("Classified {updated} package(s) as AUR."));
+            }
+            Ok(AurLookup {
+                versions: found,
+                not_found,
+            })
+        }
+        Err(err) => {
+            logger.warn("AUR", format!("AUR origin detection skipped: {err}"));
+            completeness.record(format!(
+                "AUR lookup failed for {} foreign package(s) ({}): {err}",
+                candidates.len(),
+                candidates.join(", ")
+            ));
+            Ok(AurLookup::default())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::ExitCode;
+
+    use super::*;
+    #[cfg(feature = "aur")]
+    use crate::aur::test_support;
+
+    fn installed(name: &str) -> InstalledPackage {
+        InstalledPackage {
+            name: name.into(),
+            version: "1.0-1".into(),
+            repository: Some("pacman".into()),
+            installed_size: None,
+            install_date: None,
+            validated_by: None,
+            package_hash: None,
+            required_by: Vec::new(),
+            provides: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn foreign(name: &str) -> InstalledPackage {
+        InstalledPackage {
+            repository: Some("local".into()),
+            ..installed(name)
+        }
+    }
+
+    /// What `pacman -Qm` would list for `pkgs`.
+    fn foreign_names(pkgs: &[InstalledPackage]) -> HashSet<String> {
+        pkgs.iter()
+            .filter(|pkg| pkg.repository.as_deref() == Some("local"))
+            .map(|pkg| pkg.name.clone())
+            .collect()
+    }
+
+    #[cfg(feature = "aur")]
+    fn aur_config(base: &str) -> SynsyuConfig {
+        let mut config = SynsyuConfig::default();
+        config.aur.base_url = format!("{base}/rpc/");
+        config.aur.max_args = 1;
+        config.aur.max_retries = 1;
+        // Keep test lookups out of the invoking user's cache directory.
+        config.aur.cache_ttl_secs = 0;
+        config
+    }
+
+    #[cfg(feature = "aur")]
+    const AUR_HIT: &str =
+        r#"{"resultcount":1,"results":[{"Name":"yay","Version":"12.4.2-1","Maintainer":"jguer"}]}"#;
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    async fn failed_aur_chunk_makes_run_incomplete() {
+        let (base, _server) = test_support::mock_server(vec![
+            test_support::json_response(AUR_HIT),
+            "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                .to_string(),
+        ])
+        .await;
+        let logger = Logger::new(None, false).unwrap();
+        let mut pkgs = vec![foreign("yay"), foreign("paru")];
+        let mut completeness = Completeness::default();
+        let foreign = foreign_names(&pkgs);
+        let found = classify_aur_packages(
+            &mut pkgs,
+            &foreign,
+            &aur_config(&base),
+            false,
+            None,
+            &mut completeness,
+            &logger,
+        )
+        .await
+        .unwrap();
+
+        assert!(found.versions.is_empty());
+        assert!(found.not_found.is_empty(), "failures are not misses");
+        assert_eq!(completeness.gaps().len(), 1);
+        assert!(completeness.gaps()[0].starts_with("AUR lookup failed for 2 foreign package(s)"));
+        let err = completeness.enforce(true, &logger).unwrap_err();
+        assert!(err.to_string().contains("yay, paru"));
+        assert_eq!(err.exit_code(), ExitCode::from(60));
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    async fn clean_aur_lookup_passes_require_complete() {
+        let (base, _server) =
+            test_support::mock_server(vec![test_support::json_response(AUR_HIT)]).await;
+        let logger = Logger::new(None, false).unwrap();
+        let mut pkgs = vec![installed("zsh"), foreign("yay")];
+        let mut completeness = Completeness::default();
+        let foreign = foreign_names(&pkgs);
+        let found = classify_aur_packages(
+            &mut pkgs,
+            &foreign,
+            &aur_config(&base),
+            false,
+            None,
+            &mut completeness,
+            &logger,
+        )
+        .await
+        .unwrap();
+
+        assert!(found.versions.contains_key("yay"));
+        assert_eq!(pkgs[1].repository.as_deref(), Some("aur"));
+        assert!(completeness.gaps().is_empty());
+        assert!(completeness.enforce(true, &logger).is_ok());
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    async fn requested_names_missing_from_results_are_reported() {
+        let (base, _server) = test_support::mock_server(vec![
+            test_support::json_response(AUR_HIT),
+            test_support::json_response(r#"{"resultcount":0,"results":[]}"#),
+        ])
+        .await;
+        let logger = Logger::new(None, false).unwrap();
+        let mut pkgs = vec![foreign("yay"), foreign("ghost")];
+        let mut completeness = Completeness::default();
+        let foreign = foreign_names(&pkgs);
+        let lookup = classify_aur_packages(
+            &mut pkgs,
+            &foreign,
+            &aur_config(&base),
+            false,
+            None,
+            &mut completeness,
+            &logger,
+        )
+        .await
+        .unwrap();
+
+        assert!(lookup.versions.contains_key("yay"));
+        assert_eq!(lookup.not_found, HashSet::from(["ghost".to_string()]));
+        assert_eq!(pkgs[1].repository.as_deref(), Some("local"));
+        assert!(completeness.gaps().is_empty());
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    async fn only_foreign_packages_are_looked_up_in_the_aur() {
+        let (base, server) = test_support::mock_server(vec![
+            test_support::json_response(AUR_HIT),
+            test_support::json_response(r#"{"resultcount":0,"results":[]}"#),
+        ])
+        .await;
+        let logger = Logger::new(None, false).unwrap();
+        // No repository recorded and no -Si hit, but not foreign either.
+        let unresolved = InstalledPackage {
+            repository: None,
+            ..installed("glibc")
+        };
+        let mut pkgs = vec![unresolved, foreign("yay"), foreign("ghost")];
+        let foreign = HashSet::from(["yay".to_string(), "ghost".to_string()]);
+        let mut completeness = Completeness::default();
+        let lookup = classify_aur_packages(
+            &mut pkgs,
+            &foreign,
+            &aur_config(&base),
+            false,
+            None,
+            &mut completeness,
+            &logger,
+        )
+        .await
+        .unwrap();
+
+        let heads = server.await.unwrap();
+        assert_eq!(heads.len(), 2);
+        assert!(heads.iter().all(|head| !head.contains("glibc")));
+        assert!(!lookup.not_found.contains("glibc"));
+        assert_eq!(pkgs[0].repository, None);
+        assert_eq!(pkgs[1].repository.as_deref(), Some("aur"));
+        assert_eq!(
+            manifest::source_from_repo(pkgs[2].repository.as_deref()),
+            PackageSource::Local
+        );
+    }
+
+    #[cfg(feature = "aur")]
+    #[tokio::test]
+    async fn invalid_proxy_degrades_to_skipped_aur_lookup() {
+        let mut config = SynsyuConfig::default();
+        config.aur.proxy = Some("ftp://proxy.invalid:21".into());
+        config.aur.cache_ttl_secs = 0;
+        let logger = Logger::new(None, false).unwrap();
+        let mut pkgs = vec![installed("zsh"), foreign("yay")];
+        let mut completeness = Completeness::default();
+        let foreign = foreign_names(&pkgs);
+        let lookup = classify_aur_packages(
+            &mut pkgs,
+            &foreign,
+            &config,
+            false,
+            None,
+            &mut completeness,
+            &logger,
+        )
+        .await
+        .expect("a bad proxy must not abort the run");
+
+        assert!(lookup.versions.is_empty());
+        assert!(lookup.not_found.is_empty());
+        assert_eq!(pkgs[1].repository.as_deref(), Some("local"));
+        assert_eq!(completeness.gaps().len(), 1);
+        assert!(completeness.gaps()[0].contains("aur.proxy"));
+    }
+
+    #[cfg(not(feature = "aur"))]
+    #[tokio::test]
+    async fn minimal_build_reports_aur_lookups_as_unavailable() {
+        let logger = Logger::new(None, false).unwrap();
+        let mut pkgs = vec![installed("zsh"), foreign("yay")];
+        let mut completeness = Completeness::default();
+        let foreign = foreign_names(&pkgs);
+        let lookup = classify_aur_packages(
+            &mut pkgs,
+            &foreign,
+            &SynsyuConfig::default(),
+            false,
+            None,
+            &mut completeness,
+            &logger,
+        )
+        .await
+        .unwrap();
+
+        assert!(lookup.versions.is_empty());
+        let err = completeness.enforce(true, &logger).unwrap_err();
+        assert!(err.to_string().contains("--features aur"));
+    }
+
+    #[test]
+    fn expected_repo_package_missing_from_repo_map_is_flagged() {
+        let pkgs = vec![installed("linux"), installed("yay"), installed("vim")];
+        let mut repo = HashMap::new();
+        repo.insert(
+            "vim".to_string(),
+            VersionInfo::new("9.1.1-1".into(), None, None),
+        );
+        let expected = vec!["linux".to_string(), "vim".to_string(), "absent".to_string()];
+
+        let logger = Logger::new(None, false).unwrap();
+        let missing = check_expected_repo(&expected, &pkgs, &repo, false, &logger).unwrap();
+        assert_eq!(missing, ["linux"]);
+
+        let err = check_expected_repo(&expected, &pkgs, &repo, true, &logger).unwrap_err();
+        assert!(matches!(&err, SynsyuError::ExpectedRepoMissing(names) if names == &["linux"]));
+        assert_eq!(err.exit_code(), ExitCode::from(22));
+
+        repo.insert(
+            "linux".to_string(),
+            VersionInfo::new("6.11.2-1".into(), None, None),
+        );
+        assert!(check_expected_repo(&expected, &pkgs, &repo, true, &logger)
+            .unwrap()
+            .is_empty());
+    }
+}
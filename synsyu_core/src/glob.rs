@@ -0,0 +1,76 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::glob
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Match package names against shell-style patterns (`*`,
+    `?`), as used by pacman.conf and the config lists.
+
+  Security / Safety Notes:
+    Iterative matcher with bounded backtracking; no regex is
+    built from operator input.
+
+  Dependencies:
+    std only.
+
+  Operational Scope:
+    Used wherever a config list names packages by pattern.
+
+  Revision History:
+    2026-10-18 COD  Introduced package-name glob matching.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Whole-name matches only; no implicit prefixes
+    - Linear-time in the common case
+============================================================*/
+
+/// Whether `name` matches `pattern` in full; `*` spans any run, `?` one character.
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position after the last `*` and the name index it is currently covering.
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((after, covered)) => {
+                    star = Some((after, covered + 1));
+                    p = after;
+                    n = covered + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_match_whole_names() {
+        assert!(matches("linux", "linux"));
+        assert!(!matches("linux", "linux-lts"));
+        assert!(matches("*-firmware", "linux-firmware"));
+        assert!(matches("*-firmware", "sof-firmware"));
+        assert!(!matches("*-firmware", "linux-firmware-whence"));
+        assert!(matches("linux*", "linux-zen"));
+        assert!(!matches("lib?32-*", "lib32-glibc"));
+        assert!(matches("lib??-*", "lib32-glibc"));
+        assert!(matches("*", ""));
+        assert!(matches("a*b*c", "axxbyyc"));
+        assert!(!matches("a*b*c", "axxbyy"));
+    }
+}
@@ -0,0 +1,279 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::history
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Persist per-package update state between runs and derive a
+    concise "since last run" delta for operators.
+
+  Security / Safety Notes:
+    State is written atomically to the operator's cache
+    directory with private permissions; only package names and
+    versions are recorded.
+
+  Dependencies:
+    serde for JSON persistence.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core after manifest generation to report
+    newly-available, applied, and resolved updates.
+
+  Revision History:
+    2026-10-18 COD  Introduced run history and delta reporting.
+    2026-10-18 COD  Narrowed runs merge into the stored snapshot.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Deterministic ordering for reproducible reports
+    - Missing or unreadable history degrades to a first run
+    - Explicit error paths for persistence failures
+============================================================*/
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache;
+use crate::error::Result;
+use crate::logger::Logger;
+use crate::manifest::ManifestDocument;
+
+/// Cached update state for a single package.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PackageState {
+    pub installed_version: String,
+    #[serde(default)]
+    pub available_version: Option<String>,
+    #[serde(default)]
+    pub update_available: bool,
+}
+
+/// Update state captured at the end of a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSnapshot {
+    pub generated_at: String,
+    pub packages: BTreeMap<String, PackageState>,
+}
+
+impl RunSnapshot {
+    /// Project the per-package update state out of a manifest.
+    pub fn from_manifest(document: &ManifestDocument) -> Self {
+        let packages = document
+            .packages
+            .iter()
+            .map(|(name, entry)| {
+                (
+                    name.clone(),
+                    PackageState {
+                        installed_version: entry.installed_version.clone(),
+                        available_version: entry.available_version.clone(),
+                        update_available: entry.update_available,
+                    },
+                )
+            })
+            .collect();
+        Self {
+            generated_at: document.metadata.generated_at.clone(),
+            packages,
+        }
+    }
+
+    /// Fold a run that resolved only a subset of packages over `previous`.
+    ///
+    /// Packages outside the subset keep their last known state, so the next
+    /// full run does not report them as newly available or resolved.
+    pub fn merged_over(self, previous: Option<&RunSnapshot>) -> RunSnapshot {
+        let Some(previous) = previous else {
+            return self;
+        };
+        let mut packages = previous.packages.clone();
+        packages.extend(self.packages);
+        RunSnapshot {
+            generated_at: self.generated_at,
+            packages,
+        }
+    }
+}
+
+/// A single package transition between two runs.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct DeltaEntry {
+    pub name: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Changes in update state since the previous run.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct RunDelta {
+    pub previous_generated_at: String,
+    /// Updates available now that were not pending last run (installed -> candidate).
+    pub newly_available: Vec<DeltaEntry>,
+    /// Pending updates that were installed (previous installed -> installed now).
+    pub applied: Vec<DeltaEntry>,
+    /// Pending updates that disappeared without the candidate being installed.
+    pub resolved: Vec<DeltaEntry>,
+}
+
+impl RunDelta {
+    pub fn is_empty(&self) -> bool {
+        self.newly_available.is_empty() && self.applied.is_empty() && self.resolved.is_empty()
+    }
+}
+
+/// Location of the run history file within the cache directory.
+pub fn history_path(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("last_run.json")
+}
+
+/// Load the previous snapshot; absent or corrupt history is treated as a first run.
+pub fn load_snapshot(path: &Path, logger: &Logger) -> Option<RunSnapshot> {
+    cache::read_json(path, logger)
+}
+
+/// Persist the snapshot for the next run.
+pub fn store_snapshot(snapshot: &RunSnapshot, path: &Path) -> Result<()> {
+    cache::write_json_atomic(path, snapshot)
+}
+
+/// Compare the previous snapshot with the current one.
+pub fn compute_delta(previous: &RunSnapshot, current: &RunSnapshot) -> RunDelta {
+    let mut delta = RunDelta {
+        previous_generated_at: previous.generated_at.clone(),
+        ..RunDelta::default()
+    };
+
+    for (name, now) in &current.packages {
+        let before = previous.packages.get(name);
+        let was_pending = before.map(|b| b.update_available).unwrap_or(false);
+
+        if now.update_available && !was_pending {
+            delta.newly_available.push(DeltaEntry {
+                name: name.clone(),
+                from: now.installed_version.clone(),
+                to: now.available_version.clone().unwrap_or_default(),
+            });
+            continue;
+        }
+
+        let Some(before) = before.filter(|b| b.update_available) else {
+            continue;
+        };
+        let previous_candidate = before.available_version.as_deref().unwrap_or_default();
+        if now.installed_version == previous_candidate {
+            delta.applied.push(DeltaEntry {
+                name: name.clone(),
+                from: before.installed_version.clone(),
+                to: now.installed_version.clone(),
+            });
+        } else if !now.update_available {
+            delta.resolved.push(DeltaEntry {
+                name: name.clone(),
+                from: before.installed_version.clone(),
+                to: now.installed_version.clone(),
+            });
+        }
+    }
+
+    delta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(installed: &str, available: Option<&str>, pending: bool) -> PackageState {
+        PackageState {
+            installed_version: installed.into(),
+            available_version: available.map(str::to_string),
+            update_available: pending,
+        }
+    }
+
+    fn snapshot(entries: &[(&str, PackageState)]) -> RunSnapshot {
+        RunSnapshot {
+            generated_at: "2026-10-17T00:00:00Z".into(),
+            packages: entries
+                .iter()
+                .map(|(name, s)| (name.to_string(), s.clone()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn package_updated_between_runs_is_applied() {
+        let previous = snapshot(&[("bash", state("5.2.32-1", Some("5.2.37-1"), true))]);
+        let current = snapshot(&[("bash", state("5.2.37-1", Some("5.2.37-1"), false))]);
+        let delta = compute_delta(&previous, &current);
+        assert_eq!(
+            delta.applied,
+            vec![DeltaEntry {
+                name: "bash".into(),
+                from: "5.2.32-1".into(),
+                to: "5.2.37-1".into(),
+            }]
+        );
+        assert!(delta.newly_available.is_empty());
+        assert!(delta.resolved.is_empty());
+    }
+
+    #[test]
+    fn new_candidate_is_reported_once() {
+        let previous = snapshot(&[("zsh", state("5.9-4", Some("5.9-4"), false))]);
+        let current = snapshot(&[("zsh", state("5.9-4", Some("5.9-5"), true))]);
+        let delta = compute_delta(&previous, &current);
+        assert_eq!(delta.newly_available.len(), 1);
+        assert_eq!(delta.newly_available[0].to, "5.9-5");
+
+        let unchanged = compute_delta(&current, &current);
+        assert!(unchanged.is_empty(), "still-pending update is not new");
+    }
+
+    #[test]
+    fn withdrawn_candidate_is_resolved() {
+        let previous = snapshot(&[("foo", state("1.0-1", Some("1.1-1"), true))]);
+        let current = snapshot(&[("foo", state("1.0-1", Some("1.0-1"), false))]);
+        let delta = compute_delta(&previous, &current);
+        assert_eq!(delta.resolved.len(), 1);
+        assert!(delta.applied.is_empty());
+    }
+
+    #[test]
+    fn filtered_run_does_not_skew_the_next_full_run() {
+        let full = snapshot(&[
+            ("bash", state("5.2.32-1", Some("5.2.37-1"), true)),
+            ("vim", state("9.1.0-1", Some("9.1.0-1"), false)),
+            ("zsh", state("5.9-4", Some("5.9-5"), true)),
+        ]);
+        // `--package vim`: only vim was resolved this time.
+        let filtered = snapshot(&[("vim", state("9.1.0-1", Some("9.1.1-1"), true))]);
+        let delta = compute_delta(&full, &filtered);
+        assert_eq!(delta.newly_available.len(), 1);
+        let stored = filtered.merged_over(Some(&full));
+        assert_eq!(stored.packages.len(), 3);
+
+        let next_full = snapshot(&[
+            ("bash", state("5.2.32-1", Some("5.2.37-1"), true)),
+            ("vim", state("9.1.0-1", Some("9.1.1-1"), true)),
+            ("zsh", state("5.9-5", Some("5.9-5"), false)),
+        ]);
+        let delta = compute_delta(&stored, &next_full);
+        assert!(delta.newly_available.is_empty(), "{delta:?}");
+        assert_eq!(delta.applied.len(), 1);
+        assert_eq!(delta.applied[0].name, "zsh");
+        assert!(delta.resolved.is_empty());
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_cache() {
+        let dir = std::env::temp_dir().join(format!("synsyu-history-{}", std::process::id()));
+        let path = history_path(&dir);
+        let original = snapshot(&[("bash", state("5.2.32-1", Some("5.2.37-1"), true))]);
+        store_snapshot(&original, &path).expect("history should persist");
+        let logger = Logger::new(None, false).unwrap();
+        let loaded = load_snapshot(&path, &logger).expect("history should load");
+        assert_eq!(loaded.packages, original.packages);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,185 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::integrity
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Verify installed files of update candidates with
+    `pacman -Qkk` to spot local database/file mismatches.
+
+  Security / Safety Notes:
+    Read-only verification with user privileges; files the user
+    cannot read may be reported as altered by pacman.
+
+  Dependencies:
+    tokio::process for the pacman invocation.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core when `--integrity-check` is set.
+
+  Revision History:
+    2026-10-18 COD  Introduced pacman -Qkk integrity check.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Mismatch exits are data, not failures
+    - Unusable verifier output degrades to a warning
+============================================================*/
+
+use std::collections::HashMap;
+use std::process::Stdio;
+
+use tokio::process::Command;
+
+use crate::error::{Result, SynsyuError};
+use crate::logger::Logger;
+use crate::manifest::ManifestDocument;
+
+/// Run `pacman -Qkk` for every update candidate and record `integrity_ok`.
+pub async fn check_integrity(document: &mut ManifestDocument, chunk_size: usize, logger: &Logger) {
+    let names: Vec<String> = document
+        .packages
+        .iter()
+        .filter(|(_, entry)| entry.update_available)
+        .map(|(name, _)| name.clone())
+        .collect();
+    let mut verdicts = HashMap::new();
+    for chunk in names.chunks(chunk_size.max(1)) {
+        match run_qkk(chunk).await {
+            Ok(found) => verdicts.extend(found),
+            Err(err) => {
+                logger.warn("INTEGRITY", format!("Integrity check skipped: {err}"));
+                return;
+            }
+        }
+    }
+    apply_verdicts(document, &verdicts, logger);
+}
+
+async fn run_qkk(packages: &[String]) -> Result<HashMap<String, bool>> {
+    let output = Command::new("pacman")
+        .arg("-Qkk")
+        .args(packages)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await
+        .map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                SynsyuError::CommandMissing {
+                    command: "pacman".into(),
+                }
+            } else {
+                SynsyuError::Runtime(format!("Failed to spawn pacman: {err}"))
+            }
+        })?;
+    // Altered files make -Qkk exit non-zero; the summaries still tell us which package.
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let verdicts = parse_qkk(&text);
+    if !output.status.success() && verdicts.is_empty() {
+        return Err(SynsyuError::CommandFailure {
+            command: format!("pacman -Qkk {}", packages.join(" ")),
+            status: output.status.code().unwrap_or(-1),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(verdicts)
+}
+
+/// Map package name to whether all of its files verified cleanly.
+///
+/// Summary lines read `name: N total files, M altered files`; any
+/// `warning: name: <path> (...)` line also marks the package as failed.
+pub fn parse_qkk(text: &str) -> HashMap<String, bool> {
+    let mut verdicts: HashMap<String, bool> = HashMap::new();
+    for line in text.lines() {
+        if let Some(rest) = line.strip_prefix("warning: ") {
+            if let Some((name, _)) = rest.split_once(": ") {
+                verdicts.insert(name.to_string(), false);
+            }
+            continue;
+        }
+        let Some((name, counts)) = line.split_once(": ") else {
+            continue;
+        };
+        if !counts.contains("total files") {
+            continue;
+        }
+        let clean = counts
+            .split(", ")
+            .skip(1)
+            .all(|part| part.split_whitespace().next() == Some("0"));
+        let verdict = verdicts.entry(name.to_string()).or_insert(true);
+        *verdict = *verdict && clean;
+    }
+    verdicts
+}
+
+fn apply_verdicts(
+    document: &mut ManifestDocument,
+    verdicts: &HashMap<String, bool>,
+    logger: &Logger,
+) {
+    for (name, entry) in document.packages.iter_mut() {
+        let Some(&ok) = verdicts.get(name) else {
+            continue;
+        };
+        entry.integrity_ok = Some(ok);
+        if !ok {
+            logger.warn(
+                "INTEGRITY",
+                format!(
+                    "{name}: installed files fail verification; upgrading may not resolve the underlying corruption"
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::{fixtures, PackageSource};
+
+    const CLEAN: &str =
+        "bash: 1210 total files, 0 altered files\nzsh: 1543 total files, 0 altered files\n";
+    const BROKEN: &str = "warning: glibc: /usr/lib/libc.so.6 (Modification time mismatch)\nwarning: glibc: /usr/lib/libc.so.6 (Size mismatch)\nbash: 1210 total files, 0 altered files\nglibc: 4980 total files, 1 altered file\nopenssl: 2001 total files, 0 altered files, 2 missing files\n";
+
+    #[test]
+    fn clean_output_verifies_every_package() {
+        let verdicts = parse_qkk(CLEAN);
+        assert_eq!(verdicts.len(), 2);
+        assert!(verdicts.values().all(|ok| *ok));
+    }
+
+    #[test]
+    fn altered_and_missing_files_fail_verification() {
+        let verdicts = parse_qkk(BROKEN);
+        assert!(verdicts["bash"]);
+        assert!(!verdicts["glibc"]);
+        assert!(!verdicts["openssl"]);
+
+        let mut document = fixtures::document(vec![
+            (
+                "bash",
+                fixtures::entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1")),
+            ),
+            (
+                "glibc",
+                fixtures::entry("2.40-1", PackageSource::Pacman, Some("2.40-2")),
+            ),
+            (
+                "vim",
+                fixtures::entry("9.1.0-1", PackageSource::Pacman, None),
+            ),
+        ]);
+        let logger = Logger::new(None, false).unwrap();
+        apply_verdicts(&mut document, &verdicts, &logger);
+        assert_eq!(document.packages["bash"].integrity_ok, Some(true));
+        assert_eq!(document.packages["glibc"].integrity_ok, Some(false));
+        assert_eq!(document.packages["vim"].integrity_ok, None);
+    }
+}
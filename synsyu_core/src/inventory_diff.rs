@@ -0,0 +1,247 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::inventory_diff
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Compare the installed package inventory with a golden
+    reference set of `name version` lines.
+
+  Security / Safety Notes:
+    Reads one operator-supplied text file; no network access.
+
+  Dependencies:
+    crate::pacman for the installed inventory.
+
+  Operational Scope:
+    Backs Syn-Syu-Core `--diff-installed`, independent of update
+    resolution.
+
+  Revision History:
+    2026-10-18 COD  Introduced reference inventory diffing.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Deterministic, name-sorted output
+    - Malformed reference lines are rejected with their location
+============================================================*/
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::error::{Result, SynsyuError};
+use crate::pacman::InstalledPackage;
+
+/// A package present on only one side of the comparison.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct DiffPackage {
+    pub name: String,
+    pub version: String,
+}
+
+/// A package present on both sides at different versions.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct VersionDifference {
+    pub name: String,
+    pub installed: String,
+    pub reference: String,
+}
+
+/// How the local inventory differs from a reference package set.
+#[derive(Debug, Serialize)]
+pub struct InventoryDiff {
+    pub reference: String,
+    /// Installed locally but absent from the reference.
+    pub added: Vec<DiffPackage>,
+    /// Listed in the reference but not installed.
+    pub missing: Vec<DiffPackage>,
+    pub version_differs: Vec<VersionDifference>,
+}
+
+impl InventoryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.missing.is_empty() && self.version_differs.is_empty()
+    }
+
+    pub fn render(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(text, "→ Inventory diff against {}", self.reference);
+        if self.is_empty() {
+            let _ = writeln!(text, "  Installed packages match the reference.");
+            return text;
+        }
+        for package in &self.added {
+            let _ = writeln!(text, "  + {} {}", package.name, package.version);
+        }
+        for package in &self.missing {
+            let _ = writeln!(text, "  - {} {}", package.name, package.version);
+        }
+        for change in &self.version_differs {
+            let _ = writeln!(
+                text,
+                "  ~ {} {} (reference {})",
+                change.name, change.installed, change.reference
+            );
+        }
+        let _ = writeln!(
+            text,
+            "  {} added, {} missing, {} at different versions",
+            self.added.len(),
+            self.missing.len(),
+            self.version_differs.len()
+        );
+        text
+    }
+}
+
+/// Read a reference set; blank lines and `#` comments are ignored.
+pub fn load_reference(path: &Path) -> Result<BTreeMap<String, String>> {
+    let text = fs::read_to_string(path).map_err(|err| {
+        SynsyuError::Filesystem(format!(
+            "Failed to read reference package set {}: {err}",
+            path.display()
+        ))
+    })?;
+    parse_reference(&text).map_err(|detail| {
+        SynsyuError::Config(format!(
+            "Invalid reference package set {}: {detail}",
+            path.display()
+        ))
+    })
+}
+
+fn parse_reference(text: &str) -> std::result::Result<BTreeMap<String, String>, String> {
+    let mut reference = BTreeMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some(name), Some(version), None) => {
+                reference.insert(name.to_string(), version.to_string());
+            }
+            _ => {
+                return Err(format!(
+                    "line {}: expected `name version`, found `{line}`",
+                    index + 1
+                ))
+            }
+        }
+    }
+    Ok(reference)
+}
+
+/// Compare installed packages with `reference`.
+pub fn diff_installed(
+    installed: &[InstalledPackage],
+    reference: &BTreeMap<String, String>,
+    label: &str,
+) -> InventoryDiff {
+    let local: BTreeMap<&str, &str> = installed
+        .iter()
+        .map(|package| (package.name.as_str(), package.version.as_str()))
+        .collect();
+    let mut diff = InventoryDiff {
+        reference: label.to_string(),
+        added: Vec::new(),
+        missing: Vec::new(),
+        version_differs: Vec::new(),
+    };
+    for (&name, &version) in &local {
+        match reference.get(name) {
+            None => diff.added.push(DiffPackage {
+                name: name.to_string(),
+                version: version.to_string(),
+            }),
+            Some(expected) if expected != version => diff.version_differs.push(VersionDifference {
+                name: name.to_string(),
+                installed: version.to_string(),
+                reference: expected.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (name, version) in reference {
+        if !local.contains_key(name.as_str()) {
+            diff.missing.push(DiffPackage {
+                name: name.clone(),
+                version: version.clone(),
+            });
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(name: &str, version: &str) -> InstalledPackage {
+        InstalledPackage {
+            name: name.into(),
+            version: version.into(),
+            repository: None,
+            installed_size: None,
+            install_date: None,
+            validated_by: None,
+            package_hash: None,
+            required_by: Vec::new(),
+            provides: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn additions_removals_and_version_changes_are_reported() {
+        let reference = parse_reference(
+            "# golden workstation\nbash 5.2.037-1\nlinux 6.11.2-1\n\nvim 9.1.0-1\n",
+        )
+        .unwrap();
+        let local = [
+            installed("bash", "5.2.037-1"),
+            installed("linux", "6.11.3-1"),
+            installed("htop", "3.3.0-1"),
+        ];
+        let diff = diff_installed(&local, &reference, "golden.txt");
+
+        assert_eq!(
+            diff.added,
+            [DiffPackage {
+                name: "htop".into(),
+                version: "3.3.0-1".into()
+            }]
+        );
+        assert_eq!(
+            diff.missing,
+            [DiffPackage {
+                name: "vim".into(),
+                version: "9.1.0-1".into()
+            }]
+        );
+        assert_eq!(
+            diff.version_differs,
+            [VersionDifference {
+                name: "linux".into(),
+                installed: "6.11.3-1".into(),
+                reference: "6.11.2-1".into()
+            }]
+        );
+        let text = diff.render();
+        assert!(text.contains("+ htop 3.3.0-1"));
+        assert!(text.contains("~ linux 6.11.3-1 (reference 6.11.2-1)"));
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["missing"][0]["name"], "vim");
+    }
+
+    #[test]
+    fn malformed_reference_lines_are_rejected() {
+        let err = parse_reference("bash 5.2.037-1\nlinux\n").unwrap_err();
+        assert!(err.starts_with("line 2:"));
+        assert!(parse_reference("a 1-1 extra\n").is_err());
+    }
+}
@@ -0,0 +1,173 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::journal
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Send the run summary (and optionally warnings) to the
+    systemd journal with structured SYNSYU_* fields.
+
+  Security / Safety Notes:
+    Writes datagrams to the local journald socket only; when it
+    is absent the summary goes to stderr instead.
+
+  Dependencies:
+    std::os::unix::net for the native journal protocol.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core at the end of a run under
+    `--journald`.
+
+  Revision History:
+    2026-10-18 COD  Introduced journald run summaries.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Journal delivery never fails a completed run
+    - Fields are machine-filterable (`journalctl SYNSYU_UPDATES=...`)
+============================================================*/
+
+use std::io;
+use std::path::Path;
+
+use crate::logger::Logger;
+use crate::manifest::ManifestDocument;
+
+/// Socket journald listens on for native protocol datagrams.
+pub const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+
+const PRIORITY_WARNING: u8 = 4;
+const PRIORITY_INFO: u8 = 6;
+
+/// Send the run summary, then each warning when `warnings` is given.
+///
+/// Returns `false` when journald was unreachable and stderr was used instead.
+pub fn emit_run(
+    document: &ManifestDocument,
+    warnings: Option<&[String]>,
+    socket: &Path,
+    logger: &Logger,
+) -> bool {
+    let metadata = &document.metadata;
+    let message = format!(
+        "Syn-Syu run: {} update(s) across {} package(s)",
+        metadata.updates_available, metadata.total_packages
+    );
+    let summary = [
+        ("MESSAGE", message.clone()),
+        ("PRIORITY", PRIORITY_INFO.to_string()),
+        ("SYSLOG_IDENTIFIER", "synsyu_core".to_string()),
+        ("SYNSYU_UPDATES", metadata.updates_available.to_string()),
+        ("SYNSYU_TOTAL", metadata.total_packages.to_string()),
+    ];
+    if let Err(err) = send(socket, &encode(&summary)) {
+        logger.warn(
+            "JOURNAL",
+            format!(
+                "journald unavailable at {} ({err}); summary written to stderr",
+                socket.display()
+            ),
+        );
+        eprintln!(
+            "{message} SYNSYU_UPDATES={} SYNSYU_TOTAL={}",
+            metadata.updates_available, metadata.total_packages
+        );
+        return false;
+    }
+    for warning in warnings.unwrap_or_default() {
+        let fields = [
+            ("MESSAGE", warning.clone()),
+            ("PRIORITY", PRIORITY_WARNING.to_string()),
+            ("SYSLOG_IDENTIFIER", "synsyu_core".to_string()),
+        ];
+        if let Err(err) = send(socket, &encode(&fields)) {
+            logger.warn(
+                "JOURNAL",
+                format!("Failed to send warning to journald: {err}"),
+            );
+            break;
+        }
+    }
+    true
+}
+
+/// Encode fields in the journal export format; values with newlines use the binary form.
+fn encode(fields: &[(&str, String)]) -> Vec<u8> {
+    let mut payload = Vec::new();
+    for (key, value) in fields {
+        payload.extend_from_slice(key.as_bytes());
+        if value.contains('\n') {
+            payload.push(b'\n');
+            payload.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        } else {
+            payload.push(b'=');
+        }
+        payload.extend_from_slice(value.as_bytes());
+        payload.push(b'\n');
+    }
+    payload
+}
+
+#[cfg(unix)]
+fn send(socket: &Path, payload: &[u8]) -> io::Result<()> {
+    let datagram = std::os::unix::net::UnixDatagram::unbound()?;
+    datagram.send_to(payload, socket).map(|_| ())
+}
+
+#[cfg(not(unix))]
+fn send(_socket: &Path, _payload: &[u8]) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "journald requires a Unix host",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::{fixtures, PackageSource};
+
+    fn document() -> ManifestDocument {
+        fixtures::document(vec![
+            (
+                "curl",
+                fixtures::entry("8.10.1-1", PackageSource::Pacman, Some("8.10.1-2")),
+            ),
+            ("zsh", fixtures::entry("5.9-5", PackageSource::Pacman, None)),
+        ])
+    }
+
+    #[test]
+    fn missing_socket_falls_back_without_failing() {
+        let logger = Logger::new(None, false).unwrap();
+        let socket = std::env::temp_dir().join("synsyu-no-journal/socket");
+        assert!(!emit_run(&document(), Some(&[]), &socket, &logger));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn summary_carries_structured_fields() {
+        let dir = std::env::temp_dir().join(format!("synsyu-journal-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket = dir.join("socket");
+        let journal = std::os::unix::net::UnixDatagram::bind(&socket).unwrap();
+        let logger = Logger::new(None, false).unwrap();
+
+        let warnings = vec!["line one\nline two".to_string()];
+        assert!(emit_run(&document(), Some(&warnings), &socket, &logger));
+
+        let mut buf = [0u8; 1024];
+        let len = journal.recv(&mut buf).unwrap();
+        let summary = String::from_utf8_lossy(&buf[..len]).into_owned();
+        assert!(summary.contains("SYNSYU_UPDATES=1\n"));
+        assert!(summary.contains("SYNSYU_TOTAL=2\n"));
+        assert!(summary.contains("PRIORITY=6\n"));
+
+        let len = journal.recv(&mut buf).unwrap();
+        let mut expected = b"MESSAGE\n".to_vec();
+        expected.extend_from_slice(&17u64.to_le_bytes());
+        expected.extend_from_slice(b"line one\nline two\n");
+        assert!(buf[..len].starts_with(&expected));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
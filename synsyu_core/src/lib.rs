@@ -0,0 +1,92 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core (library)
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Library face of Syn-Syu Core. Exposes package enumeration,
+    candidate lookup, and manifest construction so other tools
+    can generate manifests without going through the CLI.
+
+  Security / Safety Notes:
+    Same privileges and external commands (pacman, optional
+    AUR RPC) as the binary; nothing runs at load time.
+
+  Dependencies:
+    See the individual modules.
+
+  Operational Scope:
+    Linked by the `synsyu_core` binary, a thin CLI over these
+    modules, and by downstream crates (`generate_manifest`).
+
+  Revision History:
+    2026-10-18 COD  Split the reusable core out of the binary.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - One entry point for programmatic manifest generation
+    - Files and logging happen only when the caller asks
+============================================================*/
+
+pub mod annotations;
+pub mod audit;
+#[cfg(feature = "aur")]
+pub mod aur;
+pub mod bench;
+pub mod build_info;
+pub mod cache;
+pub mod cascade;
+pub mod checkpoint;
+#[cfg(feature = "aur")]
+pub mod clock;
+pub mod completeness;
+pub mod config;
+pub mod confirm;
+pub mod deadline;
+pub mod dep_graph;
+pub mod error;
+pub mod flatpak;
+pub mod future;
+pub mod fwupd;
+pub mod generate;
+pub mod glob;
+pub mod history;
+pub mod integrity;
+pub mod inventory_diff;
+pub mod journal;
+pub mod log_api;
+pub mod logger;
+pub mod manifest;
+pub mod manifest_diff;
+pub mod mirror;
+pub mod new_deps;
+pub mod output;
+pub mod package_info;
+pub mod pacman;
+pub mod pacman_conf;
+pub mod paths;
+pub mod pkg_cache;
+pub mod plan;
+pub mod policy;
+pub mod raw_dump;
+pub mod report;
+pub mod rng;
+pub mod run_event;
+pub mod selection;
+pub mod show;
+pub mod space;
+pub mod split;
+pub mod stability;
+pub mod sync_db;
+pub mod updates;
+pub mod upgrade_plan;
+pub mod vercmp;
+
+#[cfg(feature = "aur")]
+pub use aur::AurClient;
+pub use config::SynsyuConfig;
+pub use error::{Result, SynsyuError};
+pub use generate::{generate, generate_manifest, Generation, ManifestOptions};
+pub use logger::Logger;
+pub use manifest::{build_manifest, ManifestDocument, ManifestEntry};
+pub use package_info::VersionInfo;
+pub use pacman::{enumerate_installed_packages, query_repo_versions, InstalledPackage};
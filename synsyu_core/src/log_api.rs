@@ -6,7 +6,7 @@ use chrono::{SecondsFormat, Utc};
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 
-use crate::config::SynsyuConfig;
+use crate::config::{LogNaming, SynsyuConfig, FIXED_CORE_LOG};
 use crate::error::{Result, SynsyuError};
 
 #[derive(Debug, Serialize, Clone)]
@@ -104,6 +104,11 @@ pub fn log_prune(config: &SynsyuConfig) -> Result<()> {
     if days == 0 && bytes_limit == 0 {
         return Ok(());
     }
+    // A fixed-name core log belongs to external rotation; never prune it here.
+    let prunable = |name: &str| {
+        name.ends_with(".log")
+            && !(config.logging.naming == LogNaming::Fixed && name == FIXED_CORE_LOG)
+    };
     if days > 0 {
         let cutoff = std::time::SystemTime::now()
             .checked_sub(std::time::Duration::from_secs(60 * 60 * 24 * days))
@@ -113,7 +118,7 @@ pub fn log_prune(config: &SynsyuConfig) -> Result<()> {
                 if let Ok(meta) = entry.metadata() {
                     if meta.is_file()
                         && meta.modified().unwrap_or(std::time::SystemTime::now()) < cutoff
-                        && entry.file_name().to_string_lossy().ends_with(".log")
+                        && prunable(&entry.file_name().to_string_lossy())
                     {
                         let _ = fs::remove_file(entry.path());
                         let mut hash_path = entry.path();
@@ -130,7 +135,7 @@ pub fn log_prune(config: &SynsyuConfig) -> Result<()> {
         if let Ok(entries) = fs::read_dir(&dir) {
             for entry in entries.flatten() {
                 if let Ok(meta) = entry.metadata() {
-                    if meta.is_file() && entry.file_name().to_string_lossy().ends_with(".log") {
+                    if meta.is_file() && prunable(&entry.file_name().to_string_lossy()) {
                         logs.push((
                             meta.modified().unwrap_or(std::time::SystemTime::now()),
                             entry.path(),
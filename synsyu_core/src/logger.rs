@@ -20,6 +20,12 @@
 
   Revision History:
     2024-11-04 COD  Established logging module for Syn-Syu-Core.
+    2026-10-18 COD  Retained warnings for journald forwarding.
+    2026-10-18 COD  Per-code stderr verbosity (`--log-filter`).
+    2026-10-18 COD  Rendered paths in messages per `paths.style`.
+    2026-10-18 COD  Masked registered secrets in every message.
+    2026-10-18 COD  Added a silent logger for library callers.
+    2026-10-18 COD  Optional JSON-lines entries (`logging.format`).
   ------------------------------------------------------------
   SSE Principles Observed:
     - Append-only logging with UTC timestamps
@@ -27,6 +33,7 @@
     - Graceful error propagation on I/O failures
 ============================================================*/
 
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::{BufWriter, Write};
 use std::path::{Path, PathBuf};
@@ -35,10 +42,12 @@ use std::sync::Mutex;
 use chrono::{SecondsFormat, Utc};
 use sha2::{Digest, Sha256};
 
+use crate::config::{LogFormat, REDACTED};
 use crate::error::{Result, SynsyuError};
+use crate::paths::PathFormatter;
 
 /// Structured log level for Syn-Syu-Core events.
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum LogLevel {
     Info,
     Warn,
@@ -47,6 +56,16 @@ pub enum LogLevel {
 }
 
 impl LogLevel {
+    /// Severity rank used by log filters (higher is more severe).
+    fn severity(self) -> u8 {
+        match self {
+            LogLevel::Debug => 0,
+            LogLevel::Info => 1,
+            LogLevel::Warn => 2,
+            LogLevel::Error => 3,
+        }
+    }
+
     fn as_str(self) -> &'static str {
         match self {
             LogLevel::Info => "INFO",
@@ -62,6 +81,44 @@ pub struct Logger {
     file: Option<Mutex<BufWriter<File>>>,
     path: Option<PathBuf>,
     verbose: bool,
+    /// Line encoding shared by the file and stderr sinks (`logging.format`).
+    format: LogFormat,
+    /// Nothing reaches stderr (library callers that did not ask for logging).
+    silent: bool,
+    /// Minimum level shown on stderr per event code; the file records everything.
+    filter: HashMap<String, LogLevel>,
+    /// Rewrites paths inside every message (`paths.style`).
+    paths: PathFormatter,
+    /// Values replaced by `<redacted>` wherever they appear in a message.
+    secrets: Vec<String>,
+    warnings: Mutex<Vec<String>>,
+}
+
+/// Parse `CODE=level,...` (levels: debug, info, warn, error) into a stderr filter.
+pub fn parse_log_filter(spec: &str) -> Result<HashMap<String, LogLevel>> {
+    let mut filter = HashMap::new();
+    for rule in spec
+        .split(',')
+        .map(str::trim)
+        .filter(|rule| !rule.is_empty())
+    {
+        let (code, level) = rule.split_once('=').ok_or_else(|| {
+            SynsyuError::Config(format!("Log filter rule `{rule}` must be CODE=level"))
+        })?;
+        let level = match level.trim().to_ascii_lowercase().as_str() {
+            "debug" => LogLevel::Debug,
+            "info" => LogLevel::Info,
+            "warn" => LogLevel::Warn,
+            "error" => LogLevel::Error,
+            other => {
+                return Err(SynsyuError::Config(format!(
+                    "Unknown log level `{other}` in filter rule `{rule}`"
+                )))
+            }
+        };
+        filter.insert(code.trim().to_ascii_uppercase(), level);
+    }
+    Ok(filter)
 }
 
 impl Logger {
@@ -96,41 +153,122 @@ impl Logger {
             file,
             path,
             verbose,
+            format: LogFormat::default(),
+            silent: false,
+            filter: HashMap::new(),
+            paths: PathFormatter::default(),
+            secrets: Vec::new(),
+            warnings: Mutex::new(Vec::new()),
         })
     }
 
+    /// Logger that writes nowhere but still collects warnings.
+    pub fn silent() -> Self {
+        Self {
+            file: None,
+            path: None,
+            verbose: false,
+            format: LogFormat::default(),
+            silent: true,
+            filter: HashMap::new(),
+            paths: PathFormatter::default(),
+            secrets: Vec::new(),
+            warnings: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Gate stderr output per event code (see [`parse_log_filter`]).
+    pub fn with_filter(mut self, filter: HashMap<String, LogLevel>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Render paths inside messages with `paths` (stderr, file and retained warnings).
+    pub fn with_paths(mut self, paths: PathFormatter) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Encode every entry as `format` on both sinks.
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Mask `secrets` (credentials, tokens) in every message.
+    pub fn with_secrets<I: IntoIterator<Item = String>>(mut self, secrets: I) -> Self {
+        self.secrets
+            .extend(secrets.into_iter().filter(|secret| !secret.is_empty()));
+        self
+    }
+
+    /// Whether an event reaches stderr: a filtered code uses its own threshold,
+    /// other codes show warnings and errors, or everything under `--verbose`.
+    fn shows_on_stderr(&self, level: LogLevel, code: &str) -> bool {
+        if self.silent {
+            return false;
+        }
+        match self.filter.get(code) {
+            Some(threshold) => level.severity() >= threshold.severity(),
+            None => self.verbose || level == LogLevel::Error || level == LogLevel::Warn,
+        }
+    }
+
+    /// One log line: `timestamp [LEVEL] [CODE] message`, or a JSON object with those fields.
+    fn render(&self, timestamp: &str, level: LogLevel, code: &str, message: &str) -> String {
+        match self.format {
+            LogFormat::Text => format!("{timestamp} [{}] [{code}] {message}", level.as_str()),
+            LogFormat::Json => serde_json::json!({
+                "timestamp": timestamp,
+                "level": level.as_str(),
+                "code": code,
+                "message": message,
+            })
+            .to_string(),
+        }
+    }
+
     /// Emit a log entry with the given level, code, and message.
     pub fn log<S: AsRef<str>>(&self, level: LogLevel, code: &str, message: S) {
         let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
-        let payload = format!(
-            "{timestamp} [{}] [{}] {}",
-            level.as_str(),
-            code,
-            message.as_ref()
-        );
+        let mut message = self.paths.rewrite(message.as_ref());
+        for secret in &self.secrets {
+            message = message.replace(secret.as_str(), REDACTED);
+        }
+        let payload = self.render(&timestamp, level, code, &message);
 
-        if self.verbose || level == LogLevel::Error || level == LogLevel::Warn {
+        if self.shows_on_stderr(level, code) {
             eprintln!("{payload}");
         }
 
+        if level == LogLevel::Warn {
+            if let Ok(mut warnings) = self.warnings.lock() {
+                warnings.push(format!("[{code}] {message}"));
+            }
+        }
+
         if let Some(file) = &self.file {
             if let Ok(mut guard) = file.lock() {
                 if writeln!(guard, "{payload}").is_err() {
                     eprintln!(
-                        "{} [{}] [{}] {}",
-                        timestamp,
-                        LogLevel::Error.as_str(),
-                        "LOGGER",
-                        "Failed to write to log file"
+                        "{}",
+                        self.render(
+                            &timestamp,
+                            LogLevel::Error,
+                            "LOGGER",
+                            "Failed to write to log file"
+                        )
                     );
                 }
                 if guard.flush().is_err() {
                     eprintln!(
-                        "{} [{}] [{}] {}",
-                        timestamp,
-                        LogLevel::Warn.as_str(),
-                        "LOGGER",
-                        "Failed to flush log writer"
+                        "{}",
+                        self.render(
+                            &timestamp,
+                            LogLevel::Warn,
+                            "LOGGER",
+                            "Failed to flush log writer"
+                        )
                     );
                 }
             }
@@ -158,6 +296,14 @@ impl Logger {
         self.log(LogLevel::Debug, code, message);
     }
 
+    /// `WARN` entries emitted so far, as `[CODE] message`.
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings
+            .lock()
+            .map(|warnings| warnings.clone())
+            .unwrap_or_default()
+    }
+
     /// Return the path backing this logger, if any.
     pub fn path(&self) -> Option<&Path> {
         self.path.as_deref()
@@ -200,3 +346,98 @@ impl Logger {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_surfaces_only_matching_codes() {
+        let filter = parse_log_filter("aur=debug, MANIFEST=warn").unwrap();
+        let logger = Logger::new(None, false).unwrap().with_filter(filter);
+
+        assert!(logger.shows_on_stderr(LogLevel::Debug, "AUR"));
+        assert!(!logger.shows_on_stderr(LogLevel::Info, "MANIFEST"));
+        assert!(logger.shows_on_stderr(LogLevel::Warn, "MANIFEST"));
+        assert!(!logger.shows_on_stderr(LogLevel::Debug, "PACMAN"));
+        assert!(logger.shows_on_stderr(LogLevel::Warn, "PACMAN"));
+
+        let verbose = Logger::new(None, true)
+            .unwrap()
+            .with_filter(parse_log_filter("MANIFEST=error").unwrap());
+        assert!(verbose.shows_on_stderr(LogLevel::Debug, "PACMAN"));
+        assert!(!verbose.shows_on_stderr(LogLevel::Warn, "MANIFEST"));
+    }
+
+    #[test]
+    fn silent_logger_keeps_warnings_off_stderr() {
+        let logger = Logger::silent();
+        assert!(!logger.shows_on_stderr(LogLevel::Error, "AUR"));
+        logger.warn("AUR", "lookup skipped");
+        assert_eq!(logger.warnings(), ["[AUR] lookup skipped"]);
+        assert_eq!(logger.path(), None);
+    }
+
+    #[test]
+    fn malformed_filters_are_rejected() {
+        for spec in ["AUR", "AUR=loud"] {
+            assert!(matches!(
+                parse_log_filter(spec),
+                Err(SynsyuError::Config(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn secrets_are_masked_in_file_and_warnings() {
+        let path = std::env::temp_dir().join(format!("synsyu-secret-{}.log", std::process::id()));
+        let logger = Logger::new(Some(path.clone()), false)
+            .unwrap()
+            .with_secrets(["tok-abc123".to_string(), String::new()]);
+        logger.warn("AUR", "RPC rejected token tok-abc123");
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("RPC rejected token <redacted>"));
+        assert!(!contents.contains("tok-abc123"));
+        assert_eq!(
+            logger.warnings(),
+            vec!["[AUR] RPC rejected token <redacted>".to_string()]
+        );
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn json_entries_are_one_object_per_line_and_hashed() {
+        let path = std::env::temp_dir().join(format!("synsyu-json-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let logger = Logger::new(Some(path.clone()), false)
+            .unwrap()
+            .with_format(LogFormat::Json);
+        logger.info("INIT", "awakening");
+        logger.warn("AUR", "message with \"quotes\"\nand a newline");
+        logger.finalize().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let entries: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1]["level"], "WARN");
+        assert_eq!(entries[1]["code"], "AUR");
+        assert_eq!(
+            entries[1]["message"],
+            "message with \"quotes\"\nand a newline"
+        );
+        assert!(entries[0]["timestamp"].as_str().unwrap().ends_with('Z'));
+        // The warning list keeps its text form whatever the log format.
+        assert_eq!(logger.warnings().len(), 1);
+
+        let hash_path = path.with_extension("log.hash");
+        let recorded = std::fs::read_to_string(&hash_path).unwrap();
+        let digest = format!("{:x}", Sha256::digest(contents.as_bytes()));
+        assert!(recorded.starts_with(&digest));
+        let _ = std::fs::remove_file(path);
+        let _ = std::fs::remove_file(hash_path);
+    }
+}
@@ -20,6 +20,11 @@
 
   Revision History:
     2025-10-28 COD  Authored Syn-Syu Core runtime.
+    2026-10-18 COD  Moved the reusable core into the library crate.
+    2026-10-18 COD  Core runs go through generate::generate; only
+                    argument mapping and output remain here.
+    2026-10-18 COD  Narrowed runs merge into the history snapshot.
+    2026-10-18 COD  A closed stdout no longer aborts the core run.
   ------------------------------------------------------------
   SSE Principles Observed:
     - Result-first error handling with deterministic exits
@@ -27,43 +32,49 @@
     - Configurable execution via CLI and config file
 ============================================================*/
 
-mod build_info;
-mod config;
-mod error;
-mod flatpak;
-mod future;
-mod fwupd;
-mod log_api;
-mod logger;
-mod manifest;
-mod package_info;
-mod pacman;
-mod plan;
-mod space;
-mod updates;
-
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use chrono::Utc;
-use clap::{ArgAction, Parser, Subcommand};
+use clap::{ArgAction, ArgGroup, Parser, Subcommand, ValueEnum};
 use std::io::IsTerminal;
 use std::io::{self, Write};
 
-use build_info::BUILD_INFO;
-use config::SynsyuConfig;
-use error::Result;
-use flatpak::collect_flatpak;
-use fwupd::collect_fwupd;
-use log_api::{log_emit, log_hash, log_init, log_prune};
-use logger::Logger;
-use manifest::{build_manifest, write_manifest, ManifestDocument};
-use pacman::{
-    enumerate_installed_packages, query_aur_helper_versions, query_repo_versions, InstalledPackage,
+use synsyu_core::bench::BenchCommand;
+use synsyu_core::build_info::BUILD_INFO;
+use synsyu_core::checkpoint::Checkpoint;
+use synsyu_core::completeness::Completeness;
+use synsyu_core::config::{LogFormat, SynsyuConfig};
+use synsyu_core::error::{Result, SynsyuError};
+use synsyu_core::generate::{enumerate_packages, generate, Generation, ManifestOptions};
+use synsyu_core::history::{
+    compute_delta, history_path, load_snapshot, store_snapshot, RunDelta, RunSnapshot,
+};
+use synsyu_core::log_api::{log_emit, log_hash, log_init, log_prune};
+use synsyu_core::logger::{parse_log_filter, Logger};
+use synsyu_core::manifest::{
+    ensure_manifest_writable, resolve_output_file, write_manifest, write_manifest_canonical,
+    ManifestDocument, ManifestFormat, SymlinkGuard,
+};
+use synsyu_core::output::{out, outln};
+use synsyu_core::pacman::{
+    check_active_transaction, query_aur_helper_versions, query_repo_versions, RetryPolicy,
+};
+use synsyu_core::paths::PathFormatter;
+use synsyu_core::plan::PlanCommand;
+use synsyu_core::raw_dump::RawDump;
+use synsyu_core::report::HelperKind;
+use synsyu_core::run_event::{ConfigSummary, RunEvent};
+use synsyu_core::selection::{LimitSort, MissingPackages, Sample};
+use synsyu_core::show::{ColorChoice, ShowOptions, ShowSort};
+use synsyu_core::updates::{collect_updates, UpdatesFilter};
+use synsyu_core::{
+    annotations, bench, cache, confirm, deadline, dep_graph, inventory_diff, journal, manifest,
+    manifest_diff, output, pacman_conf, policy, report, rng, show, space, split, upgrade_plan,
 };
-use plan::PlanCommand;
-use updates::{collect_updates, UpdatesFilter};
 
 /// Top-level CLI entrypoint.
 #[derive(Debug, Parser)]
@@ -80,6 +91,210 @@ struct Cli {
     command: Option<Commands>,
     #[command(flatten)]
     core: CoreArgs,
+    /// Seed every randomized component (retry jitter, sampling) for a reproducible run.
+    #[arg(long, global = true, value_name = "U64")]
+    seed: Option<u64>,
+}
+
+impl Cli {
+    /// Reject flag combinations that conflict or would silently have no effect.
+    fn validate(&self) -> Result<()> {
+        match &self.command {
+            None => self.core.validate(),
+            Some(Commands::Plan(plan)) => {
+                let no_package_source = plan.no_repo && (plan.no_aur || plan.offline);
+                if no_package_source && !plan.with_flatpak && !plan.with_fwupd {
+                    return Err(conflict(
+                        "plan: --no-repo with --no-aur/--offline leaves no update source; \
+                         add --with-flatpak or --with-fwupd, or drop a --no-* flag",
+                    ));
+                }
+                Ok(())
+            }
+            Some(Commands::Updates(updates)) if updates.no_repo && updates.no_aur => Err(conflict(
+                "updates: --no-repo and --no-aur together leave nothing to list",
+            )),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+impl CoreArgs {
+    fn validate(&self) -> Result<()> {
+        if self.aur_report && self.helper_output.is_some() {
+            return Err(conflict(
+                "--aur-report and --helper-output both write to stdout; choose one",
+            ));
+        }
+        let event_to_stdout = self
+            .emit_event
+            .as_deref()
+            .is_some_and(|target| target.as_os_str() == "-");
+        if event_to_stdout && (self.aur_report || self.helper_output.is_some()) {
+            return Err(conflict(
+                "--emit-event - would interleave with --aur-report/--helper-output on stdout; \
+                 write the event to a file instead",
+            ));
+        }
+        let manifest_to_stdout =
+            !self.dry_run && self.manifest.as_deref().is_some_and(manifest::is_stdout);
+        if manifest_to_stdout
+            && (event_to_stdout || self.aur_report || self.helper_output.is_some())
+        {
+            return Err(conflict(
+                "--manifest - writes the manifest to stdout; --emit-event -, --aur-report and \
+                 --helper-output would interleave with it",
+            ));
+        }
+        if self.diff.is_some()
+            && (manifest_to_stdout
+                || event_to_stdout
+                || self.aur_report
+                || self.helper_output.is_some())
+        {
+            return Err(conflict(
+                "--diff prints the manifest diff on stdout; --manifest -, --emit-event -, \
+                 --aur-report and --helper-output would interleave with it",
+            ));
+        }
+        if self.no_cache && self.refresh_cache {
+            return Err(conflict(
+                "--no-cache bypasses the AUR lookup cache that --refresh-cache rewrites; choose one",
+            ));
+        }
+        if self.interactive && self.dry_run {
+            return Err(conflict(
+                "--interactive confirms the manifest write, which --dry-run skips",
+            ));
+        }
+        if let Some(minimum) = self
+            .min_aur_popularity
+            .filter(|minimum| !minimum.is_finite() || *minimum < 0.0)
+        {
+            return Err(SynsyuError::Config(format!(
+                "--min-aur-popularity must be a non-negative number, got {minimum}"
+            )));
+        }
+        if self.sample == Some(0) {
+            return Err(SynsyuError::Config(
+                "--sample must be at least 1".to_string(),
+            ));
+        }
+        if let Some(percent) = self
+            .sample_percent
+            .filter(|percent| !(*percent > 0.0 && *percent <= 100.0))
+        {
+            return Err(SynsyuError::Config(format!(
+                "--sample-percent must be within (0, 100], got {percent}"
+            )));
+        }
+        if self.limit == Some(0) {
+            return Err(SynsyuError::Config(
+                "--limit must be at least 1".to_string(),
+            ));
+        }
+        if self.split_by_source.is_some() && self.dry_run {
+            return Err(conflict(
+                "--split-by-source writes manifest parts, which --dry-run skips",
+            ));
+        }
+        if self.diff_installed.is_some() {
+            if self.explain_policy.is_some() {
+                return Err(conflict(
+                    "--explain-policy and --diff-installed are separate modes; choose one",
+                ));
+            }
+            let ignored: Vec<&str> = [
+                (self.resume, "--resume"),
+                (self.require_complete, "--require-complete"),
+                (self.audit_changes, "--audit-changes"),
+                (self.integrity_check, "--integrity-check"),
+                (self.group_updates_by_repo, "--group-updates-by-repo"),
+                (self.report_unknown, "--report-unknown"),
+                (self.limit.is_some(), "--limit"),
+                (self.strict_expected_repo, "--strict-expected-repo"),
+                (self.sample.is_some(), "--sample"),
+                (self.sample_percent.is_some(), "--sample-percent"),
+                (self.min_aur_popularity.is_some(), "--min-aur-popularity"),
+                (self.annotations.is_some(), "--annotations"),
+                (self.aur_report, "--aur-report"),
+                (self.helper_output.is_some(), "--helper-output"),
+                (self.pkglist.is_some(), "--pkglist"),
+                (self.emit_event.is_some(), "--emit-event"),
+                (self.report.is_some(), "--report"),
+                (self.diff.is_some(), "--diff"),
+                (self.plan.is_some(), "--plan"),
+                (self.export_graph.is_some(), "--export-graph"),
+                (self.split_by_source.is_some(), "--split-by-source"),
+                (self.timeout_per_package.is_some(), "--timeout-per-package"),
+                (self.interactive, "--interactive"),
+            ]
+            .into_iter()
+            .filter_map(|(set, flag)| set.then_some(flag))
+            .collect();
+            if !ignored.is_empty() {
+                return Err(conflict(&format!(
+                    "--diff-installed exits before resolving updates; {} would have no effect",
+                    ignored.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Library pipeline inputs for this invocation's selection and annotation flags.
+    fn manifest_options(
+        &self,
+        config: SynsyuConfig,
+        annotations: Option<annotations::Annotations>,
+        raw_dump: Option<Arc<RawDump>>,
+        checkpoint: PathBuf,
+        progress: &deadline::RunProgress,
+        logger: &Arc<Logger>,
+    ) -> ManifestOptions {
+        ManifestOptions {
+            packages: self.packages.clone(),
+            always: self.always.clone(),
+            ignore: self.ignore.clone(),
+            missing_packages: self.missing_packages,
+            sample: self
+                .sample
+                .map(Sample::Count)
+                .or(self.sample_percent.map(Sample::Percent)),
+            limit: self.limit,
+            limit_by: self.limit_by,
+            offline: self.offline,
+            // `--no-cache` already zeroed `aur.cache_ttl_secs` in the config.
+            aur_cache: true,
+            respect_ignore: !self.no_respect_ignore,
+            strict_expected_repo: self.strict_expected_repo,
+            require_no_active_txn: self.require_no_active_txn,
+            require_complete: self.require_complete,
+            with_deps: self.with_deps,
+            only_stable: self.only_stable,
+            skip_orphaned_aur: self.skip_orphaned_aur,
+            skip_out_of_date: self.skip_out_of_date,
+            min_aur_popularity: self.min_aur_popularity,
+            ignore_pkgrel_only: self.ignore_pkgrel_only,
+            cascade: self.cascade,
+            check_pkg_cache: self.check_pkg_cache,
+            audit_changes: self.audit_changes,
+            integrity_check: self.integrity_check,
+            with_flatpak: self.with_flatpak,
+            with_fwupd: self.with_fwupd,
+            annotations,
+            raw_dump,
+            checkpoint: Some(checkpoint),
+            resume: self.resume,
+            progress: Some(progress.clone()),
+            logger: Some(Arc::clone(logger)),
+            ..ManifestOptions::new(config)
+        }
+    }
+}
+
+fn conflict(detail: &str) -> SynsyuError {
+    SynsyuError::Config(format!("conflicting flags: {detail}"))
 }
 
 /// Subcommands.
@@ -95,14 +310,20 @@ enum Commands {
     Updates(UpdatesCommand),
     /// Logging helper commands.
     Logs(LogsCommand),
+    /// Measure backend latency and recommend batching settings.
+    Bench(BenchCommand),
+    /// Maintain the on-disk cache directory.
+    Cache(CacheCommand),
+    /// Print a human report of a saved manifest (grouped by source, updates highlighted).
+    Show(ShowCommand),
 }
 
 /// Core manifest-building arguments (also used as default when no subcommand is given).
 #[derive(Debug, Parser, Clone)]
 struct CoreArgs {
-    /// Override configuration file path.
-    #[arg(long, value_name = "PATH")]
-    config: Option<PathBuf>,
+    /// Configuration file path; repeat to layer overrides (later files win).
+    #[arg(long, value_name = "PATH", action = ArgAction::Append)]
+    config: Vec<PathBuf>,
     /// Override manifest output path.
     #[arg(long, value_name = "PATH")]
     manifest: Option<PathBuf>,
@@ -112,15 +333,194 @@ struct CoreArgs {
     /// Limit manifest to specific packages.
     #[arg(long = "package", value_name = "PKG", action = ArgAction::Append)]
     packages: Vec<String>,
+    /// Always include a package, even outside the `--package` allowlist (adds to `core.always_include`).
+    #[arg(long = "always", value_name = "PKG", action = ArgAction::Append)]
+    always: Vec<String>,
+    /// Leave a package out of the manifest (adds to `core.ignore`; wins over `--always`).
+    #[arg(long = "ignore", value_name = "PKG", action = ArgAction::Append)]
+    ignore: Vec<String>,
+    /// How to react when a requested package is not installed.
+    #[arg(long = "missing-packages", value_enum, default_value_t = MissingPackages::Warn)]
+    missing_packages: MissingPackages,
+    /// Resolve and emit at most N packages; metadata records `limited` and `total_available`.
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+    /// Resolve a random sample of N packages (reproducible with `--seed`).
+    #[arg(long, value_name = "N", conflicts_with_all = ["sample_percent", "limit"])]
+    sample: Option<usize>,
+    /// Resolve a random sample of PERCENT % of the packages (reproducible with `--seed`).
+    #[arg(
+        long = "sample-percent",
+        value_name = "PERCENT",
+        conflicts_with = "limit"
+    )]
+    sample_percent: Option<f64>,
+    /// Which packages `--limit` keeps.
+    #[arg(
+        long = "limit-by",
+        value_enum,
+        default_value = "name",
+        requires = "limit"
+    )]
+    limit_by: LimitSort,
+    /// Emit AUR update candidates (with snapshot URLs) as JSON on stdout.
+    #[arg(long = "aur-report", action = ArgAction::SetTrue)]
+    aur_report: bool,
+    /// List installed dependents affected by each pending update.
+    #[arg(long, action = ArgAction::SetTrue)]
+    cascade: bool,
+    /// Do not offer updates for orphaned (maintainer-less) AUR packages.
+    #[arg(long = "skip-orphaned-aur", action = ArgAction::SetTrue)]
+    skip_orphaned_aur: bool,
+    /// Hold back packages matched by pacman.conf `IgnorePkg`/`IgnoreGroup` (the default).
+    #[arg(long = "respect-ignore", overrides_with = "no_respect_ignore")]
+    respect_ignore: bool,
+    /// Report updates for `IgnorePkg`/`IgnoreGroup` packages anyway.
+    #[arg(long = "no-respect-ignore", overrides_with = "respect_ignore")]
+    no_respect_ignore: bool,
+    /// Do not offer updates for AUR packages flagged out of date (warned as OUTOFDATE either way).
+    #[arg(long = "skip-out-of-date", action = ArgAction::SetTrue)]
+    skip_out_of_date: bool,
+    /// Record the Depends/MakeDepends/CheckDepends of AUR candidates on their entries.
+    #[arg(long = "with-deps", action = ArgAction::SetTrue)]
+    with_deps: bool,
+    /// Do not offer updates that fail the `[stability]` policy (they are noted either way).
+    #[arg(long = "only-stable", action = ArgAction::SetTrue)]
+    only_stable: bool,
+    /// Warn (LOWPOP) for AUR candidates whose AUR popularity is below this score.
+    #[arg(long = "min-aur-popularity", value_name = "SCORE")]
+    min_aur_popularity: Option<f64>,
+    /// Withdraw pkgrel-only rebuilds from the update set (entries get a note).
+    #[arg(long = "ignore-pkgrel-only", action = ArgAction::SetTrue)]
+    ignore_pkgrel_only: bool,
+    /// Mark update candidates already (or partially) present in the pacman package cache.
+    #[arg(long = "check-pkg-cache", action = ArgAction::SetTrue)]
+    check_pkg_cache: bool,
+    /// Record how many days each updatable package's installed version has been in place.
+    #[arg(long = "audit-changes", action = ArgAction::SetTrue)]
+    audit_changes: bool,
+    /// Print how many updates come from each repository.
+    #[arg(long = "group-updates-by-repo", action = ArgAction::SetTrue)]
+    group_updates_by_repo: bool,
+    /// List packages whose source stayed unknown, with a best-guess reason.
+    #[arg(long = "report-unknown", action = ArgAction::SetTrue)]
+    report_unknown: bool,
+    /// Append operator notes from a TOML or JSON file mapping package name to note(s).
+    #[arg(long, value_name = "PATH")]
+    annotations: Option<PathBuf>,
+    /// Verify installed files of update candidates with `pacman -Qkk`.
+    #[arg(long = "integrity-check", action = ArgAction::SetTrue)]
+    integrity_check: bool,
+    /// Print AUR helper command lines that would apply the pending updates.
+    #[arg(long = "helper-output", value_enum, value_name = "HELPER")]
+    helper_output: Option<HelperKind>,
+    /// Write repo update candidates one per line (for `pacman -S -`).
+    #[arg(long, value_name = "PATH")]
+    pkglist: Option<PathBuf>,
+    /// Write package list entries as `name=version`.
+    #[arg(long = "pkglist-versions", action = ArgAction::SetTrue, requires = "pkglist")]
+    pkglist_versions: bool,
+    /// Prefix the package list with a generation-time comment.
+    #[arg(long = "pkglist-header", action = ArgAction::SetTrue, requires = "pkglist")]
+    pkglist_header: bool,
+    /// Print the resolved candidate selection rules and exit.
+    #[arg(
+        long = "explain-policy",
+        value_enum,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "human"
+    )]
+    explain_policy: Option<ReportFormat>,
+    /// Compare installed packages with a reference set of `name version` lines and exit.
+    #[arg(long = "diff-installed", value_name = "REFERENCE")]
+    diff_installed: Option<PathBuf>,
+    /// Rendering for `--diff-installed`.
+    #[arg(
+        long = "diff-format",
+        value_enum,
+        default_value = "human",
+        requires = "diff_installed"
+    )]
+    diff_format: ReportFormat,
+    /// Also print how this run differs from OLD_MANIFEST, encoded like the manifest.
+    #[arg(long = "diff", value_name = "OLD_MANIFEST")]
+    diff: Option<PathBuf>,
+    /// Reuse entries from an interrupted run's `<manifest>.partial` checkpoint.
+    #[arg(long, action = ArgAction::SetTrue)]
+    resume: bool,
+    /// Write raw pacman output and AUR JSON responses into DIR for bug reports.
+    #[arg(long = "dump-raw", value_name = "DIR")]
+    dump_raw: Option<PathBuf>,
+    /// Fail (exit 60) if any lookup was skipped or failed, listing each gap.
+    #[arg(long = "require-complete", action = ArgAction::SetTrue)]
+    require_complete: bool,
+    /// Write a single JSON run receipt (timing, counts, warnings, manifest path) to PATH or `-`.
+    #[arg(long = "emit-event", value_name = "PATH|-")]
+    emit_event: Option<PathBuf>,
+    /// Fail (exit 22) instead of logging REPOMISS when a `core.expected_repo` package is not in the repos.
+    #[arg(long = "strict-expected-repo", action = ArgAction::SetTrue)]
+    strict_expected_repo: bool,
+    /// Fail (exit 75) instead of warning when a pacman transaction holds the db lock.
+    #[arg(long = "require-no-active-txn", action = ArgAction::SetTrue)]
+    require_no_active_txn: bool,
+    /// Stop the whole run after SECS, writing the entries resolved so far as a truncated manifest (exit 61).
+    #[arg(long = "max-runtime", value_name = "SECS")]
+    max_runtime: Option<u64>,
+    /// Give up on a single package after SECS, recording it as unknown (0 = no limit).
+    #[arg(long = "timeout-per-package", value_name = "SECS")]
+    timeout_per_package: Option<u64>,
+    /// Compare versions by spawning `vercmp` instead of the built-in port (`core.vercmp_external`).
+    #[arg(long = "vercmp-external", action = ArgAction::SetTrue)]
+    vercmp_external: bool,
+    /// Write manifests with object keys sorted at every level, for stable diffs and content hashes.
+    #[arg(long = "canonical", action = ArgAction::SetTrue)]
+    canonical: bool,
+    /// Manifest encoding; defaults to the `--manifest` extension (`.yaml`/`.yml`, `.toml`), else JSON.
+    #[arg(long = "format", value_enum, value_name = "FORMAT")]
+    format: Option<ManifestFormat>,
+    /// Also write `pacman.json`, `aur.json`, `local.json`, `unknown.json` and `index.json` into DIR.
+    #[arg(long = "split-by-source", value_name = "DIR")]
+    split_by_source: Option<PathBuf>,
+    /// Write a versioned, display-oriented JSON report (grouped, sorted, sized) to PATH.
+    #[arg(long = "report", value_name = "PATH")]
+    report: Option<PathBuf>,
+    /// Write the installed dependency graph as Graphviz DOT to PATH (updates filled, cycles red).
+    #[arg(long = "export-graph", value_name = "PATH")]
+    export_graph: Option<PathBuf>,
+    /// Write the pending updates as an ordered upgrade plan (dependencies first, with sizes) to PATH.
+    #[arg(long = "plan", value_name = "PATH")]
+    plan: Option<PathBuf>,
+    /// Send the run summary to the systemd journal (stderr if journald is absent).
+    #[arg(long, action = ArgAction::SetTrue)]
+    journald: bool,
+    /// Also send every warning of the run to the journal.
+    #[arg(long = "journald-warnings", action = ArgAction::SetTrue, requires = "journald")]
+    journald_warnings: bool,
+    /// Show pending updates and ask for confirmation before writing the manifest (needs a TTY).
+    #[arg(long, action = ArgAction::SetTrue)]
+    interactive: bool,
     /// Do not write manifest; emit summary only.
     #[arg(long, action = ArgAction::SetTrue)]
     dry_run: bool,
     /// Enable verbose logging to stderr.
     #[arg(long, action = ArgAction::SetTrue)]
     verbose: bool,
+    /// Per-code stderr verbosity, e.g. `AUR=debug,MANIFEST=warn` (the log file keeps everything).
+    #[arg(long = "log-filter", value_name = "CODE=LEVEL,...")]
+    log_filter: Option<String>,
+    /// Log entry encoding on stderr and in the log file; overrides `logging.format`.
+    #[arg(long = "log-format", value_enum, value_name = "FORMAT")]
+    log_format: Option<LogFormat>,
     /// Disable network access (skip AUR origin detection).
     #[arg(long, action = ArgAction::SetTrue)]
     offline: bool,
+    /// Neither read nor update the AUR lookup cache this run.
+    #[arg(long = "no-cache", action = ArgAction::SetTrue)]
+    no_cache: bool,
+    /// Refetch every AUR lookup, ignoring cached results, and rewrite the cache.
+    #[arg(long = "refresh-cache", action = ArgAction::SetTrue)]
+    refresh_cache: bool,
     /// Include firmware state via fwupdmgr in the manifest.
     #[arg(long = "with-fwupd", action = ArgAction::SetTrue)]
     with_fwupd: bool,
@@ -129,12 +529,19 @@ struct CoreArgs {
     with_flatpak: bool,
 }
 
+/// Rendering for `--explain-policy` and `--diff-installed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReportFormat {
+    Human,
+    Json,
+}
+
 /// Configuration inspection subcommand.
 #[derive(Debug, Parser, Clone)]
 struct ConfigCommand {
-    /// Override configuration file path.
-    #[arg(long, value_name = "PATH")]
-    config: Option<PathBuf>,
+    /// Configuration file path; repeat to layer overrides (later files win).
+    #[arg(long, value_name = "PATH", action = ArgAction::Append)]
+    config: Vec<PathBuf>,
     /// Emit JSON output.
     #[arg(long, action = ArgAction::SetTrue)]
     json: bool,
@@ -143,9 +550,9 @@ struct ConfigCommand {
 /// Disk space assessment subcommand.
 #[derive(Debug, Parser, Clone)]
 struct SpaceCommand {
-    /// Override configuration file path.
-    #[arg(long, value_name = "PATH")]
-    config: Option<PathBuf>,
+    /// Configuration file path; repeat to layer overrides (later files win).
+    #[arg(long, value_name = "PATH", action = ArgAction::Append)]
+    config: Vec<PathBuf>,
     /// Override manifest path.
     #[arg(long, value_name = "PATH")]
     manifest: Option<PathBuf>,
@@ -172,9 +579,9 @@ struct UpdatesCommand {
     /// Override manifest path.
     #[arg(long, value_name = "PATH")]
     manifest: Option<PathBuf>,
-    /// Override configuration file path.
-    #[arg(long, value_name = "PATH")]
-    config: Option<PathBuf>,
+    /// Configuration file path; repeat to layer overrides (later files win).
+    #[arg(long, value_name = "PATH", action = ArgAction::Append)]
+    config: Vec<PathBuf>,
     /// Include packages matching regex (repeatable).
     #[arg(long = "include", value_name = "REGEX", action = ArgAction::Append)]
     include: Vec<String>,
@@ -195,12 +602,29 @@ struct UpdatesCommand {
     json: bool,
 }
 
+/// Human manifest report subcommand.
+#[derive(Debug, Parser, Clone)]
+struct ShowCommand {
+    /// Manifest to render, e.g. one copied from another machine.
+    #[arg(value_name = "MANIFEST")]
+    manifest: PathBuf,
+    /// List only packages with a pending update.
+    #[arg(long = "only-updates", action = ArgAction::SetTrue)]
+    only_updates: bool,
+    /// Order of packages within each source group.
+    #[arg(long, value_enum, default_value = "name")]
+    sort: ShowSort,
+    /// Color output: auto (terminal, NO_COLOR unset), always or never.
+    #[arg(long, value_enum, default_value = "auto")]
+    color: ColorChoice,
+}
+
 /// Logging helper subcommand.
 #[derive(Debug, Parser, Clone)]
 struct LogsCommand {
-    /// Override configuration file path.
-    #[arg(long, value_name = "PATH")]
-    config: Option<PathBuf>,
+    /// Configuration file path; repeat to layer overrides (later files win).
+    #[arg(long, value_name = "PATH", action = ArgAction::Append)]
+    config: Vec<PathBuf>,
     /// Emit a log entry: LEVEL CODE MESSAGE.
     #[arg(long = "emit", num_args = 3, value_names = ["LEVEL", "CODE", "MESSAGE"])]
     emit: Option<Vec<String>>,
@@ -218,10 +642,41 @@ struct LogsCommand {
     path: Option<PathBuf>,
 }
 
+/// Cache maintenance subcommand.
+#[derive(Debug, Parser, Clone)]
+struct CacheCommand {
+    #[command(subcommand)]
+    action: CacheAction,
+}
+
+/// Cache maintenance actions.
+#[derive(Debug, Subcommand, Clone)]
+enum CacheAction {
+    /// Remove stale or least recently written cache files and report bytes reclaimed.
+    Prune(CachePruneCommand),
+}
+
+/// `cache prune` arguments.
+#[derive(Debug, Parser, Clone)]
+#[command(group(ArgGroup::new("limit").required(true).multiple(true).args(["older_than", "max_size"])))]
+struct CachePruneCommand {
+    /// Configuration file path; repeat to layer overrides (later files win).
+    #[arg(long, value_name = "PATH", action = ArgAction::Append)]
+    config: Vec<PathBuf>,
+    /// Remove entries last written longer ago than this (e.g. 30d, 12h, 45m).
+    #[arg(long = "older-than", value_name = "DURATION", value_parser = cache::parse_age)]
+    older_than: Option<Duration>,
+    /// Then remove the oldest entries until the cache holds at most BYTES.
+    #[arg(long = "max-size", value_name = "BYTES")]
+    max_size: Option<u64>,
+}
+
 #[tokio::main]
 async fn main() -> ExitCode {
     match run().await {
         Ok(code) => code,
+        // A stdout consumer that stopped reading (`| head`) is not a failure.
+        Err(err) if output::is_broken_pipe(&err) => ExitCode::SUCCESS,
         Err(err) => {
             eprintln!("[Syn-Syu-Core] {}", err);
             err.exit_code()
@@ -231,6 +686,7 @@ async fn main() -> ExitCode {
 
 async fn run() -> Result<ExitCode> {
     let cli = Cli::parse();
+    cli.validate()?;
 
     if let Some(cmd) = &cli.command {
         return match cmd {
@@ -239,15 +695,20 @@ async fn run() -> Result<ExitCode> {
             Commands::Space(space_cmd) => run_space(space_cmd).await,
             Commands::Updates(up_cmd) => run_updates(up_cmd),
             Commands::Logs(log_cmd) => run_logs(log_cmd),
+            Commands::Bench(bench_cmd) => run_bench(bench_cmd, cli.seed).await,
+            Commands::Cache(cache_cmd) => run_cache(cache_cmd),
+            Commands::Show(show_cmd) => run_show(show_cmd),
         };
     }
 
     // Default to core mode if no subcommand provided.
-    run_core(&cli.core).await
+    let progress = deadline::RunProgress::default();
+    let limit = cli.core.max_runtime.map(Duration::from_secs);
+    deadline::run_capped(limit, &progress, run_core(&cli.core, cli.seed, &progress)).await
 }
 
 async fn run_plan(cmd: &PlanCommand) -> Result<ExitCode> {
-    let config = SynsyuConfig::load_from_optional_path(cmd.config.as_deref())?;
+    let config = SynsyuConfig::load_layers(&cmd.config)?;
     let plan_path = cmd.plan.clone().unwrap_or_else(|| {
         dirs::config_dir()
             .unwrap_or_else(|| PathBuf::from("."))
@@ -256,7 +717,7 @@ async fn run_plan(cmd: &PlanCommand) -> Result<ExitCode> {
     let output = cmd.execute(&config, plan_path.clone()).await?;
 
     if cmd.json {
-        println!(
+        outln!(
             "{}",
             serde_json::to_string_pretty(&output.plan_json).unwrap_or_else(|_| "{}".to_string())
         );
@@ -296,34 +757,34 @@ async fn run_plan(cmd: &PlanCommand) -> Result<ExitCode> {
     }
     let error_count = errors.len();
 
-    println!("Plan created at {}", generated);
+    outln!("Plan created at {}", generated);
     let sources_display: Vec<String> = sources
         .iter()
         .filter_map(|v| v.as_str().map(|s| s.to_string()))
         .collect();
-    println!("Sources: {}", sources_display.join(", "));
-    println!("Repo updates: {}", pac);
-    println!("AUR updates: {}", aur);
-    println!("Flatpak updates: {}", flat);
-    println!("fwupd: {}", fw);
-    println!();
-    println!("Detailed JSON written to: {}", plan_path_val);
+    outln!("Sources: {}", sources_display.join(", "));
+    outln!("Repo updates: {}", pac);
+    outln!("AUR updates: {}", aur);
+    outln!("Flatpak updates: {}", flat);
+    outln!("fwupd: {}", fw);
+    outln!();
+    outln!("Detailed JSON written to: {}", plan_path_val);
     if error_count > 0 {
-        println!("Errors: {}", error_count);
+        outln!("Errors: {}", error_count);
     }
 
     if total > 0 && io::stdout().is_terminal() {
-        println!();
-        print!("Show update summary now? [y/N]: ");
+        outln!();
+        out!("Show update summary now? [y/N]: ");
         io::stdout().flush().ok();
         let mut line = String::new();
         if io::stdin().read_line(&mut line).is_ok() {
             let resp = line.trim().to_lowercase();
             if resp == "y" || resp == "yes" {
-                println!("Pacman: {pac}");
-                println!("AUR   : {aur}");
-                println!("Flatpak: {flat}");
-                println!("fwupd : {fw}");
+                outln!("Pacman: {pac}");
+                outln!("AUR   : {aur}");
+                outln!("Flatpak: {flat}");
+                outln!("fwupd : {fw}");
             }
         }
     }
@@ -339,22 +800,135 @@ async fn run_plan(cmd: &PlanCommand) -> Result<ExitCode> {
     Ok(ExitCode::SUCCESS)
 }
 
-async fn run_core(args: &CoreArgs) -> Result<ExitCode> {
-    let config_path = args.config.as_deref();
-    let config = SynsyuConfig::load_from_optional_path(config_path)?;
+async fn run_core(
+    args: &CoreArgs,
+    seed: Option<u64>,
+    progress: &deadline::RunProgress,
+) -> Result<ExitCode> {
+    let started_at = Utc::now();
+    let started = Instant::now();
+    let mut config = SynsyuConfig::load_layers(&args.config)?;
+    // Always run seeded so any run can be replayed from its logged seed.
+    config.seed = Some(seed.or(config.seed).unwrap_or_else(rng::entropy_seed));
+    if args.no_cache {
+        config.aur.cache_ttl_secs = 0;
+    }
+    config.aur.refresh_cache = args.refresh_cache;
+    config.core.vercmp_external |= args.vercmp_external;
+    if let Some(secs) = args.timeout_per_package {
+        config.core.package_timeout_secs = secs;
+    }
 
-    let manifest_path = args
-        .manifest
-        .clone()
-        .unwrap_or_else(|| config.manifest_path());
+    if let Some(format) = args.explain_policy {
+        let missing = args
+            .missing_packages
+            .to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default();
+        // No log file yet; an unreadable pacman.conf still warns on stderr.
+        let pacman_ignore = if args.no_respect_ignore {
+            None
+        } else {
+            let logger = Logger::new(None, args.verbose)?;
+            Some(pacman_conf::load_ignore_rules(
+                Path::new(&config.core.pacman_conf_path),
+                &logger,
+            ))
+        };
+        let explanation = policy::explain(
+            &config,
+            &policy::PolicyFlags {
+                allowlist: &args.packages,
+                always: &args.always,
+                ignore: &args.ignore,
+                missing_packages: &missing,
+                offline: args.offline,
+                strict_expected_repo: args.strict_expected_repo,
+                pacman_ignore: pacman_ignore.as_ref(),
+                only_stable: args.only_stable,
+                skip_orphaned_aur: args.skip_orphaned_aur,
+                skip_out_of_date: args.skip_out_of_date,
+                min_aur_popularity: args.min_aur_popularity,
+            },
+        );
+        match format {
+            ReportFormat::Human => out!("{}", explanation.render()),
+            ReportFormat::Json => outln!(
+                "{}",
+                serde_json::to_string_pretty(&explanation).unwrap_or_else(|_| "{}".to_string())
+            ),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
 
     let session_stamp = Utc::now().format("%Y-%m-%d_%H-%M-%S").to_string();
-    let log_path = args
-        .log
+    let named_file = config.manifest.dir_generates_named_file;
+    let requested_manifest = args
+        .manifest
         .clone()
-        .or_else(|| Some(config.log_dir().join(format!("core_{session_stamp}.log"))));
-    let logger = Logger::new(log_path.clone(), args.verbose)?;
-    logger.info("INIT", "Syn-Syu Core awakening.");
+        .unwrap_or_else(|| config.manifest_path());
+    let manifest_to_stdout = manifest::is_stdout(&requested_manifest);
+    let manifest_path = if manifest_to_stdout {
+        requested_manifest
+    } else {
+        resolve_output_file(
+            &requested_manifest,
+            "--manifest",
+            &format!(
+                "manifest_{session_stamp}.{}",
+                args.format.unwrap_or_default().extension()
+            ),
+            named_file,
+        )?
+    };
+    let manifest_format = args
+        .format
+        .unwrap_or_else(|| ManifestFormat::from_path(&manifest_path));
+    let symlink_guard = SymlinkGuard::from_config(&config.manifest);
+    if !args.dry_run && args.diff_installed.is_none() && !manifest_to_stdout {
+        symlink_guard.check(&manifest_path)?;
+        ensure_manifest_writable(&manifest_path)?;
+    }
+    let annotations = args
+        .annotations
+        .as_deref()
+        .map(annotations::load_annotations)
+        .transpose()?;
+    // Read before resolving so a bad path fails fast.
+    let diff_base = args
+        .diff
+        .as_deref()
+        .map(manifest::read_manifest)
+        .transpose()?;
+
+    let log_name = config.logging.core_log_name(&session_stamp);
+    let log_path = match &args.log {
+        Some(path) => resolve_output_file(path, "--log", &log_name, named_file)?,
+        None => config.log_dir().join(log_name),
+    };
+    let log_filter = match &args.log_filter {
+        Some(spec) => parse_log_filter(spec)?,
+        None => HashMap::new(),
+    };
+    let path_style = PathFormatter::from_config(&config.paths);
+    let logger = Arc::new(
+        Logger::new(Some(log_path), args.verbose)?
+            .with_filter(log_filter)
+            .with_format(args.log_format.unwrap_or(config.logging.format))
+            .with_paths(path_style)
+            .with_secrets(config.aur.token().map(str::to_string)),
+    );
+    let writes_manifest = !args.dry_run && args.diff_installed.is_none();
+    progress.attach(
+        writes_manifest.then(|| manifest_path.clone()),
+        manifest_format,
+        symlink_guard.clone(),
+        Arc::clone(&logger),
+    );
+    logger.info(
+        "INIT",
+        format!("Syn-Syu Core awakening (session {session_stamp})."),
+    );
     let aur_pkg = if BUILD_INFO.aur_pkgver.is_empty() {
         "n/a".to_string()
     } else {
@@ -400,96 +974,341 @@ async fn run_core(args: &CoreArgs) -> Result<ExitCode> {
             features
         ),
     );
-
-    let mut installed = enumerate_installed_packages().await?;
-    classify_aur_packages(&mut installed, args.offline, &logger).await;
-    logger.info(
-        "PACKAGES",
-        format!("Detected {} installed packages", installed.len()),
-    );
-
-    let enable_flatpak = args.with_flatpak || config.flatpak_enabled();
-    let enable_fwupd = args.with_fwupd || config.fwupd_enabled();
-
-    let selected = filter_packages(&mut installed, &args.packages, &logger)?;
-    if selected.is_empty() {
-        logger.warn(
-            "EMPTY",
-            "No packages selected for manifest generation; exiting",
+    if let Some(seed) = config.seed {
+        logger.info(
+            "SEED",
+            format!("Random seed {seed} (replay with --seed {seed})"),
         );
-        logger.finalize()?;
-        return Ok(ExitCode::SUCCESS);
     }
 
-    let mut document = build_manifest(&selected, &logger).await?;
-
-    if enable_flatpak {
-        match collect_flatpak(&logger).await {
-            Some(flatpak) => {
-                document.applications.flatpak = Some(flatpak);
-            }
-            None => logger.warn(
-                "FLATPAK",
-                "Flatpak state unavailable; proceeding without flatpak data.",
-            ),
+    let raw_dump = match &args.dump_raw {
+        Some(dir) => {
+            let dump = RawDump::create(dir)?;
+            logger.info(
+                "RAWDUMP",
+                format!("Raw backend responses written to {}", dump.dir().display()),
+            );
+            Some(Arc::new(dump))
         }
-    }
+        None => None,
+    };
 
-    if enable_fwupd {
-        match collect_fwupd(&logger, true).await {
-            Ok(Some(fwupd)) => {
-                document.applications.fwupd = Some(fwupd);
-            }
-            Ok(None) => logger.warn(
-                "FWUPD",
-                "Firmware state unavailable; proceeding without fwupd data.",
+    if let Some(reference_path) = &args.diff_installed {
+        check_active_transaction(
+            Path::new(&config.core.pacman_db_lock),
+            args.require_no_active_txn,
+            &logger,
+        )?;
+        let (_, installed) = enumerate_packages(
+            &config,
+            raw_dump.as_deref(),
+            &mut Completeness::default(),
+            &logger,
+        )
+        .await?;
+        let reference = inventory_diff::load_reference(reference_path)?;
+        let diff = inventory_diff::diff_installed(
+            &installed,
+            &reference,
+            &reference_path.display().to_string(),
+        );
+        match args.diff_format {
+            ReportFormat::Human => out!("{}", diff.render()),
+            ReportFormat::Json => outln!(
+                "{}",
+                serde_json::to_string_pretty(&diff).unwrap_or_else(|_| "{}".to_string())
             ),
-            Err(err) => logger.warn("FWUPD", format!("Firmware capture failed: {err}")),
         }
+        logger.info(
+            "DIFF",
+            format!(
+                "added={} missing={} version_differs={}",
+                diff.added.len(),
+                diff.missing.len(),
+                diff.version_differs.len()
+            ),
+        );
+        logger.finalize()?;
+        return Ok(ExitCode::SUCCESS);
     }
 
-    document.refresh_application_metadata();
+    // A stdout manifest has no directory of its own; its checkpoint lives in the cache dir.
+    let checkpoint_path = if manifest_to_stdout {
+        config.cache_dir().join("manifest-stdout.json.partial")
+    } else {
+        Checkpoint::partial_path(&manifest_path)
+    };
+    let options = args.manifest_options(
+        config.clone(),
+        annotations,
+        raw_dump,
+        checkpoint_path,
+        progress,
+        &logger,
+    );
+    let narrowed = options.narrows_selection();
+    let Generation {
+        mut document,
+        installed,
+        checkpoint,
+    } = generate(options).await?;
+    document.metadata.diff_against = diff_base
+        .as_ref()
+        .map(|old| old.metadata.generated_at.clone());
+
+    if let Some(path) = &args.pkglist {
+        let contents = report::pkglist(&document, args.pkglist_versions, args.pkglist_header);
+        std::fs::write(path, contents).map_err(|err| {
+            SynsyuError::Filesystem(format!(
+                "Failed to write package list {}: {err}",
+                path.display()
+            ))
+        })?;
+        logger.info(
+            "PKGLIST",
+            format!("Package list written to {}", path.display()),
+        );
+    }
 
+    // Machine-readable stdout must not be interleaved with human summaries.
+    let machine_output = args.aur_report
+        || args.helper_output.is_some()
+        || args.diff.is_some()
+        || (manifest_to_stdout && !args.dry_run);
+    // A reader closing stdout early must not cost the manifest, checkpoint or history.
+    output::tolerate_broken_pipe(print_reports(args, &document, &config, machine_output))?;
     if args.dry_run {
-        print_summary(&document);
+        if !machine_output {
+            output::tolerate_broken_pipe(print_summary(&document))?;
+        }
     } else {
-        write_manifest(&document, &manifest_path)?;
+        if args.interactive {
+            let stdin = io::stdin();
+            confirm::confirm_write(
+                &document,
+                &manifest_path,
+                stdin.lock(),
+                io::stderr(),
+                stdin.is_terminal(),
+            )?;
+        }
+        if args.canonical {
+            write_manifest_canonical(&document, &manifest_path, manifest_format, &symlink_guard)?;
+        } else {
+            write_manifest(&document, &manifest_path, manifest_format, &symlink_guard)?;
+        }
+        progress.mark_written();
+        // The log reaches stderr only, never the stdout stream carrying the manifest.
+        let destination = if manifest_to_stdout {
+            "stdout".to_string()
+        } else {
+            manifest_path.display().to_string()
+        };
+        logger.info("MANIFEST", format!("Manifest written to {destination}"));
+        if let Some(dir) = &args.split_by_source {
+            let index = split::write_split(&document, dir, args.canonical, &symlink_guard)?;
+            logger.info(
+                "SPLIT",
+                format!(
+                    "Manifest split into {} parts under {}",
+                    index.parts.len(),
+                    dir.display()
+                ),
+            );
+        }
+    }
+    if let Some(checkpoint) = checkpoint {
+        checkpoint.clear(&logger);
+    }
+
+    if let Some(old) = &diff_base {
+        let diff = manifest_diff::diff_manifests(old, &document);
         logger.info(
-            "MANIFEST",
-            format!("Manifest written to {}", manifest_path.display()),
+            "DIFF",
+            format!(
+                "against={} new_updates={} target_changed={} removed={} added={}",
+                diff.old_generated_at,
+                diff.new_updates.len(),
+                diff.target_changed.len(),
+                diff.removed.len(),
+                diff.added.len()
+            ),
         );
+        write_manifest(&diff, Path::new("-"), manifest_format, &symlink_guard)?;
+    }
+
+    let history_file = history_path(&config.cache_dir());
+    let snapshot = RunSnapshot::from_manifest(&document);
+    let previous = load_snapshot(&history_file, &logger);
+    match &previous {
+        Some(previous) => {
+            let delta = compute_delta(previous, &snapshot);
+            if !machine_output {
+                output::tolerate_broken_pipe(print_delta(&delta))?;
+            }
+            logger.info(
+                "DELTA",
+                format!(
+                    "since={} new={} applied={} resolved={}",
+                    delta.previous_generated_at,
+                    delta.newly_available.len(),
+                    delta.applied.len(),
+                    delta.resolved.len()
+                ),
+            );
+        }
+        None => logger.info("DELTA", "No previous run recorded; delta unavailable."),
+    }
+    if !args.dry_run {
+        let snapshot = if narrowed {
+            snapshot.merged_over(previous.as_ref())
+        } else {
+            snapshot
+        };
+        if let Err(err) = store_snapshot(&snapshot, &history_file) {
+            logger.warn("DELTA", format!("Failed to record run history: {err}"));
+        }
     }
 
     logger.info(
         "SUMMARY",
         format!(
-            "packages={} pacman={} aur={} local={} unknown={}",
+            "packages={} pacman={} aur={} local={} unknown={} updates={}",
             document.metadata.total_packages,
             document.metadata.pacman_packages,
             document.metadata.aur_packages,
             document.metadata.local_packages,
-            document.metadata.unknown_packages
+            document.metadata.unknown_packages,
+            document.metadata.updates_available
         ),
     );
+    if args.journald {
+        let warnings = args.journald_warnings.then(|| logger.warnings());
+        journal::emit_run(
+            &document,
+            warnings.as_deref(),
+            Path::new(journal::JOURNAL_SOCKET),
+            &logger,
+        );
+    }
+    if let Some(target) = &args.emit_event {
+        let event = RunEvent::new(
+            &session_stamp,
+            started_at,
+            started.elapsed(),
+            (!args.dry_run).then_some(manifest_path.as_path()),
+            ConfigSummary::from_config(&config, args.offline),
+            &document,
+            logger.warnings(),
+        );
+        if target.as_os_str() == "-" {
+            let line = event.to_line()?;
+            output::tolerate_broken_pipe(output::write_out(format_args!("{line}\n")))?;
+        } else {
+            event.write(target)?;
+            logger.info(
+                "EVENT",
+                format!("Run event written to {}", target.display()),
+            );
+        }
+    }
+    if let Some(target) = &args.report {
+        let display = report::display_report(&document, &logger.warnings());
+        let json = serde_json::to_vec_pretty(&display).map_err(|err| {
+            SynsyuError::Serialization(format!("Failed to serialize display report: {err}"))
+        })?;
+        cache::write_atomic(target, &json)?;
+        logger.info(
+            "REPORT",
+            format!("Display report written to {}", target.display()),
+        );
+    }
+    if let Some(target) = &args.plan {
+        let plan = upgrade_plan::upgrade_plan(&document, &installed);
+        let json = serde_json::to_vec_pretty(&plan).map_err(|err| {
+            SynsyuError::Serialization(format!("Failed to serialize upgrade plan: {err}"))
+        })?;
+        cache::write_atomic(target, &json)?;
+        logger.info(
+            "PLAN",
+            format!(
+                "Upgrade plan with {} step(s) written to {}",
+                plan.totals.steps,
+                target.display()
+            ),
+        );
+    }
+    if let Some(target) = &args.export_graph {
+        let dot = dep_graph::render_dot(&document, &installed);
+        cache::write_atomic(target, dot.as_bytes())?;
+        logger.info(
+            "GRAPH",
+            format!("Dependency graph written to {}", target.display()),
+        );
+    }
     logger.info("COMPLETE", "Consciousness synchronised.");
     logger.finalize()?;
 
     Ok(ExitCode::SUCCESS)
 }
 
+/// Print the stdout reports requested alongside the manifest.
+fn print_reports(
+    args: &CoreArgs,
+    document: &ManifestDocument,
+    config: &SynsyuConfig,
+    machine_output: bool,
+) -> Result<()> {
+    if args.aur_report {
+        let report = report::aur_report(document, &config.aur.base_url);
+        outln!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_else(|_| "{}".to_string())
+        );
+    }
+    if let Some(helper) = args.helper_output {
+        for argv in report::helper_commands(document, helper) {
+            outln!("{}", argv.join(" "));
+        }
+    }
+    if args.group_updates_by_repo && !machine_output {
+        out!("{}", report::updates_by_repo_view(document));
+    }
+    if args.report_unknown && !machine_output {
+        out!("{}", report::unknown_view(document));
+    }
+    Ok(())
+}
+
+async fn run_bench(cmd: &BenchCommand, seed: Option<u64>) -> Result<ExitCode> {
+    let mut config = SynsyuConfig::load_layers(&cmd.config)?;
+    config.seed = seed.or(config.seed);
+    let report = cmd.execute(&config).await?;
+    if cmd.json {
+        outln!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_else(|_| "{}".to_string())
+        );
+    } else {
+        bench::print_report(&report)?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
 fn run_config(cmd: &ConfigCommand) -> Result<ExitCode> {
-    let config = SynsyuConfig::load_from_optional_path(cmd.config.as_deref())?;
+    let config = SynsyuConfig::load_layers(&cmd.config)?;
     let report = config.to_report();
     if cmd.json {
-        println!(
+        outln!(
             "{}",
             serde_json::to_string_pretty(&report).unwrap_or_else(|_| "{}".to_string())
         );
     } else {
-        println!("Manifest: {}", report.manifest_path.display());
-        println!("Log dir : {}", report.log_directory.display());
-        println!("Batch   : {}", report.batch_size);
-        println!(
+        outln!("Manifest: {}", report.manifest_path.display());
+        outln!("Log dir : {}", report.log_directory.display());
+        outln!("Batch   : {}", report.batch_size);
+        outln!("Sources : {}", report.source_policy);
+        outln!(
             "Helpers : {}",
             if report.helper_priority.is_empty() {
                 "<none>".to_string()
@@ -497,20 +1316,26 @@ fn run_config(cmd: &ConfigCommand) -> Result<ExitCode> {
                 report.helper_priority.join(", ")
             }
         );
-        println!(
+        outln!(
             "Space   : min_free_bytes={} policy={}",
-            report.space_min_free_bytes, report.space_policy
+            report.space_min_free_bytes,
+            report.space_policy
         );
-        println!(
+        outln!(
             "Apps    : flatpak={} fwupd={}",
-            report.applications_flatpak, report.applications_fwupd
+            report.applications_flatpak,
+            report.applications_fwupd
+        );
+        outln!(
+            "AUR     : token={}",
+            report.aur_token.as_deref().unwrap_or("<none>")
         );
     }
     Ok(ExitCode::SUCCESS)
 }
 
 async fn run_space(cmd: &SpaceCommand) -> Result<ExitCode> {
-    let config = SynsyuConfig::load_from_optional_path(cmd.config.as_deref())?;
+    let config = SynsyuConfig::load_layers(&cmd.config)?;
     let manifest_path = cmd
         .manifest
         .clone()
@@ -526,13 +1351,13 @@ async fn run_space(cmd: &SpaceCommand) -> Result<ExitCode> {
 
     let manifest: serde_json::Value =
         serde_json::from_reader(std::fs::File::open(&manifest_path).map_err(|err| {
-            crate::error::SynsyuError::Filesystem(format!(
+            SynsyuError::Filesystem(format!(
                 "Failed to open manifest {}: {err}",
                 manifest_path.display()
             ))
         })?)
         .map_err(|err| {
-            crate::error::SynsyuError::Serialization(format!(
+            SynsyuError::Serialization(format!(
                 "Failed to parse manifest {}: {err}",
                 manifest_path.display()
             ))
@@ -557,9 +1382,16 @@ async fn run_space(cmd: &SpaceCommand) -> Result<ExitCode> {
     let _repo_sizes = if repo_pkg_names.is_empty() {
         std::collections::HashMap::new()
     } else {
-        query_repo_versions(&repo_pkg_names)
-            .await
-            .unwrap_or_default()
+        query_repo_versions(
+            &repo_pkg_names,
+            config.core.si_chunk_size,
+            config.core.si_max_parallel,
+            RetryPolicy::from_config(&config.pacman),
+            None,
+            None,
+        )
+        .await
+        .unwrap_or_default()
     };
 
     // Optional AUR helper size lookup.
@@ -628,10 +1460,13 @@ async fn run_space(cmd: &SpaceCommand) -> Result<ExitCode> {
             .get("transient_size_total")
             .and_then(|v| v.as_u64())
             .unwrap_or(0);
-        let download = meta
-            .get("download_size_total")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0);
+        let field = |key: &str| meta.get(key).and_then(|v| v.as_u64());
+        // Core manifests split downloads into exact and estimated totals.
+        let download = field("download_size_total").unwrap_or_else(|| {
+            field("download_size_exact")
+                .unwrap_or(0)
+                .saturating_add(field("download_size_estimated").unwrap_or(0))
+        });
         let build = meta
             .get("build_size_total")
             .and_then(|v| v.as_u64())
@@ -692,9 +1527,16 @@ async fn run_space(cmd: &SpaceCommand) -> Result<ExitCode> {
     let repo_sizes = if repo_pkg_names.is_empty() {
         std::collections::HashMap::new()
     } else {
-        query_repo_versions(&repo_pkg_names)
-            .await
-            .unwrap_or_default()
+        query_repo_versions(
+            &repo_pkg_names,
+            config.core.si_chunk_size,
+            config.core.si_max_parallel,
+            RetryPolicy::from_config(&config.pacman),
+            None,
+            None,
+        )
+        .await
+        .unwrap_or_default()
     };
 
     // Per-package checks when requested.
@@ -796,13 +1638,13 @@ async fn run_space(cmd: &SpaceCommand) -> Result<ExitCode> {
             "unknown": unknowns,
             "details": details,
         });
-        println!(
+        outln!(
             "{}",
             serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string())
         );
     } else {
         for line in &details {
-            println!("{line}");
+            outln!("{line}");
         }
         for line in &failures {
             eprintln!("{line}");
@@ -822,7 +1664,7 @@ async fn run_space(cmd: &SpaceCommand) -> Result<ExitCode> {
 }
 
 fn run_updates(cmd: &UpdatesCommand) -> Result<ExitCode> {
-    let config = SynsyuConfig::load_from_optional_path(cmd.config.as_deref())?;
+    let config = SynsyuConfig::load_layers(&cmd.config)?;
     let manifest_path = cmd
         .manifest
         .clone()
@@ -837,24 +1679,64 @@ fn run_updates(cmd: &UpdatesCommand) -> Result<ExitCode> {
     };
     let updates = collect_updates(filter)?;
     if cmd.json {
-        println!(
+        outln!(
             "{}",
             serde_json::to_string_pretty(&updates).unwrap_or_else(|_| "[]".to_string())
         );
     } else {
         for u in updates {
-            println!("{}|{}|{}|{}", u.name, u.source, u.installed, u.available);
+            outln!("{}|{}|{}|{}", u.name, u.source, u.installed, u.available);
         }
     }
     Ok(ExitCode::SUCCESS)
 }
 
+fn run_show(cmd: &ShowCommand) -> Result<ExitCode> {
+    let document = manifest::read_manifest(&cmd.manifest)?;
+    let options = ShowOptions {
+        only_updates: cmd.only_updates,
+        sort: cmd.sort,
+        color: cmd.color.enabled(),
+    };
+    out!("{}", show::render(&document, options));
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_cache(cmd: &CacheCommand) -> Result<ExitCode> {
+    let CacheAction::Prune(prune) = &cmd.action;
+    let config = SynsyuConfig::load_layers(&prune.config)?;
+    let dir = config.cache_dir();
+    let report = cache::prune(
+        &dir,
+        prune.older_than,
+        prune.max_size,
+        std::time::SystemTime::now(),
+    )?;
+    for path in &report.removed {
+        outln!("removed {}", path.display());
+    }
+    if report.skipped_in_flight > 0 {
+        outln!(
+            "skipped {} in-flight temp file(s)",
+            report.skipped_in_flight
+        );
+    }
+    outln!(
+        "Reclaimed {} ({} bytes) from {} file(s) in {}",
+        space::format_bytes(report.bytes_reclaimed),
+        report.bytes_reclaimed,
+        report.removed.len(),
+        dir.display()
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
 fn run_logs(cmd: &LogsCommand) -> Result<ExitCode> {
-    let config = SynsyuConfig::load_from_optional_path(cmd.config.as_deref())?;
+    let config = SynsyuConfig::load_layers(&cmd.config)?;
 
     if cmd.init {
         let init = log_init(&config)?;
-        println!(
+        outln!(
             "{}|{}|{}",
             init.path.display(),
             init.level,
@@ -869,7 +1751,7 @@ fn run_logs(cmd: &LogsCommand) -> Result<ExitCode> {
 
     if let Some(path) = &cmd.hash {
         let hash_path = log_hash(path)?;
-        println!("{}", hash_path.display());
+        outln!("{}", hash_path.display());
     }
 
     if let Some(parts) = &cmd.emit {
@@ -890,97 +1772,407 @@ fn run_logs(cmd: &LogsCommand) -> Result<ExitCode> {
     Ok(ExitCode::SUCCESS)
 }
 
-fn filter_packages(
-    installed: &mut Vec<InstalledPackage>,
-    requested: &[String],
-    logger: &Logger,
-) -> Result<Vec<InstalledPackage>> {
-    installed.sort_by(|a, b| a.name.cmp(&b.name));
+fn print_summary(document: &ManifestDocument) -> Result<()> {
+    outln!(
+        "→ Manifest dry-run. Packages={} (pacman={} aur={} local={} unknown={}) updates={}",
+        document.metadata.total_packages,
+        document.metadata.pacman_packages,
+        document.metadata.aur_packages,
+        document.metadata.local_packages,
+        document.metadata.unknown_packages,
+        document.metadata.updates_available
+    );
+    Ok(())
+}
 
-    if requested.is_empty() {
-        return Ok(installed.clone());
+fn print_delta(delta: &RunDelta) -> Result<()> {
+    if delta.is_empty() {
+        outln!("→ No update changes since last run.");
+        return Ok(());
+    }
+    outln!(
+        "→ Since last run ({}): {} new, {} applied, {} resolved",
+        delta.previous_generated_at,
+        delta.newly_available.len(),
+        delta.applied.len(),
+        delta.resolved.len()
+    );
+    for entry in &delta.newly_available {
+        outln!("  + {} {} -> {}", entry.name, entry.from, entry.to);
+    }
+    for entry in &delta.applied {
+        outln!("  ✓ {} {} -> {}", entry.name, entry.from, entry.to);
     }
+    for entry in &delta.resolved {
+        outln!("  - {} {} -> {}", entry.name, entry.from, entry.to);
+    }
+    Ok(())
+}
 
-    let mut requested_set: HashSet<String> = HashSet::new();
-    for pkg in requested {
-        requested_set.insert(pkg.to_string());
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds the crate with every optional feature off; run with `cargo test -- --ignored`.
+    #[test]
+    #[ignore = "compiles the crate a second time"]
+    fn minimal_feature_set_builds() {
+        let status = std::process::Command::new(env!("CARGO"))
+            .args(["check", "--quiet", "--offline", "--no-default-features"])
+            .arg("--manifest-path")
+            .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+            .env(
+                "CARGO_TARGET_DIR",
+                concat!(env!("CARGO_MANIFEST_DIR"), "/target/minimal-features"),
+            )
+            .status()
+            .expect("cargo runs");
+        assert!(status.success());
     }
 
-    let mut selected = Vec::new();
-    for pkg in installed.iter() {
-        if requested_set.contains(&pkg.name) {
-            selected.push(pkg.clone());
+    #[test]
+    fn ignore_directives_are_respected_unless_the_last_flag_says_otherwise() {
+        let respected = |args: &[&str]| {
+            let cli =
+                Cli::try_parse_from(std::iter::once("synsyu_core").chain(args.iter().copied()))
+                    .unwrap();
+            !cli.core.no_respect_ignore
+        };
+        assert!(respected(&[]));
+        assert!(!respected(&["--no-respect-ignore"]));
+        assert!(respected(&["--no-respect-ignore", "--respect-ignore"]));
+        assert!(!respected(&["--respect-ignore", "--no-respect-ignore"]));
+    }
+
+    #[test]
+    fn conflicting_flag_combinations_are_rejected() {
+        let invalid: [&[&str]; 9] = [
+            &["--aur-report", "--helper-output", "paru"],
+            &["--no-cache", "--refresh-cache"],
+            &["--diff", "old.json", "--manifest", "-"],
+            &["--emit-event", "-", "--aur-report"],
+            &["--manifest", "-", "--emit-event", "-"],
+            &["--manifest", "-", "--helper-output", "paru"],
+            &[
+                "--diff-installed",
+                "ref.txt",
+                "--resume",
+                "--integrity-check",
+            ],
+            &["updates", "--no-repo", "--no-aur"],
+            &["plan", "--no-repo", "--offline"],
+        ];
+        for args in invalid {
+            let cli =
+                Cli::try_parse_from(std::iter::once("synsyu_core").chain(args.iter().copied()))
+                    .expect("arguments parse");
+            let err = cli.validate().unwrap_err();
+            assert!(matches!(err, SynsyuError::Config(_)), "{args:?}");
+            assert!(err.to_string().contains("conflicting flags"), "{args:?}");
+        }
+        let err = Cli::try_parse_from([
+            "synsyu_core",
+            "--diff-installed",
+            "r",
+            "--resume",
+            "--integrity-check",
+        ])
+        .unwrap()
+        .validate()
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("--resume, --integrity-check would have no effect"));
+
+        for args in [
+            &["--emit-event", "run.json", "--aur-report"][..],
+            &["--manifest", "-", "--emit-event", "run.json"],
+            &["--manifest", "-", "--dry-run", "--aur-report"],
+            &["--diff", "old.json", "--manifest", "-", "--dry-run"],
+            &["plan", "--no-repo", "--offline", "--with-flatpak"],
+            &["updates", "--no-aur"],
+        ] {
+            let cli =
+                Cli::try_parse_from(std::iter::once("synsyu_core").chain(args.iter().copied()))
+                    .unwrap();
+            assert!(cli.validate().is_ok(), "{args:?}");
         }
     }
 
-    let missing: Vec<String> = requested_set
-        .into_iter()
-        .filter(|name| !selected.iter().any(|pkg| &pkg.name == name))
-        .collect();
+    #[test]
+    fn sample_and_limit_sizes_are_validated() {
+        for args in [
+            &["--sample", "0"][..],
+            &["--sample-percent", "0"],
+            &["--sample-percent", "150"],
+        ] {
+            let cli =
+                Cli::try_parse_from(std::iter::once("synsyu_core").chain(args.iter().copied()))
+                    .unwrap();
+            assert!(
+                matches!(cli.validate(), Err(SynsyuError::Config(_))),
+                "{args:?}"
+            );
+        }
+        assert!(Cli::try_parse_from(["synsyu_core", "--sample", "5", "--limit", "3"]).is_err());
+        let zero = Cli::try_parse_from(["synsyu_core", "--limit", "0"]).unwrap();
+        assert!(matches!(zero.validate(), Err(SynsyuError::Config(_))));
+    }
 
-    if !missing.is_empty() {
-        logger.warn(
-            "PKG404",
-            format!("Requested packages not installed: {}", missing.join(", ")),
-        );
+    /// Stand-in `pacman` answering `-Qm`, `-Qi` and `-Si` for a four-package system.
+    const STUB_PACMAN: &str = r#"#!/bin/sh
+case "$1" in
+-Qm) echo "yay 12.3.5-1" ;;
+-Qi) cat <<'INFO'
+Name            : bash
+Version         : 5.2.32-1
+Installed Size  : 9.20 MiB
+
+Name            : linux
+Version         : 6.11.1-1
+Installed Size  : 140.00 MiB
+Required By     : None
+
+Name            : vim
+Version         : 9.1.0-1
+Installed Size  : 40.00 MiB
+
+Name            : yay
+Version         : 12.3.5-1
+Installed Size  : 8.00 MiB
+
+Name            : zsh
+Version         : 5.9-4
+Installed Size  : 5.00 MiB
+
+INFO
+;;
+-Si) shift
+for name in "$@"; do
+  case "$name" in
+  bash|linux|vim|zsh) ;;
+  *) echo "error: package '$name' was not found" >&2; exit 1 ;;
+  esac
+done
+for name in "$@"; do
+  case "$name" in
+  bash) version=5.2.37-1 ;;
+  linux) version=6.11.2-1 ;;
+  vim) version=9.1.1-1 ;;
+  zsh) version=5.9-5 ;;
+  esac
+  printf 'Repository      : core
+Name            : %s
+Version         : %s
+Architecture    : x86_64
+Depends On      : None\nDownload Size   : 1.80 MiB\nInstalled Size  : 4.00 MiB\n\n' "$name" "$version"
+done
+;;
+*) exit 1 ;;
+esac
+"#;
+
+    /// Scratch system under `temp_dir`: the stub `pacman` in `bin/`, plus a
+    /// config pointing every core path at the directory.
+    fn stub_system(label: &str) -> (PathBuf, PathBuf) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("synsyu-{label}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let bin = dir.join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        let pacman = bin.join("pacman");
+        std::fs::write(&pacman, STUB_PACMAN).unwrap();
+        std::fs::set_permissions(&pacman, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        std::fs::write(
+            dir.join("pacman.conf"),
+            format!(
+                "[options]\nIgnorePkg = vim\n\n[core]\nInclude = {}\n",
+                dir.join("mirrorlist").display()
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("mirrorlist"),
+            "Server = https://mirror.example/$repo/os/$arch\n",
+        )
+        .unwrap();
+        // core.db as pacman keeps it, with the `desc` of each repo candidate.
+        let sync = dir.join("sync");
+        std::fs::create_dir_all(&sync).unwrap();
+        let mut db = tar::Builder::new(Vec::new());
+        for (name, version) in [
+            ("bash", "5.2.37-1"),
+            ("linux", "6.11.2-1"),
+            ("vim", "9.1.1-1"),
+        ] {
+            let desc = format!("%FILENAME%\n{name}-{version}-x86_64.pkg.tar.zst\n\n");
+            let mut header = tar::Header::new_gnu();
+            header.set_size(desc.len() as u64);
+            header.set_cksum();
+            db.append_data(
+                &mut header,
+                format!("{name}-{version}/desc"),
+                desc.as_bytes(),
+            )
+            .unwrap();
+        }
+        std::fs::write(sync.join("core.db"), db.into_inner().unwrap()).unwrap();
+        let config_path = dir.join("config.toml");
+        std::fs::write(
+            &config_path,
+            format!(
+                "[core]\n\
+                 cache_directory = \"{root}/cache\"\n\
+                 log_directory = \"{root}/logs\"\n\
+                 pacman_conf_path = \"{root}/pacman.conf\"\n\
+                 sync_db_directory = \"{root}/sync\"\n\
+                 pacman_db_lock = \"{root}/db.lck\"\n\
+                 ignore = [\"zsh\"]\n\
+                 reboot_required = [\"linux\"]\n\
+                 size_skew_percent = 10\n",
+                root = dir.display()
+            ),
+        )
+        .unwrap();
+        (dir, config_path)
     }
 
-    Ok(selected)
-}
+    /// Names the stub system of a test re-run by `run_isolated`.
+    const ISOLATED_DIR: &str = "SYNSYU_TEST_ISOLATED_DIR";
+
+    /// Re-run the ignored test `name` in a child test process with the stub
+    /// `bin/` first on PATH, leaving this process's environment untouched.
+    fn run_isolated(name: &str, dir: &Path) {
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let mut dirs = vec![dir.join("bin")];
+        dirs.extend(std::env::split_paths(&path));
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                name,
+                "--exact",
+                "--ignored",
+                "--test-threads=1",
+                "--nocapture",
+            ])
+            .env("PATH", std::env::join_paths(dirs).unwrap())
+            .env(ISOLATED_DIR, dir)
+            .output()
+            .expect("test binary runs");
+        assert!(
+            output.status.success(),
+            "{name} failed:\n{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
 
-fn print_summary(document: &ManifestDocument) {
-    println!(
-        "→ Manifest dry-run. Packages={} (pacman={} aur={} local={} unknown={})",
-        document.metadata.total_packages,
-        document.metadata.pacman_packages,
-        document.metadata.aur_packages,
-        document.metadata.local_packages,
-        document.metadata.unknown_packages
-    );
-}
+    #[test]
+    fn closed_stdout_still_writes_the_manifest() {
+        let (dir, _) = stub_system("closed-stdout");
+        run_isolated("tests::closed_stdout_run", &dir);
+        assert!(dir.join("manifest.json").is_file());
+        assert!(dir.join("cache/last_run.json").is_file());
+        let _ = std::fs::remove_dir_all(dir);
+    }
 
-async fn classify_aur_packages(packages: &mut [InstalledPackage], offline: bool, logger: &Logger) {
-    let mut candidates = Vec::new();
-    for pkg in packages.iter() {
-        if pkg
-            .repository
-            .as_deref()
-            .map(|r| r.eq_ignore_ascii_case("local"))
-            .unwrap_or(true)
-        {
-            candidates.push(pkg.name.clone());
+    #[tokio::test]
+    #[ignore = "child half of closed_stdout_still_writes_the_manifest"]
+    async fn closed_stdout_run() {
+        let Some(dir) = std::env::var_os(ISOLATED_DIR).map(PathBuf::from) else {
+            return;
+        };
+        let cli = Cli::try_parse_from([
+            "synsyu_core",
+            "--config",
+            dir.join("config.toml").to_str().unwrap(),
+            "--manifest",
+            dir.join("manifest.json").to_str().unwrap(),
+            "--offline",
+            "--aur-report",
+        ])
+        .unwrap();
+        cli.validate().unwrap();
+
+        // Point stdout at a pipe whose reader is already gone, as `| head` leaves it.
+        let mut fds = [0; 2];
+        let saved = unsafe {
+            assert_eq!(libc::pipe(fds.as_mut_ptr()), 0);
+            libc::close(fds[0]);
+            let saved = libc::dup(1);
+            libc::dup2(fds[1], 1);
+            libc::close(fds[1]);
+            saved
+        };
+        let result = run_core(&cli.core, cli.seed, &deadline::RunProgress::default()).await;
+        unsafe {
+            libc::dup2(saved, 1);
+            libc::close(saved);
         }
+        assert_eq!(result.unwrap(), ExitCode::SUCCESS);
     }
-    if candidates.is_empty() {
-        return;
-    }
-    if offline {
-        logger.info("AUR", "Offline flag set; skipping AUR origin detection.");
-        return;
+
+    #[test]
+    fn cli_run_and_library_produce_the_same_manifest() {
+        let (dir, _) = stub_system("cli-lib");
+        run_isolated("tests::cli_and_library_run", &dir);
+        let _ = std::fs::remove_dir_all(dir);
     }
-    match pacman::aur_presence(&candidates, offline).await {
-        Ok(found) => {
-            if found.is_empty() {
-                logger.info("AUR", "No AUR matches found for foreign packages.");
-                return;
-            }
-            let mut updated = 0usize;
-            for pkg in packages.iter_mut() {
-                if pkg
-                    .repository
-                    .as_deref()
-                    .map(|r| r.eq_ignore_ascii_case("local"))
-                    .unwrap_or(true)
-                    && found.contains(&pkg.name)
-                {
-                    pkg.repository = Some("aur".to_string());
-                    updated += 1;
-                }
-            }
-            logger.info("AUR", format!("Classified {updated} package(s) as AUR."));
-        }
-        Err(err) => {
-            logger.warn("AUR", format!("AUR origin detection skipped: {err}"));
+
+    #[tokio::test]
+    #[ignore = "child half of cli_run_and_library_produce_the_same_manifest"]
+    async fn cli_and_library_run() {
+        let Some(dir) = std::env::var_os(ISOLATED_DIR).map(PathBuf::from) else {
+            return;
+        };
+        let config_path = dir.join("config.toml");
+        let manifest_path = dir.join("manifest.json");
+        let cli = Cli::try_parse_from([
+            "synsyu_core",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--manifest",
+            manifest_path.to_str().unwrap(),
+            "--log",
+            dir.join("core.log").to_str().unwrap(),
+            "--offline",
+        ])
+        .unwrap();
+        cli.validate().unwrap();
+        let code = run_core(&cli.core, cli.seed, &deadline::RunProgress::default())
+            .await
+            .unwrap();
+        assert_eq!(code, ExitCode::SUCCESS);
+        let mut from_cli: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&manifest_path).unwrap()).unwrap();
+
+        let config = SynsyuConfig::load_layers(&[config_path]).unwrap();
+        let document = synsyu_core::generate_manifest(ManifestOptions {
+            offline: true,
+            ..ManifestOptions::new(config)
+        })
+        .await
+        .unwrap();
+        // Round-trip like the written file so float fields compare equal.
+        let mut from_library: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&document).unwrap()).unwrap();
+
+        for manifest in [&mut from_cli, &mut from_library] {
+            manifest["metadata"]
+                .as_object_mut()
+                .unwrap()
+                .remove("generated_at");
         }
+        assert_eq!(from_cli, from_library);
+        // The run exercised selection and post-processing, not just resolution.
+        let packages = from_cli["packages"].as_object().unwrap();
+        assert!(!packages.contains_key("zsh"));
+        assert_eq!(from_cli["metadata"]["reboot_required"], true);
+        assert_eq!(from_cli["metadata"]["updates_available"], 2);
+        assert!(packages["bash"]["download_url"]
+            .as_str()
+            .is_some_and(|url| url.starts_with("https://mirror.example/core/os/")
+                && url.ends_with("/bash-5.2.37-1-x86_64.pkg.tar.zst")));
     }
 }
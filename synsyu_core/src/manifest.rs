@@ -6,14 +6,14 @@
   Purpose:
     Construct the Syn-Syu manifest as a snapshot of the
     user-defined desired system state: what is installed right
-    now, with source attribution.
+    now, with source attribution and candidate versions.
 
   Security / Safety Notes:
     Manifest data is written to operator-controlled paths with
     private permissions; no privileged operations are performed.
 
   Dependencies:
-    serde for JSON serialization.
+    serde for JSON, YAML and TOML serialization.
 
   Operational Scope:
     Consumed by the Bash orchestrator as the authoritative
@@ -21,6 +21,35 @@
 
   Revision History:
     2024-11-04 COD  Authored manifest builder.
+    2026-10-18 COD  Restored per-package update state.
+    2026-10-18 COD  Added manifest writability preflight.
+    2026-10-18 COD  Scored per-entry resolution confidence.
+    2026-10-18 COD  Handled output paths naming an existing directory.
+    2026-10-18 COD  Bounded per-package resolution time (timed_out_count).
+    2026-10-18 COD  Reported resolved entries for `--max-runtime` (truncated).
+    2026-10-18 COD  Flagged updates that require a reboot.
+    2026-10-18 COD  Recorded AUR votes/popularity (LOWPOP).
+    2026-10-18 COD  Recorded `--limit` caps (limited, total_available).
+    2026-10-18 COD  Recorded random-sample runs and extrapolated updates.
+    2026-10-18 COD  Added canonical (sorted-key) manifest output.
+    2026-10-18 COD  Borrowed missing sizes from the other source's candidate.
+    2026-10-18 COD  Made the manifest readable back (`show`).
+    2026-10-18 COD  Guarded manifest writes against redirecting symlinks.
+    2026-10-18 COD  Threaded the vercmp backend through resolution.
+    2026-10-18 COD  Recorded dependencies updates would newly install.
+    2026-10-18 COD  Added YAML and TOML manifest formats (--format).
+    2026-10-18 COD  Recorded candidate release dates and stability verdicts.
+    2026-10-18 COD  Wrote the manifest to stdout for a `-` path.
+    2026-10-18 COD  Warned on (and optionally skipped) flagged AUR updates.
+    2026-10-18 COD  Recorded the `--diff` base manifest (diff_against).
+    2026-10-18 COD  Held back IgnorePkg/IgnoreGroup packages.
+    2026-10-18 COD  Classified candidates by VersionDelta (DOWNGRADE).
+    2026-10-18 COD  Recorded AUR dependency arrays (`--with-deps`).
+    2026-10-18 COD  First-release manifests read back with defaults.
+    2026-10-18 COD  Guarded manifest paths before creating directories;
+                    O_NOFOLLOW under manifest.no_symlink.
+    2026-10-18 COD  --ignore-pkgrel-only withdraws rebuilds like the
+                    other update filters.
   ------------------------------------------------------------
   SSE Principles Observed:
     - Deterministic ordering for reproducible manifests
@@ -28,32 +57,51 @@
     - Rich metadata for audit and observability
 ============================================================*/
 
-use std::collections::BTreeMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::{self, File};
-use std::path::Path;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[cfg(unix)]
-use std::os::unix::fs::PermissionsExt;
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
 
-use chrono::{SecondsFormat, Utc};
-use serde::Serialize;
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 
+use crate::checkpoint::Checkpoint;
+use crate::config::{ManifestConfig, SourceOverride, SourcePolicy};
+use crate::deadline::RunProgress;
 use crate::error::{Result, SynsyuError};
 use crate::flatpak::FlatpakState;
+use crate::glob;
 use crate::logger::Logger;
-use crate::pacman::InstalledPackage;
+use crate::package_info::{
+    check_version, fold_version_case, AurDependencies, VersionInfo, VersionParts,
+};
+use crate::pacman::{compare_versions, InstalledPackage, VercmpBackend};
+use crate::pacman_conf::IgnoreRules;
+use crate::pkg_cache::PkgCacheSummary;
 
 /// Wrapper representing the full manifest document.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ManifestDocument {
     pub metadata: ManifestMetadata,
     pub packages: BTreeMap<String, ManifestEntry>,
+    #[serde(default)]
     pub packages_by_source: Vec<PackageGroup>,
+    #[serde(default)]
     pub applications: Applications,
 }
 
 /// Metadata block describing manifest context.
-#[derive(Debug, Serialize)]
+///
+/// Fields missing from a manifest read back (older writers) take their defaults.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct ManifestMetadata {
     pub generated_at: String,
     pub generated_by: String,
@@ -62,28 +110,251 @@ pub struct ManifestMetadata {
     pub aur_packages: usize,
     pub local_packages: usize,
     pub unknown_packages: usize,
+    pub updates_available: usize,
+    /// Pending updates that only bump pkgrel (same epoch and pkgver).
+    pub pkgrel_only_updates: usize,
+    /// Pending updates per sync repository (`aur` for AUR candidates).
+    pub updates_by_repo: BTreeMap<String, usize>,
+    /// Sum of current installed sizes of the selected packages (bytes).
+    pub installed_size_total: u64,
+    /// Installed footprint once every pending update is applied (bytes).
+    pub installed_size_after_updates: u64,
+    /// Download bytes of pending updates whose size the source reported directly.
+    pub download_size_exact: u64,
+    /// Download bytes of pending updates whose size was inferred (AUR snapshot tarballs).
+    pub download_size_estimated: u64,
+    /// Distinct packages pending updates would newly install as dependencies.
+    pub new_packages_count: usize,
+    /// Download bytes of those new packages, where the sync databases report them.
+    pub new_packages_download_size: u64,
+    /// Installed footprint of those new packages, where reported (bytes).
+    pub new_packages_installed_size: u64,
+    /// Packages whose resolution exceeded the per-package timeout.
+    pub timed_out_count: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub apps_flatpak: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub apps_fwupd: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub application_state: Option<ApplicationStateSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pkg_cache: Option<PkgCacheSummary>,
+    /// Local clock minus AUR server clock, in seconds, when measured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock_skew_secs: Option<i64>,
+    /// Config file(s) the run loaded; omitted for pure defaults.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_path: Option<String>,
+    /// SHA-256 of the loaded config file bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_sha256: Option<String>,
+    /// `--max-runtime` expired; only the packages resolved by then are listed.
+    pub truncated: bool,
+    /// At least one pending update matches `core.reboot_required`.
+    pub reboot_required: bool,
+    /// `--limit` left packages out; every count above covers only the emitted ones.
+    pub limited: bool,
+    /// Packages selected before `--limit` applied (equals `total_packages` otherwise).
+    pub total_available: usize,
+    /// `--ignore-pkgrel-only` withdrew pkgrel-only rebuilds from the update set.
+    pub pkgrel_only_ignored: bool,
+    /// The run resolved a random sample (`--sample` / `--sample-percent`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample: Option<SampleInfo>,
+    /// `generated_at` of the manifest this run was diffed against (`--diff`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff_against: Option<String>,
+}
+
+/// Parameters and extrapolation of a sampled run.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SampleInfo {
+    /// Packages resolved.
+    pub size: usize,
+    /// Packages the sample was drawn from.
+    pub population: usize,
+    /// Share requested with `--sample-percent`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent: Option<f64>,
+    /// Seed the sample was drawn with; `--seed` reproduces it.
+    pub seed: u64,
+    /// `updates_available` scaled from the sample to the population.
+    pub estimated_updates: usize,
 }
 
 /// Per-package manifest entry.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ManifestEntry {
     pub installed_version: String,
     pub repository: Option<String>,
     pub source: PackageSource,
+    pub available_version: Option<String>,
+    #[serde(default)]
+    pub version_parts: VersionPartsPair,
+    /// Source the candidate was taken from, per the configured source policy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub candidate_source: Option<PackageSource>,
+    pub newer_version: Option<String>,
+    #[serde(default)]
+    pub update_available: bool,
+    /// Candidate against installed version; absent without a candidate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_delta: Option<VersionDelta>,
+    /// The pending update is a rebuild: epoch and pkgver unchanged, pkgrel differs.
+    #[serde(default)]
+    pub pkgrel_only: bool,
+    /// Download size reported for the selected candidate.
+    pub download_size_selected: Option<u64>,
+    /// `download_size_selected` was inferred rather than reported by the source.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub download_size_estimated: bool,
+    /// Installed size reported for the selected candidate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installed_size_selected: Option<u64>,
+    /// A selected size was missing and borrowed from the other source's candidate.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub size_from_other_source: bool,
+    /// Installed packages depending on this one, transitively (`--cascade`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub affected_dependents: Option<Vec<String>>,
+    /// Dependencies of the repo candidate that are not installed yet.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub new_dependencies: Vec<String>,
+    /// Mirror URL the repo candidate would be fetched from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    /// Candidate package file already present in the pacman cache (`--check-pkg-cache`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached: Option<bool>,
+    /// Cached file is partial or its size differs from `download_size_selected`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_mismatch: Option<bool>,
+    /// Sync repository the repo candidate comes from (`core`, `extra`, ...).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo_name: Option<String>,
+    /// `pacman -Qkk` file verification result (`--integrity-check`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integrity_ok: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aur_maintainer: Option<String>,
+    /// The AUR candidate has no maintainer.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub aur_orphaned: bool,
+    /// AUR `NumVotes` of the AUR candidate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aur_votes: Option<u64>,
+    /// AUR `Popularity` of the AUR candidate (see `--min-aur-popularity`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aur_popularity: Option<f64>,
+    /// Build date (repo) or last modification (AUR) of the selected candidate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidate_released_at: Option<DateTime<Utc>>,
+    /// When AUR users flagged the AUR candidate out of date.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flagged_out_of_date: Option<DateTime<Utc>>,
+    /// Dependency arrays of the AUR candidate (`--with-deps`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aur_dependencies: Option<AurDependencies>,
+    /// The pending update passes the `[stability]` policy (always true without one).
+    #[serde(default = "stable_by_default")]
+    pub stable: bool,
+    /// Why the pending update failed the `[stability]` policy.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stability_reasons: Vec<String>,
+    /// The pending update matches `core.reboot_required`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub reboot_required: bool,
+    /// Human-readable remarks on how the candidate was chosen.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    /// Certainty of the resolved source, 0.0–1.0 (see `resolution_confidence`).
+    #[serde(default)]
+    pub confidence: f32,
     pub installed_size: Option<u64>,
     pub install_date: Option<String>,
+    /// Days the installed version has been in place (`--audit-changes`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installed_age_days: Option<i64>,
     pub validated_by: Option<String>,
     pub package_hash: Option<String>,
 }
 
+fn stable_by_default() -> bool {
+    true
+}
+
+impl ManifestEntry {
+    /// Repository an update would come from: `aur`, the sync repo, or the installed repo.
+    pub fn update_repo(&self) -> String {
+        match self.candidate_source {
+            Some(PackageSource::Aur) => "aur".to_string(),
+            _ => self
+                .repo_name
+                .clone()
+                .or_else(|| self.repository.clone())
+                .unwrap_or_else(|| "unknown".to_string()),
+        }
+    }
+}
+
+/// Parsed components of the installed and candidate versions; `None` when unparseable.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct VersionPartsPair {
+    pub installed: Option<VersionParts>,
+    pub available: Option<VersionParts>,
+}
+
+impl VersionPartsPair {
+    /// True when both sides parse and only pkgrel differs.
+    fn pkgrel_only(&self) -> bool {
+        match (&self.installed, &self.available) {
+            (Some(installed), Some(available)) => {
+                installed.epoch == available.epoch
+                    && installed.pkgver == available.pkgver
+                    && installed.pkgrel != available.pkgrel
+            }
+            _ => false,
+        }
+    }
+
+    fn from_versions(installed: &str, available: Option<&str>) -> Self {
+        Self {
+            installed: VersionParts::parse(installed),
+            available: available.and_then(VersionParts::parse),
+        }
+    }
+}
+
+/// How the selected candidate relates to the installed version.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionDelta {
+    /// The candidate is newer.
+    Upgrade,
+    /// The candidate is older, e.g. the repo rolled back or a VCS package got ahead.
+    Downgrade,
+    /// Same version from the source it is installed from.
+    Same,
+    /// Same version, but taking it means switching source (repo <-> AUR).
+    Reinstall,
+}
+
+impl VersionDelta {
+    /// Delta for an installed-vs-candidate `ordering`; `same_source` tells `Same` from `Reinstall`.
+    fn from_ordering(ordering: Ordering, same_source: bool) -> Self {
+        match ordering {
+            Ordering::Less => VersionDelta::Upgrade,
+            Ordering::Greater => VersionDelta::Downgrade,
+            Ordering::Equal if same_source => VersionDelta::Same,
+            Ordering::Equal => VersionDelta::Reinstall,
+        }
+    }
+}
+
 /// Group of package names for a particular source.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PackageGroup {
     pub source: PackageSource,
     pub count: usize,
@@ -91,7 +362,7 @@ pub struct PackageGroup {
 }
 
 /// Optional application/firmware state.
-#[derive(Debug, Serialize, Default, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct Applications {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub flatpak: Option<FlatpakState>,
@@ -100,14 +371,14 @@ pub struct Applications {
 }
 
 /// Lightweight summary of application state for manifest metadata.
-#[derive(Debug, Serialize, Default, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct ApplicationStateSummary {
     pub flatpak: usize,
     pub fwupd: usize,
 }
 
 /// Source classification for an update candidate.
-#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum PackageSource {
     Pacman,
@@ -116,9 +387,90 @@ pub enum PackageSource {
     Unknown,
 }
 
-/// Build a manifest from installed package data.
+impl PackageSource {
+    pub const ALL: [PackageSource; 4] = [
+        PackageSource::Pacman,
+        PackageSource::Aur,
+        PackageSource::Local,
+        PackageSource::Unknown,
+    ];
+
+    /// Lowercase name, as used for `--split-by-source` file names.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PackageSource::Pacman => "pacman",
+            PackageSource::Aur => "aur",
+            PackageSource::Local => "local",
+            PackageSource::Unknown => "unknown",
+        }
+    }
+}
+
+/// Candidate versions keyed by package name, split by where they came from.
+#[derive(Debug, Default)]
+pub struct Candidates {
+    pub repo: HashMap<String, VersionInfo>,
+    pub aur: HashMap<String, VersionInfo>,
+    /// Names queried successfully but absent from the AUR results.
+    pub aur_not_found: HashSet<String>,
+}
+
+/// Settings steering how every package of a run is resolved.
+#[derive(Debug, Clone, Copy)]
+pub struct ResolveOptions<'a> {
+    pub policy: SourcePolicy,
+    pub overrides: &'a BTreeMap<String, SourceOverride>,
+    pub compare: VersionCompare,
+    /// Longest one package may take to resolve; zero disables the limit.
+    pub package_timeout: Duration,
+    /// Receives every resolved entry as it completes (`--max-runtime`).
+    pub progress: Option<&'a RunProgress>,
+    /// pacman.conf `IgnorePkg`/`IgnoreGroup`; held packages offer no update.
+    pub ignore: Option<&'a IgnoreRules>,
+    /// Keep the AUR candidate's dependency arrays on entries (`--with-deps`).
+    pub with_deps: bool,
+}
+
+impl<'a> ResolveOptions<'a> {
+    /// Options with strict comparison and no per-package time limit.
+    pub fn new(policy: SourcePolicy, overrides: &'a BTreeMap<String, SourceOverride>) -> Self {
+        Self {
+            policy,
+            overrides,
+            compare: VersionCompare::default(),
+            package_timeout: Duration::ZERO,
+            progress: None,
+            ignore: None,
+            with_deps: false,
+        }
+    }
+}
+
+/// How two version strings are ordered during resolution.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VersionCompare {
+    /// Fold version case before comparing (`core.version_case_fold`).
+    pub case_fold: bool,
+    pub backend: VercmpBackend,
+}
+
+/// Withdraw the pending update of a package pacman itself would skip.
+fn hold_back(entry: &mut ManifestEntry, directive: &str) {
+    if !entry.update_available {
+        return;
+    }
+    entry.update_available = false;
+    entry.newer_version = None;
+    entry.pkgrel_only = false;
+    entry.notes.push(format!("held by {directive}"));
+}
+
+/// Build a manifest from installed package data and known candidate versions.
 pub async fn build_manifest(
     packages: &[InstalledPackage],
+    candidates: &Candidates,
+    options: &ResolveOptions<'_>,
+    mut checkpoint: Option<&mut Checkpoint>,
     logger: &Logger,
 ) -> Result<ManifestDocument> {
     let mut entries = BTreeMap::new();
@@ -127,9 +479,63 @@ pub async fn build_manifest(
     let mut aur_packages = 0usize;
     let mut local_packages = 0usize;
     let mut unknown_packages = 0usize;
+    let mut updates_available = 0usize;
+    let mut pkgrel_only_updates = 0usize;
+    let mut timed_out_count = 0usize;
 
+    let mut reused = 0usize;
     for package in packages {
-        let resolved = resolve_package(package);
+        let previous = checkpoint
+            .as_deref()
+            .and_then(|cp| cp.resolved(&package.name, &package.version));
+        let mut resolved = match previous {
+            Some(entry) => {
+                reused += 1;
+                entry
+            }
+            None => {
+                let resolution = resolve_package(
+                    package,
+                    candidates.repo.get(&package.name),
+                    candidates.aur.get(&package.name),
+                    options.policy,
+                    options.overrides.get(&package.name).copied(),
+                    options.compare,
+                    logger,
+                );
+                match resolve_within(package, options.package_timeout, resolution, logger).await? {
+                    Some(mut entry) => {
+                        if candidates.aur_not_found.contains(&package.name) {
+                            entry.notes.push(NOTE_AUR_NOT_FOUND.to_string());
+                        }
+                        if let Some(cp) = checkpoint.as_deref_mut() {
+                            cp.record(&package.name, &entry)?;
+                        }
+                        entry
+                    }
+                    // Left out of the checkpoint so a resumed run tries again.
+                    None => {
+                        timed_out_count += 1;
+                        timed_out_entry(package, options.package_timeout)
+                    }
+                }
+            }
+        };
+        if let Some(directive) = options.ignore.and_then(|rules| rules.holds(package)) {
+            hold_back(&mut resolved, directive);
+        }
+        if !options.with_deps {
+            resolved.aur_dependencies = None;
+        }
+        if let Some(progress) = options.progress {
+            progress.record(&package.name, &resolved);
+        }
+        if resolved.update_available {
+            updates_available += 1;
+        }
+        if resolved.pkgrel_only {
+            pkgrel_only_updates += 1;
+        }
         match resolved.source {
             PackageSource::Pacman => pacman_packages += 1,
             PackageSource::Aur => aur_packages += 1,
@@ -144,11 +550,21 @@ pub async fn build_manifest(
             ),
         );
 
-        entries.insert(package.name.clone(), resolved);
         grouped
-            .entry(source_from_repo(package.repository.as_deref()))
+            .entry(resolved.source)
             .or_default()
             .push(package.name.clone());
+        entries.insert(package.name.clone(), resolved);
+    }
+
+    if let Some(cp) = checkpoint {
+        cp.flush()?;
+    }
+    if reused > 0 {
+        logger.info(
+            "RESUME",
+            format!("Reused {reused} entries resolved by an interrupted run"),
+        );
     }
 
     let mut packages_by_source: Vec<PackageGroup> = grouped
@@ -172,20 +588,310 @@ pub async fn build_manifest(
         aur_packages,
         local_packages,
         unknown_packages,
+        updates_available,
+        pkgrel_only_updates,
+        updates_by_repo: BTreeMap::new(),
+        installed_size_total: 0,
+        installed_size_after_updates: 0,
+        download_size_exact: 0,
+        download_size_estimated: 0,
+        new_packages_count: 0,
+        new_packages_download_size: 0,
+        new_packages_installed_size: 0,
+        timed_out_count,
         apps_flatpak: None,
         apps_fwupd: None,
         application_state: None,
+        pkg_cache: None,
+        clock_skew_secs: None,
+        config_path: None,
+        config_sha256: None,
+        truncated: false,
+        reboot_required: false,
+        limited: false,
+        total_available: packages.len(),
+        pkgrel_only_ignored: false,
+        sample: None,
+        diff_against: None,
     };
 
-    Ok(ManifestDocument {
+    let mut document = ManifestDocument {
         metadata,
         packages: entries,
         packages_by_source,
         applications: Applications::default(),
-    })
+    };
+    document.refresh_updates_by_repo();
+    document.refresh_size_totals();
+    Ok(document)
 }
 
 impl ManifestDocument {
+    /// Document over already resolved entries, with metadata derived from them.
+    pub fn from_entries(packages: BTreeMap<String, ManifestEntry>) -> Self {
+        let mut grouped: BTreeMap<PackageSource, Vec<String>> = BTreeMap::new();
+        for (name, entry) in &packages {
+            grouped.entry(entry.source).or_default().push(name.clone());
+        }
+        let mut packages_by_source: Vec<PackageGroup> = grouped
+            .into_iter()
+            .map(|(source, names)| PackageGroup {
+                source,
+                count: names.len(),
+                packages: names,
+            })
+            .collect();
+        packages_by_source
+            .sort_by(|a, b| a.count.cmp(&b.count).then_with(|| a.source.cmp(&b.source)));
+
+        let count =
+            |source: PackageSource| packages.values().filter(|e| e.source == source).count();
+        let metadata = ManifestMetadata {
+            generated_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            generated_by: "synsyu_core".to_string(),
+            total_packages: packages.len(),
+            pacman_packages: count(PackageSource::Pacman),
+            aur_packages: count(PackageSource::Aur),
+            local_packages: count(PackageSource::Local),
+            unknown_packages: count(PackageSource::Unknown),
+            updates_available: packages.values().filter(|e| e.update_available).count(),
+            pkgrel_only_updates: packages.values().filter(|e| e.pkgrel_only).count(),
+            updates_by_repo: BTreeMap::new(),
+            installed_size_total: 0,
+            installed_size_after_updates: 0,
+            download_size_exact: 0,
+            download_size_estimated: 0,
+            new_packages_count: 0,
+            new_packages_download_size: 0,
+            new_packages_installed_size: 0,
+            timed_out_count: packages
+                .values()
+                .filter(|e| e.notes.iter().any(|note| note.starts_with(NOTE_TIMED_OUT)))
+                .count(),
+            apps_flatpak: None,
+            apps_fwupd: None,
+            application_state: None,
+            pkg_cache: None,
+            clock_skew_secs: None,
+            config_path: None,
+            config_sha256: None,
+            truncated: false,
+            reboot_required: false,
+            limited: false,
+            total_available: packages.len(),
+            pkgrel_only_ignored: false,
+            sample: None,
+            diff_against: None,
+        };
+        let mut document = Self {
+            metadata,
+            packages,
+            packages_by_source,
+            applications: Applications::default(),
+        };
+        document.refresh_updates_by_repo();
+        document.refresh_size_totals();
+        document
+    }
+
+    /// Recount pending updates per repository from the entries.
+    pub fn refresh_updates_by_repo(&mut self) {
+        let mut counts = BTreeMap::new();
+        for entry in self
+            .packages
+            .values()
+            .filter(|entry| entry.update_available)
+        {
+            *counts.entry(entry.update_repo()).or_insert(0) += 1;
+        }
+        self.metadata.updates_by_repo = counts;
+    }
+
+    /// Recompute the installed footprint and the exact/estimated download totals.
+    ///
+    /// Packages without a known size count as zero; an update whose candidate
+    /// size is unknown keeps the current size.
+    pub fn refresh_size_totals(&mut self) {
+        let mut current = 0u64;
+        let mut after = 0u64;
+        let (mut exact, mut estimated) = (0u64, 0u64);
+        for entry in self.packages.values() {
+            let installed = entry.installed_size.unwrap_or(0);
+            current += installed;
+            after += match entry.installed_size_selected {
+                Some(candidate) if entry.update_available => candidate,
+                _ => installed,
+            };
+            if let Some(download) = entry
+                .download_size_selected
+                .filter(|_| entry.update_available)
+            {
+                if entry.download_size_estimated {
+                    estimated += download;
+                } else {
+                    exact += download;
+                }
+            }
+        }
+        self.metadata.installed_size_total = current;
+        self.metadata.installed_size_after_updates = after;
+        self.metadata.download_size_exact = exact;
+        self.metadata.download_size_estimated = estimated;
+    }
+
+    /// Withdraw orphaned AUR candidates from the update set; entries keep their tags.
+    pub fn skip_orphaned_aur(&mut self) {
+        self.withdraw_updates(|entry| entry.aur_orphaned);
+    }
+
+    /// Withdraw updates whose AUR package is flagged out of date (`--skip-out-of-date`).
+    pub fn skip_out_of_date(&mut self) {
+        self.withdraw_updates(|entry| entry.flagged_out_of_date.is_some());
+    }
+
+    /// Withdraw updates failing the `[stability]` policy (`--only-stable`); entries keep their verdict.
+    pub fn skip_unstable(&mut self) {
+        self.withdraw_updates(|entry| !entry.stable);
+    }
+
+    /// Clear the pending update of every entry matching `withdraw` and recount metadata.
+    fn withdraw_updates(&mut self, withdraw: impl Fn(&ManifestEntry) -> bool) {
+        for entry in self.packages.values_mut() {
+            if !(entry.update_available && withdraw(entry)) {
+                continue;
+            }
+            entry.update_available = false;
+            entry.newer_version = None;
+            self.metadata.updates_available = self.metadata.updates_available.saturating_sub(1);
+            if entry.pkgrel_only {
+                entry.pkgrel_only = false;
+                self.metadata.pkgrel_only_updates =
+                    self.metadata.pkgrel_only_updates.saturating_sub(1);
+            }
+        }
+        self.refresh_updates_by_repo();
+        self.refresh_size_totals();
+    }
+
+    /// Warn `SIZESKEW` where the candidate has the installed version but an
+    /// installed size more than `percent` % away, hinting at a stale sync db.
+    ///
+    /// Flagged entries get a note; returns how many were flagged (0 when `percent` is 0).
+    pub fn flag_size_skew(&mut self, percent: f64, logger: &Logger) -> usize {
+        if percent <= 0.0 {
+            return 0;
+        }
+        let mut flagged = 0;
+        for (name, entry) in self.packages.iter_mut() {
+            let same_version = entry.available_version.as_deref() == Some(&entry.installed_version);
+            let (Some(local), Some(candidate)) =
+                (entry.installed_size, entry.installed_size_selected)
+            else {
+                continue;
+            };
+            if !same_version || local == candidate {
+                continue;
+            }
+            let skew = local.abs_diff(candidate) as f64 * 100.0 / local.max(candidate) as f64;
+            if skew <= percent {
+                continue;
+            }
+            logger.warn(
+                "SIZESKEW",
+                format!(
+                    "{name}: {} installed as {local} bytes but the candidate reports {candidate} bytes ({skew:.1}%); the sync database may be inconsistent",
+                    entry.installed_version
+                ),
+            );
+            entry.notes.push(format!(
+                "installed size differs from candidate by {skew:.1}%"
+            ));
+            flagged += 1;
+        }
+        flagged
+    }
+
+    /// Record that `--limit` kept these packages out of `total_available` selected ones.
+    pub fn record_limit(&mut self, total_available: usize) {
+        self.metadata.total_available = total_available.max(self.metadata.total_packages);
+        self.metadata.limited = self.metadata.total_available > self.metadata.total_packages;
+    }
+
+    /// Record that the packages are a random sample of `population` and
+    /// extrapolate the update count to the whole population.
+    pub fn record_sample(&mut self, population: usize, percent: Option<f64>, seed: u64) {
+        let size = self.metadata.total_packages;
+        let estimated_updates = if size == 0 {
+            0
+        } else {
+            (self.metadata.updates_available as f64 * population as f64 / size as f64).round()
+                as usize
+        };
+        self.metadata.sample = Some(SampleInfo {
+            size,
+            population,
+            percent,
+            seed,
+            estimated_updates,
+        });
+    }
+
+    /// Warn `LOWPOP` for AUR candidates whose popularity is below `minimum`.
+    ///
+    /// Flagged entries get a note; candidates without a reported popularity
+    /// are left alone. Returns how many were flagged.
+    pub fn flag_low_popularity(&mut self, minimum: f64, logger: &Logger) -> usize {
+        let mut flagged = 0;
+        for (name, entry) in self.packages.iter_mut() {
+            let Some(popularity) = entry
+                .aur_popularity
+                .filter(|_| entry.candidate_source == Some(PackageSource::Aur))
+            else {
+                continue;
+            };
+            if popularity >= minimum {
+                continue;
+            }
+            logger.warn(
+                "LOWPOP",
+                format!(
+                    "{name}: AUR popularity {popularity:.2} ({} votes) is below {minimum}; review before updating",
+                    entry.aur_votes.unwrap_or(0)
+                ),
+            );
+            entry
+                .notes
+                .push(format!("AUR popularity {popularity:.2} below {minimum}"));
+            flagged += 1;
+        }
+        flagged
+    }
+
+    /// Mark pending updates whose name matches one of the glob `patterns`
+    /// and set `metadata.reboot_required`; returns how many were marked.
+    pub fn flag_reboot_required(&mut self, patterns: &[String]) -> usize {
+        let mut flagged = 0;
+        for (name, entry) in self.packages.iter_mut() {
+            entry.reboot_required = entry.update_available
+                && patterns.iter().any(|pattern| glob::matches(pattern, name));
+            flagged += usize::from(entry.reboot_required);
+        }
+        self.metadata.reboot_required = flagged > 0;
+        flagged
+    }
+
+    /// Withdraw pkgrel-only rebuilds from the update set (`--ignore-pkgrel-only`);
+    /// each withdrawn entry gets a note in place of its `pkgrel_only` tag.
+    pub fn ignore_pkgrel_only(&mut self) {
+        for entry in self.packages.values_mut() {
+            if entry.update_available && entry.pkgrel_only {
+                entry.notes.push(NOTE_PKGREL_IGNORED.to_string());
+            }
+        }
+        self.withdraw_updates(|entry| entry.pkgrel_only);
+        self.metadata.pkgrel_only_ignored = true;
+    }
+
     /// Refresh metadata summaries based on collected application state.
     pub fn refresh_application_metadata(&mut self) {
         let flatpak_enabled = self
@@ -222,7 +928,14 @@ impl ManifestDocument {
     }
 }
 
-fn source_from_repo(repo: Option<&str>) -> PackageSource {
+/// Note on entries the AUR was asked about but did not return.
+pub const NOTE_AUR_NOT_FOUND: &str = "not found in AUR";
+/// Leading text of the note on entries whose resolution timed out.
+pub const NOTE_TIMED_OUT: &str = "resolution timed out after";
+/// Note on pkgrel-only rebuilds withdrawn by `--ignore-pkgrel-only`.
+pub const NOTE_PKGREL_IGNORED: &str = "pkgrel-only rebuild ignored (--ignore-pkgrel-only)";
+
+pub fn source_from_repo(repo: Option<&str>) -> PackageSource {
     match repo {
         Some(name) if name.eq_ignore_ascii_case("aur") => PackageSource::Aur,
         Some(name) if name.eq_ignore_ascii_case("local") => PackageSource::Local,
@@ -231,24 +944,398 @@ fn source_from_repo(repo: Option<&str>) -> PackageSource {
     }
 }
 
-fn resolve_package(package: &InstalledPackage) -> ManifestEntry {
+/// Confidence for a source confirmed by an exact match or an explicit override.
+pub const CONFIDENCE_HIGH: f32 = 1.0;
+/// Confidence for a source inferred across repositories or sources.
+pub const CONFIDENCE_MEDIUM: f32 = 0.6;
+/// Confidence when no candidate corroborates the installed source.
+pub const CONFIDENCE_LOW: f32 = 0.2;
+
+/// Score how certain the resolved source of an entry is.
+///
+/// - high: a `source_override`, an AUR candidate for an AUR package, or a repo
+///   candidate from the very repository the package is installed from;
+/// - medium: a repo candidate from another (or an unreported) repository, or a
+///   candidate from a different source than the installed one;
+/// - low: an unknown installed source or no candidate at all.
+pub fn resolution_confidence(
+    source: PackageSource,
+    forced: bool,
+    candidate_source: Option<PackageSource>,
+    installed_repo: Option<&str>,
+    candidate_repo: Option<&str>,
+) -> f32 {
+    if forced {
+        return CONFIDENCE_HIGH;
+    }
+    match (source, candidate_source) {
+        (PackageSource::Unknown, _) | (_, None) => CONFIDENCE_LOW,
+        (PackageSource::Aur, Some(PackageSource::Aur)) => CONFIDENCE_HIGH,
+        (PackageSource::Pacman, Some(PackageSource::Pacman))
+            if candidate_repo.is_some() && candidate_repo == installed_repo =>
+        {
+            CONFIDENCE_HIGH
+        }
+        _ => CONFIDENCE_MEDIUM,
+    }
+}
+
+async fn resolve_package(
+    package: &InstalledPackage,
+    repo_candidate: Option<&VersionInfo>,
+    aur_candidate: Option<&VersionInfo>,
+    policy: SourcePolicy,
+    forced: Option<SourceOverride>,
+    compare: VersionCompare,
+    logger: &Logger,
+) -> Result<ManifestEntry> {
     let repo = package.repository.clone();
-    let source = source_from_repo(repo.as_deref());
+    let source = match forced {
+        Some(SourceOverride::Aur) => PackageSource::Aur,
+        Some(SourceOverride::Repo) => PackageSource::Pacman,
+        Some(SourceOverride::Local) => PackageSource::Local,
+        None => source_from_repo(repo.as_deref()),
+    };
 
-    ManifestEntry {
+    let repo_candidate = repo_candidate.filter(|info| usable_versions(package, info, logger));
+    let aur_candidate = aur_candidate.filter(|info| usable_versions(package, info, logger));
+    let mut notes = Vec::new();
+    if let Some(forced) = forced {
+        notes.push(format!("source forced to {forced} by source_override"));
+    }
+    let (candidate_source, candidate) = match (forced, repo_candidate, aur_candidate) {
+        // An override restricts the comparison to the forced source's candidate.
+        (Some(SourceOverride::Aur), _, aur_info) => {
+            (aur_info.map(|_| PackageSource::Aur), aur_info)
+        }
+        (Some(SourceOverride::Repo), repo_info, _) => {
+            (repo_info.map(|_| PackageSource::Pacman), repo_info)
+        }
+        (Some(SourceOverride::Local), _, _) => (None, None),
+        (None, Some(repo_info), Some(aur_info)) => {
+            let ordering =
+                compare_version_case(&repo_info.version, &aur_info.version, compare, &mut notes)
+                    .await?;
+            let chosen = select_source(policy, ordering, source);
+            if ordering == Ordering::Equal {
+                let label = if chosen == PackageSource::Aur {
+                    "AUR"
+                } else {
+                    "repo"
+                };
+                notes.push(format!(
+                    "repo and AUR versions equal; chose {label} per {policy} policy"
+                ));
+            }
+            match chosen {
+                PackageSource::Aur => (Some(PackageSource::Aur), Some(aur_info)),
+                _ => (Some(PackageSource::Pacman), Some(repo_info)),
+            }
+        }
+        (None, Some(repo_info), None) => (Some(PackageSource::Pacman), Some(repo_info)),
+        (None, None, Some(aur_info)) => (Some(PackageSource::Aur), Some(aur_info)),
+        (None, None, None) => (None, None),
+    };
+    let available_version = candidate.map(|info| info.version.clone());
+    let version_delta = match &available_version {
+        Some(remote) => {
+            let ordering =
+                compare_version_case(&package.version, remote, compare, &mut notes).await?;
+            Some(VersionDelta::from_ordering(
+                ordering,
+                candidate_source == Some(source),
+            ))
+        }
+        None => None,
+    };
+    let update_available = version_delta == Some(VersionDelta::Upgrade);
+    let newer_version = if update_available {
+        available_version.clone()
+    } else {
+        None
+    };
+
+    let version_parts =
+        VersionPartsPair::from_versions(&package.version, available_version.as_deref());
+    if version_delta == Some(VersionDelta::Downgrade) {
+        let remote = available_version.as_deref().unwrap_or_default();
+        let mut note = format!(
+            "candidate {remote} is older than installed {} (downgrade)",
+            package.version
+        );
+        // An epoch drop outranks any pkgver, so call it out explicitly.
+        if let (Some(installed), Some(available)) =
+            (&version_parts.installed, &version_parts.available)
+        {
+            if installed.epoch != available.epoch {
+                note.push_str(&format!(
+                    "; epoch {} -> {}",
+                    installed.epoch, available.epoch
+                ));
+            }
+        }
+        logger.warn("DOWNGRADE", format!("{}: {note}", package.name));
+        notes.push(note);
+    }
+
+    // Sizes the selected source leaves out are borrowed from the other source's candidate.
+    let (alternate, alternate_label) = match candidate_source {
+        Some(PackageSource::Aur) => (repo_candidate, "repo"),
+        Some(_) => (aur_candidate, "AUR"),
+        None => (None, ""),
+    };
+    let (download_size_selected, download_borrowed) = size_with_fallback(
+        candidate.and_then(|info| info.download_size),
+        alternate.and_then(|info| info.download_size),
+    );
+    let (installed_size_selected, installed_borrowed) = size_with_fallback(
+        candidate.and_then(|info| info.installed_size),
+        alternate.and_then(|info| info.installed_size),
+    );
+    if download_borrowed || installed_borrowed {
+        notes.push(format!(
+            "sizes estimated from the {alternate_label} candidate"
+        ));
+    }
+
+    let aur_maintainer = candidate
+        .filter(|_| candidate_source == Some(PackageSource::Aur))
+        .and_then(|info| info.maintainer.clone());
+    let aur_orphaned = candidate_source == Some(PackageSource::Aur) && aur_maintainer.is_none();
+    let aur_candidate = candidate.filter(|_| candidate_source == Some(PackageSource::Aur));
+    let aur_votes = aur_candidate.and_then(|info| info.aur_votes);
+    let aur_popularity = aur_candidate.and_then(|info| info.aur_popularity);
+    if aur_orphaned {
+        logger.warn(
+            "ORPHANED",
+            format!(
+                "{}: AUR package has no maintainer; review before updating",
+                package.name
+            ),
+        );
+    }
+    let flagged_out_of_date = aur_candidate.and_then(|info| info.flagged_out_of_date);
+    let aur_dependencies = aur_candidate.and_then(|info| info.aur_dependencies.clone());
+    if let Some(flagged) = flagged_out_of_date.filter(|_| update_available) {
+        logger.warn(
+            "OUTOFDATE",
+            format!(
+                "{}: AUR package flagged out of date since {}; the update may fail to build",
+                package.name,
+                flagged.format("%Y-%m-%d")
+            ),
+        );
+    }
+
+    let repo_name = candidate
+        .filter(|_| candidate_source == Some(PackageSource::Pacman))
+        .and_then(|info| info.repository.clone());
+    let confidence = resolution_confidence(
+        source,
+        forced.is_some(),
+        candidate_source,
+        repo.as_deref(),
+        repo_name.as_deref(),
+    );
+
+    Ok(ManifestEntry {
         installed_version: package.version.clone(),
         repository: repo,
         source,
+        pkgrel_only: update_available && version_parts.pkgrel_only(),
+        version_parts,
+        available_version,
+        candidate_source,
+        newer_version,
+        update_available,
+        version_delta,
+        download_size_selected,
+        download_size_estimated: download_borrowed
+            || candidate.is_some_and(|info| info.size_estimated),
+        installed_size_selected,
+        size_from_other_source: download_borrowed || installed_borrowed,
+        affected_dependents: None,
+        new_dependencies: Vec::new(),
+        download_url: None,
+        cached: None,
+        cache_mismatch: None,
+        integrity_ok: None,
+        repo_name,
+        url_path: candidate.and_then(|info| info.url_path.clone()),
+        aur_maintainer,
+        aur_orphaned,
+        aur_votes,
+        aur_popularity,
+        candidate_released_at: candidate.and_then(|info| info.released_at),
+        flagged_out_of_date,
+        aur_dependencies,
+        stable: true,
+        stability_reasons: Vec::new(),
+        reboot_required: false,
+        notes,
+        confidence,
         installed_size: package.installed_size,
         install_date: package.install_date.clone(),
+        installed_age_days: None,
         validated_by: package.validated_by.clone(),
         package_hash: package
             .package_hash
             .as_ref()
             .map(|h| truncate_hash(h.as_str())),
+    })
+}
+
+/// The selected size, else the alternate one flagged as borrowed.
+fn size_with_fallback(selected: Option<u64>, alternate: Option<u64>) -> (Option<u64>, bool) {
+    match selected {
+        Some(size) => (Some(size), false),
+        None => (alternate, alternate.is_some()),
     }
 }
 
+/// Await one package's resolution for at most `limit` (zero waits indefinitely).
+///
+/// Returns `None` after logging a `TIMEOUT` warning when the limit is hit, so a
+/// single stuck comparison or lookup cannot stall the whole run.
+async fn resolve_within<F>(
+    package: &InstalledPackage,
+    limit: Duration,
+    resolution: F,
+    logger: &Logger,
+) -> Result<Option<ManifestEntry>>
+where
+    F: Future<Output = Result<ManifestEntry>>,
+{
+    if limit.is_zero() {
+        return resolution.await.map(Some);
+    }
+    match tokio::time::timeout(limit, resolution).await {
+        Ok(result) => result.map(Some),
+        Err(_) => {
+            logger.warn(
+                "TIMEOUT",
+                format!(
+                    "{}: resolution exceeded {}s; recorded as unknown",
+                    package.name,
+                    limit.as_secs_f64()
+                ),
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Placeholder entry for a package whose resolution timed out.
+fn timed_out_entry(package: &InstalledPackage, limit: Duration) -> ManifestEntry {
+    ManifestEntry {
+        installed_version: package.version.clone(),
+        repository: package.repository.clone(),
+        source: PackageSource::Unknown,
+        available_version: None,
+        version_parts: VersionPartsPair::from_versions(&package.version, None),
+        candidate_source: None,
+        newer_version: None,
+        update_available: false,
+        version_delta: None,
+        pkgrel_only: false,
+        download_size_selected: None,
+        download_size_estimated: false,
+        installed_size_selected: None,
+        size_from_other_source: false,
+        affected_dependents: None,
+        new_dependencies: Vec::new(),
+        download_url: None,
+        cached: None,
+        cache_mismatch: None,
+        repo_name: None,
+        integrity_ok: None,
+        url_path: None,
+        aur_maintainer: None,
+        aur_orphaned: false,
+        aur_votes: None,
+        aur_popularity: None,
+        candidate_released_at: None,
+        flagged_out_of_date: None,
+        aur_dependencies: None,
+        stable: true,
+        stability_reasons: Vec::new(),
+        reboot_required: false,
+        notes: vec![format!("{NOTE_TIMED_OUT} {}s", limit.as_secs_f64())],
+        confidence: CONFIDENCE_LOW,
+        installed_size: package.installed_size,
+        install_date: package.install_date.clone(),
+        installed_age_days: None,
+        validated_by: package.validated_by.clone(),
+        package_hash: package
+            .package_hash
+            .as_ref()
+            .map(|h| truncate_hash(h.as_str())),
+    }
+}
+
+/// Compare versions, folding ASCII case first when `version_case_fold` is enabled.
+///
+/// Folding deviates from strict vercmp semantics, so every comparison it changes is noted.
+async fn compare_version_case(
+    left: &str,
+    right: &str,
+    compare: VersionCompare,
+    notes: &mut Vec<String>,
+) -> Result<Ordering> {
+    if !compare.case_fold || left == right {
+        return compare_versions(left, right, compare.backend).await;
+    }
+    let (folded_left, folded_right) = (fold_version_case(left), fold_version_case(right));
+    if folded_left != left || folded_right != right {
+        notes.push(format!(
+            "compared {left} and {right} case-insensitively (version_case_fold)"
+        ));
+    }
+    compare_versions(&folded_left, &folded_right, compare.backend).await
+}
+
+/// Choose between repo and AUR candidates given how the repo version orders against the AUR one.
+fn select_source(
+    policy: SourcePolicy,
+    repo_vs_aur: Ordering,
+    installed: PackageSource,
+) -> PackageSource {
+    match (policy, repo_vs_aur) {
+        // Equal versions: the policy alone decides, so spell out every case.
+        (SourcePolicy::Repo, Ordering::Equal) => PackageSource::Pacman,
+        (SourcePolicy::Aur, Ordering::Equal) => PackageSource::Aur,
+        (SourcePolicy::Newer, Ordering::Equal) if installed == PackageSource::Aur => {
+            PackageSource::Aur
+        }
+        (SourcePolicy::Newer, Ordering::Equal) => PackageSource::Pacman,
+        (SourcePolicy::Repo, Ordering::Less) => PackageSource::Aur,
+        (SourcePolicy::Repo, Ordering::Greater) => PackageSource::Pacman,
+        (SourcePolicy::Aur, _) => PackageSource::Aur,
+        (SourcePolicy::Newer, Ordering::Greater) => PackageSource::Pacman,
+        (SourcePolicy::Newer, Ordering::Less) => PackageSource::Aur,
+    }
+}
+
+/// Validate both sides of a comparison; anomalies make the candidate unavailable.
+fn usable_versions(package: &InstalledPackage, candidate: &VersionInfo, logger: &Logger) -> bool {
+    for (label, version) in [
+        ("installed", package.version.as_str()),
+        ("candidate", candidate.version.as_str()),
+    ] {
+        if let Err(anomaly) = check_version(version) {
+            logger.warn(
+                "BADVER",
+                format!(
+                    "{}: {label} {anomaly} ({version:?}); treating candidate as unavailable",
+                    package.name
+                ),
+            );
+            return false;
+        }
+    }
+    true
+}
+
 fn truncate_hash(value: &str) -> String {
     let trimmed = value.trim();
     if trimmed.len() <= 16 {
@@ -258,8 +1345,364 @@ fn truncate_hash(value: &str) -> String {
     }
 }
 
-/// Persist the manifest to the given path.
-pub fn write_manifest(document: &ManifestDocument, path: &Path) -> Result<()> {
+/// Resolve an output path that may name an existing directory.
+///
+/// A directory is rejected with a `Filesystem` error unless `generate_named_file`
+/// is set (`manifest.dir_generates_named_file`), in which case `file_name` is
+/// placed inside it. `flag` names the option in the error message.
+pub fn resolve_output_file(
+    path: &Path,
+    flag: &str,
+    file_name: &str,
+    generate_named_file: bool,
+) -> Result<PathBuf> {
+    if !path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+    if generate_named_file {
+        return Ok(path.join(file_name));
+    }
+    Err(SynsyuError::Filesystem(format!(
+        "{flag} {} is a directory; pass a file path, or set \
+         manifest.dir_generates_named_file = true to write {file_name} inside it",
+        path.display()
+    )))
+}
+
+/// Fail early when the manifest directory cannot be created or written.
+///
+/// A probe file is created beside the manifest and removed again, so a slow
+/// resolution never ends in an unwritable destination.
+pub fn ensure_manifest_writable(path: &Path) -> Result<()> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent).map_err(|err| {
+        SynsyuError::Filesystem(format!(
+            "Manifest directory {} cannot be created: {err}",
+            parent.display()
+        ))
+    })?;
+    let probe = parent.join(format!(".synsyu-preflight-{}", std::process::id()));
+    File::options()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .map_err(|err| {
+            SynsyuError::Filesystem(format!(
+                "Manifest directory {} is not writable: {err}",
+                parent.display()
+            ))
+        })?;
+    fs::remove_file(&probe).map_err(|err| {
+        SynsyuError::Filesystem(format!(
+            "Failed to remove preflight file {}: {err}",
+            probe.display()
+        ))
+    })
+}
+
+/// Limits on where a manifest write may land (`manifest.no_symlink`, `manifest.allowed_base`).
+#[derive(Debug, Clone, Default)]
+pub struct SymlinkGuard {
+    pub refuse_symlinks: bool,
+    pub allowed_base: Option<PathBuf>,
+}
+
+impl SymlinkGuard {
+    pub fn from_config(config: &ManifestConfig) -> Self {
+        Self {
+            refuse_symlinks: config.no_symlink,
+            allowed_base: config.allowed_base.as_ref().map(PathBuf::from),
+        }
+    }
+
+    /// Refuse `path` when it is a forbidden symlink or resolves outside the allowed base.
+    ///
+    /// The real location is the canonicalized parent directory of the final link
+    /// target joined with its file name, so a dangling link is judged by where the
+    /// write would create the file. Missing parent directories are judged by their
+    /// deepest existing ancestor, so this runs before anything is created.
+    pub fn check(&self, path: &Path) -> Result<()> {
+        let is_symlink = fs::symlink_metadata(path)
+            .map(|meta| meta.file_type().is_symlink())
+            .unwrap_or(false);
+        if is_symlink && self.refuse_symlinks {
+            return Err(SynsyuError::Filesystem(format!(
+                "Manifest target {} is a symlink; refusing to follow it (manifest.no_symlink)",
+                path.display()
+            )));
+        }
+        let Some(base) = &self.allowed_base else {
+            return Ok(());
+        };
+        let base = fs::canonicalize(base).map_err(|err| {
+            SynsyuError::Config(format!(
+                "manifest.allowed_base {} cannot be resolved: {err}",
+                base.display()
+            ))
+        })?;
+        let resolved = resolve_write_target(path)?;
+        if !resolved.starts_with(&base) {
+            return Err(SynsyuError::Filesystem(format!(
+                "Manifest target {} resolves to {}, outside manifest.allowed_base {}",
+                path.display(),
+                resolved.display(),
+                base.display()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Where a write to `path` lands: symlinks followed, parent directory canonicalized.
+fn resolve_write_target(path: &Path) -> Result<PathBuf> {
+    let unresolvable = |err: std::io::Error| {
+        SynsyuError::Filesystem(format!(
+            "Manifest target {} cannot be resolved: {err}",
+            path.display()
+        ))
+    };
+    let mut target = path.to_path_buf();
+    // Bounded like the kernel's own limit on nested links.
+    for _ in 0..40 {
+        let is_symlink = fs::symlink_metadata(&target)
+            .map(|meta| meta.file_type().is_symlink())
+            .unwrap_or(false);
+        if !is_symlink {
+            let parent = match target.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            let name = target
+                .file_name()
+                .ok_or_else(|| unresolvable(std::io::ErrorKind::InvalidInput.into()))?;
+            return Ok(canonicalize_existing(parent)
+                .map_err(unresolvable)?
+                .join(name));
+        }
+        let link = fs::read_link(&target).map_err(unresolvable)?;
+        target = match target.parent() {
+            Some(parent) if link.is_relative() => parent.join(link),
+            _ => link,
+        };
+    }
+    Err(unresolvable(std::io::Error::other(
+        "too many levels of symbolic links",
+    )))
+}
+
+/// Canonicalize the deepest existing ancestor of `dir` and append the missing
+/// components, which hold no links yet; a `..` among them is refused.
+fn canonicalize_existing(dir: &Path) -> std::io::Result<PathBuf> {
+    let mut existing = dir;
+    let mut missing = Vec::new();
+    while fs::symlink_metadata(existing).is_err() {
+        missing.push(
+            existing
+                .file_name()
+                .ok_or(std::io::ErrorKind::InvalidInput)?,
+        );
+        existing = match existing.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+    }
+    let mut resolved = fs::canonicalize(existing)?;
+    resolved.extend(missing.iter().rev());
+    Ok(resolved)
+}
+
+/// On-disk encoding of a manifest (`--format`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ManifestFormat {
+    #[default]
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ManifestFormat {
+    /// Format implied by the file extension: `.yaml`/`.yml`, `.toml`, otherwise JSON.
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("yaml" | "yml") => Self::Yaml,
+            Some("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+
+    /// File extension for generated manifest names.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Toml => "toml",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Yaml => "YAML",
+            Self::Toml => "TOML",
+        }
+    }
+}
+
+/// Persist the manifest to the given path in `format`.
+///
+/// The document is encoded before the file is opened, so a format that cannot
+/// represent it fails without truncating an existing manifest.
+pub fn write_manifest<T: Serialize>(
+    document: &T,
+    path: &Path,
+    format: ManifestFormat,
+    guard: &SymlinkGuard,
+) -> Result<()> {
+    let bytes = encode(document, format)?;
+    write_manifest_bytes(&bytes, path, guard)
+}
+
+/// Load a manifest written by this or an earlier build (e.g. copied from another machine).
+///
+/// The encoding follows the file extension, as with `--format` left unset.
+pub fn read_manifest(path: &Path) -> Result<ManifestDocument> {
+    let text = fs::read_to_string(path).map_err(|err| {
+        SynsyuError::Filesystem(format!("Failed to open manifest {}: {err}", path.display()))
+    })?;
+    let parsed = match ManifestFormat::from_path(path) {
+        ManifestFormat::Json => serde_json::from_str(&text).map_err(|err| err.to_string()),
+        ManifestFormat::Yaml => serde_yaml::from_str(&text).map_err(|err| err.to_string()),
+        ManifestFormat::Toml => toml::from_str(&text).map_err(|err| err.to_string()),
+    };
+    parsed.map_err(|err| {
+        SynsyuError::Serialization(format!(
+            "Failed to parse manifest {}: {err}",
+            path.display()
+        ))
+    })
+}
+
+/// Persist the manifest with every object's keys sorted (`--canonical`).
+pub fn write_manifest_canonical<T: Serialize>(
+    document: &T,
+    path: &Path,
+    format: ManifestFormat,
+    guard: &SymlinkGuard,
+) -> Result<()> {
+    let bytes = match format {
+        ManifestFormat::Json => canonical_json(document)?,
+        // TOML has no null: absent values are omitted, as for `Option` fields.
+        ManifestFormat::Toml => encode(&without_nulls(sorted_value(document)?), format)?,
+        ManifestFormat::Yaml => encode(&sorted_value(document)?, format)?,
+    };
+    write_manifest_bytes(&bytes, path, guard)
+}
+
+/// Pretty JSON with lexicographically sorted object keys at every depth, so
+/// equal documents always produce identical bytes (stable diffs and hashes).
+pub fn canonical_json<T: Serialize>(document: &T) -> Result<Vec<u8>> {
+    serde_json::to_vec_pretty(&sorted_value(document)?).map_err(|err| {
+        SynsyuError::Serialization(format!("Failed to canonicalize manifest: {err}"))
+    })
+}
+
+fn sorted_value<T: Serialize>(document: &T) -> Result<serde_json::Value> {
+    fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut entries: Vec<_> = map.into_iter().collect();
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                serde_json::Value::Object(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (key, sort_keys(value)))
+                        .collect(),
+                )
+            }
+            serde_json::Value::Array(items) => {
+                serde_json::Value::Array(items.into_iter().map(sort_keys).collect())
+            }
+            other => other,
+        }
+    }
+    let value = serde_json::to_value(document).map_err(|err| {
+        SynsyuError::Serialization(format!("Failed to canonicalize manifest: {err}"))
+    })?;
+    Ok(sort_keys(value))
+}
+
+/// Drop null object members at every depth (nulls inside arrays are kept).
+fn without_nulls(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, without_nulls(value)))
+                .collect(),
+        ),
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(without_nulls).collect())
+        }
+        other => other,
+    }
+}
+
+/// Serialize `document` as pretty `format` text.
+fn encode<T: Serialize>(document: &T, format: ManifestFormat) -> Result<Vec<u8>> {
+    let encoded = match format {
+        ManifestFormat::Json => serde_json::to_vec_pretty(document).map_err(|err| err.to_string()),
+        ManifestFormat::Yaml => serde_yaml::to_string(document)
+            .map(String::into_bytes)
+            .map_err(|err| err.to_string()),
+        ManifestFormat::Toml => toml::to_string_pretty(document)
+            .map(String::into_bytes)
+            .map_err(|err| err.to_string()),
+    };
+    encoded.map_err(|err| {
+        SynsyuError::Serialization(format!(
+            "Manifest cannot be represented as {}: {err}",
+            format.label()
+        ))
+    })
+}
+
+/// Manifest path meaning "write to stdout" (`--manifest -`).
+pub fn is_stdout(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+fn write_manifest_bytes(bytes: &[u8], path: &Path, guard: &SymlinkGuard) -> Result<()> {
+    if is_stdout(path) {
+        let mut stdout = std::io::stdout().lock();
+        return stdout
+            .write_all(bytes)
+            .and_then(|()| stdout.write_all(b"\n"))
+            .and_then(|()| stdout.flush())
+            .map_err(SynsyuError::Io);
+    }
+    let mut file = create_manifest_file(path, guard)?;
+    file.write_all(bytes).map_err(|err| {
+        SynsyuError::Filesystem(format!(
+            "Failed to write manifest {}: {err}",
+            path.display()
+        ))
+    })
+}
+
+/// Create (or truncate) a manifest file with private permissions on a private directory.
+///
+/// The guard runs before any directory is created or re-permissioned. Under
+/// `manifest.no_symlink` the file is opened with `O_NOFOLLOW`, so a link swapped
+/// in after the check still fails the open.
+fn create_manifest_file(path: &Path, guard: &SymlinkGuard) -> Result<File> {
+    guard.check(path)?;
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|err| {
             SynsyuError::Filesystem(format!(
@@ -278,7 +1721,13 @@ pub fn write_manifest(document: &ManifestDocument, path: &Path) -> Result<()> {
             })?;
         }
     }
-    let mut file = File::create(path).map_err(|err| {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if guard.refuse_symlinks {
+        options.custom_flags(libc::O_NOFOLLOW);
+    }
+    let file = options.open(path).map_err(|err| {
         SynsyuError::Filesystem(format!(
             "Failed to create manifest file {}: {err}",
             path.display()
@@ -294,11 +1743,1443 @@ pub fn write_manifest(document: &ManifestDocument, path: &Path) -> Result<()> {
             ))
         })?;
     }
-    serde_json::to_writer_pretty(&mut file, document).map_err(|err| {
-        SynsyuError::Filesystem(format!(
-            "Failed to write manifest {}: {err}",
-            path.display()
-        ))
-    })?;
-    Ok(())
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CoreConfig;
+
+    fn installed(version: &str) -> InstalledPackage {
+        InstalledPackage {
+            name: "foo".into(),
+            version: version.into(),
+            repository: Some("aur".into()),
+            installed_size: None,
+            install_date: None,
+            validated_by: None,
+            package_hash: None,
+            required_by: Vec::new(),
+            provides: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn named(name: &str, version: &str) -> InstalledPackage {
+        InstalledPackage {
+            name: name.into(),
+            repository: Some("core".into()),
+            ..installed(version)
+        }
+    }
+
+    #[tokio::test]
+    async fn interrupted_run_resumes_without_re_resolving() {
+        let logger = Logger::new(None, false).unwrap();
+        let dir = std::env::temp_dir().join(format!("synsyu-resume-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let partial = Checkpoint::partial_path(&dir.join("manifest.json"));
+        let overrides = BTreeMap::new();
+        let all = vec![
+            named("bash", "5.2.37-1"),
+            named("curl", "8.10.1-1"),
+            named("zsh", "5.9-5"),
+        ];
+
+        // First run is "interrupted" after resolving the first two packages.
+        let mut checkpoint = Checkpoint::start(partial.clone(), 1, false, &logger);
+        build_manifest(
+            &all[..2],
+            &Candidates::default(),
+            &ResolveOptions::new(SourcePolicy::Repo, &overrides),
+            Some(&mut checkpoint),
+            &logger,
+        )
+        .await
+        .unwrap();
+        drop(checkpoint);
+        assert!(partial.exists());
+
+        // On resume, candidates that would change bash/curl are ignored for reused entries.
+        let mut candidates = Candidates::default();
+        for pkg in &all {
+            candidates.repo.insert(
+                pkg.name.clone(),
+                VersionInfo::new(pkg.version.clone(), Some(7), None),
+            );
+        }
+        let mut checkpoint = Checkpoint::start(partial.clone(), 1, true, &logger);
+        let document = build_manifest(
+            &all,
+            &candidates,
+            &ResolveOptions::new(SourcePolicy::Repo, &overrides),
+            Some(&mut checkpoint),
+            &logger,
+        )
+        .await
+        .unwrap();
+        assert_eq!(document.packages.len(), 3);
+        assert_eq!(document.packages["bash"].available_version, None);
+        assert_eq!(document.packages["curl"].available_version, None);
+        assert_eq!(
+            document.packages["zsh"].available_version.as_deref(),
+            Some("5.9-5")
+        );
+        assert_eq!(document.metadata.pacman_packages, 3);
+
+        checkpoint.clear(&logger);
+        assert!(!partial.exists());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn download_totals_split_exact_and_estimated_sizes() {
+        let logger = Logger::new(None, false).unwrap();
+        let tarball = VersionInfo::new("2.0-1".into(), Some(4_096), None)
+            .with_maintainer(Some("me".into()))
+            .with_size_estimated(true);
+        let resolved = resolve_package(
+            &installed("2.0-1"),
+            None,
+            Some(&tarball),
+            SourcePolicy::Repo,
+            None,
+            VersionCompare::default(),
+            &logger,
+        )
+        .await
+        .unwrap();
+        assert!(resolved.download_size_estimated);
+
+        let sized = |source, candidate: &str, size: u64, estimated: bool| {
+            let mut entry = fixtures::entry("1.0-1", source, Some(candidate));
+            entry.download_size_selected = Some(size);
+            entry.download_size_estimated = estimated;
+            entry
+        };
+        let document = fixtures::document(vec![
+            (
+                "bash",
+                sized(PackageSource::Pacman, "1.1-1", 1_800_000, false),
+            ),
+            (
+                "glibc",
+                sized(PackageSource::Pacman, "1.0-2", 10_000_000, false),
+            ),
+            // AUR RPC reported the size itself.
+            ("yay", sized(PackageSource::Aur, "1.2-1", 9_000, false)),
+            // Sizes inferred from snapshot tarballs.
+            ("paru", sized(PackageSource::Aur, "1.1-1", 4_096, true)),
+            ("zen", sized(PackageSource::Aur, "1.3-1", 2_048, true)),
+            // No pending update: contributes to neither total.
+            ("current", sized(PackageSource::Aur, "1.0-1", 777, true)),
+        ]);
+        assert_eq!(document.metadata.download_size_exact, 11_809_000);
+        assert_eq!(document.metadata.download_size_estimated, 6_144);
+    }
+
+    #[test]
+    fn size_totals_follow_growing_and_shrinking_updates() {
+        let sized = |installed: &str, candidate: Option<&str>, now: u64, next: Option<u64>| {
+            let mut entry = fixtures::entry(installed, PackageSource::Pacman, candidate);
+            entry.installed_size = Some(now);
+            entry.installed_size_selected = next;
+            entry
+        };
+        let mut document = fixtures::document(vec![
+            ("grows", sized("1.0-1", Some("1.1-1"), 1_000, Some(1_500))),
+            ("shrinks", sized("2.0-1", Some("2.1-1"), 4_000, Some(2_500))),
+            ("unknown-next", sized("3.0-1", Some("3.1-1"), 700, None)),
+            ("current", sized("5.9-5", Some("5.9-5"), 300, Some(9_999))),
+        ]);
+        assert_eq!(document.metadata.installed_size_total, 6_000);
+        assert_eq!(document.metadata.installed_size_after_updates, 5_000);
+
+        document.packages.get_mut("grows").unwrap().aur_orphaned = true;
+        document.skip_orphaned_aur();
+        assert_eq!(document.metadata.installed_size_after_updates, 4_500);
+    }
+
+    #[tokio::test]
+    async fn hung_resolution_times_out_without_blocking_the_rest() {
+        let logger = Logger::new(None, false).unwrap();
+        let limit = Duration::from_millis(50);
+        let packages = [
+            named("bash", "5.2.37-1"),
+            named("stuck", "1.0-1"),
+            named("zsh", "5.9-5"),
+        ];
+        let mut resolved = Vec::new();
+        for package in &packages {
+            // The injected comparator never answers for `stuck`.
+            let comparator = async {
+                if package.name == "stuck" {
+                    std::future::pending::<()>().await;
+                }
+            };
+            let resolution = async {
+                comparator.await;
+                resolve_package(
+                    package,
+                    None,
+                    None,
+                    SourcePolicy::Repo,
+                    None,
+                    VersionCompare::default(),
+                    &logger,
+                )
+                .await
+            };
+            resolved.push(
+                resolve_within(package, limit, resolution, &logger)
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        assert!(resolved[0].is_some());
+        assert!(resolved[1].is_none());
+        assert!(resolved[2].is_some());
+        assert!(logger.warnings()[0].starts_with("[TIMEOUT] stuck:"));
+        let placeholder = timed_out_entry(&packages[1], limit);
+        assert_eq!(placeholder.source, PackageSource::Unknown);
+        assert_eq!(placeholder.notes, ["resolution timed out after 0.05s"]);
+
+        // A generous limit leaves a normal run untouched.
+        let overrides = BTreeMap::new();
+        let options = ResolveOptions {
+            package_timeout: Duration::from_secs(300),
+            ..ResolveOptions::new(SourcePolicy::Repo, &overrides)
+        };
+        let document = build_manifest(&packages, &Candidates::default(), &options, None, &logger)
+            .await
+            .unwrap();
+        assert_eq!(document.metadata.timed_out_count, 0);
+        assert_eq!(document.packages.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn empty_selection_writes_a_valid_manifest() {
+        let logger = Logger::new(None, false).unwrap();
+        let overrides = BTreeMap::new();
+        let options = ResolveOptions::new(SourcePolicy::Repo, &overrides);
+        let document = build_manifest(&[], &Candidates::default(), &options, None, &logger)
+            .await
+            .unwrap();
+        let dir = std::env::temp_dir().join(format!("synsyu-empty-{}", std::process::id()));
+        for file in ["manifest.json", "manifest.toml"] {
+            let path = dir.join(file);
+            write_manifest(
+                &document,
+                &path,
+                ManifestFormat::from_path(&path),
+                &SymlinkGuard::default(),
+            )
+            .unwrap();
+            let read = read_manifest(&path).unwrap();
+            assert!(read.packages.is_empty(), "{file}");
+            assert_eq!(read.metadata.total_packages, 0);
+            assert_eq!(read.metadata.updates_available, 0);
+            assert_eq!(read.metadata.generated_at, document.metadata.generated_at);
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn ignored_packages_are_held_back() {
+        let logger = Logger::new(None, false).unwrap();
+        let mut candidates = Candidates::default();
+        for (name, version) in [("linux", "6.12.1-1"), ("gdm", "47.1-1"), ("zsh", "5.9-6")] {
+            candidates
+                .repo
+                .insert(name.into(), VersionInfo::new(version.into(), None, None));
+        }
+        let gdm = InstalledPackage {
+            groups: vec!["gnome".into()],
+            ..named("gdm", "47.0-1")
+        };
+        let packages = [named("linux", "6.11.9-1"), gdm, named("zsh", "5.9-5")];
+        let rules = IgnoreRules {
+            packages: vec!["linux*".into()],
+            groups: vec!["gnome".into()],
+        };
+        let overrides = BTreeMap::new();
+        let options = ResolveOptions {
+            ignore: Some(&rules),
+            ..ResolveOptions::new(SourcePolicy::Repo, &overrides)
+        };
+        let document = build_manifest(&packages, &candidates, &options, None, &logger)
+            .await
+            .unwrap();
+
+        let linux = &document.packages["linux"];
+        assert!(!linux.update_available);
+        assert_eq!(linux.newer_version, None);
+        assert_eq!(linux.available_version.as_deref(), Some("6.12.1-1"));
+        assert_eq!(linux.notes, ["held by IgnorePkg"]);
+        assert_eq!(document.packages["gdm"].notes, ["held by IgnoreGroup"]);
+        assert!(document.packages["zsh"].update_available);
+        assert_eq!(document.metadata.updates_available, 1);
+        assert_eq!(document.metadata.pkgrel_only_updates, 1);
+    }
+
+    #[tokio::test]
+    async fn aur_dependencies_are_kept_only_with_deps() {
+        let logger = Logger::new(None, false).unwrap();
+        let dependencies = AurDependencies {
+            depends: vec!["git".into()],
+            make_depends: vec!["cargo".into()],
+            check_depends: Vec::new(),
+        };
+        let mut candidates = Candidates::default();
+        candidates.aur.insert(
+            "paru".into(),
+            VersionInfo::new("2.0.4-1".into(), None, None)
+                .with_maintainer(Some("someone".into()))
+                .with_aur_dependencies(dependencies.clone()),
+        );
+        candidates
+            .repo
+            .insert("zsh".into(), VersionInfo::new("5.9-6".into(), None, None));
+        let packages = [named("paru", "2.0.3-1"), named("zsh", "5.9-5")];
+        let overrides = BTreeMap::new();
+        let build = |with_deps| {
+            let options = ResolveOptions {
+                with_deps,
+                ..ResolveOptions::new(SourcePolicy::Repo, &overrides)
+            };
+            let (packages, candidates, logger) = (&packages, &candidates, &logger);
+            async move {
+                build_manifest(packages, candidates, &options, None, logger)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let document = build(true).await;
+        assert_eq!(
+            document.packages["paru"].aur_dependencies.as_ref(),
+            Some(&dependencies)
+        );
+        assert_eq!(document.packages["zsh"].aur_dependencies, None);
+        let json = serde_json::to_value(&document.packages["paru"]).unwrap();
+        assert_eq!(
+            json["aur_dependencies"],
+            serde_json::json!({"depends": ["git"], "make_depends": ["cargo"]})
+        );
+        assert_eq!(build(false).await.packages["paru"].aur_dependencies, None);
+    }
+
+    #[tokio::test]
+    async fn packages_absent_from_aur_are_noted() {
+        let logger = Logger::new(None, false).unwrap();
+        let mut candidates = Candidates::default();
+        candidates.aur_not_found.insert("ghost".to_string());
+        let document = build_manifest(
+            &[named("ghost", "0.1-1"), named("zsh", "5.9-5")],
+            &candidates,
+            &ResolveOptions::new(SourcePolicy::Repo, &BTreeMap::new()),
+            None,
+            &logger,
+        )
+        .await
+        .unwrap();
+        assert_eq!(document.packages["ghost"].notes, ["not found in AUR"]);
+        assert!(document.packages["zsh"].notes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn checkpoint_entries_for_changed_versions_are_re_resolved() {
+        let logger = Logger::new(None, false).unwrap();
+        let dir = std::env::temp_dir().join(format!("synsyu-stale-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let partial = dir.join("manifest.json.partial");
+        let mut checkpoint = Checkpoint::start(partial.clone(), 10, false, &logger);
+        let stale = fixtures::entry("5.9-4", PackageSource::Pacman, None);
+        checkpoint.record("zsh", &stale).unwrap();
+        checkpoint.flush().unwrap();
+
+        let resumed = Checkpoint::start(partial, 10, true, &logger);
+        assert!(resumed.resolved("zsh", "5.9-4").is_some());
+        assert!(resumed.resolved("zsh", "5.9-5").is_none());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn anomalous_candidate_versions_are_dropped() {
+        let logger = Logger::new(None, false).unwrap();
+        for bogus in ["", "   ", "1.0\u{7}-1", "2.0\n-1"] {
+            let candidate = VersionInfo::new(bogus.into(), None, None);
+            let entry = resolve_package(
+                &installed("1.0-1"),
+                None,
+                Some(&candidate),
+                SourcePolicy::default(),
+                None,
+                VersionCompare::default(),
+                &logger,
+            )
+            .await
+            .expect("anomalies must not abort");
+            assert_eq!(entry.available_version, None, "candidate {bogus:?}");
+            assert!(!entry.update_available);
+        }
+    }
+
+    #[tokio::test]
+    async fn anomalous_installed_version_disables_comparison() {
+        let logger = Logger::new(None, false).unwrap();
+        let candidate = VersionInfo::new("1.1-1".into(), None, None);
+        let entry = resolve_package(
+            &installed(""),
+            None,
+            Some(&candidate),
+            SourcePolicy::default(),
+            None,
+            VersionCompare::default(),
+            &logger,
+        )
+        .await
+        .expect("anomalies must not abort");
+        assert_eq!(entry.available_version, None);
+        assert!(!entry.update_available);
+    }
+
+    #[tokio::test]
+    async fn version_delta_flags_downgrades_including_epoch_drops() {
+        let logger = Logger::new(None, false).unwrap();
+        let resolve = |repo: Option<&str>, aur: Option<&str>| {
+            let repo = repo.map(|v| VersionInfo::new(v.into(), None, None));
+            let aur = aur.map(|v| {
+                VersionInfo::new(v.into(), None, None).with_maintainer(Some("someone".into()))
+            });
+            let logger = &logger;
+            async move {
+                resolve_package(
+                    &installed("2:1.0-1"),
+                    repo.as_ref(),
+                    aur.as_ref(),
+                    SourcePolicy::default(),
+                    None,
+                    VersionCompare::default(),
+                    logger,
+                )
+                .await
+                .unwrap()
+            }
+        };
+
+        let upgrade = resolve(None, Some("2:1.1-1")).await;
+        assert_eq!(upgrade.version_delta, Some(VersionDelta::Upgrade));
+        assert!(upgrade.update_available);
+        let same = resolve(None, Some("2:1.0-1")).await;
+        assert_eq!(same.version_delta, Some(VersionDelta::Same));
+        // Installed from the AUR; the repo now carries the same version.
+        let reinstall = resolve(Some("2:1.0-1"), None).await;
+        assert_eq!(reinstall.version_delta, Some(VersionDelta::Reinstall));
+        assert!(!reinstall.update_available);
+        assert!(logger.warnings().is_empty());
+
+        // A higher pkgver does not outrank the lower epoch.
+        let downgrade = resolve(Some("1:3.0-1"), None).await;
+        assert_eq!(downgrade.version_delta, Some(VersionDelta::Downgrade));
+        assert!(!downgrade.update_available);
+        assert_eq!(downgrade.newer_version, None);
+        let note = "candidate 1:3.0-1 is older than installed 2:1.0-1 (downgrade); epoch 2 -> 1";
+        assert!(downgrade.notes.iter().any(|n| n == note));
+        assert_eq!(logger.warnings(), [format!("[DOWNGRADE] foo: {note}")]);
+
+        let none = resolve(None, None).await;
+        assert_eq!(none.version_delta, None);
+    }
+
+    #[test]
+    fn pkgrel_only_bumps_are_tagged_and_can_be_uncounted() {
+        let rebuild = fixtures::entry("1:2.4.1-1", PackageSource::Pacman, Some("1:2.4.1-2"));
+        let release = fixtures::entry("2.4.1-1", PackageSource::Pacman, Some("2.4.2-1"));
+        assert!(rebuild.pkgrel_only);
+        assert!(rebuild.update_available);
+        assert!(!release.pkgrel_only);
+
+        let mut document = fixtures::document(vec![("gnupg", rebuild), ("curl", release)]);
+        assert_eq!(document.metadata.updates_available, 2);
+        document.ignore_pkgrel_only();
+        assert_eq!(document.metadata.updates_available, 1);
+        assert_eq!(document.metadata.pkgrel_only_updates, 0);
+        assert!(document.metadata.pkgrel_only_ignored);
+        assert_eq!(document.metadata.updates_by_repo["core"], 1);
+        let gnupg = &document.packages["gnupg"];
+        assert!(!gnupg.update_available);
+        assert_eq!(gnupg.notes, [NOTE_PKGREL_IGNORED]);
+    }
+
+    #[test]
+    fn orphaned_aur_candidates_can_be_skipped() {
+        let maintained = fixtures::entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1"));
+        let mut orphaned = fixtures::entry("0.3-1", PackageSource::Aur, Some("0.3-2"));
+        orphaned.aur_orphaned = true;
+        let mut document = fixtures::document(vec![("paru", maintained), ("old-tool", orphaned)]);
+        assert_eq!(document.metadata.updates_available, 2);
+        assert_eq!(document.metadata.pkgrel_only_updates, 1);
+
+        document.skip_orphaned_aur();
+        assert!(document.packages["paru"].update_available);
+        let skipped = &document.packages["old-tool"];
+        assert!(!skipped.update_available);
+        assert!(skipped.aur_orphaned);
+        assert_eq!(skipped.available_version.as_deref(), Some("0.3-2"));
+        assert_eq!(document.metadata.updates_available, 1);
+        assert_eq!(document.metadata.pkgrel_only_updates, 0);
+    }
+
+    #[tokio::test]
+    async fn flagged_aur_updates_warn_outofdate_and_can_be_skipped() {
+        let logger = Logger::new(None, false).unwrap();
+        let flagged_at = DateTime::parse_from_rfc3339("2026-09-30T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let flagged = |version: &str| {
+            VersionInfo::new(version.into(), None, None)
+                .with_maintainer(Some("someone".into()))
+                .with_out_of_date(Some(flagged_at))
+        };
+        let mut candidates = Candidates::default();
+        candidates.aur.insert("stale-git".into(), flagged("1.1-1"));
+        candidates.aur.insert("current".into(), flagged("2.0-1"));
+        let packages = [
+            InstalledPackage {
+                name: "stale-git".into(),
+                ..installed("1.0-1")
+            },
+            InstalledPackage {
+                name: "current".into(),
+                ..installed("2.0-1")
+            },
+        ];
+        let mut document = build_manifest(
+            &packages,
+            &candidates,
+            &ResolveOptions::new(SourcePolicy::Repo, &BTreeMap::new()),
+            None,
+            &logger,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            document.packages["stale-git"].flagged_out_of_date,
+            Some(flagged_at)
+        );
+        // Only the pending update is worth a warning.
+        assert_eq!(
+            logger.warnings(),
+            [
+                "[OUTOFDATE] stale-git: AUR package flagged out of date since 2026-09-30; \
+              the update may fail to build"
+            ]
+        );
+
+        document.skip_out_of_date();
+        let skipped = &document.packages["stale-git"];
+        assert!(!skipped.update_available);
+        assert_eq!(skipped.available_version.as_deref(), Some("1.1-1"));
+        assert_eq!(document.metadata.updates_available, 0);
+    }
+
+    #[test]
+    fn aur_candidates_below_min_popularity_warn_lowpop() {
+        let aur = |popularity| {
+            let mut entry = fixtures::entry("1.0-1", PackageSource::Aur, Some("1.1-1"));
+            entry.aur_votes = Some(7);
+            entry.aur_popularity = popularity;
+            entry
+        };
+        let mut document = fixtures::document(vec![
+            ("paru", aur(Some(28.41))),
+            ("niche", aur(Some(0.02))),
+            ("no-score", aur(None)),
+            (
+                "bash",
+                fixtures::entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1")),
+            ),
+        ]);
+
+        let logger = Logger::new(None, false).unwrap();
+        assert_eq!(document.flag_low_popularity(0.5, &logger), 1);
+        assert_eq!(
+            document.packages["niche"].notes,
+            ["AUR popularity 0.02 below 0.5"]
+        );
+        assert!(document.packages["paru"].notes.is_empty());
+        let warnings = logger.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("[LOWPOP] niche: AUR popularity 0.02 (7 votes)"));
+        let json = serde_json::to_value(&document.packages["paru"]).unwrap();
+        assert_eq!(json["aur_votes"], 7);
+        assert_eq!(json["aur_popularity"], 28.41);
+    }
+
+    #[test]
+    fn limited_manifest_counts_only_emitted_entries() {
+        let mut document = fixtures::document(vec![
+            (
+                "bash",
+                fixtures::entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1")),
+            ),
+            (
+                "paru",
+                fixtures::entry("2.0.4-1", PackageSource::Aur, Some("2.0.4-1")),
+            ),
+        ]);
+        document.record_limit(2);
+        assert!(!document.metadata.limited);
+        assert_eq!(document.metadata.total_available, 2);
+
+        document.record_limit(7);
+        let json = serde_json::to_value(&document.metadata).unwrap();
+        assert_eq!(json["limited"], true);
+        assert_eq!(json["total_available"], 7);
+        assert_eq!(json["total_packages"], 2);
+        assert_eq!(json["updates_available"], 1);
+    }
+
+    #[test]
+    fn sampled_manifest_extrapolates_update_count() {
+        let mut document = fixtures::document(vec![
+            (
+                "a",
+                fixtures::entry("1-1", PackageSource::Pacman, Some("2-1")),
+            ),
+            (
+                "b",
+                fixtures::entry("1-1", PackageSource::Pacman, Some("1-1")),
+            ),
+            (
+                "c",
+                fixtures::entry("1-1", PackageSource::Pacman, Some("1-1")),
+            ),
+            (
+                "d",
+                fixtures::entry("1-1", PackageSource::Pacman, Some("1-1")),
+            ),
+        ]);
+        assert!(serde_json::to_value(&document.metadata).unwrap()["sample"].is_null());
+        document.record_sample(1_000, Some(0.4), 42);
+        let sample = document.metadata.sample.as_ref().unwrap();
+        assert_eq!(sample.size, 4);
+        assert_eq!(sample.population, 1_000);
+        assert_eq!(sample.estimated_updates, 250);
+        let json = serde_json::to_value(&document.metadata).unwrap();
+        assert_eq!(json["sample"]["seed"], 42);
+        assert_eq!(json["sample"]["percent"], 0.4);
+    }
+
+    #[test]
+    fn kernel_and_firmware_updates_require_reboot() {
+        let patterns = CoreConfig::default().reboot_required;
+        let mut document = fixtures::document(vec![
+            (
+                "linux",
+                fixtures::entry("6.11.1-1", PackageSource::Pacman, Some("6.11.2-1")),
+            ),
+            (
+                "linux-firmware",
+                fixtures::entry("20241010-1", PackageSource::Pacman, Some("20241111-1")),
+            ),
+            (
+                "systemd",
+                fixtures::entry("256.7-1", PackageSource::Pacman, Some("256.7-1")),
+            ),
+            (
+                "vim",
+                fixtures::entry("9.1.0-1", PackageSource::Pacman, Some("9.1.1-1")),
+            ),
+        ]);
+        assert_eq!(document.flag_reboot_required(&patterns), 2);
+        assert!(document.metadata.reboot_required);
+        assert!(document.packages["linux"].reboot_required);
+        assert!(document.packages["linux-firmware"].reboot_required);
+        // Matches a pattern but has no pending update.
+        assert!(!document.packages["systemd"].reboot_required);
+        assert!(!document.packages["vim"].reboot_required);
+        let json = serde_json::to_value(&document).unwrap();
+        assert_eq!(json["metadata"]["reboot_required"], true);
+        assert_eq!(json["packages"]["linux"]["reboot_required"], true);
+    }
+
+    #[test]
+    fn no_reboot_without_kernel_update() {
+        let patterns = CoreConfig::default().reboot_required;
+        let mut document = fixtures::document(vec![
+            (
+                "linux",
+                fixtures::entry("6.11.2-1", PackageSource::Pacman, Some("6.11.2-1")),
+            ),
+            (
+                "vim",
+                fixtures::entry("9.1.0-1", PackageSource::Pacman, Some("9.1.1-1")),
+            ),
+        ]);
+        assert_eq!(document.flag_reboot_required(&patterns), 0);
+        assert!(!document.metadata.reboot_required);
+        let json = serde_json::to_value(&document).unwrap();
+        assert_eq!(json["metadata"]["reboot_required"], false);
+        assert!(json["packages"]["vim"].get("reboot_required").is_none());
+    }
+
+    #[test]
+    fn equal_versions_with_divergent_sizes_warn_sizeskew() {
+        let sized = |local, candidate| {
+            let mut entry = fixtures::entry("1.2-1", PackageSource::Pacman, Some("1.2-1"));
+            entry.installed_size = Some(local);
+            entry.installed_size_selected = Some(candidate);
+            entry
+        };
+        let mut bumped = fixtures::entry("1.2-1", PackageSource::Pacman, Some("1.3-1"));
+        bumped.installed_size = Some(1_000);
+        bumped.installed_size_selected = Some(5_000);
+        let mut document = fixtures::document(vec![
+            ("skewed", sized(10_000, 14_000)),
+            ("close", sized(10_000, 10_500)),
+            ("bumped", bumped),
+        ]);
+
+        let logger = Logger::new(None, false).unwrap();
+        assert_eq!(document.flag_size_skew(0.0, &logger), 0);
+        assert_eq!(document.flag_size_skew(10.0, &logger), 1);
+        let warnings = logger.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("[SIZESKEW] skewed: 1.2-1 installed as 10000 bytes"));
+        assert_eq!(
+            document.packages["skewed"].notes,
+            ["installed size differs from candidate by 28.6%"]
+        );
+        assert!(!document.packages["skewed"].update_available);
+        assert!(document.packages["close"].notes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn source_override_forces_aur_over_a_shadowing_repo_package() {
+        let logger = Logger::new(None, false).unwrap();
+        let repo = VersionInfo::new("3.0-1".into(), None, None);
+        let aur =
+            VersionInfo::new("2.0-1".into(), Some(512), None).with_maintainer(Some("me".into()));
+        let entry = resolve_package(
+            &installed("2.0-1"),
+            Some(&repo),
+            Some(&aur),
+            SourcePolicy::Repo,
+            Some(SourceOverride::Aur),
+            VersionCompare::default(),
+            &logger,
+        )
+        .await
+        .expect("override skips the repo comparison");
+
+        assert_eq!(entry.source, PackageSource::Aur);
+        assert_eq!(entry.candidate_source, Some(PackageSource::Aur));
+        assert_eq!(entry.available_version.as_deref(), Some("2.0-1"));
+        assert_eq!(entry.download_size_selected, Some(512));
+        assert!(!entry.update_available);
+        assert_eq!(entry.notes, vec!["source forced to aur by source_override"]);
+
+        let local = resolve_package(
+            &installed("2.0-1"),
+            Some(&repo),
+            Some(&aur),
+            SourcePolicy::Repo,
+            Some(SourceOverride::Local),
+            VersionCompare::default(),
+            &logger,
+        )
+        .await
+        .unwrap();
+        assert_eq!(local.source, PackageSource::Local);
+        assert_eq!(local.candidate_source, None);
+        assert!(!local.update_available);
+    }
+
+    #[tokio::test]
+    async fn missing_sizes_are_borrowed_from_the_other_source() {
+        let logger = Logger::new(None, false).unwrap();
+        let resolve = |repo: Option<VersionInfo>, aur: VersionInfo, policy| {
+            let logger = &logger;
+            async move {
+                resolve_package(
+                    &installed("2.0-1"),
+                    repo.as_ref(),
+                    Some(&aur.with_maintainer(Some("me".into()))),
+                    policy,
+                    None,
+                    VersionCompare::default(),
+                    logger,
+                )
+                .await
+                .unwrap()
+            }
+        };
+        let repo = || VersionInfo::new("2.0-1".into(), Some(700), None);
+        let aur = || VersionInfo::new("2.0-1".into(), None, Some(3_000));
+
+        // AUR selected: its missing download size comes from the repo candidate.
+        let entry = resolve(Some(repo()), aur(), SourcePolicy::Aur).await;
+        assert_eq!(entry.candidate_source, Some(PackageSource::Aur));
+        assert_eq!(entry.download_size_selected, Some(700));
+        assert!(entry.download_size_estimated);
+        assert_eq!(entry.installed_size_selected, Some(3_000));
+        assert!(entry.size_from_other_source);
+        assert_eq!(
+            entry.notes.last().map(String::as_str),
+            Some("sizes estimated from the repo candidate")
+        );
+
+        // Repo selected: its own download size wins, the installed size is borrowed.
+        let entry = resolve(Some(repo()), aur(), SourcePolicy::Repo).await;
+        assert_eq!(entry.candidate_source, Some(PackageSource::Pacman));
+        assert_eq!(entry.download_size_selected, Some(700));
+        assert!(!entry.download_size_estimated);
+        assert_eq!(entry.installed_size_selected, Some(3_000));
+        assert!(entry.size_from_other_source);
+        assert_eq!(
+            entry.notes.last().map(String::as_str),
+            Some("sizes estimated from the AUR candidate")
+        );
+
+        // Without a second source there is nothing to borrow.
+        let entry = resolve(None, aur(), SourcePolicy::Repo).await;
+        assert_eq!(entry.download_size_selected, None);
+        assert!(!entry.size_from_other_source);
+        assert!(entry.notes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn equal_repo_and_aur_versions_follow_policy_with_note() {
+        let logger = Logger::new(None, false).unwrap();
+        let repo = VersionInfo::new("1.4-2".into(), None, None);
+        let aur = VersionInfo::new("1.4-2".into(), None, None).with_maintainer(Some("me".into()));
+        for (policy, expected, label) in [
+            (SourcePolicy::Repo, PackageSource::Pacman, "repo"),
+            (SourcePolicy::Aur, PackageSource::Aur, "AUR"),
+            (SourcePolicy::Newer, PackageSource::Aur, "AUR"),
+        ] {
+            let entry = resolve_package(
+                &installed("1.4-2"),
+                Some(&repo),
+                Some(&aur),
+                policy,
+                None,
+                VersionCompare::default(),
+                &logger,
+            )
+            .await
+            .expect("equal versions resolve without vercmp");
+            assert_eq!(entry.candidate_source, Some(expected), "{policy}");
+            assert_eq!(
+                entry.notes,
+                vec![format!(
+                    "repo and AUR versions equal; chose {label} per {policy} policy"
+                )]
+            );
+            assert!(!entry.update_available);
+        }
+    }
+
+    /// Resolve a package installed from `from` at 1.0-1 against real repo and
+    /// AUR candidates; returns the chosen source and version.
+    async fn choose(
+        policy: SourcePolicy,
+        from: &str,
+        repo: &str,
+        aur: &str,
+    ) -> (Option<PackageSource>, Option<String>) {
+        let logger = Logger::new(None, false).unwrap();
+        let package = InstalledPackage {
+            repository: Some(from.into()),
+            ..installed("1.0-1")
+        };
+        let repo = VersionInfo::new(repo.into(), None, None);
+        let aur = VersionInfo::new(aur.into(), None, None).with_maintainer(Some("me".into()));
+        let entry = resolve_package(
+            &package,
+            Some(&repo),
+            Some(&aur),
+            policy,
+            None,
+            VersionCompare::default(),
+            &logger,
+        )
+        .await
+        .unwrap();
+        (entry.candidate_source, entry.available_version)
+    }
+
+    #[tokio::test]
+    async fn prefer_repo_yields_only_to_a_strictly_newer_aur() {
+        let policy = SourcePolicy::Repo;
+        assert_eq!(
+            choose(policy, "aur", "1.2-1", "1.3-1").await,
+            (Some(PackageSource::Aur), Some("1.3-1".into()))
+        );
+        assert_eq!(
+            choose(policy, "aur", "2.0-1", "1.9-1").await,
+            (Some(PackageSource::Pacman), Some("2.0-1".into()))
+        );
+        assert_eq!(
+            choose(policy, "aur", "1.4-2", "1.4-2").await.0,
+            Some(PackageSource::Pacman)
+        );
+    }
+
+    #[tokio::test]
+    async fn prefer_aur_keeps_the_aur_candidate() {
+        let policy = SourcePolicy::Aur;
+        assert_eq!(
+            choose(policy, "core", "1.2-1", "1.3-1").await,
+            (Some(PackageSource::Aur), Some("1.3-1".into()))
+        );
+        assert_eq!(
+            choose(policy, "core", "2.0-1", "1.9-1").await,
+            (Some(PackageSource::Aur), Some("1.9-1".into()))
+        );
+        assert_eq!(
+            choose(policy, "core", "1.4-2", "1.4-2").await.0,
+            Some(PackageSource::Aur)
+        );
+    }
+
+    #[tokio::test]
+    async fn prefer_newer_picks_the_higher_version() {
+        let policy = SourcePolicy::Newer;
+        assert_eq!(
+            choose(policy, "core", "1.2-1", "1.3-1").await,
+            (Some(PackageSource::Aur), Some("1.3-1".into()))
+        );
+        assert_eq!(
+            choose(policy, "aur", "2.0-1", "1.9-1").await,
+            (Some(PackageSource::Pacman), Some("2.0-1".into()))
+        );
+        // A tie keeps the installed source.
+        assert_eq!(
+            choose(policy, "aur", "1.4-2", "1.4-2").await.0,
+            Some(PackageSource::Aur)
+        );
+        assert_eq!(
+            choose(policy, "core", "1.4-2", "1.4-2").await.0,
+            Some(PackageSource::Pacman)
+        );
+    }
+
+    #[test]
+    fn preflight_rejects_unwritable_manifest_targets() {
+        let base = std::env::temp_dir().join(format!("synsyu-preflight-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        fs::create_dir_all(&base).unwrap();
+
+        ensure_manifest_writable(&base.join("nested/manifest.json")).unwrap();
+        assert_eq!(fs::read_dir(base.join("nested")).unwrap().count(), 0);
+
+        let blocker = base.join("file");
+        fs::write(&blocker, b"").unwrap();
+        let err = ensure_manifest_writable(&blocker.join("manifest.json")).unwrap_err();
+        assert!(matches!(err, SynsyuError::Filesystem(_)));
+
+        #[cfg(unix)]
+        {
+            let locked = base.join("locked");
+            fs::create_dir(&locked).unwrap();
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o500)).unwrap();
+            // Root bypasses directory permissions; only assert where they apply.
+            if unsafe { libc::geteuid() } != 0 {
+                let err = ensure_manifest_writable(&locked.join("manifest.json")).unwrap_err();
+                assert!(err.to_string().contains("not writable"));
+            }
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o700)).unwrap();
+        }
+        let _ = fs::remove_dir_all(base);
+    }
+
+    #[test]
+    fn directory_output_targets_error_or_get_a_named_file() {
+        let base = std::env::temp_dir().join(format!("synsyu-dir-target-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&base);
+        fs::create_dir_all(&base).unwrap();
+
+        let err = resolve_output_file(&base, "--manifest", "manifest_x.json", false).unwrap_err();
+        assert!(matches!(err, SynsyuError::Filesystem(_)));
+        assert!(err.to_string().contains("is a directory"), "{err}");
+        let err = resolve_output_file(&base, "--log", "core_x.log", false).unwrap_err();
+        assert!(err.to_string().contains("--log "), "{err}");
+
+        assert_eq!(
+            resolve_output_file(&base, "--manifest", "manifest_x.json", true).unwrap(),
+            base.join("manifest_x.json")
+        );
+        assert_eq!(
+            resolve_output_file(&base, "--log", "core_x.log", true).unwrap(),
+            base.join("core_x.log")
+        );
+
+        // File paths, existing or not, pass through untouched in both modes.
+        let file = base.join("manifest.json");
+        for generate in [false, true] {
+            assert_eq!(
+                resolve_output_file(&file, "--manifest", "unused", generate).unwrap(),
+                file
+            );
+        }
+        let _ = fs::remove_dir_all(base);
+    }
+
+    #[tokio::test]
+    async fn case_fold_equates_mixed_case_versions() {
+        let logger = Logger::new(None, false).unwrap();
+        let candidate = VersionInfo::new("1.0rc1-1".into(), None, None);
+        let folded = resolve_package(
+            &installed("1.0RC1-1"),
+            None,
+            Some(&candidate),
+            SourcePolicy::default(),
+            None,
+            VersionCompare {
+                case_fold: true,
+                ..VersionCompare::default()
+            },
+            &logger,
+        )
+        .await
+        .unwrap();
+        assert!(!folded.update_available);
+        assert!(folded
+            .notes
+            .iter()
+            .any(|n| n.contains("case-insensitively")));
+
+        // Strict comparison orders the literal strings apart (`RC` < `rc` bytewise).
+        let strict = resolve_package(
+            &installed("1.0RC1-1"),
+            None,
+            Some(&candidate),
+            SourcePolicy::default(),
+            None,
+            VersionCompare::default(),
+            &logger,
+        )
+        .await
+        .unwrap();
+        assert!(strict.update_available);
+        assert!(strict.notes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn confidence_buckets_follow_resolution_path() {
+        let logger = Logger::new(None, false).unwrap();
+        let from_core =
+            VersionInfo::new("1.0-1".into(), None, None).with_repository(Some("core".into()));
+        let from_testing = VersionInfo::new("1.0-1".into(), None, None)
+            .with_repository(Some("core-testing".into()));
+        let aur = VersionInfo::new("1.0-1".into(), None, None).with_maintainer(Some("me".into()));
+        let mut unknown = named("mystery", "1.0-1");
+        unknown.repository = None;
+        let cases = [
+            // Repo candidate from the installed repository.
+            (
+                named("bash", "1.0-1"),
+                Some(&from_core),
+                None,
+                None,
+                CONFIDENCE_HIGH,
+            ),
+            // AUR candidate for a foreign package.
+            (installed("1.0-1"), None, Some(&aur), None, CONFIDENCE_HIGH),
+            // Operator override.
+            (
+                installed("1.0-1"),
+                Some(&from_core),
+                None,
+                Some(SourceOverride::Repo),
+                CONFIDENCE_HIGH,
+            ),
+            // Candidate found in a different repository than the installed one.
+            (
+                named("bash", "1.0-1"),
+                Some(&from_testing),
+                None,
+                None,
+                CONFIDENCE_MEDIUM,
+            ),
+            // Foreign package now provided by a sync repository.
+            (
+                installed("1.0-1"),
+                Some(&from_core),
+                None,
+                None,
+                CONFIDENCE_MEDIUM,
+            ),
+            // No candidate anywhere.
+            (installed("1.0-1"), None, None, None, CONFIDENCE_LOW),
+            // Installed source unknown.
+            (unknown, Some(&from_core), None, None, CONFIDENCE_LOW),
+        ];
+        for (index, (package, repo, aur, forced, expected)) in cases.into_iter().enumerate() {
+            let entry = resolve_package(
+                &package,
+                repo,
+                aur,
+                SourcePolicy::Repo,
+                forced,
+                VersionCompare::default(),
+                &logger,
+            )
+            .await
+            .unwrap();
+            assert_eq!(entry.confidence, expected, "case {index}");
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_targets_must_stay_inside_the_allowed_base() {
+        use super::fixtures::document;
+        use std::os::unix::fs::symlink;
+
+        let root = std::env::temp_dir().join(format!("synsyu-symlink-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let base = root.join("base");
+        let outside = root.join("outside");
+        fs::create_dir_all(base.join("real")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        let inside_link = base.join("inside.json");
+        let outside_link = base.join("escape.json");
+        symlink(base.join("real/manifest.json"), &inside_link).unwrap();
+        symlink(outside.join("manifest.json"), &outside_link).unwrap();
+        let doc = document(Vec::new());
+
+        let guard = SymlinkGuard {
+            refuse_symlinks: false,
+            allowed_base: Some(base.clone()),
+        };
+        write_manifest(&doc, &inside_link, ManifestFormat::Json, &guard).unwrap();
+        assert!(base.join("real/manifest.json").exists());
+        let err = write_manifest(&doc, &outside_link, ManifestFormat::Json, &guard).unwrap_err();
+        assert!(
+            err.to_string().contains("outside manifest.allowed_base"),
+            "{err}"
+        );
+        assert!(!outside.join("manifest.json").exists());
+
+        // A symlinked parent directory is resolved too.
+        symlink(&outside, base.join("linked-dir")).unwrap();
+        assert!(guard.check(&base.join("linked-dir/manifest.json")).is_err());
+
+        // Missing directories are judged before anything is created.
+        write_manifest(
+            &doc,
+            &base.join("new/sub/m.json"),
+            ManifestFormat::Json,
+            &guard,
+        )
+        .unwrap();
+        let escape = outside.join("made/by/us/manifest.json");
+        assert!(write_manifest(&doc, &escape, ManifestFormat::Json, &guard).is_err());
+        assert!(!outside.join("made").exists());
+        assert!(guard.check(&base.join("new/../../outside/m.json")).is_err());
+
+        let refuse = SymlinkGuard {
+            refuse_symlinks: true,
+            allowed_base: None,
+        };
+        let err = write_manifest(&doc, &inside_link, ManifestFormat::Json, &refuse).unwrap_err();
+        assert!(err.to_string().contains("manifest.no_symlink"), "{err}");
+        write_manifest(
+            &doc,
+            &base.join("plain.json"),
+            ManifestFormat::Json,
+            &refuse,
+        )
+        .unwrap();
+
+        // Without a guard the link is followed as before.
+        write_manifest(
+            &doc,
+            &outside_link,
+            ManifestFormat::Json,
+            &SymlinkGuard::default(),
+        )
+        .unwrap();
+        assert!(outside.join("manifest.json").exists());
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn written_manifests_read_back() {
+        use super::fixtures::{document, entry};
+
+        let path = std::env::temp_dir().join(format!("synsyu-read-{}.json", std::process::id()));
+        let mut doc = document(vec![(
+            "bash",
+            entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1")),
+        )]);
+        doc.metadata.sample = Some(SampleInfo {
+            size: 1,
+            population: 10,
+            percent: None,
+            seed: 7,
+            estimated_updates: 10,
+        });
+        write_manifest(&doc, &path, ManifestFormat::Json, &SymlinkGuard::default()).unwrap();
+        let read = read_manifest(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(read.metadata.generated_at, doc.metadata.generated_at);
+        assert_eq!(read.metadata.sample, doc.metadata.sample);
+        assert_eq!(
+            read.packages["bash"].newer_version.as_deref(),
+            Some("5.2.37-1")
+        );
+        assert_eq!(read.packages_by_source.len(), doc.packages_by_source.len());
+
+        // Metadata fields added by later builds default when absent.
+        let minimal = r#"{"metadata": {"generated_at": "2024-01-01T00:00:00Z"}, "packages": {}}"#;
+        let old: ManifestDocument = serde_json::from_str(minimal).unwrap();
+        assert_eq!(old.metadata.total_packages, 0);
+        assert!(!old.metadata.truncated);
+    }
+
+    #[test]
+    fn manifests_from_the_first_release_still_read() {
+        let path = Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/baseline-manifest.json"
+        ));
+        let old = read_manifest(path).unwrap();
+        assert_eq!(old.metadata.total_packages, 3);
+        assert_eq!(old.metadata.updates_available, 0);
+        let bash = &old.packages["bash"];
+        assert_eq!(bash.installed_version, "5.2.32-1");
+        assert_eq!(bash.version_parts, VersionPartsPair::default());
+        assert!(!bash.update_available && !bash.pkgrel_only);
+        assert!(bash.stable);
+        assert_eq!(old.packages["yay"].source, PackageSource::Aur);
+    }
+
+    #[test]
+    fn yaml_and_toml_manifests_round_trip_by_extension() {
+        use super::fixtures::{document, entry};
+
+        let mut bash = entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1"));
+        bash.download_size_selected = Some(1_800_000);
+        bash.notes.push("repo candidate preferred".into());
+        let doc = document(vec![
+            ("bash", bash),
+            ("custom", entry("0.1-1", PackageSource::Local, None)),
+        ]);
+        let dir = std::env::temp_dir().join(format!("synsyu-formats-{}", std::process::id()));
+        let guard = SymlinkGuard::default();
+        for (file, format) in [
+            ("manifest.yaml", ManifestFormat::Yaml),
+            ("manifest.YML", ManifestFormat::Yaml),
+            ("manifest.toml", ManifestFormat::Toml),
+            ("manifest.json", ManifestFormat::Json),
+            ("manifest", ManifestFormat::Json),
+        ] {
+            let path = dir.join(file);
+            assert_eq!(ManifestFormat::from_path(&path), format, "{file}");
+            write_manifest(&doc, &path, format, &guard).unwrap();
+            let read = read_manifest(&path).unwrap();
+            assert_eq!(read.packages.len(), 2, "{file}");
+            assert_eq!(
+                read.packages["bash"].download_size_selected,
+                Some(1_800_000)
+            );
+            assert_eq!(read.packages["custom"].available_version, None);
+            assert_eq!(read.metadata.updates_available, 1);
+
+            write_manifest_canonical(&doc, &path, format, &guard).unwrap();
+            let read = read_manifest(&path).unwrap();
+            assert_eq!(read.packages["bash"].notes, ["repo candidate preferred"]);
+        }
+        let yaml = fs::read_to_string(dir.join("manifest.yaml")).unwrap();
+        assert!(yaml.contains("newer_version: 5.2.37-1"), "{yaml}");
+        let toml = fs::read_to_string(dir.join("manifest.toml")).unwrap();
+        assert!(toml.contains("[packages.bash]"), "{toml}");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unrepresentable_toml_is_a_serialization_error() {
+        // TOML has no null, so a null inside an array cannot be written.
+        let value = serde_json::json!({ "sizes": [1, null] });
+        let path = std::env::temp_dir().join(format!("synsyu-badtoml-{}.toml", std::process::id()));
+        fs::write(&path, "previous").unwrap();
+        let err = write_manifest(
+            &value,
+            &path,
+            ManifestFormat::Toml,
+            &SymlinkGuard::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, SynsyuError::Serialization(_)), "{err}");
+        assert!(
+            err.to_string().contains("cannot be represented as TOML"),
+            "{err}"
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "previous");
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn canonical_output_is_byte_identical_across_builds() {
+        use super::fixtures::{document, entry};
+
+        let build = |reversed: bool| {
+            let mut entries = vec![
+                (
+                    "zlib",
+                    entry("1.3-1", PackageSource::Pacman, Some("1.3.1-1")),
+                ),
+                (
+                    "paru",
+                    entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1")),
+                ),
+                ("ghost", entry("1.0-1", PackageSource::Unknown, None)),
+            ];
+            if reversed {
+                entries.reverse();
+            }
+            let mut doc = document(entries);
+            doc.packages.get_mut("paru").unwrap().notes = vec!["b".into(), "a".into()];
+            doc
+        };
+        let first = canonical_json(&build(false)).unwrap();
+        let second = canonical_json(&build(true)).unwrap();
+        assert_eq!(first, second);
+
+        // serde_json's own map is sorted, so re-rendering the parse must be a no-op.
+        let parsed: serde_json::Value = serde_json::from_slice(&first).unwrap();
+        assert_eq!(serde_json::to_vec_pretty(&parsed).unwrap(), first);
+        // Struct order puts metadata first; canonical order does not.
+        let plain = serde_json::to_vec_pretty(&build(false)).unwrap();
+        assert_ne!(plain, first);
+        // Arrays keep their order.
+        assert_eq!(
+            parsed["packages"]["paru"]["notes"],
+            serde_json::json!(["b", "a"])
+        );
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+
+    /// Minimal manifest entry for tests.
+    pub fn entry(installed: &str, source: PackageSource, candidate: Option<&str>) -> ManifestEntry {
+        let update_available = matches!(candidate, Some(c) if c != installed);
+        let version_parts = VersionPartsPair::from_versions(installed, candidate);
+        ManifestEntry {
+            installed_version: installed.into(),
+            repository: Some(match source {
+                PackageSource::Pacman => "core".into(),
+                PackageSource::Aur => "aur".into(),
+                PackageSource::Local => "local".into(),
+                PackageSource::Unknown => "unknown".into(),
+            }),
+            source,
+            available_version: candidate.map(str::to_string),
+            pkgrel_only: update_available && version_parts.pkgrel_only(),
+            version_parts,
+            candidate_source: candidate.map(|_| source),
+            newer_version: candidate.filter(|_| update_available).map(str::to_string),
+            update_available,
+            version_delta: candidate.map(|_| {
+                if update_available {
+                    VersionDelta::Upgrade
+                } else {
+                    VersionDelta::Same
+                }
+            }),
+            download_size_selected: None,
+            download_size_estimated: false,
+            installed_size_selected: None,
+            size_from_other_source: false,
+            affected_dependents: None,
+            new_dependencies: Vec::new(),
+            download_url: None,
+            cached: None,
+            cache_mismatch: None,
+            repo_name: None,
+            integrity_ok: None,
+            url_path: None,
+            aur_maintainer: None,
+            aur_orphaned: false,
+            aur_votes: None,
+            aur_popularity: None,
+            candidate_released_at: None,
+            flagged_out_of_date: None,
+            aur_dependencies: None,
+            stable: true,
+            stability_reasons: Vec::new(),
+            reboot_required: false,
+            notes: Vec::new(),
+            confidence: resolution_confidence(source, false, candidate.map(|_| source), None, None),
+            installed_size: None,
+            install_date: None,
+            installed_age_days: None,
+            validated_by: None,
+            package_hash: None,
+        }
+    }
+
+    /// Assemble a manifest document from named entries.
+    pub fn document(entries: Vec<(&str, ManifestEntry)>) -> ManifestDocument {
+        let packages: BTreeMap<String, ManifestEntry> = entries
+            .into_iter()
+            .map(|(name, entry)| (name.to_string(), entry))
+            .collect();
+        let count =
+            |source: PackageSource| packages.values().filter(|e| e.source == source).count();
+        let mut document = ManifestDocument {
+            metadata: ManifestMetadata {
+                generated_at: "2026-10-18T00:00:00Z".into(),
+                generated_by: "synsyu_core".into(),
+                total_packages: packages.len(),
+                pacman_packages: count(PackageSource::Pacman),
+                aur_packages: count(PackageSource::Aur),
+                local_packages: count(PackageSource::Local),
+                unknown_packages: count(PackageSource::Unknown),
+                updates_available: packages.values().filter(|e| e.update_available).count(),
+                pkgrel_only_updates: packages.values().filter(|e| e.pkgrel_only).count(),
+                updates_by_repo: BTreeMap::new(),
+                installed_size_total: 0,
+                installed_size_after_updates: 0,
+                download_size_exact: 0,
+                download_size_estimated: 0,
+                new_packages_count: 0,
+                new_packages_download_size: 0,
+                new_packages_installed_size: 0,
+                timed_out_count: 0,
+                apps_flatpak: None,
+                apps_fwupd: None,
+                application_state: None,
+                pkg_cache: None,
+                clock_skew_secs: None,
+                config_path: None,
+                config_sha256: None,
+                truncated: false,
+                reboot_required: false,
+                limited: false,
+                total_available: packages.len(),
+                pkgrel_only_ignored: false,
+                sample: None,
+                diff_against: None,
+            },
+            packages,
+            packages_by_source: Vec::new(),
+            applications: Applications::default(),
+        };
+        document.refresh_updates_by_repo();
+        document.refresh_size_totals();
+        document
+    }
 }
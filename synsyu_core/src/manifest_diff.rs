@@ -0,0 +1,216 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::manifest_diff
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Compare a previously written manifest with the current
+    run: updates newly on offer, moved update targets, and
+    packages installed or removed in between.
+
+  Security / Safety Notes:
+    Pure comparison of two parsed documents; no I/O performed
+    in this module.
+
+  Dependencies:
+    serde for the structured diff output.
+
+  Operational Scope:
+    Backs Syn-Syu-Core `--diff <OLD_MANIFEST>`; the diff is
+    encoded in the run's manifest format.
+
+  Revision History:
+    2026-10-18 COD  Introduced manifest-to-manifest diffing.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Deterministic, name-sorted output
+    - Each package lands in at most one category
+============================================================*/
+
+use serde::Serialize;
+
+use crate::manifest::{ManifestDocument, ManifestEntry};
+
+/// A package present in only one of the two manifests.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct DiffPackage {
+    pub name: String,
+    pub version: String,
+}
+
+/// An update the older manifest did not offer.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct NewUpdate {
+    pub name: String,
+    pub installed_version: String,
+    pub target_version: String,
+}
+
+/// An update pending in both manifests whose target version moved.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct TargetChange {
+    pub name: String,
+    pub old_target: String,
+    pub new_target: String,
+}
+
+/// What changed between an older manifest and the current one.
+#[derive(Debug, Serialize)]
+pub struct ManifestDiff {
+    /// `generated_at` of the older manifest.
+    pub old_generated_at: String,
+    /// `generated_at` of the current manifest.
+    pub new_generated_at: String,
+    /// Installed in both, but only the current manifest offers an update.
+    pub new_updates: Vec<NewUpdate>,
+    pub target_changed: Vec<TargetChange>,
+    /// Listed in the older manifest only.
+    pub removed: Vec<DiffPackage>,
+    /// Listed in the current manifest only.
+    pub added: Vec<DiffPackage>,
+}
+
+/// Version a pending update would install.
+fn target(entry: &ManifestEntry) -> Option<&str> {
+    entry
+        .newer_version
+        .as_deref()
+        .filter(|_| entry.update_available)
+}
+
+/// Compare `old` with `new`.
+pub fn diff_manifests(old: &ManifestDocument, new: &ManifestDocument) -> ManifestDiff {
+    let mut diff = ManifestDiff {
+        old_generated_at: old.metadata.generated_at.clone(),
+        new_generated_at: new.metadata.generated_at.clone(),
+        new_updates: Vec::new(),
+        target_changed: Vec::new(),
+        removed: Vec::new(),
+        added: Vec::new(),
+    };
+    for (name, entry) in &new.packages {
+        let Some(previous) = old.packages.get(name) else {
+            diff.added.push(DiffPackage {
+                name: name.clone(),
+                version: entry.installed_version.clone(),
+            });
+            continue;
+        };
+        match (target(previous), target(entry)) {
+            (None, Some(current)) => diff.new_updates.push(NewUpdate {
+                name: name.clone(),
+                installed_version: entry.installed_version.clone(),
+                target_version: current.to_string(),
+            }),
+            (Some(before), Some(current)) if before != current => {
+                diff.target_changed.push(TargetChange {
+                    name: name.clone(),
+                    old_target: before.to_string(),
+                    new_target: current.to_string(),
+                })
+            }
+            _ => {}
+        }
+    }
+    diff.removed = old
+        .packages
+        .iter()
+        .filter(|(name, _)| !new.packages.contains_key(*name))
+        .map(|(name, entry)| DiffPackage {
+            name: name.clone(),
+            version: entry.installed_version.clone(),
+        })
+        .collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures::{document, entry};
+    use crate::manifest::PackageSource;
+
+    #[test]
+    fn diff_sorts_packages_into_each_category() {
+        let mut old = document(vec![
+            (
+                "bash",
+                entry("5.2.32-1", PackageSource::Pacman, Some("5.2.32-1")),
+            ),
+            (
+                "curl",
+                entry("8.10-1", PackageSource::Pacman, Some("8.11-1")),
+            ),
+            (
+                "paru",
+                entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1")),
+            ),
+            (
+                "yay",
+                entry("12.4.1-1", PackageSource::Aur, Some("12.4.2-1")),
+            ),
+            ("gone", entry("1.0-1", PackageSource::Local, None)),
+        ]);
+        old.metadata.generated_at = "2026-10-17T00:00:00Z".into();
+        let new = document(vec![
+            (
+                "bash",
+                entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1")),
+            ),
+            (
+                "curl",
+                entry("8.10-1", PackageSource::Pacman, Some("8.12-1")),
+            ),
+            (
+                "paru",
+                entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1")),
+            ),
+            (
+                "yay",
+                entry("12.4.2-1", PackageSource::Aur, Some("12.4.2-1")),
+            ),
+            (
+                "fresh",
+                entry("0.3-1", PackageSource::Pacman, Some("0.4-1")),
+            ),
+        ]);
+
+        let diff = diff_manifests(&old, &new);
+        assert_eq!(diff.old_generated_at, "2026-10-17T00:00:00Z");
+        assert_eq!(diff.new_generated_at, "2026-10-18T00:00:00Z");
+        assert_eq!(
+            diff.new_updates,
+            [NewUpdate {
+                name: "bash".into(),
+                installed_version: "5.2.32-1".into(),
+                target_version: "5.2.37-1".into(),
+            }]
+        );
+        assert_eq!(
+            diff.target_changed,
+            [TargetChange {
+                name: "curl".into(),
+                old_target: "8.11-1".into(),
+                new_target: "8.12-1".into(),
+            }]
+        );
+        assert_eq!(
+            diff.removed,
+            [DiffPackage {
+                name: "gone".into(),
+                version: "1.0-1".into(),
+            }]
+        );
+        // Newly installed packages are only `added`, even with an update pending.
+        assert_eq!(
+            diff.added,
+            [DiffPackage {
+                name: "fresh".into(),
+                version: "0.3-1".into(),
+            }]
+        );
+        let unchanged = diff_manifests(&new, &new);
+        assert!(unchanged.new_updates.is_empty() && unchanged.target_changed.is_empty());
+        assert!(unchanged.removed.is_empty() && unchanged.added.is_empty());
+    }
+}
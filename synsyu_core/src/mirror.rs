@@ -0,0 +1,312 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::mirror
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Resolve the mirror URL a repo update candidate would be
+    downloaded from, for operators debugging slow downloads.
+
+  Security / Safety Notes:
+    Reads pacman.conf, the mirrorlists it includes, and the sync
+    databases only; nothing is fetched.
+
+  Dependencies:
+    std::fs for the configuration; crate::sync_db for package
+    file names.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core after manifest construction to fill
+    `download_url` on repo update candidates.
+
+  Revision History:
+    2026-10-18 COD  Introduced mirror URL resolution.
+    2026-10-18 COD  Per-repo servers from pacman.conf; file names
+                    from the sync databases.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Read-only inspection of system configuration
+    - No URL without a known server and database file name
+============================================================*/
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::logger::Logger;
+use crate::manifest::{ManifestDocument, PackageSource};
+use crate::package_info::VersionInfo;
+use crate::sync_db;
+
+/// First server of every sync repository in the pacman.conf at `path`.
+///
+/// Within a repository section, `Server` lines and the servers of `Include`d
+/// mirrorlists count in file order, as pacman tries them. An unreadable
+/// pacman.conf or mirrorlist contributes no servers.
+pub fn load_repo_servers(path: &Path, logger: &Logger) -> HashMap<String, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => parse_repo_servers(&text, |include| match fs::read_to_string(include) {
+            Ok(mirrorlist) => Some(mirrorlist),
+            Err(err) => {
+                logger.debug(
+                    "MIRROR",
+                    format!("Mirrorlist {} unreadable: {err}", include.display()),
+                );
+                None
+            }
+        }),
+        Err(err) => {
+            logger.debug(
+                "MIRROR",
+                format!("{} unreadable; no download URLs: {err}", path.display()),
+            );
+            HashMap::new()
+        }
+    }
+}
+
+/// Repository servers from pacman.conf `text`, reading includes through `include`.
+pub fn parse_repo_servers(
+    text: &str,
+    include: impl Fn(&Path) -> Option<String>,
+) -> HashMap<String, String> {
+    let mut servers = HashMap::new();
+    let mut repo: Option<&str> = None;
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            repo = Some(section.trim()).filter(|section| *section != "options");
+            continue;
+        }
+        let Some(name) = repo.filter(|name| !servers.contains_key(*name)) else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let server = match key.trim() {
+            "Server" if !value.is_empty() => Some(value.to_string()),
+            "Include" => include(Path::new(value)).and_then(|list| first_server(&list)),
+            _ => None,
+        };
+        if let Some(server) = server {
+            servers.insert(name.to_string(), server);
+        }
+    }
+    servers
+}
+
+/// First uncommented `Server = ...` line; commented-out mirrors are skipped.
+pub fn first_server(mirrorlist: &str) -> Option<String> {
+    mirrorlist.lines().find_map(|line| {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return None;
+        }
+        let (key, value) = line.split_once('=')?;
+        let value = value.trim();
+        (key.trim() == "Server" && !value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// Expand `$repo`/`$arch` in a server template and append the package file name.
+pub fn download_url(server: &str, repo: &str, arch: &str, filename: &str) -> String {
+    let base = server.replace("$repo", repo).replace("$arch", arch);
+    format!("{}/{}", base.trim_end_matches('/'), filename)
+}
+
+/// Fill `download_url` on repo update candidates; returns how many were filled.
+///
+/// The file name is the candidate's `%FILENAME%` in `<db_dir>/<repo>.db`. A
+/// repository without a server, or whose database cannot be read, leaves its
+/// candidates without a URL rather than guessing one.
+pub fn annotate_download_urls(
+    document: &mut ManifestDocument,
+    repo_candidates: &HashMap<String, VersionInfo>,
+    servers: &HashMap<String, String>,
+    db_dir: &Path,
+    arch: &str,
+    logger: &Logger,
+) -> usize {
+    let mut by_repo: BTreeMap<&str, HashMap<&str, &str>> = BTreeMap::new();
+    for (name, entry) in &document.packages {
+        if !entry.update_available || entry.candidate_source != Some(PackageSource::Pacman) {
+            continue;
+        }
+        let Some(info) = repo_candidates.get(name) else {
+            continue;
+        };
+        if let Some(repo) = info.repository.as_deref() {
+            by_repo
+                .entry(repo)
+                .or_default()
+                .insert(name.as_str(), info.version.as_str());
+        }
+    }
+
+    let mut urls = Vec::new();
+    for (repo, wanted) in by_repo {
+        let Some(server) = servers.get(repo) else {
+            logger.debug("MIRROR", format!("No server configured for [{repo}]"));
+            continue;
+        };
+        let db = db_dir.join(format!("{repo}.db"));
+        match sync_db::read_filenames(&db, &wanted) {
+            Ok(filenames) => urls.extend(
+                filenames
+                    .into_iter()
+                    .map(|(name, filename)| (name, download_url(server, repo, arch, &filename))),
+            ),
+            Err(err) => logger.debug(
+                "MIRROR",
+                format!(
+                    "Sync database {} unreadable; no download URLs for [{repo}]: {err}",
+                    db.display()
+                ),
+            ),
+        }
+    }
+    let filled = urls.len();
+    for (name, url) in urls {
+        if let Some(entry) = document.packages.get_mut(&name) {
+            entry.download_url = Some(url);
+        }
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures;
+
+    const MIRRORLIST: &str = "\
+##
+## Arch Linux repository mirrorlist
+##
+
+## Worldwide
+#Server = https://geo.mirror.pkgbuild.com/$repo/os/$arch
+  # Server = https://disabled.example.org/$repo/os/$arch
+
+## Germany
+Server = https://mirror.example.de/archlinux/$repo/os/$arch
+Server = https://second.example.de/$repo/os/$arch
+";
+
+    #[test]
+    fn first_enabled_mirror_is_used() {
+        assert_eq!(
+            first_server(MIRRORLIST).as_deref(),
+            Some("https://mirror.example.de/archlinux/$repo/os/$arch")
+        );
+        assert_eq!(first_server("#Server = https://x/$repo\n"), None);
+    }
+
+    #[test]
+    fn each_repo_uses_its_own_first_server() {
+        let conf = "\
+[options]
+Architecture = auto
+
+[core-testing]
+Include = /missing/mirrorlist
+
+[core]
+Include = /etc/pacman.d/mirrorlist
+Server = https://late.example/$repo/os/$arch
+
+[custom] # local builds
+Server = file:///srv/repo/$arch
+Include = /etc/pacman.d/mirrorlist
+";
+        let servers = parse_repo_servers(conf, |path| {
+            (path == Path::new("/etc/pacman.d/mirrorlist")).then(|| MIRRORLIST.to_string())
+        });
+        assert_eq!(
+            servers.get("core").map(String::as_str),
+            Some("https://mirror.example.de/archlinux/$repo/os/$arch")
+        );
+        assert_eq!(
+            servers.get("custom").map(String::as_str),
+            Some("file:///srv/repo/$arch")
+        );
+        assert!(!servers.contains_key("core-testing"));
+        assert!(!servers.contains_key("options"));
+    }
+
+    #[test]
+    fn repo_candidates_receive_download_urls() {
+        let logger = Logger::new(None, false).unwrap();
+        let mut document = fixtures::document(vec![
+            (
+                "bash",
+                fixtures::entry("5.2.032-1", PackageSource::Pacman, Some("5.2.037-1")),
+            ),
+            (
+                "gnupg",
+                fixtures::entry("1:2.4.1-1", PackageSource::Pacman, Some("1:2.4.1-2")),
+            ),
+            (
+                "zsh",
+                fixtures::entry("5.9-5", PackageSource::Pacman, Some("5.9-5")),
+            ),
+            (
+                "vim",
+                fixtures::entry("9.1.0-1", PackageSource::Pacman, Some("9.1.1-1")),
+            ),
+        ]);
+        let candidate = |version: &str, repo: &str| {
+            VersionInfo::new(version.into(), None, None).with_repository(Some(repo.into()))
+        };
+        let candidates = HashMap::from([
+            ("bash".to_string(), candidate("5.2.037-1", "core")),
+            ("gnupg".to_string(), candidate("1:2.4.1-2", "core")),
+            ("vim".to_string(), candidate("9.1.1-1", "extra")),
+        ]);
+        let dir = std::env::temp_dir().join(format!("synsyu-mirror-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("core.db"),
+            sync_db::fixtures::database(
+                &[
+                    ("bash", "5.2.037-1", "bash-5.2.037-1-x86_64.pkg.tar.zst"),
+                    ("gnupg", "1:2.4.1-2", "gnupg-1:2.4.1-2-x86_64.pkg.tar.xz"),
+                ],
+                true,
+            ),
+        )
+        .unwrap();
+        // [extra] has a server but no readable database.
+        let servers = HashMap::from([
+            ("core".to_string(), first_server(MIRRORLIST).unwrap()),
+            (
+                "extra".to_string(),
+                "https://extra.example/$repo".to_string(),
+            ),
+        ]);
+        let filled = annotate_download_urls(
+            &mut document,
+            &candidates,
+            &servers,
+            &dir,
+            "x86_64",
+            &logger,
+        );
+
+        assert_eq!(filled, 2);
+        assert_eq!(
+            document.packages["bash"].download_url.as_deref(),
+            Some("https://mirror.example.de/archlinux/core/os/x86_64/bash-5.2.037-1-x86_64.pkg.tar.zst")
+        );
+        assert!(document.packages["gnupg"]
+            .download_url
+            .as_deref()
+            .is_some_and(|url| url.ends_with(".pkg.tar.xz")));
+        assert_eq!(document.packages["zsh"].download_url, None);
+        assert_eq!(document.packages["vim"].download_url, None);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
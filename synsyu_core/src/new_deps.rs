@@ -0,0 +1,181 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::new_deps
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Foresee packages a pending repo update would pull in as
+    brand-new dependencies, and total what they would cost.
+
+  Security / Safety Notes:
+    Pure set arithmetic over `pacman -Qi` / `pacman -Si` data;
+    no I/O performed in this module.
+
+  Dependencies:
+    None beyond std.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core after the manifest is built. Only
+    repo candidates carry dependency lists; AUR candidates are
+    left unannotated.
+
+  Revision History:
+    2026-10-18 COD  Introduced new-dependency foresight.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - A dependency satisfied by any installed provider is not new
+    - Deterministic, sorted output
+============================================================*/
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::manifest::{ManifestDocument, PackageSource};
+use crate::package_info::VersionInfo;
+use crate::pacman::InstalledPackage;
+
+/// Fill `new_dependencies` on every pending repo update; returns the union.
+///
+/// A dependency is new when no installed package is named after it or provides it.
+pub fn annotate_new_dependencies(
+    document: &mut ManifestDocument,
+    installed: &[InstalledPackage],
+    repo: &HashMap<String, VersionInfo>,
+) -> BTreeSet<String> {
+    let satisfied: HashSet<&str> = installed
+        .iter()
+        .flat_map(|pkg| std::iter::once(&pkg.name).chain(&pkg.provides))
+        .map(String::as_str)
+        .collect();
+    let mut all = BTreeSet::new();
+    for (name, entry) in document.packages.iter_mut() {
+        entry.new_dependencies.clear();
+        if !entry.update_available || entry.candidate_source != Some(PackageSource::Pacman) {
+            continue;
+        }
+        let Some(candidate) = repo.get(name) else {
+            continue;
+        };
+        let new: BTreeSet<&String> = candidate
+            .depends
+            .iter()
+            .filter(|dep| !satisfied.contains(dep.as_str()))
+            .collect();
+        entry.new_dependencies = new.into_iter().cloned().collect();
+        all.extend(entry.new_dependencies.iter().cloned());
+    }
+    all
+}
+
+/// Record the count and the sizes `pacman -Si` reported for `new` packages.
+///
+/// Names the sync databases do not carry (virtual provisions such as
+/// `libfoo.so`) still count but add no bytes.
+pub fn record_new_packages(
+    document: &mut ManifestDocument,
+    new: &BTreeSet<String>,
+    resolved: &HashMap<String, VersionInfo>,
+) {
+    let metadata = &mut document.metadata;
+    metadata.new_packages_count = new.len();
+    metadata.new_packages_download_size = new
+        .iter()
+        .filter_map(|name| resolved.get(name)?.download_size)
+        .sum();
+    metadata.new_packages_installed_size = new
+        .iter()
+        .filter_map(|name| resolved.get(name)?.installed_size)
+        .sum();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures::{document, entry};
+
+    fn package(name: &str, provides: &[&str]) -> InstalledPackage {
+        InstalledPackage {
+            name: name.into(),
+            version: "1.0-1".into(),
+            repository: Some("core".into()),
+            installed_size: None,
+            install_date: None,
+            validated_by: None,
+            package_hash: None,
+            required_by: Vec::new(),
+            provides: provides.iter().map(|s| s.to_string()).collect(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn candidate(version: &str, depends: &[&str]) -> VersionInfo {
+        VersionInfo::new(version.into(), Some(1_000), Some(4_000))
+            .with_depends(depends.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn candidate_introducing_a_missing_dependency_is_annotated() {
+        let installed = vec![
+            package("glibc", &[]),
+            package("bash", &["sh"]),
+            package("curl", &["libcurl.so"]),
+            package("ffmpeg", &[]),
+        ];
+        let mut doc = document(vec![
+            (
+                "curl",
+                entry("8.10-1", PackageSource::Pacman, Some("8.11-1")),
+            ),
+            (
+                "ffmpeg",
+                entry("2:7.0-1", PackageSource::Pacman, Some("2:7.1-1")),
+            ),
+            ("bash", entry("5.2-1", PackageSource::Pacman, Some("5.2-1"))),
+        ]);
+        let repo = HashMap::from([
+            (
+                "curl".to_string(),
+                candidate("8.11-1", &["glibc", "sh", "libpsl", "libcurl.so"]),
+            ),
+            (
+                "ffmpeg".to_string(),
+                candidate("2:7.1-1", &["glibc", "libpsl", "libvpl", "libfoo.so"]),
+            ),
+            // Not pending, so its missing dependency is ignored.
+            ("bash".to_string(), candidate("5.2-1", &["readline"])),
+        ]);
+
+        let new = annotate_new_dependencies(&mut doc, &installed, &repo);
+        assert_eq!(doc.packages["curl"].new_dependencies, ["libpsl"]);
+        assert_eq!(
+            doc.packages["ffmpeg"].new_dependencies,
+            ["libfoo.so", "libpsl", "libvpl"]
+        );
+        assert!(doc.packages["bash"].new_dependencies.is_empty());
+        assert_eq!(
+            new.iter().map(String::as_str).collect::<Vec<_>>(),
+            ["libfoo.so", "libpsl", "libvpl"]
+        );
+
+        // `libfoo.so` is virtual: counted, but -Si has no sizes for it.
+        let resolved = HashMap::from([
+            ("libpsl".to_string(), candidate("0.21-1", &[])),
+            ("libvpl".to_string(), candidate("2.13-1", &[])),
+        ]);
+        record_new_packages(&mut doc, &new, &resolved);
+        assert_eq!(doc.metadata.new_packages_count, 3);
+        assert_eq!(doc.metadata.new_packages_download_size, 2_000);
+        assert_eq!(doc.metadata.new_packages_installed_size, 8_000);
+    }
+
+    #[test]
+    fn aur_candidates_are_not_annotated() {
+        let installed = vec![package("glibc", &[])];
+        let mut doc = document(vec![(
+            "paru",
+            entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1")),
+        )]);
+        let repo = HashMap::from([("paru".to_string(), candidate("2.0.4-1", &["libgit2"]))]);
+        assert!(annotate_new_dependencies(&mut doc, &installed, &repo).is_empty());
+        assert!(doc.packages["paru"].new_dependencies.is_empty());
+    }
+}
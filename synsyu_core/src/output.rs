@@ -0,0 +1,115 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::output
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Route stdout writes through fallible helpers so a consumer
+    closing the pipe early (`| head`) ends the run cleanly.
+
+  Security / Safety Notes:
+    No state beyond the process stdout handle.
+
+  Dependencies:
+    std::io only.
+
+  Operational Scope:
+    Used by every command that prints reports or listings on
+    stdout.
+
+  Revision History:
+    2026-10-18 COD  Introduced broken-pipe aware stdout helpers.
+    2026-10-18 COD  Exported the macros for the binary crate.
+    2026-10-18 COD  Added tolerate_broken_pipe for mid-run reports.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Write failures surface as errors, never panics
+    - A closed stdout consumer is a normal termination
+============================================================*/
+
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::error::{Result, SynsyuError};
+
+/// `println!` replacement that propagates write errors with `?`.
+#[macro_export]
+macro_rules! outln {
+    () => {
+        $crate::output::write_out(format_args!("\n"))?
+    };
+    ($($arg:tt)*) => {
+        $crate::output::write_out(format_args!("{}\n", format_args!($($arg)*)))?
+    };
+}
+
+/// `print!` replacement that propagates write errors with `?`.
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::output::write_out(format_args!($($arg)*))?
+    };
+}
+
+pub use crate::{out, outln};
+
+/// Write formatted text to stdout.
+pub fn write_out(args: fmt::Arguments<'_>) -> Result<()> {
+    write_to(&mut io::stdout().lock(), args)
+}
+
+/// Write formatted text to `writer`, surfacing I/O failures as errors.
+pub fn write_to<W: Write>(writer: &mut W, args: fmt::Arguments<'_>) -> Result<()> {
+    writer.write_fmt(args).map_err(SynsyuError::Io)
+}
+
+/// True when the error means stdout's reader went away.
+pub fn is_broken_pipe(err: &SynsyuError) -> bool {
+    matches!(err, SynsyuError::Io(io) if io.kind() == io::ErrorKind::BrokenPipe)
+}
+
+/// Swallow a broken pipe so a run can finish its remaining work.
+pub fn tolerate_broken_pipe(result: Result<()>) -> Result<()> {
+    match result {
+        Err(err) if is_broken_pipe(&err) => Ok(()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::{Command, Stdio};
+
+    #[test]
+    fn early_closing_reader_is_a_broken_pipe() {
+        let mut head = Command::new("head")
+            .args(["-n", "1"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .expect("spawn head");
+        let mut stdin = head.stdin.take().unwrap();
+
+        let mut result = Ok(());
+        for line in 0..1_000_000 {
+            result = write_to(&mut stdin, format_args!("package-{line} 1.0-1\n"));
+            if result.is_err() {
+                break;
+            }
+        }
+        drop(stdin);
+        head.wait().unwrap();
+
+        let err = result.expect_err("reader closed after the first line");
+        assert!(is_broken_pipe(&err), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn other_errors_are_not_broken_pipes() {
+        let err = SynsyuError::Io(io::Error::new(io::ErrorKind::PermissionDenied, "denied"));
+        assert!(!is_broken_pipe(&err));
+        assert!(!is_broken_pipe(&SynsyuError::Runtime("x".into())));
+        assert!(tolerate_broken_pipe(Err(err)).is_err());
+    }
+}
@@ -19,20 +19,60 @@
 
   Revision History:
     2024-11-04 COD  Introduced shared VersionInfo type.
+    2026-10-18 COD  Flagged inferred (estimated) download sizes.
+    2026-10-18 COD  Carried AUR votes and popularity.
+    2026-10-18 COD  Carried repo candidate dependency names.
+    2026-10-18 COD  Carried candidate release dates and AUR out-of-date flags.
+    2026-10-18 COD  Made VersionInfo deserializable for the AUR lookup cache.
+    2026-10-18 COD  Carried AUR dependency arrays (AurDependencies).
+    2026-10-18 COD  Dropped the guessed package file name.
   ------------------------------------------------------------
   SSE Principles Observed:
     - Clear data contracts between modules
     - Serializable structures for manifest output
 ============================================================*/
 
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Dependency arrays of an AUR package, as the RPC reports them (version constraints kept).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AurDependencies {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub make_depends: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub check_depends: Vec<String>,
+}
 
 /// Captures version metadata for a package source (repo or AUR).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VersionInfo {
     pub version: String,
     pub download_size: Option<u64>,
     pub installed_size: Option<u64>,
+    /// AUR snapshot path relative to the AUR web root (AUR results only).
+    pub url_path: Option<String>,
+    /// Sync repository carrying the candidate (repo results only).
+    pub repository: Option<String>,
+    /// AUR maintainer; `None` on an AUR result means the package is orphaned.
+    pub maintainer: Option<String>,
+    /// AUR `NumVotes` (AUR results only).
+    pub aur_votes: Option<u64>,
+    /// AUR `Popularity`, a decaying vote score (AUR results only).
+    pub aur_popularity: Option<f64>,
+    /// `download_size` was inferred (e.g. from the AUR snapshot tarball) rather than reported.
+    pub size_estimated: bool,
+    /// Dependency names of the candidate, version constraints stripped (repo results only).
+    pub depends: Vec<String>,
+    /// When the candidate was built (repo `Build Date`) or last modified (AUR `LastModified`).
+    pub released_at: Option<DateTime<Utc>>,
+    /// When AUR users flagged the package out of date (AUR results only).
+    pub flagged_out_of_date: Option<DateTime<Utc>>,
+    /// `Depends`/`MakeDepends`/`CheckDepends` (AUR results only).
+    #[serde(default)]
+    pub aur_dependencies: Option<AurDependencies>,
 }
 
 impl VersionInfo {
@@ -41,6 +81,199 @@ impl VersionInfo {
             version,
             download_size,
             installed_size,
+            url_path: None,
+            repository: None,
+            maintainer: None,
+            aur_votes: None,
+            aur_popularity: None,
+            size_estimated: false,
+            depends: Vec::new(),
+            released_at: None,
+            flagged_out_of_date: None,
+            aur_dependencies: None,
+        }
+    }
+
+    /// Attach the sync repository carrying this candidate.
+    pub fn with_repository(mut self, repository: Option<String>) -> Self {
+        self.repository = repository;
+        self
+    }
+
+    /// Attach the dependency names of this candidate.
+    pub fn with_depends(mut self, depends: Vec<String>) -> Self {
+        self.depends = depends;
+        self
+    }
+
+    /// Attach the build or last-modified time of this candidate.
+    pub fn with_released_at(mut self, released_at: Option<DateTime<Utc>>) -> Self {
+        self.released_at = released_at;
+        self
+    }
+
+    /// Attach the AUR out-of-date flag time for this candidate.
+    #[cfg_attr(not(feature = "aur"), allow(dead_code))]
+    pub fn with_out_of_date(mut self, flagged: Option<DateTime<Utc>>) -> Self {
+        self.flagged_out_of_date = flagged;
+        self
+    }
+
+    /// Attach the AUR dependency arrays for this candidate.
+    #[cfg_attr(not(feature = "aur"), allow(dead_code))]
+    pub fn with_aur_dependencies(mut self, dependencies: AurDependencies) -> Self {
+        self.aur_dependencies = Some(dependencies);
+        self
+    }
+
+    /// Attach the AUR maintainer for this candidate.
+    #[cfg_attr(not(feature = "aur"), allow(dead_code))]
+    pub fn with_maintainer(mut self, maintainer: Option<String>) -> Self {
+        self.maintainer = maintainer;
+        self
+    }
+
+    /// Attach the AUR vote count and popularity for this candidate.
+    #[cfg_attr(not(feature = "aur"), allow(dead_code))]
+    pub fn with_popularity(mut self, votes: Option<u64>, popularity: Option<f64>) -> Self {
+        self.aur_votes = votes;
+        self.aur_popularity = popularity;
+        self
+    }
+
+    /// Mark `download_size` as inferred rather than reported by the source.
+    #[cfg_attr(not(feature = "aur"), allow(dead_code))]
+    pub fn with_size_estimated(mut self, estimated: bool) -> Self {
+        self.size_estimated = estimated;
+        self
+    }
+
+    /// Attach the AUR snapshot path for this candidate.
+    #[cfg_attr(not(feature = "aur"), allow(dead_code))]
+    pub fn with_url_path(mut self, url_path: Option<String>) -> Self {
+        self.url_path = url_path;
+        self
+    }
+}
+
+/// Reasons a version string is unusable for comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionAnomaly {
+    Empty,
+    ControlCharacter,
+}
+
+impl std::fmt::Display for VersionAnomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionAnomaly::Empty => write!(f, "empty version"),
+            VersionAnomaly::ControlCharacter => write!(f, "version contains control characters"),
+        }
+    }
+}
+
+/// Reject version strings that are empty or carry control characters.
+pub fn check_version(version: &str) -> std::result::Result<(), VersionAnomaly> {
+    if version.trim().is_empty() {
+        Err(VersionAnomaly::Empty)
+    } else if version.chars().any(char::is_control) {
+        Err(VersionAnomaly::ControlCharacter)
+    } else {
+        Ok(())
+    }
+}
+
+/// Canonical (ASCII lowercase) form of a version used when `version_case_fold` is enabled.
+pub fn fold_version_case(version: &str) -> String {
+    version.to_ascii_lowercase()
+}
+
+/// Components of a pacman version string (`[epoch:]pkgver-pkgrel`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VersionParts {
+    pub epoch: u32,
+    pub pkgver: String,
+    pub pkgrel: String,
+}
+
+impl VersionParts {
+    /// Parse a full version; returns `None` unless every component is well formed.
+    pub fn parse(version: &str) -> Option<Self> {
+        let (epoch, rest) = split_epoch(version);
+        let epoch = match epoch {
+            Some(raw) => raw.parse().ok()?,
+            None => 0,
+        };
+        let (pkgver, pkgrel) = rest.rsplit_once('-')?;
+        let pkgver_ok = !pkgver.is_empty()
+            && !pkgver
+                .chars()
+                .any(|c| c == ':' || c == '-' || c.is_whitespace() || c.is_control());
+        let pkgrel_ok = !pkgrel.is_empty()
+            && pkgrel.chars().all(|c| c.is_ascii_digit() || c == '.')
+            && !pkgrel.starts_with('.')
+            && !pkgrel.ends_with('.');
+        if !pkgver_ok || !pkgrel_ok {
+            return None;
+        }
+        Some(Self {
+            epoch,
+            pkgver: pkgver.to_string(),
+            pkgrel: pkgrel.to_string(),
+        })
+    }
+}
+
+/// Split a leading `epoch:` off a version string, as vercmp does.
+pub fn split_epoch(version: &str) -> (Option<&str>, &str) {
+    match version.split_once(':') {
+        Some((epoch, rest)) if !epoch.is_empty() && epoch.chars().all(|c| c.is_ascii_digit()) => {
+            (Some(epoch), rest)
+        }
+        _ => (None, version),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(epoch: u32, pkgver: &str, pkgrel: &str) -> VersionParts {
+        VersionParts {
+            epoch,
+            pkgver: pkgver.into(),
+            pkgrel: pkgrel.into(),
+        }
+    }
+
+    #[test]
+    fn versions_with_and_without_epoch_parse() {
+        assert_eq!(
+            VersionParts::parse("5.2.37-1"),
+            Some(parts(0, "5.2.37", "1"))
+        );
+        assert_eq!(
+            VersionParts::parse("1:1.2.3-2"),
+            Some(parts(1, "1.2.3", "2"))
+        );
+        assert_eq!(
+            VersionParts::parse("2:r123.gabcdef-1.1"),
+            Some(parts(2, "r123.gabcdef", "1.1"))
+        );
+    }
+
+    #[test]
+    fn malformed_versions_have_no_parts() {
+        for version in [
+            "1.2.3",
+            "1.2.3-",
+            "-1",
+            "x:1.0-1",
+            "1.0-a",
+            "1.0 beta-1",
+            "",
+        ] {
+            assert_eq!(VersionParts::parse(version), None, "{version:?}");
         }
     }
 }
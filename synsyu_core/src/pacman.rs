@@ -8,7 +8,7 @@
     packages, query repository metadata, and compare versions.
 
   Security / Safety Notes:
-    Executes pacman/vercmp binaries with user privileges only;
+    Executes pacman (and, on request, vercmp) with user privileges only;
     no privilege escalation is attempted.
 
   Dependencies:
@@ -20,6 +20,24 @@
 
   Revision History:
     2024-11-04 COD  Crafted pacman integration layer.
+    2026-10-18 COD  Optional raw stdout capture for -Qi/-Si.
+    2026-10-18 COD  Streamed -Qi parsing to bound memory.
+    2026-10-18 COD  Resolved multi-repo -Si results by pacman.conf order.
+    2026-10-18 COD  Rejected -Si output cut short by a signal or closed pipe.
+    2026-10-18 COD  Collapsed duplicate -Qi entries (DUPE).
+    2026-10-18 COD  Preflight check for an active transaction (db.lck).
+    2026-10-18 COD  Tolerated noise around the vercmp verdict.
+    2026-10-18 COD  Forced LC_ALL=C and parsed sizes strictly.
+    2026-10-18 COD  Compared versions natively by default (--vercmp-external).
+    2026-10-18 COD  Parsed installed Provides and candidate Depends On.
+    2026-10-18 COD  Parsed candidate Build Date.
+    2026-10-18 COD  Exposed foreign package detection (`pacman -Qm`).
+    2026-10-18 COD  Parsed installed Groups.
+    2026-10-18 COD  Ran -Si chunks concurrently (core.si_max_parallel).
+    2026-10-18 COD  Retried -Qi/-Si on transient failures ([pacman]).
+    2026-10-18 COD  Added pacman_size_tolerance for rounded sizes.
+    2026-10-18 COD  Only the final vercmp token counts as the verdict.
+    2026-10-18 COD  Stopped guessing package file names from -Si.
   ------------------------------------------------------------
   SSE Principles Observed:
     - Deterministic command invocation with explicit checks
@@ -28,17 +46,29 @@
 ============================================================*/
 
 use std::collections::{HashMap, HashSet};
-use std::io;
-use std::process::Stdio;
+use std::fs;
+use std::future::Future;
+use std::io::{self, Write};
+use std::os::unix::process::ExitStatusExt;
+use std::path::Path;
+use std::process::{ExitStatus, Stdio};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
-use reqwest::Client;
-use serde::Deserialize;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, BufReader};
 use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::sleep;
 
+use crate::audit::parse_install_date;
+use crate::config::{DuplicatePolicy, PacmanConfig};
 use crate::error::{Result, SynsyuError};
+use crate::logger::Logger;
 use crate::package_info::VersionInfo;
-use urlencoding::encode;
+use crate::raw_dump::{capture, RawDump};
+use crate::vercmp::vercmp_native;
 
 /// Represents a package currently installed on the system.
 #[derive(Debug, Clone)]
@@ -50,154 +80,475 @@ pub struct InstalledPackage {
     pub install_date: Option<String>,
     pub validated_by: Option<String>,
     pub package_hash: Option<String>,
+    /// Installed packages that depend on this one (`Required By`).
+    pub required_by: Vec<String>,
+    /// Names this package provides (`Provides`), version constraints stripped.
+    pub provides: Vec<String>,
+    /// Package groups this package belongs to (`Groups`).
+    pub groups: Vec<String>,
 }
 
-/// Enumerate all installed packages via `pacman -Qi`.
-pub async fn enumerate_installed_packages() -> Result<Vec<InstalledPackage>> {
-    let foreign = detect_foreign_packages().await.unwrap_or_default();
-    let output = Command::new("pacman")
+/// Enumerate all installed packages via `pacman -Qi`; those in `foreign` are marked `local`.
+///
+/// stdout is parsed line-by-line as it arrives, so memory stays bounded by the
+/// package list rather than the full `-Qi` text. Size fields that are not in
+/// pacman's C-locale format are logged to `logger` at debug level and left unset.
+///
+/// Transient failures (see [`RetryPolicy`]) are retried.
+pub async fn enumerate_installed_packages(
+    foreign: &HashSet<String>,
+    retry: RetryPolicy,
+    raw_dump: Option<&RawDump>,
+    logger: Option<&Logger>,
+) -> Result<Vec<InstalledPackage>> {
+    with_retry(retry, "pacman -Qi", logger, || {
+        query_installed(foreign, raw_dump, logger)
+    })
+    .await
+}
+
+/// One `pacman -Qi` run.
+async fn query_installed(
+    foreign: &HashSet<String>,
+    raw_dump: Option<&RawDump>,
+    logger: Option<&Logger>,
+) -> Result<Vec<InstalledPackage>> {
+    let mut child = Command::new("pacman")
         .arg("-Qi")
+        .env("LC_ALL", "C")
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .output()
-        .await
+        .spawn()
         .map_err(|err| map_spawn_error(err, "pacman"))?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| SynsyuError::Runtime("pacman -Qi stdout unavailable".into()))?;
+    let mut stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| SynsyuError::Runtime("pacman -Qi stderr unavailable".into()))?;
 
-    if !output.status.success() {
+    let mut dump = raw_dump
+        .map(|dump| dump.create_file("pacman-Qi", "txt"))
+        .transpose()?
+        .map(std::io::BufWriter::new);
+    // Drain stderr concurrently so a chatty pacman cannot stall on a full pipe.
+    let mut stderr_bytes = Vec::new();
+    let (parsed, _) = tokio::join!(
+        parse_installed_stream(
+            BufReader::new(stdout),
+            foreign,
+            dump.as_mut().map(|w| w as &mut dyn Write),
+            logger,
+        ),
+        stderr.read_to_end(&mut stderr_bytes),
+    );
+    let status = child
+        .wait()
+        .await
+        .map_err(|err| SynsyuError::Runtime(format!("Failed to wait for pacman -Qi: {err}")))?;
+    if !status.success() {
         return Err(SynsyuError::CommandFailure {
             command: "pacman -Qi".into(),
-            status: output.status.code().unwrap_or(-1),
-            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            status: status.code().unwrap_or(-1),
+            stderr: String::from_utf8_lossy(&stderr_bytes).trim().to_string(),
         });
     }
+    let mut packages = parsed?;
+    if let Some(mut dump) = dump {
+        dump.flush().map_err(dump_error)?;
+    }
 
-    let stdout = String::from_utf8(output.stdout).map_err(|err| {
-        SynsyuError::Serialization(format!("pacman -Qi emitted invalid UTF-8: {err}"))
-    })?;
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(packages)
+}
 
-    let mut packages = Vec::new();
-    for block in stdout.split("\n\n") {
-        let mut name: Option<String> = None;
-        let mut version: Option<String> = None;
-        let mut repository: Option<String> = None;
-        let mut installed_size: Option<u64> = None;
-        let mut install_date: Option<String> = None;
-        let mut validated_by: Option<String> = None;
-        let mut package_hash: Option<String> = None;
+/// stderr fragments of pacman failures that clear up on their own, such as
+/// another pacman holding the database lock.
+const TRANSIENT_FAILURES: &[&str] = &["unable to lock database", "failed to init transaction"];
 
-        for line in block.lines() {
-            if let Some((raw_key, raw_value)) = line.split_once(':') {
-                let key = raw_key.trim();
-                let value = raw_value.trim();
-                match key {
-                    "Name" => name = Some(value.to_string()),
-                    "Version" => version = Some(value.to_string()),
-                    "Repository" => repository = Some(value.to_string()),
-                    "Install Date" => install_date = Some(value.to_string()),
-                    "Installed Size" => installed_size = parse_pacman_size(value),
-                    "Validated By" => validated_by = Some(value.to_string()),
-                    "SHA-256 Sum" => package_hash = Some(value.to_string()),
-                    _ => {}
+/// How a pacman call is retried after a transient failure (`[pacman]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total tries, including the first.
+    pub attempts: u32,
+    /// Delay before the first retry; doubled for each further one.
+    pub delay: Duration,
+}
+
+impl RetryPolicy {
+    /// A single try.
+    pub const NONE: RetryPolicy = RetryPolicy {
+        attempts: 1,
+        delay: Duration::ZERO,
+    };
+
+    pub fn from_config(config: &PacmanConfig) -> Self {
+        Self {
+            attempts: config.retry_attempts.max(1),
+            delay: Duration::from_millis(config.retry_delay_ms),
+        }
+    }
+}
+
+/// True for a pacman failure whose stderr matches a [`TRANSIENT_FAILURES`] signature.
+///
+/// A missing command, a signal, or unparseable output is never transient.
+fn is_transient(err: &SynsyuError) -> bool {
+    match err {
+        SynsyuError::CommandFailure { status, stderr, .. } => {
+            *status < 128 && TRANSIENT_FAILURES.iter().any(|sig| stderr.contains(sig))
+        }
+        _ => false,
+    }
+}
+
+/// Run `attempt` until it succeeds, fails for good, or `policy` runs out of tries.
+async fn with_retry<T, F, Fut>(
+    policy: RetryPolicy,
+    command: &str,
+    logger: Option<&Logger>,
+    mut attempt: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut delay = policy.delay;
+    let mut tried = 1;
+    loop {
+        match attempt().await {
+            Err(err) if tried < policy.attempts && is_transient(&err) => {
+                if let Some(logger) = logger {
+                    logger.info(
+                        "PACMANRETRY",
+                        format!(
+                            "{command} failed transiently (try {tried} of {}); retrying in {}ms: {err}",
+                            policy.attempts,
+                            delay.as_millis()
+                        ),
+                    );
                 }
+                sleep(delay).await;
+                delay = delay.saturating_mul(2);
+                tried += 1;
             }
+            outcome => return outcome,
         }
+    }
+}
 
-        if let (Some(mut name), Some(version)) = (name, version) {
-            if repository.is_none() {
-                if foreign.contains(&name) {
-                    repository = Some("local".to_string());
-                } else {
-                    repository = Some("pacman".to_string());
-                }
+/// Report whether pacman's database lock exists, i.e. a transaction is running.
+///
+/// Only stats `lock`. A held lock is a `TXNACTIVE` warning, or an
+/// `ActiveTransaction` error when `require_none` is set.
+pub fn check_active_transaction(lock: &Path, require_none: bool, logger: &Logger) -> Result<bool> {
+    if fs::symlink_metadata(lock).is_err() {
+        return Ok(false);
+    }
+    if require_none {
+        return Err(SynsyuError::ActiveTransaction(lock.display().to_string()));
+    }
+    logger.warn(
+        "TXNACTIVE",
+        format!(
+            "pacman transaction in progress ({} exists); results may be inconsistent",
+            lock.display()
+        ),
+    );
+    Ok(true)
+}
+
+/// Collapse names `pacman -Qi` listed more than once, logging each duplicate as `DUPE`.
+///
+/// Under `keep_highest` the entry with the higher version survives; if the versions
+/// cannot be compared, the first entry is kept. Order of first appearance is preserved.
+pub async fn dedupe_installed(
+    packages: Vec<InstalledPackage>,
+    policy: DuplicatePolicy,
+    backend: VercmpBackend,
+    logger: &Logger,
+) -> Vec<InstalledPackage> {
+    let mut kept: Vec<InstalledPackage> = Vec::with_capacity(packages.len());
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for package in packages {
+        let Some(&slot) = index.get(&package.name) else {
+            index.insert(package.name.clone(), kept.len());
+            kept.push(package);
+            continue;
+        };
+        let existing = &kept[slot];
+        let replace = match policy {
+            DuplicatePolicy::KeepFirst => Ok(false),
+            DuplicatePolicy::KeepHighest => {
+                compare_versions(&existing.version, &package.version, backend)
+                    .await
+                    .map(|ordering| ordering == std::cmp::Ordering::Less)
             }
-            packages.push(InstalledPackage {
-                name: std::mem::take(&mut name),
-                version,
-                repository,
-                installed_size,
-                install_date,
-                validated_by,
-                package_hash,
-            });
+        };
+        let (winner, reason) = match &replace {
+            Ok(true) => (&package.version, policy.to_string()),
+            Ok(false) => (&existing.version, policy.to_string()),
+            Err(err) => (&existing.version, format!("versions not comparable: {err}")),
+        };
+        logger.warn(
+            "DUPE",
+            format!(
+                "{}: pacman -Qi lists it more than once ({} and {}); keeping {winner} ({reason})",
+                package.name, existing.version, package.version
+            ),
+        );
+        if matches!(replace, Ok(true)) {
+            kept[slot] = package;
         }
     }
+    kept
+}
 
-    packages.sort_by(|a, b| a.name.cmp(&b.name));
+/// Parse `pacman -Qi` output one line at a time, mirroring each line into `dump`.
+async fn parse_installed_stream<R: AsyncBufRead + Unpin>(
+    mut reader: R,
+    foreign: &HashSet<String>,
+    mut dump: Option<&mut dyn Write>,
+    logger: Option<&Logger>,
+) -> Result<Vec<InstalledPackage>> {
+    let mut packages = Vec::new();
+    let mut block = InstalledBlock::default();
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line).await.map_err(|err| {
+            SynsyuError::Runtime(format!("Failed to read pacman -Qi output: {err}"))
+        })?;
+        if read == 0 {
+            break;
+        }
+        if let Some(dump) = dump.as_mut() {
+            dump.write_all(&line).map_err(dump_error)?;
+        }
+        let text = std::str::from_utf8(&line).map_err(|err| {
+            SynsyuError::Serialization(format!("pacman -Qi emitted invalid UTF-8: {err}"))
+        })?;
+        let text = text.trim_end_matches(['\n', '\r']);
+        if text.is_empty() {
+            packages.extend(std::mem::take(&mut block).finish(foreign));
+        } else {
+            block.feed(text, logger);
+        }
+    }
+    packages.extend(block.finish(foreign));
     Ok(packages)
 }
 
+fn dump_error(err: io::Error) -> SynsyuError {
+    SynsyuError::Filesystem(format!("Failed to write raw dump: {err}"))
+}
+
+/// Fields collected from one `pacman -Qi` block.
+#[derive(Default)]
+struct InstalledBlock {
+    name: Option<String>,
+    version: Option<String>,
+    repository: Option<String>,
+    installed_size: Option<u64>,
+    install_date: Option<String>,
+    validated_by: Option<String>,
+    package_hash: Option<String>,
+    required_by: Vec<String>,
+    provides: Vec<String>,
+    groups: Vec<String>,
+    /// List field whose wrapped continuation lines are still arriving.
+    open_list: Option<InstalledList>,
+}
+
+#[derive(Clone, Copy)]
+enum InstalledList {
+    RequiredBy,
+    Provides,
+    Groups,
+}
+
+impl InstalledBlock {
+    fn feed(&mut self, line: &str, logger: Option<&Logger>) {
+        // Long lists wrap onto indented continuation lines without a key.
+        if let Some(list) = self
+            .open_list
+            .filter(|_| line.starts_with(char::is_whitespace))
+        {
+            let names = parse_name_list(line.trim());
+            match list {
+                InstalledList::RequiredBy => self.required_by.extend(names),
+                InstalledList::Provides => self.provides.extend(names),
+                InstalledList::Groups => self.groups.extend(names),
+            }
+            return;
+        }
+        self.open_list = None;
+        if let Some((raw_key, raw_value)) = line.split_once(':') {
+            let key = raw_key.trim();
+            let value = raw_value.trim();
+            match key {
+                "Required By" => {
+                    self.open_list = Some(InstalledList::RequiredBy);
+                    self.required_by = parse_name_list(value);
+                }
+                "Provides" => {
+                    self.open_list = Some(InstalledList::Provides);
+                    self.provides = parse_name_list(value);
+                }
+                "Groups" => {
+                    self.open_list = Some(InstalledList::Groups);
+                    self.groups = parse_name_list(value);
+                }
+                "Name" => self.name = Some(value.to_string()),
+                "Version" => self.version = Some(value.to_string()),
+                "Repository" => self.repository = Some(value.to_string()),
+                "Install Date" => self.install_date = Some(value.to_string()),
+                "Installed Size" => self.installed_size = parse_size_field(key, value, logger),
+                "Validated By" => self.validated_by = Some(value.to_string()),
+                "SHA-256 Sum" => self.package_hash = Some(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    fn finish(self, foreign: &HashSet<String>) -> Option<InstalledPackage> {
+        let (Some(name), Some(version)) = (self.name, self.version) else {
+            return None;
+        };
+        let repository = self.repository.or_else(|| {
+            if foreign.contains(&name) {
+                Some("local".to_string())
+            } else {
+                Some("pacman".to_string())
+            }
+        });
+        Some(InstalledPackage {
+            name,
+            version,
+            repository,
+            installed_size: self.installed_size,
+            install_date: self.install_date,
+            validated_by: self.validated_by,
+            package_hash: self.package_hash,
+            required_by: self.required_by,
+            provides: self.provides,
+            groups: self.groups,
+        })
+    }
+}
+
+/// Split a pacman name list, where `None` means empty.
+///
+/// Version constraints are dropped: `python>=3.12` and `libfoo.so=1-64` yield
+/// `python` and `libfoo.so`.
+fn parse_name_list(value: &str) -> Vec<String> {
+    if value == "None" {
+        Vec::new()
+    } else {
+        value
+            .split_whitespace()
+            .map(|item| {
+                item.split(['<', '>', '='])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+}
+
 /// Retrieve remote repository versions for the specified packages via `pacman -Si`.
-pub async fn query_repo_versions(packages: &[String]) -> Result<HashMap<String, VersionInfo>> {
+///
+/// Chunks of `chunk_size` run concurrently, at most `max_parallel` at a time.
+/// A transient failure reruns the lookup as a whole.
+pub async fn query_repo_versions(
+    packages: &[String],
+    chunk_size: usize,
+    max_parallel: usize,
+    retry: RetryPolicy,
+    raw_dump: Option<&RawDump>,
+    logger: Option<&Logger>,
+) -> Result<HashMap<String, VersionInfo>> {
     let mut versions = HashMap::new();
     if packages.is_empty() {
         return Ok(versions);
     }
 
-    const CHUNK_SIZE: usize = 64;
-    for chunk in packages.chunks(CHUNK_SIZE) {
-        let output = Command::new("pacman")
-            .arg("-Si")
-            .args(chunk)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await
-            .map_err(|err| map_spawn_error(err, "pacman"))?;
-
-        if !output.status.success() {
-            return Err(SynsyuError::CommandFailure {
-                command: format!("pacman -Si {}", chunk.join(" ")),
-                status: output.status.code().unwrap_or(-1),
-                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
-            });
-        }
-
-        let stdout = String::from_utf8(output.stdout).map_err(|err| {
+    let outputs = with_retry(retry, "pacman -Si", logger, || {
+        run_sync_info("pacman", packages, chunk_size, max_parallel)
+    })
+    .await?;
+    let priority = sync_repo_order(Path::new(PACMAN_CONF));
+    // Merged in chunk order, so the result matches a sequential run.
+    for stdout in outputs {
+        capture(raw_dump, "pacman-Si", "txt", &stdout)?;
+        let stdout = String::from_utf8(stdout).map_err(|err| {
             SynsyuError::Serialization(format!("pacman -Si emitted invalid UTF-8: {err}"))
         })?;
 
-        let mut current: Option<String> = None;
-        let mut current_version: Option<String> = None;
-        let mut download_size: Option<u64> = None;
-        let mut installed_size: Option<u64> = None;
-        for line in stdout.lines() {
-            if let Some((raw_key, raw_value)) = line.split_once(':') {
-                let key = raw_key.trim();
-                let value = raw_value.trim();
-                match key {
-                    "Name" => {
-                        current = Some(value.to_string());
-                        current_version = None;
-                        download_size = None;
-                        installed_size = None;
-                    }
-                    "Version" => {
-                        current_version = Some(value.to_string());
-                    }
-                    "Download Size" => {
-                        download_size = parse_pacman_size(value);
-                    }
-                    "Installed Size" => {
-                        installed_size = parse_pacman_size(value);
-                    }
-                    _ => {}
-                }
-            } else if line.trim().is_empty() {
-                if let (Some(name), Some(ver)) = (current.take(), current_version.take()) {
-                    versions.insert(name, VersionInfo::new(ver, download_size, installed_size));
-                }
-                download_size = None;
-                installed_size = None;
-            }
-        }
-        if let (Some(name), Some(ver)) = (current.take(), current_version.take()) {
-            versions.insert(name, VersionInfo::new(ver, download_size, installed_size));
-        }
+        parse_sync_info(&stdout, &priority, &mut versions, logger);
     }
 
     Ok(versions)
 }
 
+/// Run `<program> -Si` over `packages` in chunks, returning each chunk's stdout in chunk order.
+///
+/// The first chunk to fail aborts the others (killing their children) and its error is returned.
+async fn run_sync_info(
+    program: &str,
+    packages: &[String],
+    chunk_size: usize,
+    max_parallel: usize,
+) -> Result<Vec<Vec<u8>>> {
+    let permits = Arc::new(Semaphore::new(max_parallel.max(1)));
+    let mut tasks: JoinSet<Result<(usize, Vec<u8>)>> = JoinSet::new();
+    for (index, chunk) in packages.chunks(chunk_size.max(1)).enumerate() {
+        let (program, chunk) = (program.to_string(), chunk.to_vec());
+        let permits = Arc::clone(&permits);
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await.map_err(|err| {
+                SynsyuError::Runtime(format!("{program} -Si slot unavailable: {err}"))
+            })?;
+            Ok((index, sync_info_chunk(&program, &chunk).await?))
+        });
+    }
+
+    let mut outputs = vec![Vec::new(); tasks.len()];
+    while let Some(joined) = tasks.join_next().await {
+        let (index, stdout) = joined
+            .map_err(|err| SynsyuError::Runtime(format!("{program} -Si task failed: {err}")))??;
+        outputs[index] = stdout;
+    }
+    Ok(outputs)
+}
+
+/// One `<program> -Si` invocation; stdout is returned only when it can be trusted.
+async fn sync_info_chunk(program: &str, chunk: &[String]) -> Result<Vec<u8>> {
+    let output = Command::new(program)
+        .arg("-Si")
+        .args(chunk)
+        .env("LC_ALL", "C")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|err| map_spawn_error(err, program))?;
+
+    if let Some(err) = sync_info_failure(
+        &format!("{program} -Si {}", chunk.join(" ")),
+        output.status,
+        &output.stderr,
+    ) {
+        return Err(err);
+    }
+    Ok(output.stdout)
+}
+
 /// Retrieve version and size info for the specified packages via an AUR helper (paru/yay/etc.).
 pub async fn query_aur_helper_versions(
     helper: &str,
@@ -213,6 +564,7 @@ pub async fn query_aur_helper_versions(
         let output = Command::new(helper)
             .arg("-Si")
             .args(chunk)
+            .env("LC_ALL", "C")
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .output()
@@ -273,8 +625,168 @@ pub async fn query_aur_helper_versions(
     Ok(versions)
 }
 
-/// Compare two package versions using `vercmp`.
-pub async fn compare_versions(local: &str, remote: &str) -> Result<std::cmp::Ordering> {
+const PACMAN_CONF: &str = "/etc/pacman.conf";
+
+/// Sync repositories in the order pacman.conf declares them (highest priority first).
+///
+/// An unreadable file yields an empty list, leaving `-Si` output order to decide.
+fn sync_repo_order(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .map(|text| parse_repo_sections(&text))
+        .unwrap_or_default()
+}
+
+fn parse_repo_sections(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter_map(|line| line.strip_prefix('[')?.strip_suffix(']'))
+        .map(str::trim)
+        .filter(|section| *section != "options")
+        .map(str::to_string)
+        .collect()
+}
+
+/// Parse `pacman -Si` records into candidate versions keyed by package name.
+///
+/// When a name appears in several repositories, the repository listed first in
+/// `priority` wins; repositories missing from it rank last, in output order.
+/// Records lacking `Name` or `Version` are dropped, as is a final line without
+/// its newline (output cut off mid-write).
+fn parse_sync_info(
+    stdout: &str,
+    priority: &[String],
+    versions: &mut HashMap<String, VersionInfo>,
+    logger: Option<&Logger>,
+) {
+    #[derive(Default)]
+    struct Record {
+        name: Option<String>,
+        version: Option<String>,
+        repository: Option<String>,
+        download_size: Option<u64>,
+        installed_size: Option<u64>,
+        depends: Vec<String>,
+        in_depends: bool,
+        build_date: Option<String>,
+    }
+
+    fn rank(priority: &[String], repository: Option<&str>) -> usize {
+        repository
+            .and_then(|repo| priority.iter().position(|known| known == repo))
+            .unwrap_or(priority.len())
+    }
+
+    fn flush(record: Record, priority: &[String], versions: &mut HashMap<String, VersionInfo>) {
+        let (Some(name), Some(version)) = (record.name, record.version) else {
+            return;
+        };
+        if let Some(existing) = versions.get(&name) {
+            if rank(priority, existing.repository.as_deref())
+                <= rank(priority, record.repository.as_deref())
+            {
+                return;
+            }
+        }
+        let info = VersionInfo::new(version, record.download_size, record.installed_size)
+            .with_repository(record.repository)
+            .with_depends(record.depends)
+            .with_released_at(record.build_date.as_deref().and_then(parse_install_date));
+        versions.insert(name, info);
+    }
+
+    let complete = match stdout.rfind('\n') {
+        Some(end) => &stdout[..=end],
+        None => "",
+    };
+    let mut record = Record::default();
+    for line in complete.lines() {
+        // `Depends On` may wrap onto indented continuation lines without a key.
+        if record.in_depends && line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+            record.depends.extend(parse_name_list(line.trim()));
+            continue;
+        }
+        record.in_depends = false;
+        if let Some((raw_key, raw_value)) = line.split_once(':') {
+            let value = raw_value.trim();
+            let key = raw_key.trim();
+            match key {
+                "Depends On" => {
+                    record.in_depends = true;
+                    record.depends = parse_name_list(value);
+                }
+                "Repository" => record.repository = Some(value.to_string()),
+                "Name" => record.name = Some(value.to_string()),
+                "Version" => record.version = Some(value.to_string()),
+                "Build Date" => record.build_date = Some(value.to_string()),
+                "Download Size" => record.download_size = parse_size_field(key, value, logger),
+                "Installed Size" => record.installed_size = parse_size_field(key, value, logger),
+                _ => {}
+            }
+        } else if line.trim().is_empty() {
+            flush(std::mem::take(&mut record), priority, versions);
+        }
+    }
+    flush(record, priority, versions);
+}
+
+/// Error for a failed `pacman -Si` call, or `None` when its stdout can be trusted.
+///
+/// Names absent from the sync databases fail the call but still print the rest.
+/// A child killed by a signal may have stopped mid-record, so its output is
+/// never used, whatever stderr says.
+fn sync_info_failure(command: &str, status: ExitStatus, stderr: &[u8]) -> Option<SynsyuError> {
+    if let Some(signal) = status.signal() {
+        return Some(SynsyuError::CommandFailure {
+            command: command.to_string(),
+            status: 128 + signal,
+            stderr: format!("terminated by signal {signal}; output may be truncated"),
+        });
+    }
+    if status.success() || only_missing_targets(stderr) {
+        return None;
+    }
+    Some(SynsyuError::CommandFailure {
+        command: command.to_string(),
+        status: status.code().unwrap_or(-1),
+        stderr: String::from_utf8_lossy(stderr).trim().to_string(),
+    })
+}
+
+/// True when every stderr line is pacman's "package 'x' was not found" error.
+fn only_missing_targets(stderr: &[u8]) -> bool {
+    let stderr = String::from_utf8_lossy(stderr);
+    let mut lines = stderr
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .peekable();
+    lines.peek().is_some()
+        && lines
+            .all(|line| line.starts_with("error: package '") && line.ends_with("' was not found"))
+}
+
+/// Implementation behind `compare_versions`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VercmpBackend {
+    /// In-process port of libalpm's comparison (`vercmp_native`).
+    #[default]
+    Native,
+    /// The `vercmp` binary, one process per comparison (`--vercmp-external`).
+    External,
+}
+
+/// Compare two package versions with pacman's `vercmp` semantics.
+pub async fn compare_versions(
+    local: &str,
+    remote: &str,
+    backend: VercmpBackend,
+) -> Result<std::cmp::Ordering> {
+    // Identical strings always compare equal; skip the subprocess.
+    if local == remote {
+        return Ok(std::cmp::Ordering::Equal);
+    }
+    if backend == VercmpBackend::Native {
+        return Ok(vercmp_native(local, remote));
+    }
     let output = Command::new("vercmp")
         .arg(local)
         .arg(remote)
@@ -295,113 +807,115 @@ pub async fn compare_versions(local: &str, remote: &str) -> Result<std::cmp::Ord
     let stdout = String::from_utf8(output.stdout).map_err(|err| {
         SynsyuError::Serialization(format!("vercmp emitted invalid UTF-8: {err}"))
     })?;
-    let verdict = stdout.trim();
-    let ordering = i32::from_str(verdict).map_err(|err| {
-        SynsyuError::Serialization(format!("Failed to parse vercmp output `{verdict}`: {err}"))
-    })?;
-
+    let ordering = parse_vercmp_output(&stdout)?;
     Ok(ordering.cmp(&0))
 }
 
-async fn detect_foreign_packages() -> Result<HashSet<String>> {
+/// Take the final token of vercmp stdout as the verdict, so wrapper scripts may
+/// print noise before it; anything after the verdict is an error.
+fn parse_vercmp_output(stdout: &str) -> Result<i32> {
+    stdout
+        .split_whitespace()
+        .next_back()
+        .and_then(|token| i32::from_str(token).ok())
+        .ok_or_else(|| {
+            SynsyuError::Serialization(format!(
+                "Failed to parse vercmp output `{}`: last token is not an integer verdict",
+                stdout.trim()
+            ))
+        })
+}
+
+/// Names of foreign packages (`pacman -Qm`): installed but carried by no sync
+/// database, so either from the AUR or built locally.
+pub async fn enumerate_foreign_packages() -> Result<HashSet<String>> {
     let output = Command::new("pacman")
         .arg("-Qm")
+        .env("LC_ALL", "C")
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()
-        .await;
+        .await
+        .map_err(|err| map_spawn_error(err, "pacman"))?;
 
-    let Ok(output) = output else {
-        return Ok(HashSet::new());
-    };
-    if !output.status.success() {
-        return Ok(HashSet::new());
+    // pacman exits 1 without any output when no installed package is foreign.
+    let none_foreign =
+        output.status.code() == Some(1) && output.stdout.is_empty() && output.stderr.is_empty();
+    if !output.status.success() && !none_foreign {
+        return Err(SynsyuError::CommandFailure {
+            command: "pacman -Qm".into(),
+            status: output.status.code().unwrap_or(-1),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
     }
-    let stdout = String::from_utf8(output.stdout).unwrap_or_default();
-    let set = stdout
+    Ok(parse_foreign_list(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Package names from `pacman -Qm` output (`<name> <version>` per line).
+fn parse_foreign_list(stdout: &str) -> HashSet<String> {
+    stdout
         .lines()
         .filter_map(|line| line.split_whitespace().next())
-        .map(|s| s.to_string())
-        .collect();
-    Ok(set)
-}
-
-/// Query AUR to see which package names exist there.
-pub async fn aur_presence(names: &[String], offline: bool) -> Result<HashSet<String>> {
-    if offline || names.is_empty() {
-        return Ok(HashSet::new());
-    }
-    let client = Client::new();
-    let mut found = HashSet::new();
-    const CHUNK: usize = 100;
-    for chunk in names.chunks(CHUNK) {
-        let mut query = String::from("https://aur.archlinux.org/rpc/?v=5&type=info");
-        for name in chunk {
-            query.push_str("&arg[]=");
-            query.push_str(encode(name).as_ref());
-        }
-        let resp = client
-            .get(&query)
-            .send()
-            .await
-            .map_err(|err| SynsyuError::Network(format!("AUR request failed: {err}")))?;
-        let status = resp.status();
-        if !status.is_success() {
-            return Err(SynsyuError::Network(format!(
-                "AUR request failed with status {status}"
-            )));
-        }
-        let body: AurResponse = resp
-            .json()
-            .await
-            .map_err(|err| SynsyuError::Network(format!("AUR response parse failed: {err}")))?;
-        if body.resp_type.as_deref() != Some("multiinfo") {
-            continue;
-        }
-        if let Some(results) = body.results {
-            for entry in results {
-                if let Some(name) = entry.name {
-                    found.insert(name);
-                }
-            }
-        }
-    }
-    Ok(found)
+        .map(str::to_string)
+        .collect()
 }
 
-#[derive(Debug, Deserialize)]
-struct AurResponse {
-    #[serde(rename = "type")]
-    resp_type: Option<String>,
-    resultcount: Option<u64>,
-    results: Option<Vec<AurEntry>>,
+/// Parse a pacman size field in the C-locale format pacman prints under
+/// `LC_ALL=C`: `<digits>[.<digits>] <unit>` with a binary unit.
+///
+/// Anything else (thousands separators, decimal commas, unknown units, missing
+/// unit) yields `None` rather than a plausible but wrong number.
+pub fn parse_pacman_size(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let (number, unit) = (parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, "0"));
+    let digits = |text: &str| !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit());
+    if !digits(whole) || !digits(fraction) {
+        return None;
+    }
+    let exponent = match unit {
+        "B" => 0,
+        "KiB" => 1,
+        "MiB" => 2,
+        "GiB" => 3,
+        "TiB" => 4,
+        "PiB" => 5,
+        _ => return None,
+    };
+    let bytes = number.parse::<f64>().ok()? * 1024_f64.powi(exponent);
+    (bytes.is_finite() && bytes < u64::MAX as f64).then(|| bytes.round() as u64)
 }
 
-#[derive(Debug, Deserialize)]
-struct AurEntry {
-    #[serde(rename = "Name")]
-    name: Option<String>,
+/// Largest error, in bytes, between a real size and `parse_pacman_size` of its
+/// two-decimal rendering.
+///
+/// A size printed as `x.yy <unit>` is only known to within half of 0.01 of that
+/// unit (plus rounding back to whole bytes). The unit is not kept, so the largest
+/// unit the value reaches is assumed; that bounds both pacman's own rendering
+/// (which stays below 2048 of a unit) and a `1.80 MiB`-style one.
+pub fn pacman_size_tolerance(bytes: u64) -> u64 {
+    let mut unit = 1_f64;
+    let mut value = bytes as f64;
+    while value >= 1024.0 && unit < 1024_f64.powi(5) {
+        value /= 1024.0;
+        unit *= 1024.0;
+    }
+    (unit * 0.005 + 0.5).floor() as u64
 }
 
-pub fn parse_pacman_size(value: &str) -> Option<u64> {
-    let mut parts = value.trim().split_whitespace();
-    let number = parts.next()?.replace(',', "");
-    let unit = parts.next().unwrap_or("B");
-    let magnitude = number.parse::<f64>().ok()?;
-    let multiplier = match unit {
-        "B" => 1_f64,
-        "KiB" => 1024_f64,
-        "MiB" => 1024_f64.powi(2),
-        "GiB" => 1024_f64.powi(3),
-        "TiB" => 1024_f64.powi(4),
-        _ => 1_f64,
-    };
-    let bytes = magnitude * multiplier;
-    if bytes.is_finite() && bytes >= 0.0 {
-        Some(bytes.round() as u64)
-    } else {
-        None
+/// `parse_pacman_size` for a named field, noting rejected values at debug level.
+fn parse_size_field(field: &str, value: &str, logger: Option<&Logger>) -> Option<u64> {
+    let size = parse_pacman_size(value);
+    if let (None, Some(logger)) = (size, logger) {
+        logger.debug(
+            "SIZEFMT",
+            format!("{field} `{value}` is not a C-locale pacman size; left unset"),
+        );
     }
+    size
 }
 
 fn map_spawn_error(err: io::Error, command: &str) -> SynsyuError {
@@ -413,3 +927,452 @@ fn map_spawn_error(err: io::Error, command: &str) -> SynsyuError {
         SynsyuError::Runtime(format!("Failed to spawn {command}: {err}"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `pacman -Qi` text generated on demand, never held in memory as a whole.
+    struct SyntheticQi {
+        next: usize,
+        total: usize,
+        pending: Vec<u8>,
+        served: usize,
+    }
+
+    impl SyntheticQi {
+        fn block(index: usize) -> String {
+            format!(
+                "Name            : pkg-{index:06}\nVersion         : 1.{index}-1\nInstalled Size  : 2.00 KiB\nRequired By     : dep-a-{index} dep-b-{index}\n                  dep-c-{index}\nInstall Date    : Sat 18 Oct 2026\n\n"
+            )
+        }
+    }
+
+    impl tokio::io::AsyncRead for SyntheticQi {
+        fn poll_read(
+            mut self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<io::Result<()>> {
+            if self.served == self.pending.len() && self.next < self.total {
+                let index = self.next;
+                self.pending = Self::block(index).into_bytes();
+                self.served = 0;
+                self.next += 1;
+            }
+            let start = self.served;
+            let n = buf.remaining().min(self.pending.len() - start);
+            buf.put_slice(&self.pending[start..start + n]);
+            self.served += n;
+            std::task::Poll::Ready(Ok(()))
+        }
+    }
+
+    #[tokio::test]
+    async fn large_qi_stream_parses_incrementally() {
+        let total = 50_000;
+        let reader = SyntheticQi {
+            next: 0,
+            total,
+            pending: Vec::new(),
+            served: 0,
+        };
+        let foreign: HashSet<String> = ["pkg-000007".to_string()].into_iter().collect();
+        // A small buffer proves blocks are assembled across many partial reads.
+        let packages = parse_installed_stream(
+            tokio::io::BufReader::with_capacity(64, reader),
+            &foreign,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(packages.len(), total);
+        assert!(packages.windows(2).all(|pair| pair[0].name < pair[1].name));
+        let last = &packages[total - 1];
+        assert_eq!(last.name, "pkg-049999");
+        assert_eq!(last.version, "1.49999-1");
+        assert_eq!(last.installed_size, Some(2048));
+        assert_eq!(
+            last.required_by,
+            vec!["dep-a-49999", "dep-b-49999", "dep-c-49999"]
+        );
+        assert_eq!(packages[7].repository.as_deref(), Some("local"));
+        assert_eq!(packages[8].repository.as_deref(), Some("pacman"));
+    }
+
+    #[test]
+    fn foreign_list_yields_package_names() {
+        let stdout = "paru 2.0.4-1\nsynsyu-local 0.1-1\n\n";
+        let foreign = parse_foreign_list(stdout);
+        assert_eq!(
+            foreign,
+            HashSet::from(["paru".to_string(), "synsyu-local".to_string()])
+        );
+        assert!(parse_foreign_list("").is_empty());
+    }
+
+    #[tokio::test]
+    async fn stream_is_mirrored_into_dump_and_tolerates_missing_trailing_blank() {
+        let text = "Name            : bash\nVersion         : 5.2.037-1\nRepository      : core\n\nName            : zsh\nVersion         : 5.9-5\n";
+        let mut dump = Vec::new();
+        let packages = parse_installed_stream(
+            text.as_bytes(),
+            &HashSet::new(),
+            Some(&mut dump as &mut dyn Write),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(packages.len(), 2);
+        assert_eq!(packages[0].repository.as_deref(), Some("core"));
+        assert_eq!(packages[1].name, "zsh");
+        assert_eq!(dump, text.as_bytes());
+    }
+
+    #[test]
+    fn sync_info_records_origin_and_build_date() {
+        let stdout = "Repository      : core\nName            : bash\nVersion         : 5.2.037-1\nArchitecture    : x86_64\nDownload Size   : 1.80 MiB\nInstalled Size  : 9.23 MiB\nBuild Date      : Sat Oct 12 10:00:00 2024\n\nRepository      : extra\nName            : python-six\nVersion         : 1:1.16.0-9\nArchitecture    : any\n";
+        let mut versions = HashMap::new();
+        parse_sync_info(stdout, &[], &mut versions, None);
+
+        let bash = &versions["bash"];
+        assert_eq!(bash.version, "5.2.037-1");
+        assert_eq!(bash.repository.as_deref(), Some("core"));
+        assert_eq!(
+            bash.released_at.map(|at| at.to_rfc3339()).as_deref(),
+            Some("2024-10-12T10:00:00+00:00")
+        );
+        let six = &versions["python-six"];
+        assert_eq!(six.version, "1:1.16.0-9");
+        assert_eq!(six.repository.as_deref(), Some("extra"));
+    }
+
+    #[test]
+    fn sync_depends_and_installed_provides_drop_constraints() {
+        let stdout = "Repository      : extra\nName            : curl\nVersion         : 8.11.0-1\nDepends On      : ca-certificates  krb5  libpsl>=0.21\n                  libcurl.so=4-64  zlib\nOptional Deps   : None\n\nRepository      : core\nName            : filesystem\nVersion         : 2024.04.07-1\nDepends On      : None\n";
+        let mut versions = HashMap::new();
+        parse_sync_info(stdout, &[], &mut versions, None);
+        assert_eq!(
+            versions["curl"].depends,
+            ["ca-certificates", "krb5", "libpsl", "libcurl.so", "zlib"]
+        );
+        assert!(versions["filesystem"].depends.is_empty());
+
+        let mut block = InstalledBlock::default();
+        for line in [
+            "Name            : bash",
+            "Version         : 5.2.037-1",
+            "Provides        : sh  libreadline.so=8-64",
+            "                  bash-completion-shim",
+            "Groups          : base-devel",
+            "Required By     : None",
+        ] {
+            block.feed(line, None);
+        }
+        let bash = block.finish(&HashSet::new()).unwrap();
+        assert_eq!(
+            bash.provides,
+            ["sh", "libreadline.so", "bash-completion-shim"]
+        );
+        assert_eq!(bash.groups, ["base-devel"]);
+        assert!(bash.required_by.is_empty());
+    }
+
+    #[test]
+    fn multi_repo_package_keeps_highest_priority_repo() {
+        let conf = "[options]\nHoldPkg = pacman glibc\n\n[core-testing]\nInclude = /etc/pacman.d/mirrorlist\n\n[core] # stable\nInclude = /etc/pacman.d/mirrorlist\n\n[extra]\nInclude = /etc/pacman.d/mirrorlist\n";
+        let priority = parse_repo_sections(conf);
+        assert_eq!(priority, ["core-testing", "core", "extra"]);
+
+        let stdout = "Repository      : extra\nName            : linux\nVersion         : 6.11.2-1\nArchitecture    : x86_64\n\nRepository      : core-testing\nName            : linux\nVersion         : 6.12.0-1\nArchitecture    : x86_64\n\nRepository      : core\nName            : linux\nVersion         : 6.11.3-1\nArchitecture    : x86_64\n";
+        let mut versions = HashMap::new();
+        parse_sync_info(stdout, &priority, &mut versions, None);
+        let linux = &versions["linux"];
+        assert_eq!(linux.version, "6.12.0-1");
+        assert_eq!(linux.repository.as_deref(), Some("core-testing"));
+
+        // Without pacman.conf, the first block (pacman's own order) wins.
+        let mut versions = HashMap::new();
+        parse_sync_info(stdout, &[], &mut versions, None);
+        assert_eq!(versions["linux"].repository.as_deref(), Some("extra"));
+    }
+
+    #[test]
+    fn c_locale_sizes_parse() {
+        assert_eq!(parse_pacman_size("0.00 B"), Some(0));
+        assert_eq!(parse_pacman_size("512 B"), Some(512));
+        assert_eq!(parse_pacman_size("2.00 KiB"), Some(2_048));
+        assert_eq!(parse_pacman_size("1.80 MiB"), Some(1_887_437));
+        assert_eq!(parse_pacman_size("1234.56 MiB"), Some(1_294_529_987));
+        assert_eq!(parse_pacman_size("  3.50   GiB "), Some(3_758_096_384));
+    }
+
+    #[test]
+    fn misformatted_sizes_are_rejected() {
+        for value in [
+            "1.234,56 MiB",
+            "1,234.56 MiB",
+            "1,80 MiB",
+            "1 234 MiB",
+            "1.80",
+            "1.80 MB",
+            "-1.00 KiB",
+            "1.2.3 KiB",
+            ". KiB",
+            "1. KiB",
+            "NaN B",
+            "inf B",
+            "",
+        ] {
+            assert_eq!(parse_pacman_size(value), None, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn rejected_sizes_are_logged_and_left_unset() {
+        let log = std::env::temp_dir().join(format!("synsyu-sizefmt-{}.log", std::process::id()));
+        let logger = Logger::new(Some(log.clone()), false).unwrap();
+        let stdout = "Repository      : core\nName            : bash\nVersion         : 5.2.037-1\nDownload Size   : 1,80 MiB\nInstalled Size  : 9.23 MiB\n\n";
+        let mut versions = HashMap::new();
+        parse_sync_info(stdout, &[], &mut versions, Some(&logger));
+        assert_eq!(versions["bash"].download_size, None);
+        assert_eq!(versions["bash"].installed_size, Some(9_678_356));
+        let text = std::fs::read_to_string(&log).unwrap();
+        assert!(
+            text.contains(
+                "[DEBUG] [SIZEFMT] Download Size `1,80 MiB` is not a C-locale pacman size"
+            ),
+            "{text}"
+        );
+        let _ = std::fs::remove_file(log);
+    }
+
+    #[test]
+    fn truncated_sync_info_drops_incomplete_records() {
+        // Second record lost its Version; the third was cut mid-line.
+        let stdout = "Repository      : core\nName            : bash\nVersion         : 5.2.037-1\n\nRepository      : core\nName            : glibc\n\nRepository      : extra\nName            : zsh\nVersion         : 5.9";
+        let mut versions = HashMap::new();
+        parse_sync_info(stdout, &[], &mut versions, None);
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions["bash"].version, "5.2.037-1");
+    }
+
+    #[tokio::test]
+    async fn concurrent_sync_info_chunks_keep_chunk_order_and_surface_failures() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("synsyu-si-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let stub = dir.join("pacman");
+        // Earlier chunks answer last, so completion order differs from chunk order.
+        fs::write(
+            &stub,
+            "#!/bin/sh\nshift\nfor name in \"$@\"; do\n  case \"$name\" in\n    \
+             broken) echo 'error: failed to init transaction' >&2; exit 1;;\n    \
+             a) sleep 0.3;;\n    b) sleep 0.1;;\n  esac\n  \
+             printf 'Name : %s\\nVersion : 1.0-1\\n\\n' \"$name\"\ndone\n",
+        )
+        .unwrap();
+        fs::set_permissions(&stub, fs::Permissions::from_mode(0o755)).unwrap();
+        let program = stub.to_str().unwrap();
+        let names = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        let outputs = run_sync_info(program, &names(&["a", "b", "c"]), 1, 3)
+            .await
+            .unwrap();
+        let first_names: Vec<String> = outputs
+            .iter()
+            .map(|out| {
+                String::from_utf8_lossy(out)
+                    .lines()
+                    .next()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(first_names, ["Name : a", "Name : b", "Name : c"]);
+
+        match run_sync_info(program, &names(&["a", "broken", "c"]), 1, 2).await {
+            Err(SynsyuError::CommandFailure {
+                command, stderr, ..
+            }) => {
+                assert!(command.ends_with("-Si broken"));
+                assert_eq!(stderr, "error: failed to init transaction");
+            }
+            other => panic!("expected CommandFailure, got {other:?}"),
+        }
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn only_transient_failures_are_retried() {
+        let locked = || SynsyuError::CommandFailure {
+            command: "pacman -Qi".into(),
+            status: 1,
+            stderr: "error: failed to init transaction (unable to lock database)".into(),
+        };
+        let policy = RetryPolicy {
+            attempts: 3,
+            delay: Duration::from_millis(1),
+        };
+        let logger = Logger::new(None, false).unwrap();
+
+        let tries = std::cell::Cell::new(0);
+        let result = with_retry(policy, "pacman -Qi", Some(&logger), || {
+            tries.set(tries.get() + 1);
+            let outcome = if tries.get() < 3 {
+                Err(locked())
+            } else {
+                Ok(7)
+            };
+            async move { outcome }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 7);
+        assert_eq!(tries.get(), 3);
+
+        tries.set(0);
+        let result: Result<()> = with_retry(policy, "pacman -Qi", None, || {
+            tries.set(tries.get() + 1);
+            let outcome = Err(locked());
+            async move { outcome }
+        })
+        .await;
+        assert!(matches!(result, Err(SynsyuError::CommandFailure { .. })));
+        assert_eq!(tries.get(), 3, "gives up after the configured attempts");
+
+        for permanent in [
+            SynsyuError::CommandMissing {
+                command: "pacman".into(),
+            },
+            SynsyuError::Serialization("pacman -Si emitted invalid UTF-8".into()),
+            SynsyuError::CommandFailure {
+                command: "pacman -Si".into(),
+                status: 137,
+                stderr: "unable to lock database".into(),
+            },
+        ] {
+            tries.set(0);
+            let mut pending = Some(permanent);
+            let result: Result<()> = with_retry(policy, "pacman -Si", None, || {
+                tries.set(tries.get() + 1);
+                let outcome = Err(pending.take().expect("tried once"));
+                async move { outcome }
+            })
+            .await;
+            assert!(result.is_err());
+            assert_eq!(tries.get(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn sync_info_from_signalled_child_is_rejected() {
+        let output = Command::new("sh")
+            .args([
+                "-c",
+                "printf 'Name : bash\\nVersion : 5.2' && kill -KILL $$",
+            ])
+            .output()
+            .await
+            .unwrap();
+        assert_eq!(output.status.signal(), Some(9));
+
+        match sync_info_failure("pacman -Si bash", output.status, &output.stderr) {
+            Some(SynsyuError::CommandFailure {
+                command,
+                status,
+                stderr,
+            }) => {
+                assert_eq!(command, "pacman -Si bash");
+                assert_eq!(status, 137);
+                assert!(stderr.contains("signal 9"), "{stderr}");
+            }
+            other => panic!("expected CommandFailure, got {other:?}"),
+        }
+        let missing = b"error: package 'nope' was not found\n";
+        let status = Command::new("false").status().await.unwrap();
+        assert!(sync_info_failure("pacman -Si nope", status, missing).is_none());
+    }
+
+    #[test]
+    fn vercmp_verdict_survives_wrapper_noise() {
+        assert_eq!(parse_vercmp_output("  -1\n").unwrap(), -1);
+        assert_eq!(parse_vercmp_output("result: 1").unwrap(), 1);
+        assert_eq!(parse_vercmp_output("wrapper v2\n0\n").unwrap(), 0);
+        assert!(matches!(
+            parse_vercmp_output("garbage"),
+            Err(SynsyuError::Serialization(_))
+        ));
+        assert!(parse_vercmp_output("").is_err());
+        assert!(parse_vercmp_output("-1 (took 3 ms)").is_err());
+        assert!(parse_vercmp_output("0\ndone").is_err());
+    }
+
+    #[test]
+    fn db_lock_presence_signals_an_active_transaction() {
+        let dir = std::env::temp_dir().join(format!("synsyu-dblck-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let lock = dir.join("db.lck");
+
+        let logger = Logger::new(None, false).unwrap();
+        assert!(!check_active_transaction(&lock, true, &logger).unwrap());
+        assert!(logger.warnings().is_empty());
+
+        fs::write(&lock, b"").unwrap();
+        assert!(check_active_transaction(&lock, false, &logger).unwrap());
+        let warnings = logger.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("[TXNACTIVE] pacman transaction in progress"));
+        assert!(matches!(
+            check_active_transaction(&lock, true, &logger),
+            Err(SynsyuError::ActiveTransaction(_))
+        ));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn duplicate_qi_blocks_collapse_to_one_entry() {
+        let text = "Name            : lib32-foo\nVersion         : 1.0-1\nRepository      : multilib\n\nName            : bash\nVersion         : 5.2.037-1\n\nName            : lib32-foo\nVersion         : 1.2-1\nRepository      : multilib-testing\n\nName            : lib32-foo\nVersion         : 1.0-1\nRepository      : core\n";
+        let parsed = parse_installed_stream(text.as_bytes(), &HashSet::new(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(parsed.len(), 4);
+
+        let logger = Logger::new(None, false).unwrap();
+        let first = dedupe_installed(
+            parsed.clone(),
+            DuplicatePolicy::KeepFirst,
+            VercmpBackend::Native,
+            &logger,
+        )
+        .await;
+        let names: Vec<&str> = first.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["lib32-foo", "bash"]);
+        assert_eq!(first[0].version, "1.0-1");
+        assert_eq!(first[0].repository.as_deref(), Some("multilib"));
+        let warnings = logger.warnings();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|w| w.starts_with("[DUPE] lib32-foo:")));
+        assert!(
+            warnings[0].contains("keeping 1.0-1 (keep_first)"),
+            "{warnings:?}"
+        );
+
+        let logger = Logger::new(None, false).unwrap();
+        let highest = dedupe_installed(
+            parsed,
+            DuplicatePolicy::KeepHighest,
+            VercmpBackend::Native,
+            &logger,
+        )
+        .await;
+        assert_eq!(highest.len(), 2);
+        assert_eq!(highest[0].version, "1.2-1");
+        assert_eq!(highest[0].repository.as_deref(), Some("multilib-testing"));
+        assert!(logger.warnings()[0].contains("keeping 1.2-1 (keep_highest)"));
+    }
+}
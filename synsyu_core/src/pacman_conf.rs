@@ -0,0 +1,158 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::pacman_conf
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Read the `IgnorePkg` / `IgnoreGroup` directives of
+    pacman.conf so packages pacman holds back are not reported
+    as pending updates.
+
+  Security / Safety Notes:
+    Reads one configuration file; never writes or executes it.
+
+  Dependencies:
+    crate::glob for pacman's shell-style patterns.
+
+  Operational Scope:
+    Loaded once per Syn-Syu-Core run (`core.pacman_conf_path`)
+    unless `--no-respect-ignore` is given.
+
+  Revision History:
+    2026-10-18 COD  Introduced IgnorePkg/IgnoreGroup parsing.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Only the [options] section is consulted, as in pacman
+    - An unreadable file is warned about, never fatal
+============================================================*/
+
+use std::fs;
+use std::path::Path;
+
+use crate::glob;
+use crate::logger::Logger;
+use crate::pacman::InstalledPackage;
+
+/// Package and group patterns pacman.conf tells pacman to skip on upgrade.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IgnoreRules {
+    pub packages: Vec<String>,
+    pub groups: Vec<String>,
+}
+
+impl IgnoreRules {
+    /// The directive holding `package` back, if any.
+    pub fn holds(&self, package: &InstalledPackage) -> Option<&'static str> {
+        if self
+            .packages
+            .iter()
+            .any(|pattern| glob::matches(pattern, &package.name))
+        {
+            return Some("IgnorePkg");
+        }
+        let grouped = package.groups.iter().any(|group| {
+            self.groups
+                .iter()
+                .any(|pattern| glob::matches(pattern, group))
+        });
+        grouped.then_some("IgnoreGroup")
+    }
+}
+
+/// Ignore rules from the pacman.conf at `path`; an unreadable file yields none (PACMANCONF).
+pub fn load_ignore_rules(path: &Path, logger: &Logger) -> IgnoreRules {
+    match fs::read_to_string(path) {
+        Ok(text) => parse_ignore_rules(&text),
+        Err(err) => {
+            logger.warn(
+                "PACMANCONF",
+                format!(
+                    "{} unreadable; IgnorePkg/IgnoreGroup not applied: {err}",
+                    path.display()
+                ),
+            );
+            IgnoreRules::default()
+        }
+    }
+}
+
+/// Collect `IgnorePkg`/`IgnoreGroup` values from the `[options]` section.
+///
+/// Both directives may repeat and take space-separated lists.
+pub fn parse_ignore_rules(text: &str) -> IgnoreRules {
+    let mut rules = IgnoreRules::default();
+    let mut in_options = false;
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            in_options = section.trim() == "options";
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=').filter(|_| in_options) else {
+            continue;
+        };
+        let target = match key.trim() {
+            "IgnorePkg" => &mut rules.packages,
+            "IgnoreGroup" => &mut rules.groups,
+            _ => continue,
+        };
+        target.extend(value.split_whitespace().map(str::to_string));
+    }
+    rules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONF: &str = "\
+[options]
+HoldPkg     = pacman glibc
+IgnorePkg   = linux linux-headers  # kernel pinned
+IgnorePkg   = nvidia-*
+#IgnorePkg  = firefox
+IgnoreGroup = gnome
+
+[core]
+Include = /etc/pacman.d/mirrorlist
+IgnorePkg = not-an-option
+";
+
+    fn package(name: &str, groups: &[&str]) -> InstalledPackage {
+        InstalledPackage {
+            name: name.into(),
+            version: "1.0-1".into(),
+            repository: Some("extra".into()),
+            installed_size: None,
+            install_date: None,
+            validated_by: None,
+            package_hash: None,
+            required_by: Vec::new(),
+            provides: Vec::new(),
+            groups: groups.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn directives_are_read_from_options_only() {
+        let rules = parse_ignore_rules(CONF);
+        assert_eq!(rules.packages, ["linux", "linux-headers", "nvidia-*"]);
+        assert_eq!(rules.groups, ["gnome"]);
+    }
+
+    #[test]
+    fn packages_are_held_by_name_pattern_or_group() {
+        let rules = parse_ignore_rules(CONF);
+        assert_eq!(rules.holds(&package("linux", &[])), Some("IgnorePkg"));
+        assert_eq!(
+            rules.holds(&package("nvidia-utils", &[])),
+            Some("IgnorePkg")
+        );
+        assert_eq!(
+            rules.holds(&package("nautilus", &["gnome"])),
+            Some("IgnoreGroup")
+        );
+        assert_eq!(rules.holds(&package("firefox", &["xorg"])), None);
+        assert_eq!(rules.holds(&package("not-an-option", &[])), None);
+    }
+}
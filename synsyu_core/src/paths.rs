@@ -0,0 +1,162 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::paths
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Render file system paths for manifest metadata and log
+    messages in the operator-selected style (`paths.style`).
+
+  Security / Safety Notes:
+    `relative` and `redacted` keep home directories (and with
+    them user names) out of manifests shared across machines.
+
+  Dependencies:
+    dirs for the home directory.
+
+  Operational Scope:
+    Applied by the logger to every message and by Syn-Syu-Core
+    to path-valued manifest metadata.
+
+  Revision History:
+    2026-10-18 COD  Introduced path rendering styles.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Paths outside the configured prefix pass through untouched
+    - One formatter shared by logs and metadata
+============================================================*/
+
+use std::path::{Path, PathBuf};
+
+use crate::config::{PathStyle, PathsConfig};
+
+/// Rewrites paths according to `paths.style`.
+#[derive(Debug, Clone, Default)]
+pub struct PathFormatter {
+    style: PathStyle,
+    /// Prefix stripped under `relative`.
+    base: Option<PathBuf>,
+    /// Prefix replaced by `~` under `redacted`.
+    home: Option<PathBuf>,
+}
+
+impl PathFormatter {
+    pub fn new(style: PathStyle, base: Option<PathBuf>, home: Option<PathBuf>) -> Self {
+        Self { style, base, home }
+    }
+
+    /// Formatter for the configuration; `relative` defaults its base to the home directory.
+    pub fn from_config(config: &PathsConfig) -> Self {
+        let home = dirs::home_dir();
+        let base = config
+            .base
+            .as_ref()
+            .map(PathBuf::from)
+            .or_else(|| home.clone());
+        Self::new(config.style, base, home)
+    }
+
+    /// Render one path.
+    pub fn format_path(&self, path: &Path) -> String {
+        let Some((prefix, replacement)) = self.prefix() else {
+            return path.display().to_string();
+        };
+        match path.strip_prefix(prefix) {
+            Ok(rest) if rest.as_os_str().is_empty() => replacement.to_string(),
+            Ok(rest) if self.style == PathStyle::Relative => rest.display().to_string(),
+            Ok(rest) => format!("{replacement}/{}", rest.display()),
+            Err(_) => path.display().to_string(),
+        }
+    }
+
+    /// Rewrite every path under the prefix inside free text (log messages).
+    pub fn rewrite(&self, text: &str) -> String {
+        let Some((prefix, replacement)) = self.prefix() else {
+            return text.to_string();
+        };
+        let prefix = format!("{}/", prefix.display());
+        let replacement = match self.style {
+            PathStyle::Relative => String::new(),
+            _ => format!("{replacement}/"),
+        };
+        text.replace(&prefix, &replacement)
+    }
+
+    /// Prefix to rewrite and what a bare prefix becomes, if the style rewrites anything.
+    fn prefix(&self) -> Option<(&Path, &'static str)> {
+        let (prefix, replacement) = match self.style {
+            PathStyle::Absolute => return None,
+            PathStyle::Relative => (self.base.as_deref()?, "."),
+            PathStyle::Redacted => (self.home.as_deref()?, "~"),
+        };
+        // A root prefix would rewrite every path on the system.
+        (prefix.parent().is_some()).then_some((prefix, replacement))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn formatter(style: PathStyle) -> PathFormatter {
+        PathFormatter::new(
+            style,
+            Some(PathBuf::from("/home/ada/sync")),
+            Some(PathBuf::from("/home/ada")),
+        )
+    }
+
+    #[test]
+    fn each_style_renders_sample_paths() {
+        let manifest = Path::new("/home/ada/sync/manifest.json");
+        let config = Path::new("/home/ada/.config/syn-syu/config.toml");
+        let system = Path::new("/etc/pacman.conf");
+
+        let absolute = formatter(PathStyle::Absolute);
+        assert_eq!(
+            absolute.format_path(manifest),
+            "/home/ada/sync/manifest.json"
+        );
+        assert_eq!(absolute.format_path(system), "/etc/pacman.conf");
+
+        let relative = formatter(PathStyle::Relative);
+        assert_eq!(relative.format_path(manifest), "manifest.json");
+        assert_eq!(relative.format_path(Path::new("/home/ada/sync")), ".");
+        assert_eq!(
+            relative.format_path(config),
+            "/home/ada/.config/syn-syu/config.toml"
+        );
+
+        let redacted = formatter(PathStyle::Redacted);
+        assert_eq!(redacted.format_path(manifest), "~/sync/manifest.json");
+        assert_eq!(
+            redacted.format_path(config),
+            "~/.config/syn-syu/config.toml"
+        );
+        assert_eq!(redacted.format_path(Path::new("/home/ada")), "~");
+        assert_eq!(
+            redacted.format_path(Path::new("/home/adam/x")),
+            "/home/adam/x"
+        );
+        assert_eq!(redacted.format_path(system), "/etc/pacman.conf");
+    }
+
+    #[test]
+    fn log_text_is_rewritten_per_style() {
+        let message =
+            "Manifest written to /home/ada/sync/manifest.json (config /home/ada/.config/a.toml)";
+        assert_eq!(formatter(PathStyle::Absolute).rewrite(message), message);
+        assert_eq!(
+            formatter(PathStyle::Relative).rewrite(message),
+            "Manifest written to manifest.json (config /home/ada/.config/a.toml)"
+        );
+        assert_eq!(
+            formatter(PathStyle::Redacted).rewrite(message),
+            "Manifest written to ~/sync/manifest.json (config ~/.config/a.toml)"
+        );
+        assert_eq!(
+            formatter(PathStyle::Redacted).rewrite("/home/adam/file"),
+            "/home/adam/file"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::pkg_cache
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Inspect the pacman package cache for update candidates that
+    are already downloaded, partially downloaded, or corrupt.
+
+  Security / Safety Notes:
+    Read-only directory listing; cached files are never opened
+    or modified.
+
+  Dependencies:
+    std::fs for directory enumeration.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core when `--check-pkg-cache` is set to
+    refine the real download budget of a manifest.
+
+  Revision History:
+    2026-10-18 COD  Introduced package cache inspection.
+    2026-10-18 COD  Allowed for pacman's rounded download sizes.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Read-only inspection of system state
+    - Unreadable caches degrade to a warning
+    - Deterministic per-package verdicts
+============================================================*/
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::logger::Logger;
+use crate::manifest::{ManifestDocument, PackageSource};
+use crate::pacman::pacman_size_tolerance;
+
+/// Cache inspection totals recorded in manifest metadata.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
+pub struct PkgCacheSummary {
+    pub directory: String,
+    pub checked: usize,
+    pub cached: usize,
+    pub mismatched: usize,
+    /// Download bytes still required once valid cached files are discounted.
+    pub download_bytes_remaining: u64,
+}
+
+/// Cache verdict for one candidate package file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStatus {
+    pub cached: bool,
+    pub mismatch: bool,
+}
+
+/// Annotate repo update candidates with their cache state; returns `None` if the cache is unreadable.
+pub fn check_pkg_cache(
+    document: &mut ManifestDocument,
+    cache_dir: &Path,
+    logger: &Logger,
+) -> Option<PkgCacheSummary> {
+    let files = match list_cache(cache_dir) {
+        Ok(files) => files,
+        Err(err) => {
+            logger.warn(
+                "PKGCACHE",
+                format!(
+                    "Package cache {} unreadable; skipping check: {err}",
+                    cache_dir.display()
+                ),
+            );
+            return None;
+        }
+    };
+
+    let mut summary = PkgCacheSummary {
+        directory: cache_dir.display().to_string(),
+        ..PkgCacheSummary::default()
+    };
+    for (name, entry) in document.packages.iter_mut() {
+        if !entry.update_available || entry.candidate_source != Some(PackageSource::Pacman) {
+            continue;
+        }
+        let Some(version) = entry.available_version.as_deref() else {
+            continue;
+        };
+        let status = cache_status(&files, name, version, entry.download_size_selected);
+        summary.checked += 1;
+        if status.cached {
+            summary.cached += 1;
+        }
+        if status.mismatch {
+            summary.mismatched += 1;
+            logger.warn(
+                "PKGCACHE",
+                format!("{name} {version}: cached package is partial or size-mismatched"),
+            );
+        }
+        if !status.cached || status.mismatch {
+            summary.download_bytes_remaining += entry.download_size_selected.unwrap_or(0);
+        }
+        entry.cached = Some(status.cached);
+        entry.cache_mismatch = Some(status.mismatch);
+    }
+    Some(summary)
+}
+
+/// Judge the cached file for `name`/`version` against the expected download size.
+///
+/// `expected` comes from pacman's rounded "Download Size", so sizes within its
+/// rounding step count as a match.
+pub fn cache_status(
+    files: &[(String, u64)],
+    name: &str,
+    version: &str,
+    expected: Option<u64>,
+) -> CacheStatus {
+    let prefix = format!("{name}-{version}-");
+    let mut status = CacheStatus {
+        cached: false,
+        mismatch: false,
+    };
+    for (file, size) in files {
+        let Some(rest) = file.strip_prefix(&prefix) else {
+            continue;
+        };
+        // The remainder must be `<arch>.pkg.tar[.ext]`; anything else is another package.
+        let Some((arch, ext)) = rest.split_once(".pkg.tar") else {
+            continue;
+        };
+        if arch.is_empty() || arch.contains('-') || ext.ends_with(".sig") {
+            continue;
+        }
+        status.cached = true;
+        let partial = ext.ends_with(".part");
+        let size_differs =
+            expected.is_some_and(|bytes| bytes.abs_diff(*size) > pacman_size_tolerance(bytes));
+        if !partial && !size_differs {
+            return CacheStatus {
+                cached: true,
+                mismatch: false,
+            };
+        }
+        status.mismatch = true;
+    }
+    status
+}
+
+fn list_cache(dir: &Path) -> std::io::Result<Vec<(String, u64)>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str() {
+            files.push((name.to_string(), metadata.len()));
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures;
+
+    fn scratch(name: &str) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("synsyu-pkgcache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn candidate(installed: &str, candidate: &str, size: u64) -> crate::manifest::ManifestEntry {
+        let mut entry = fixtures::entry(installed, PackageSource::Pacman, Some(candidate));
+        entry.download_size_selected = Some(size);
+        entry
+    }
+
+    #[test]
+    fn matching_and_mismatched_files_are_reported() {
+        let dir = scratch("mixed");
+        fs::write(dir.join("bash-5.2.37-1-x86_64.pkg.tar.zst"), vec![0u8; 32]).unwrap();
+        fs::write(
+            dir.join("bash-5.2.37-1-x86_64.pkg.tar.zst.sig"),
+            vec![0u8; 5],
+        )
+        .unwrap();
+        fs::write(dir.join("zsh-5.9-5-x86_64.pkg.tar.zst"), vec![0u8; 10]).unwrap();
+        fs::write(
+            dir.join("curl-8.10.1-2-x86_64.pkg.tar.zst.part"),
+            vec![0u8; 4],
+        )
+        .unwrap();
+
+        let mut document = fixtures::document(vec![
+            ("bash", candidate("5.2.32-1", "5.2.37-1", 32)),
+            ("zsh", candidate("5.9-4", "5.9-5", 64)),
+            ("curl", candidate("8.10.1-1", "8.10.1-2", 40)),
+            ("vim", candidate("9.1.0-1", "9.1.1-1", 100)),
+        ]);
+        let logger = Logger::new(None, false).unwrap();
+        let summary = check_pkg_cache(&mut document, &dir, &logger).expect("cache readable");
+
+        let verdict = |name: &str| {
+            let entry = &document.packages[name];
+            (entry.cached, entry.cache_mismatch)
+        };
+        assert_eq!(verdict("bash"), (Some(true), Some(false)));
+        assert_eq!(verdict("zsh"), (Some(true), Some(true)));
+        assert_eq!(verdict("curl"), (Some(true), Some(true)));
+        assert_eq!(verdict("vim"), (Some(false), Some(false)));
+        assert_eq!(summary.checked, 4);
+        assert_eq!(summary.cached, 3);
+        assert_eq!(summary.mismatched, 2);
+        assert_eq!(summary.download_bytes_remaining, 64 + 40 + 100);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn rounded_download_sizes_match_real_file_sizes() {
+        use crate::pacman::parse_pacman_size;
+
+        let expected = parse_pacman_size("1.80 MiB");
+        let files = vec![
+            ("bash-5.2.37-1-x86_64.pkg.tar.zst".to_string(), 1_884_000),
+            ("zsh-5.9-5-x86_64.pkg.tar.zst".to_string(), 1_700_000),
+            ("less-668-1-x86_64.pkg.tar.zst".to_string(), 831_843),
+        ];
+        let ok = CacheStatus {
+            cached: true,
+            mismatch: false,
+        };
+        assert_eq!(cache_status(&files, "bash", "5.2.37-1", expected), ok);
+        // Truncated well below what "1.80 MiB" can round from.
+        assert!(cache_status(&files, "zsh", "5.9-5", expected).mismatch);
+        // pacman renders this file size as "1839.84 KiB".
+        let expected = parse_pacman_size("1839.84 KiB");
+        assert_eq!(cache_status(&files, "bash", "5.2.37-1", expected), ok);
+        // 831843 B renders as "812.35 KiB", which parses back to 831846 B.
+        let expected = parse_pacman_size("812.35 KiB");
+        assert_eq!(cache_status(&files, "less", "668-1", expected), ok);
+        assert!(cache_status(&files, "less", "668-1", expected.map(|b| b + 64)).mismatch);
+    }
+
+    #[test]
+    fn similarly_named_packages_do_not_match() {
+        let files = vec![("bash-completion-2.14.0-1-any.pkg.tar.zst".to_string(), 10)];
+        let status = cache_status(&files, "bash", "2.14.0-1", Some(10));
+        assert!(!status.cached);
+        let status = cache_status(&files, "bash-completion", "2.14.0-1", Some(10));
+        assert_eq!(
+            status,
+            CacheStatus {
+                cached: true,
+                mismatch: false
+            }
+        );
+    }
+}
@@ -13,9 +13,9 @@ use crate::fwupd::collect_fwupd_updates_for_plan;
 
 #[derive(Debug, Args, Clone)]
 pub struct PlanCommand {
-    /// Override configuration file path.
-    #[arg(long, value_name = "PATH")]
-    pub config: Option<PathBuf>,
+    /// Configuration file path; repeat to layer overrides (later files win).
+    #[arg(long, value_name = "PATH", action = ArgAction::Append)]
+    pub config: Vec<PathBuf>,
     /// Override manifest input path.
     #[arg(long, value_name = "PATH")]
     pub manifest: Option<PathBuf>,
@@ -52,11 +52,7 @@ pub struct PlanOutput {
 }
 
 impl PlanCommand {
-    pub async fn execute(
-        &self,
-        config: &SynsyuConfig,
-        plan_path: PathBuf,
-    ) -> Result<PlanOutput> {
+    pub async fn execute(&self, config: &SynsyuConfig, plan_path: PathBuf) -> Result<PlanOutput> {
         let mut errors: Vec<String> = Vec::new();
         let mut sources: Vec<String> = Vec::new();
 
@@ -0,0 +1,406 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::policy
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Describe the fully-resolved candidate selection rules so
+    operators can verify them before a run.
+
+  Security / Safety Notes:
+    Pure projection of merged configuration and CLI flags; no
+    I/O performed in this module.
+
+  Dependencies:
+    serde for the JSON form.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core `--explain-policy`.
+
+  Revision History:
+    2026-10-18 COD  Introduced policy explanation.
+    2026-10-18 COD  Listed per-package source overrides.
+    2026-10-18 COD  Listed ignores, always-includes and expected_repo.
+    2026-10-18 COD  Listed pacman.conf IgnorePkg/IgnoreGroup hold-backs.
+    2026-10-18 COD  Listed stability and AUR update gates.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Single source of truth: derived from the merged config
+    - Human and machine renderings of the same data
+============================================================*/
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::config::{SourcePolicy, StabilityConfig, SynsyuConfig};
+use crate::pacman_conf::IgnoreRules;
+
+/// Run flags that shape the policy on top of the merged configuration.
+#[derive(Debug, Default)]
+pub struct PolicyFlags<'a> {
+    /// `--package` allowlist.
+    pub allowlist: &'a [String],
+    /// `--always` additions to `core.always_include`.
+    pub always: &'a [String],
+    /// `--ignore` additions to `core.ignore`.
+    pub ignore: &'a [String],
+    pub missing_packages: &'a str,
+    pub offline: bool,
+    pub strict_expected_repo: bool,
+    /// Rules loaded from `core.pacman_conf_path`; `None` under `--no-respect-ignore`.
+    pub pacman_ignore: Option<&'a IgnoreRules>,
+    pub only_stable: bool,
+    pub skip_orphaned_aur: bool,
+    pub skip_out_of_date: bool,
+    pub min_aur_popularity: Option<f64>,
+}
+
+/// `[stability]` thresholds and whether updates failing them are withheld.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct StabilityGates {
+    pub min_age_days: u64,
+    pub min_aur_votes: u64,
+    pub min_aur_popularity: f64,
+    pub reject_out_of_date: bool,
+    pub require_maintainer: bool,
+    /// `--only-stable`: unstable updates are withheld instead of only noted.
+    pub only_stable: bool,
+}
+
+impl StabilityGates {
+    fn new(config: &StabilityConfig, only_stable: bool) -> Self {
+        Self {
+            min_age_days: config.min_age_days,
+            min_aur_votes: config.min_aur_votes,
+            min_aur_popularity: config.min_aur_popularity,
+            reject_out_of_date: config.reject_out_of_date,
+            require_maintainer: config.require_maintainer,
+            only_stable,
+        }
+    }
+
+    fn render(&self) -> String {
+        let yes_no = |flag: bool| if flag { "yes" } else { "no" };
+        format!(
+            "min_age_days={} min_aur_votes={} min_aur_popularity={} reject_out_of_date={} require_maintainer={}; {}",
+            self.min_age_days,
+            self.min_aur_votes,
+            self.min_aur_popularity,
+            yes_no(self.reject_out_of_date),
+            yes_no(self.require_maintainer),
+            if self.only_stable {
+                "failing updates withheld (--only-stable)"
+            } else {
+                "failing updates noted only"
+            }
+        )
+    }
+}
+
+/// Resolution rules in effect for a manifest run.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct PolicyExplanation {
+    pub source_policy: String,
+    pub source_policy_rule: String,
+    /// Packages the run is limited to; empty means every installed package.
+    pub allowlist: Vec<String>,
+    /// Packages added even outside the allowlist (`core.always_include` plus `--always`).
+    pub always_include: Vec<String>,
+    /// Packages left out of the manifest (`core.ignore` plus `--ignore`); wins over `always_include`.
+    pub ignore: Vec<String>,
+    pub missing_packages: String,
+    pub aur_lookups: bool,
+    /// Packages pinned to a source by `core.source_override`.
+    pub source_overrides: BTreeMap<String, String>,
+    /// Packages that must come from a sync repository, never the AUR.
+    pub expected_repo: Vec<String>,
+    /// Whether a missing `expected_repo` package fails the run instead of warning.
+    pub expected_repo_strict: bool,
+    /// Whether pacman.conf hold-backs apply (`--no-respect-ignore` turns them off).
+    pub respect_ignore: bool,
+    pub pacman_conf_path: String,
+    /// `IgnorePkg` patterns; matching packages are held back, not dropped.
+    pub ignore_pkg: Vec<String>,
+    /// `IgnoreGroup` patterns; members of matching groups are held back.
+    pub ignore_group: Vec<String>,
+    pub stability: StabilityGates,
+    /// `--skip-orphaned-aur`: maintainer-less AUR updates are withheld.
+    pub skip_orphaned_aur: bool,
+    /// `--skip-out-of-date`: AUR updates flagged out of date are withheld.
+    pub skip_out_of_date: bool,
+    /// `--min-aur-popularity`: AUR candidates below this score warn (LOWPOP).
+    pub min_aur_popularity: Option<f64>,
+}
+
+/// Resolve the effective policy from merged configuration and run flags.
+pub fn explain(config: &SynsyuConfig, flags: &PolicyFlags) -> PolicyExplanation {
+    PolicyExplanation {
+        source_policy: config.core.source_policy.to_string(),
+        source_policy_rule: source_rule(config.core.source_policy).to_string(),
+        allowlist: sorted(flags.allowlist.iter()),
+        always_include: sorted(config.core.always_include.iter().chain(flags.always)),
+        ignore: sorted(config.core.ignore.iter().chain(flags.ignore)),
+        missing_packages: flags.missing_packages.to_string(),
+        aur_lookups: !flags.offline,
+        source_overrides: config
+            .core
+            .source_override
+            .iter()
+            .map(|(name, forced)| (name.clone(), forced.to_string()))
+            .collect(),
+        expected_repo: sorted(config.core.expected_repo.iter()),
+        expected_repo_strict: flags.strict_expected_repo,
+        respect_ignore: flags.pacman_ignore.is_some(),
+        pacman_conf_path: config.core.pacman_conf_path.clone(),
+        ignore_pkg: flags
+            .pacman_ignore
+            .map(|rules| rules.packages.clone())
+            .unwrap_or_default(),
+        ignore_group: flags
+            .pacman_ignore
+            .map(|rules| rules.groups.clone())
+            .unwrap_or_default(),
+        stability: StabilityGates::new(&config.stability, flags.only_stable),
+        skip_orphaned_aur: flags.skip_orphaned_aur,
+        skip_out_of_date: flags.skip_out_of_date,
+        min_aur_popularity: flags.min_aur_popularity,
+    }
+}
+
+fn sorted<'a>(names: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut names: Vec<String> = names.cloned().collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn list_or_none(names: &[String]) -> String {
+    if names.is_empty() {
+        "<none>".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+fn source_rule(policy: SourcePolicy) -> &'static str {
+    match policy {
+        SourcePolicy::Repo => "repo candidate unless the AUR is strictly newer",
+        SourcePolicy::Aur => "AUR candidate whenever one exists; repo as fallback",
+        SourcePolicy::Newer => "higher version wins; ties keep the installed source",
+    }
+}
+
+impl PolicyExplanation {
+    /// Human-readable rendering, one rule per line.
+    pub fn render(&self) -> String {
+        let allowlist = if self.allowlist.is_empty() {
+            "<all installed packages>".to_string()
+        } else {
+            self.allowlist.join(", ")
+        };
+        let aur = if self.aur_lookups {
+            "enabled"
+        } else {
+            "disabled (offline)"
+        };
+        let overrides = if self.source_overrides.is_empty() {
+            "<none>".to_string()
+        } else {
+            self.source_overrides
+                .iter()
+                .map(|(name, forced)| format!("{name}={forced}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let expected_repo = if self.expected_repo.is_empty() {
+            "<none>".to_string()
+        } else if self.expected_repo_strict {
+            format!("{} (missing fails the run)", self.expected_repo.join(", "))
+        } else {
+            format!("{} (missing warns REPOMISS)", self.expected_repo.join(", "))
+        };
+        let held = if self.respect_ignore {
+            format!(
+                "IgnorePkg={} IgnoreGroup={} (from {})",
+                list_or_none(&self.ignore_pkg),
+                list_or_none(&self.ignore_group),
+                self.pacman_conf_path
+            )
+        } else {
+            "not applied (--no-respect-ignore)".to_string()
+        };
+        let withheld: Vec<String> = [
+            (self.skip_orphaned_aur, "orphaned (--skip-orphaned-aur)"),
+            (self.skip_out_of_date, "out of date (--skip-out-of-date)"),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, gate)| gate.to_string())
+        .collect();
+        let popularity = match self.min_aur_popularity {
+            Some(minimum) => format!("warn (LOWPOP) below {minimum}"),
+            None => "<none>".to_string(),
+        };
+        format!(
+            "Source policy : {} ({})\nOverrides     : {}\nExpected repo : {}\nAllowlist     : {}\nAlways        : {}\nIgnored       : {}\nHeld back     : {}\nStability     : {}\nAUR withheld  : {}\nAUR popularity: {}\nMissing pkgs  : {}\nAUR lookups   : {}\n",
+            self.source_policy,
+            self.source_policy_rule,
+            overrides,
+            expected_repo,
+            allowlist,
+            list_or_none(&self.always_include),
+            list_or_none(&self.ignore),
+            held,
+            self.stability.render(),
+            list_or_none(&withheld),
+            popularity,
+            self.missing_packages,
+            aur
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_source_policy_and_allowlist_are_explained() {
+        let mut config = SynsyuConfig::default();
+        config.core.source_policy = SourcePolicy::Aur;
+        config
+            .core
+            .source_override
+            .insert("brave-bin".into(), crate::config::SourceOverride::Aur);
+        let packages = vec!["zsh".to_string(), "bash".to_string(), "zsh".to_string()];
+        let explanation = explain(
+            &config,
+            &PolicyFlags {
+                allowlist: &packages,
+                missing_packages: "error",
+                offline: true,
+                ..PolicyFlags::default()
+            },
+        );
+
+        assert_eq!(explanation.source_policy, "prefer_aur");
+        assert_eq!(explanation.allowlist, vec!["bash", "zsh"]);
+        let text = explanation.render();
+        assert!(text.contains("Source policy : prefer_aur (AUR candidate whenever one exists"));
+        assert!(text.contains("Overrides     : brave-bin=aur"));
+        assert!(text.contains("Allowlist     : bash, zsh"));
+        assert!(text.contains("Missing pkgs  : error"));
+        assert!(text.contains("AUR lookups   : disabled (offline)"));
+
+        let json = serde_json::to_value(&explanation).unwrap();
+        assert_eq!(json["source_policy"], "prefer_aur");
+        assert_eq!(json["allowlist"][1], "zsh");
+    }
+
+    #[test]
+    fn ignores_always_includes_and_expected_repo_are_explained() {
+        let mut config = SynsyuConfig::default();
+        config.core.ignore = vec!["linux".into()];
+        config.core.always_include = vec!["base".into()];
+        config.core.expected_repo = vec!["pacman".into(), "glibc".into()];
+        let always = vec!["base".to_string(), "zsh".to_string()];
+        let ignore = vec!["firefox".to_string()];
+        let explanation = explain(
+            &config,
+            &PolicyFlags {
+                always: &always,
+                ignore: &ignore,
+                missing_packages: "warn",
+                strict_expected_repo: true,
+                ..PolicyFlags::default()
+            },
+        );
+
+        assert_eq!(explanation.always_include, vec!["base", "zsh"]);
+        assert_eq!(explanation.ignore, vec!["firefox", "linux"]);
+        let text = explanation.render();
+        assert!(text.contains("Always        : base, zsh"));
+        assert!(text.contains("Ignored       : firefox, linux"));
+        assert!(text.contains("Expected repo : glibc, pacman (missing fails the run)"));
+
+        let json = serde_json::to_value(&explanation).unwrap();
+        assert_eq!(json["expected_repo_strict"], true);
+    }
+
+    #[test]
+    fn pacman_conf_hold_backs_are_explained_unless_disabled() {
+        let mut config = SynsyuConfig::default();
+        config.core.pacman_conf_path = "/etc/pacman.conf".into();
+        let rules = crate::pacman_conf::parse_ignore_rules(
+            "[options]\nIgnorePkg = linux nvidia-*\nIgnoreGroup = gnome\n",
+        );
+        let flags = PolicyFlags {
+            missing_packages: "warn",
+            pacman_ignore: Some(&rules),
+            ..PolicyFlags::default()
+        };
+        let explanation = explain(&config, &flags);
+
+        assert!(explanation.respect_ignore);
+        assert_eq!(explanation.ignore_pkg, vec!["linux", "nvidia-*"]);
+        assert_eq!(explanation.ignore_group, vec!["gnome"]);
+        assert!(explanation.render().contains(
+            "Held back     : IgnorePkg=linux, nvidia-* IgnoreGroup=gnome (from /etc/pacman.conf)"
+        ));
+
+        let disabled = explain(
+            &config,
+            &PolicyFlags {
+                pacman_ignore: None,
+                ..flags
+            },
+        );
+        assert!(!disabled.respect_ignore);
+        assert!(disabled.ignore_pkg.is_empty());
+        assert!(disabled
+            .render()
+            .contains("Held back     : not applied (--no-respect-ignore)"));
+    }
+
+    #[test]
+    fn stability_and_aur_gates_are_explained() {
+        let mut config = SynsyuConfig::default();
+        config.stability.min_age_days = 7;
+        config.stability.min_aur_votes = 10;
+        config.stability.require_maintainer = false;
+        let flags = PolicyFlags {
+            missing_packages: "warn",
+            ..PolicyFlags::default()
+        };
+        let text = explain(&config, &flags).render();
+        assert!(text.contains(
+            "Stability     : min_age_days=7 min_aur_votes=10 min_aur_popularity=0 \
+             reject_out_of_date=yes require_maintainer=no; failing updates noted only"
+        ));
+        assert!(text.contains("AUR withheld  : <none>"));
+        assert!(text.contains("AUR popularity: <none>"));
+
+        let explanation = explain(
+            &config,
+            &PolicyFlags {
+                only_stable: true,
+                skip_orphaned_aur: true,
+                skip_out_of_date: true,
+                min_aur_popularity: Some(0.5),
+                ..flags
+            },
+        );
+        let text = explanation.render();
+        assert!(text.contains("failing updates withheld (--only-stable)"));
+        assert!(text.contains(
+            "AUR withheld  : orphaned (--skip-orphaned-aur), out of date (--skip-out-of-date)"
+        ));
+        assert!(text.contains("AUR popularity: warn (LOWPOP) below 0.5"));
+
+        let json = serde_json::to_value(&explanation).unwrap();
+        assert_eq!(json["stability"]["min_age_days"], 7);
+        assert_eq!(json["stability"]["only_stable"], true);
+        assert_eq!(json["min_aur_popularity"], 0.5);
+    }
+}
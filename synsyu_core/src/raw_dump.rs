@@ -0,0 +1,120 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::raw_dump
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Persist raw backend output (pacman stdout, AUR RPC JSON) so
+    operators can attach evidence to bug reports.
+
+  Security / Safety Notes:
+    Output is written verbatim and unredacted into an operator-
+    chosen local directory; nothing is transmitted.
+
+  Dependencies:
+    std::fs for file creation.
+
+  Operational Scope:
+    Enabled by Syn-Syu-Core `--dump-raw <dir>`; shared by the
+    pacman and AUR backends during a manifest run.
+
+  Revision History:
+    2026-10-18 COD  Introduced raw backend response capture.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Evidence captured exactly as received
+    - Capture order preserved in file names
+============================================================*/
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error::{Result, SynsyuError};
+
+/// Directory receiving one file per captured backend response.
+#[derive(Debug)]
+pub struct RawDump {
+    dir: PathBuf,
+    sequence: AtomicUsize,
+}
+
+impl RawDump {
+    /// Prepare `dir` (created if needed) for raw captures.
+    pub fn create(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir).map_err(|err| {
+            SynsyuError::Filesystem(format!(
+                "Failed to create raw dump directory {}: {err}",
+                dir.display()
+            ))
+        })?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            sequence: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write `contents` as `<seq>-<source>.<extension>`; returns the file path.
+    pub fn record(&self, source: &str, extension: &str, contents: &[u8]) -> Result<PathBuf> {
+        let path = self.next_path(source, extension);
+        fs::write(&path, contents).map_err(|err| write_error(&path, err))?;
+        Ok(path)
+    }
+
+    /// Open the next capture file for output that arrives incrementally.
+    pub fn create_file(&self, source: &str, extension: &str) -> Result<File> {
+        let path = self.next_path(source, extension);
+        File::create(&path).map_err(|err| write_error(&path, err))
+    }
+
+    fn next_path(&self, source: &str, extension: &str) -> PathBuf {
+        let seq = self.sequence.fetch_add(1, Ordering::SeqCst) + 1;
+        self.dir.join(format!("{seq:03}-{source}.{extension}"))
+    }
+}
+
+fn write_error(path: &Path, err: std::io::Error) -> SynsyuError {
+    SynsyuError::Filesystem(format!(
+        "Failed to write raw dump {}: {err}",
+        path.display()
+    ))
+}
+
+/// Capture `contents` when a dump is active.
+pub fn capture(
+    dump: Option<&RawDump>,
+    source: &str,
+    extension: &str,
+    contents: &[u8],
+) -> Result<()> {
+    if let Some(dump) = dump {
+        dump.record(source, extension, contents)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_are_numbered_in_order() {
+        let dir = std::env::temp_dir().join(format!("synsyu-rawdump-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let dump = RawDump::create(&dir).unwrap();
+
+        let qi = b"Name            : bash\nVersion         : 5.2.037-1\n\n";
+        capture(Some(&dump), "pacman-Qi", "txt", qi).unwrap();
+        capture(Some(&dump), "pacman-Si", "txt", b"").unwrap();
+        capture(None, "pacman-Si", "txt", b"ignored").unwrap();
+
+        assert_eq!(fs::read(dir.join("001-pacman-Qi.txt")).unwrap(), qi);
+        assert!(dir.join("002-pacman-Si.txt").exists());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
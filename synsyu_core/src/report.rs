@@ -0,0 +1,687 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::report
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Derive focused, machine-readable projections of the
+    manifest for downstream tooling.
+
+  Security / Safety Notes:
+    Pure transformations over manifest data; no I/O performed
+    in this module.
+
+  Dependencies:
+    serde for serializable report structures.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core report flags to feed AUR helpers
+    and build tooling without re-parsing the full manifest.
+
+  Revision History:
+    2026-10-18 COD  Added AUR update report projection.
+    2026-10-18 COD  Added AUR helper handoff command lines.
+    2026-10-18 COD  Added pacman-compatible package list output.
+    2026-10-18 COD  Added updates-by-repository view.
+    2026-10-18 COD  Added versioned display report for terminal UIs.
+    2026-10-18 COD  Added unknown-source package listing with reasons.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Deterministic ordering inherited from the manifest
+    - Narrow data contracts for external consumers
+============================================================*/
+
+use std::collections::BTreeMap;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::config::web_base_url;
+use crate::manifest::{
+    ManifestDocument, ManifestEntry, PackageSource, NOTE_AUR_NOT_FOUND, NOTE_TIMED_OUT,
+};
+use crate::space::format_bytes;
+
+/// AUR update candidates suitable for driving a downstream build tool.
+#[derive(Debug, Serialize)]
+pub struct AurReport {
+    pub base_url: String,
+    pub packages: Vec<AurReportEntry>,
+}
+
+/// Single AUR update candidate.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct AurReportEntry {
+    pub name: String,
+    pub installed: String,
+    pub candidate: String,
+    pub url_path: Option<String>,
+    /// `base_url` joined with `url_path`, when the AUR supplied a path.
+    pub url: Option<String>,
+}
+
+/// Project AUR packages with pending updates out of the manifest.
+pub fn aur_report(document: &ManifestDocument, rpc_base_url: &str) -> AurReport {
+    let base_url = web_base_url(rpc_base_url);
+    let packages = document
+        .packages
+        .iter()
+        .filter(|(_, entry)| {
+            entry.candidate_source == Some(PackageSource::Aur) && entry.update_available
+        })
+        .map(|(name, entry)| AurReportEntry {
+            name: name.clone(),
+            installed: entry.installed_version.clone(),
+            candidate: entry
+                .newer_version
+                .clone()
+                .or_else(|| entry.available_version.clone())
+                .unwrap_or_default(),
+            url_path: entry.url_path.clone(),
+            url: entry
+                .url_path
+                .as_deref()
+                .map(|path| join_url(&base_url, path)),
+        })
+        .collect();
+    AurReport { base_url, packages }
+}
+
+/// AUR helpers that can perform the manifest's updates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HelperKind {
+    Paru,
+    Yay,
+}
+
+impl HelperKind {
+    fn binary(self) -> &'static str {
+        match self {
+            HelperKind::Paru => "paru",
+            HelperKind::Yay => "yay",
+        }
+    }
+}
+
+/// Helper invocations (argv) performing pending updates: repo targets first, then AUR targets.
+pub fn helper_commands(document: &ManifestDocument, helper: HelperKind) -> Vec<Vec<String>> {
+    let mut repo = Vec::new();
+    let mut aur = Vec::new();
+    for (name, entry) in &document.packages {
+        if !entry.update_available {
+            continue;
+        }
+        match entry.candidate_source {
+            Some(PackageSource::Pacman) => repo.push(name.clone()),
+            Some(PackageSource::Aur) => aur.push(name.clone()),
+            _ => {}
+        }
+    }
+
+    let mut commands = Vec::new();
+    for (scope, targets) in [("--repo", repo), ("--aur", aur)] {
+        if targets.is_empty() {
+            continue;
+        }
+        let mut argv: Vec<String> = [helper.binary(), "-S", scope, "--needed"]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        argv.extend(targets);
+        commands.push(argv);
+    }
+    commands
+}
+
+/// Render repo update candidates one per line for `pacman -S -`.
+pub fn pkglist(document: &ManifestDocument, with_versions: bool, header: bool) -> String {
+    let mut out = String::new();
+    if header {
+        out.push_str(&format!(
+            "# Generated by synsyu_core at {}\n",
+            document.metadata.generated_at
+        ));
+    }
+    for (name, entry) in &document.packages {
+        if !entry.update_available || entry.candidate_source != Some(PackageSource::Pacman) {
+            continue;
+        }
+        match entry.available_version.as_deref().filter(|_| with_versions) {
+            Some(version) => out.push_str(&format!("{name}={version}\n")),
+            None => out.push_str(&format!("{name}\n")),
+        }
+    }
+    out
+}
+
+/// Render pending updates per repository, largest first.
+pub fn updates_by_repo_view(document: &ManifestDocument) -> String {
+    let mut groups: Vec<(&String, &usize)> = document.metadata.updates_by_repo.iter().collect();
+    groups.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    let width = groups.iter().map(|(repo, _)| repo.len()).max().unwrap_or(0);
+    let mut out = format!(
+        "→ Updates by repository ({} total)\n",
+        document.metadata.updates_available
+    );
+    for (repo, count) in groups {
+        out.push_str(&format!("  {repo:<width$}  {count}\n"));
+    }
+    out
+}
+
+/// Best guess why a package's source stayed `unknown`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UnknownReason {
+    /// Resolution hit `--timeout-per-package` before a source was found.
+    TimedOut,
+    /// The AUR was queried and has no package of that name.
+    NotFoundInAur,
+    /// pacman recorded no repository for the installed package.
+    NoRepositoryField,
+    /// The recorded repository is not one pacman syncs.
+    NotInSyncRepos,
+}
+
+impl UnknownReason {
+    pub fn describe(self) -> &'static str {
+        match self {
+            UnknownReason::TimedOut => "resolution timed out",
+            UnknownReason::NotFoundInAur => "not found in AUR",
+            UnknownReason::NoRepositoryField => "no repository field",
+            UnknownReason::NotInSyncRepos => "not in any configured repo",
+        }
+    }
+}
+
+/// Classify an `unknown`-source entry; `None` for every other source.
+pub fn unknown_reason(entry: &ManifestEntry) -> Option<UnknownReason> {
+    if entry.source != PackageSource::Unknown {
+        return None;
+    }
+    let noted = |prefix: &str| entry.notes.iter().any(|note| note.starts_with(prefix));
+    Some(if noted(NOTE_TIMED_OUT) {
+        UnknownReason::TimedOut
+    } else if entry.candidate_source.is_none() && noted(NOTE_AUR_NOT_FOUND) {
+        UnknownReason::NotFoundInAur
+    } else if entry.repository.is_none() {
+        UnknownReason::NoRepositoryField
+    } else {
+        UnknownReason::NotInSyncRepos
+    })
+}
+
+/// List `unknown`-source packages with installed versions and reasons (`--report-unknown`).
+pub fn unknown_view(document: &ManifestDocument) -> String {
+    let unknown: Vec<(&String, &ManifestEntry, UnknownReason)> = document
+        .packages
+        .iter()
+        .filter_map(|(name, entry)| Some((name, entry, unknown_reason(entry)?)))
+        .collect();
+    let name_width = unknown
+        .iter()
+        .map(|(name, ..)| name.len())
+        .max()
+        .unwrap_or(0);
+    let version_width = unknown
+        .iter()
+        .map(|(_, entry, _)| entry.installed_version.len())
+        .max()
+        .unwrap_or(0);
+    let mut out = format!(
+        "→ Packages with unknown source ({})\n",
+        document.metadata.unknown_packages
+    );
+    for (name, entry, reason) in unknown {
+        out.push_str(&format!(
+            "  {name:<name_width$}  {:<version_width$}  {}\n",
+            entry.installed_version,
+            reason.describe()
+        ));
+    }
+    out
+}
+
+/// Bumped whenever a display report field changes meaning or disappears.
+pub const DISPLAY_REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Read-only projection of the manifest shaped for rendering (`--report`).
+#[derive(Debug, Serialize)]
+pub struct DisplayReport {
+    pub schema_version: u32,
+    pub generated_at: String,
+    pub summary: DisplaySummary,
+    /// One group per installed source, in `PackageSource` order.
+    pub groups: Vec<DisplayGroup>,
+    /// Warnings of the run, collapsed per log code.
+    pub warnings: Vec<WarningSummary>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DisplaySummary {
+    pub total_packages: usize,
+    pub updates_available: usize,
+    pub download_size_total: DisplaySize,
+    pub installed_size_total: DisplaySize,
+    pub installed_size_after_updates: DisplaySize,
+}
+
+/// Byte count paired with its IEC rendering.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct DisplaySize {
+    pub bytes: u64,
+    pub human: String,
+}
+
+impl DisplaySize {
+    pub fn new(bytes: u64) -> Self {
+        Self {
+            bytes,
+            human: format_bytes(bytes),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DisplayGroup {
+    pub source: PackageSource,
+    pub updates: usize,
+    /// Sorted by `priority`, then name.
+    pub packages: Vec<DisplayEntry>,
+}
+
+/// How urgent an entry is for display: lower sorts first.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum DisplayPriority {
+    /// New upstream version (epoch or pkgver changed).
+    Upgrade,
+    /// Rebuild of the same version (pkgrel only).
+    Rebuild,
+    Current,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DisplayEntry {
+    pub name: String,
+    pub priority: DisplayPriority,
+    pub installed: String,
+    pub candidate: Option<String>,
+    pub download_size: Option<DisplaySize>,
+    pub installed_size: Option<DisplaySize>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+impl DisplayEntry {
+    fn from_entry(name: &str, entry: &ManifestEntry) -> Self {
+        let priority = match (entry.update_available, entry.pkgrel_only) {
+            (false, _) => DisplayPriority::Current,
+            (true, true) => DisplayPriority::Rebuild,
+            (true, false) => DisplayPriority::Upgrade,
+        };
+        Self {
+            name: name.to_string(),
+            priority,
+            installed: entry.installed_version.clone(),
+            candidate: entry.available_version.clone(),
+            download_size: entry.download_size_selected.map(DisplaySize::new),
+            installed_size: entry.installed_size.map(DisplaySize::new),
+            notes: entry.notes.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct WarningSummary {
+    pub code: String,
+    pub count: usize,
+    /// First message logged under the code.
+    pub first: String,
+}
+
+/// Build the display report from the manifest and the run's `[CODE] message` warnings.
+pub fn display_report(document: &ManifestDocument, warnings: &[String]) -> DisplayReport {
+    let mut grouped: BTreeMap<PackageSource, Vec<DisplayEntry>> = BTreeMap::new();
+    let mut download_total = 0;
+    for (name, entry) in &document.packages {
+        if entry.update_available {
+            download_total += entry.download_size_selected.unwrap_or(0);
+        }
+        grouped
+            .entry(entry.source)
+            .or_default()
+            .push(DisplayEntry::from_entry(name, entry));
+    }
+    let groups = grouped
+        .into_iter()
+        .map(|(source, mut packages)| {
+            packages.sort_by(|a, b| {
+                a.priority
+                    .cmp(&b.priority)
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+            DisplayGroup {
+                source,
+                updates: packages
+                    .iter()
+                    .filter(|entry| entry.priority != DisplayPriority::Current)
+                    .count(),
+                packages,
+            }
+        })
+        .collect();
+
+    let metadata = &document.metadata;
+    DisplayReport {
+        schema_version: DISPLAY_REPORT_SCHEMA_VERSION,
+        generated_at: metadata.generated_at.clone(),
+        summary: DisplaySummary {
+            total_packages: metadata.total_packages,
+            updates_available: metadata.updates_available,
+            download_size_total: DisplaySize::new(download_total),
+            installed_size_total: DisplaySize::new(metadata.installed_size_total),
+            installed_size_after_updates: DisplaySize::new(metadata.installed_size_after_updates),
+        },
+        groups,
+        warnings: summarize_warnings(warnings),
+    }
+}
+
+/// Collapse `[CODE] message` lines per code, keeping first-seen order.
+fn summarize_warnings(warnings: &[String]) -> Vec<WarningSummary> {
+    let mut summaries: Vec<WarningSummary> = Vec::new();
+    for warning in warnings {
+        let (code, message) = warning
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once("] "))
+            .unwrap_or(("", warning.as_str()));
+        match summaries.iter_mut().find(|summary| summary.code == code) {
+            Some(summary) => summary.count += 1,
+            None => summaries.push(WarningSummary {
+                code: code.to_string(),
+                count: 1,
+                first: message.to_string(),
+            }),
+        }
+    }
+    summaries
+}
+
+fn join_url(base: &str, path: &str) -> String {
+    if path.starts_with("http://") || path.starts_with("https://") {
+        path.to_string()
+    } else if path.starts_with('/') {
+        format!("{base}{path}")
+    } else {
+        format!("{base}/{path}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures::{document, entry};
+
+    #[test]
+    fn unknown_packages_are_listed_with_reasons() {
+        let mut unsynced = entry("2.1-1", PackageSource::Unknown, None);
+        unsynced.repository = Some("retired-repo".into());
+        let mut no_repo = entry("0.9-3", PackageSource::Unknown, None);
+        no_repo.repository = None;
+        let mut ghost = entry("1.4-1", PackageSource::Unknown, None);
+        ghost.repository = None;
+        ghost.notes.push(NOTE_AUR_NOT_FOUND.into());
+        let mut slow = entry("3.0-1", PackageSource::Unknown, None);
+        slow.repository = None;
+        slow.notes.push(format!("{NOTE_TIMED_OUT} 120s"));
+        let doc = document(vec![
+            ("unsynced", unsynced),
+            ("no-repo", no_repo),
+            ("ghost", ghost),
+            ("slow", slow),
+            ("bash", entry("5.2.37-1", PackageSource::Pacman, None)),
+        ]);
+
+        let reasons: Vec<Option<UnknownReason>> =
+            doc.packages.values().map(unknown_reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                None,
+                Some(UnknownReason::NotFoundInAur),
+                Some(UnknownReason::NoRepositoryField),
+                Some(UnknownReason::TimedOut),
+                Some(UnknownReason::NotInSyncRepos),
+            ]
+        );
+        assert_eq!(
+            unknown_view(&doc),
+            "→ Packages with unknown source (4)\n\
+             \x20 ghost     1.4-1  not found in AUR\n\
+             \x20 no-repo   0.9-3  no repository field\n\
+             \x20 slow      3.0-1  resolution timed out\n\
+             \x20 unsynced  2.1-1  not in any configured repo\n"
+        );
+    }
+
+    #[test]
+    fn aur_report_includes_only_aur_candidates() {
+        let mut paru = entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1"));
+        paru.url_path = Some("/cgit/aur.git/snapshot/paru.tar.gz".into());
+        let mut current = entry("1.0-1", PackageSource::Aur, Some("1.0-1"));
+        current.url_path = Some("/cgit/aur.git/snapshot/current.tar.gz".into());
+        let doc = document(vec![
+            ("paru", paru),
+            ("current", current),
+            (
+                "bash",
+                entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1")),
+            ),
+            ("custom", entry("0.1-1", PackageSource::Local, None)),
+        ]);
+
+        let report = aur_report(&doc, "https://aur.archlinux.org/rpc/");
+        assert_eq!(report.base_url, "https://aur.archlinux.org");
+        assert_eq!(
+            report.packages,
+            vec![AurReportEntry {
+                name: "paru".into(),
+                installed: "2.0.3-1".into(),
+                candidate: "2.0.4-1".into(),
+                url_path: Some("/cgit/aur.git/snapshot/paru.tar.gz".into()),
+                url: Some("https://aur.archlinux.org/cgit/aur.git/snapshot/paru.tar.gz".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn helper_commands_separate_repo_and_aur_targets() {
+        let doc = document(vec![
+            (
+                "paru",
+                entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1")),
+            ),
+            (
+                "bash",
+                entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1")),
+            ),
+            ("zsh", entry("5.9-4", PackageSource::Pacman, Some("5.9-5"))),
+            (
+                "yay-bin",
+                entry("12.4.2-1", PackageSource::Aur, Some("12.4.2-1")),
+            ),
+            ("custom", entry("0.1-1", PackageSource::Local, None)),
+        ]);
+
+        let render = |helper| {
+            helper_commands(&doc, helper)
+                .into_iter()
+                .map(|argv| argv.join(" "))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            render(HelperKind::Paru),
+            vec![
+                "paru -S --repo --needed bash zsh",
+                "paru -S --aur --needed paru",
+            ]
+        );
+        assert_eq!(
+            render(HelperKind::Yay),
+            vec![
+                "yay -S --repo --needed bash zsh",
+                "yay -S --aur --needed paru",
+            ]
+        );
+    }
+
+    #[test]
+    fn helper_commands_are_empty_without_updates() {
+        let doc = document(vec![(
+            "bash",
+            entry("5.2.37-1", PackageSource::Pacman, Some("5.2.37-1")),
+        )]);
+        assert!(helper_commands(&doc, HelperKind::Paru).is_empty());
+    }
+
+    #[test]
+    fn pkglist_lists_repo_updates_for_pacman() {
+        let doc = document(vec![
+            ("zsh", entry("5.9-4", PackageSource::Pacman, Some("5.9-5"))),
+            (
+                "bash",
+                entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1")),
+            ),
+            (
+                "curl",
+                entry("8.10.1-1", PackageSource::Pacman, Some("8.10.1-1")),
+            ),
+            (
+                "paru",
+                entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1")),
+            ),
+        ]);
+
+        assert_eq!(pkglist(&doc, false, false), "bash\nzsh\n");
+        assert_eq!(
+            pkglist(&doc, true, true),
+            "# Generated by synsyu_core at 2026-10-18T00:00:00Z\nbash=5.2.37-1\nzsh=5.9-5\n"
+        );
+    }
+
+    #[test]
+    fn updates_are_counted_per_repository() {
+        let repo = |installed: &str, candidate: &str, repo_name: &str| {
+            let mut e = entry(installed, PackageSource::Pacman, Some(candidate));
+            e.repo_name = Some(repo_name.to_string());
+            e
+        };
+        let doc = document(vec![
+            ("linux", repo("6.11.1-1", "6.11.2-1", "core")),
+            ("bash", repo("5.2.32-1", "5.2.37-1", "core")),
+            ("firefox", repo("131.0-1", "131.0.2-1", "extra")),
+            ("steam", repo("1.0.0.80-1", "1.0.0.81-1", "multilib")),
+            ("mesa", repo("24.2.3-1", "24.2.4-1", "extra")),
+            ("vim", repo("9.1.0-1", "9.1.0-1", "extra")),
+            (
+                "paru",
+                entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1")),
+            ),
+        ]);
+
+        let counts: Vec<(&str, usize)> = doc
+            .metadata
+            .updates_by_repo
+            .iter()
+            .map(|(repo, count)| (repo.as_str(), *count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![("aur", 1), ("core", 2), ("extra", 2), ("multilib", 1)]
+        );
+        assert_eq!(
+            updates_by_repo_view(&doc),
+            "→ Updates by repository (6 total)\n  core      2\n  extra     2\n  aur       1\n  multilib  1\n"
+        );
+    }
+
+    #[test]
+    fn display_report_groups_by_source_and_formats_sizes() {
+        let mut bash = entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1"));
+        bash.download_size_selected = Some(1_572_864);
+        bash.installed_size = Some(9_437_184);
+        let mut glibc = entry("2.40-1", PackageSource::Pacman, Some("2.40-2"));
+        glibc.download_size_selected = Some(512);
+        let mut paru = entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1"));
+        paru.download_size_selected = Some(2048);
+        let doc = document(vec![
+            (
+                "zlib",
+                entry("1.3.1-1", PackageSource::Pacman, Some("1.3.1-1")),
+            ),
+            ("glibc", glibc),
+            ("bash", bash),
+            ("paru", paru),
+        ]);
+        let warnings = vec![
+            "[AURBADVER] broken: ignoring malformed AUR version".to_string(),
+            "[SPACE] low disk".to_string(),
+            "[AURBADVER] other: ignoring malformed AUR version".to_string(),
+        ];
+
+        let report = display_report(&doc, &warnings);
+        assert_eq!(report.schema_version, DISPLAY_REPORT_SCHEMA_VERSION);
+        let sources: Vec<PackageSource> = report.groups.iter().map(|group| group.source).collect();
+        assert_eq!(sources, [PackageSource::Pacman, PackageSource::Aur]);
+
+        let pacman = &report.groups[0];
+        assert_eq!(pacman.updates, 2);
+        let order: Vec<(&str, DisplayPriority)> = pacman
+            .packages
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.priority))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                ("bash", DisplayPriority::Upgrade),
+                ("glibc", DisplayPriority::Rebuild),
+                ("zlib", DisplayPriority::Current),
+            ]
+        );
+        let bash = &pacman.packages[0];
+        assert_eq!(
+            bash.download_size,
+            Some(DisplaySize {
+                bytes: 1_572_864,
+                human: "1.5 MiB".into()
+            })
+        );
+        assert_eq!(bash.installed_size.as_ref().unwrap().human, "9 MiB");
+        assert_eq!(
+            pacman.packages[1].download_size.as_ref().unwrap().human,
+            "512 B"
+        );
+        assert_eq!(
+            report.summary.download_size_total.bytes,
+            1_572_864 + 512 + 2048
+        );
+        assert_eq!(report.summary.download_size_total.human, "1.5 MiB");
+
+        assert_eq!(
+            report.warnings,
+            vec![
+                WarningSummary {
+                    code: "AURBADVER".into(),
+                    count: 2,
+                    first: "broken: ignoring malformed AUR version".into(),
+                },
+                WarningSummary {
+                    code: "SPACE".into(),
+                    count: 1,
+                    first: "low disk".into(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,335 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::selection
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Narrow the installed package set to the packages a run
+    resolves: allowlist, always-include, ignore, sample, limit.
+
+  Security / Safety Notes:
+    Pure list manipulation over enumerated packages; no I/O
+    beyond logging.
+
+  Dependencies:
+    crate::rng for seeded sampling.
+
+  Operational Scope:
+    Applied by `generate::generate` for both the CLI run and
+    library callers.
+
+  Revision History:
+    2026-10-18 COD  Moved package selection out of the binary.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Selections are returned sorted by name
+    - Sampling is reproducible from the run seed
+============================================================*/
+
+use std::collections::HashSet;
+
+use clap::ValueEnum;
+
+use crate::audit;
+use crate::error::{Result, SynsyuError};
+use crate::logger::Logger;
+use crate::pacman::InstalledPackage;
+use crate::rng::SeededRng;
+
+/// Behavior when requested package names are not installed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum MissingPackages {
+    /// Log a warning and continue with the installed subset.
+    #[default]
+    Warn,
+    /// Abort with a dedicated exit code.
+    Error,
+    /// Continue silently.
+    Ignore,
+}
+
+/// Order deciding which packages a limit keeps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LimitSort {
+    /// Alphabetical by package name.
+    #[default]
+    Name,
+    /// Largest installed size first.
+    InstalledSize,
+    /// Most recently installed or upgraded first.
+    InstallDate,
+}
+
+/// Size of a random sample drawn from the selection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sample {
+    Count(usize),
+    Percent(f64),
+}
+
+impl Sample {
+    /// Packages to draw from `population`; a percentage draws at least one.
+    pub fn size(self, population: usize) -> usize {
+        match self {
+            Sample::Count(count) => count.min(population),
+            Sample::Percent(percent) => {
+                ((population as f64 * percent / 100.0).ceil() as usize).clamp(1, population)
+            }
+        }
+    }
+
+    pub fn percent(self) -> Option<f64> {
+        match self {
+            Sample::Count(_) => None,
+            Sample::Percent(percent) => Some(percent),
+        }
+    }
+}
+
+/// Keep the `requested` packages (all when empty), reacting to absent names per `on_missing`.
+pub fn filter_packages(
+    installed: &mut [InstalledPackage],
+    requested: &[String],
+    on_missing: MissingPackages,
+    logger: &Logger,
+) -> Result<Vec<InstalledPackage>> {
+    installed.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if requested.is_empty() {
+        return Ok(installed.to_vec());
+    }
+
+    let mut requested_set: HashSet<String> = HashSet::new();
+    for pkg in requested {
+        requested_set.insert(pkg.to_string());
+    }
+
+    let mut selected = Vec::new();
+    for pkg in installed.iter() {
+        if requested_set.contains(&pkg.name) {
+            selected.push(pkg.clone());
+        }
+    }
+
+    let mut missing: Vec<String> = requested_set
+        .into_iter()
+        .filter(|name| !selected.iter().any(|pkg| &pkg.name == name))
+        .collect();
+    missing.sort();
+
+    if !missing.is_empty() {
+        match on_missing {
+            MissingPackages::Warn => logger.warn(
+                "PKG404",
+                format!("Requested packages not installed: {}", missing.join(", ")),
+            ),
+            MissingPackages::Error => return Err(SynsyuError::MissingPackages(missing)),
+            MissingPackages::Ignore => {}
+        }
+    }
+
+    Ok(selected)
+}
+
+/// Add always-included packages to `selected`, then drop ignored ones (ignore wins).
+pub fn apply_always_and_ignore(
+    installed: &[InstalledPackage],
+    mut selected: Vec<InstalledPackage>,
+    always: &[String],
+    ignore: &[String],
+    logger: &Logger,
+) -> Vec<InstalledPackage> {
+    let ignored: HashSet<&str> = ignore.iter().map(String::as_str).collect();
+    for name in always {
+        if ignored.contains(name.as_str()) {
+            logger.warn(
+                "CONFLICT",
+                format!("{name} is both always-included and ignored; ignore wins"),
+            );
+            continue;
+        }
+        if selected.iter().any(|pkg| &pkg.name == name) {
+            continue;
+        }
+        match installed.iter().find(|pkg| &pkg.name == name) {
+            Some(pkg) => selected.push(pkg.clone()),
+            None => logger.debug("ALWAYS", format!("{name} is not installed; skipping")),
+        }
+    }
+    selected.retain(|pkg| !ignored.contains(pkg.name.as_str()));
+    selected.sort_by(|a, b| a.name.cmp(&b.name));
+    selected
+}
+
+/// Draw `size` packages at random with `seed`, returned sorted by name.
+pub fn apply_sample(
+    selected: Vec<InstalledPackage>,
+    size: usize,
+    seed: u64,
+) -> Vec<InstalledPackage> {
+    let mut sample = SeededRng::new(seed).sample(selected, size);
+    sample.sort_by(|a, b| a.name.cmp(&b.name));
+    sample
+}
+
+/// Keep the first `limit` packages in `key` order (ties by name), returned sorted by name.
+pub fn apply_limit(
+    mut selected: Vec<InstalledPackage>,
+    limit: usize,
+    key: LimitSort,
+) -> Vec<InstalledPackage> {
+    match key {
+        LimitSort::Name => selected.sort_by(|a, b| a.name.cmp(&b.name)),
+        LimitSort::InstalledSize => selected.sort_by(|a, b| {
+            b.installed_size
+                .cmp(&a.installed_size)
+                .then_with(|| a.name.cmp(&b.name))
+        }),
+        LimitSort::InstallDate => selected.sort_by_cached_key(|pkg| {
+            let installed = pkg
+                .install_date
+                .as_deref()
+                .and_then(audit::parse_install_date);
+            (std::cmp::Reverse(installed), pkg.name.clone())
+        }),
+    }
+    selected.truncate(limit);
+    selected.sort_by(|a, b| a.name.cmp(&b.name));
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::ExitCode;
+
+    use super::*;
+
+    fn installed(name: &str) -> InstalledPackage {
+        InstalledPackage {
+            name: name.into(),
+            version: "1.0-1".into(),
+            repository: Some("pacman".into()),
+            installed_size: None,
+            install_date: None,
+            validated_by: None,
+            package_hash: None,
+            required_by: Vec::new(),
+            provides: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn requested() -> Vec<String> {
+        ["zsh", "ghost", "bash", "phantom"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    fn names(selected: &[InstalledPackage]) -> Vec<&str> {
+        selected.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    #[test]
+    fn missing_packages_warn_and_ignore_keep_installed_subset() {
+        let logger = Logger::new(None, false).unwrap();
+        for mode in [MissingPackages::Warn, MissingPackages::Ignore] {
+            let mut pkgs = vec![installed("zsh"), installed("bash"), installed("vim")];
+            let selected = filter_packages(&mut pkgs, &requested(), mode, &logger)
+                .expect("non-fatal modes should succeed");
+            assert_eq!(names(&selected), vec!["bash", "zsh"]);
+        }
+    }
+
+    #[test]
+    fn missing_packages_error_lists_absent_names() {
+        let logger = Logger::new(None, false).unwrap();
+        let mut pkgs = vec![installed("zsh"), installed("bash")];
+        let err = filter_packages(&mut pkgs, &requested(), MissingPackages::Error, &logger)
+            .expect_err("absent packages should be fatal");
+        match &err {
+            SynsyuError::MissingPackages(missing) => {
+                assert_eq!(missing, &vec!["ghost".to_string(), "phantom".to_string()])
+            }
+            other => panic!("unexpected error {other:?}"),
+        }
+        assert_eq!(err.exit_code(), ExitCode::from(21));
+    }
+
+    #[test]
+    fn missing_packages_error_passes_when_all_present() {
+        let logger = Logger::new(None, false).unwrap();
+        let mut pkgs = vec![installed("zsh"), installed("bash")];
+        let wanted = vec!["bash".to_string()];
+        let selected = filter_packages(&mut pkgs, &wanted, MissingPackages::Error, &logger)
+            .expect("all requested packages are installed");
+        assert_eq!(names(&selected), vec!["bash"]);
+    }
+
+    #[test]
+    fn seeded_samples_are_sized_and_reproducible() {
+        let pkgs: Vec<InstalledPackage> = (0..200)
+            .map(|index| installed(&format!("pkg-{index:03}")))
+            .collect();
+        let first = apply_sample(pkgs.clone(), 20, 1234);
+        assert_eq!(first.len(), 20);
+        let mut sorted = names(&first);
+        sorted.sort();
+        assert_eq!(names(&first), sorted);
+        assert_eq!(names(&apply_sample(pkgs.clone(), 20, 1234)), names(&first));
+        assert_ne!(names(&apply_sample(pkgs.clone(), 20, 4321)), names(&first));
+
+        assert_eq!(Sample::Count(500).size(200), 200);
+        assert_eq!(Sample::Percent(10.0).size(200), 20);
+        assert_eq!(Sample::Percent(0.1).size(200), 1);
+    }
+
+    #[test]
+    fn limit_keeps_the_first_packages_by_key() {
+        let sized = |name: &str, size: u64, date: Option<&str>| InstalledPackage {
+            installed_size: Some(size),
+            install_date: date.map(str::to_string),
+            ..installed(name)
+        };
+        let pkgs = vec![
+            sized("zsh", 5, Some("2026-01-05T21:14:09Z")),
+            sized("linux", 140, Some("2026-03-01T08:00:00Z")),
+            sized("bash", 9, None),
+            sized("vim", 40, Some("2025-12-24T10:00:00Z")),
+        ];
+        let by_name = apply_limit(pkgs.clone(), 2, LimitSort::Name);
+        assert_eq!(names(&by_name), vec!["bash", "linux"]);
+        let by_size = apply_limit(pkgs.clone(), 2, LimitSort::InstalledSize);
+        assert_eq!(names(&by_size), vec!["linux", "vim"]);
+        let by_date = apply_limit(pkgs.clone(), 3, LimitSort::InstallDate);
+        assert_eq!(names(&by_date), vec!["linux", "vim", "zsh"]);
+        assert_eq!(apply_limit(pkgs, 10, LimitSort::Name).len(), 4);
+    }
+
+    #[test]
+    fn always_include_survives_allowlist_but_loses_to_ignore() {
+        let logger = Logger::new(None, false).unwrap();
+        let mut pkgs = vec![
+            installed("zsh"),
+            installed("linux"),
+            installed("systemd"),
+            installed("vim"),
+        ];
+        let wanted = vec!["zsh".to_string()];
+        let selected = filter_packages(&mut pkgs, &wanted, MissingPackages::Warn, &logger).unwrap();
+        assert_eq!(names(&selected), vec!["zsh"]);
+
+        let always = vec![
+            "linux".to_string(),
+            "systemd".to_string(),
+            "ghost".to_string(),
+        ];
+        let ignore = vec!["systemd".to_string()];
+        let selected = apply_always_and_ignore(&pkgs, selected, &always, &ignore, &logger);
+        assert_eq!(names(&selected), vec!["linux", "zsh"]);
+
+        let everything = filter_packages(&mut pkgs, &[], MissingPackages::Warn, &logger).unwrap();
+        let selected = apply_always_and_ignore(&pkgs, everything, &always, &ignore, &logger);
+        assert_eq!(names(&selected), vec!["linux", "vim", "zsh"]);
+    }
+}
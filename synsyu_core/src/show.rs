@@ -0,0 +1,297 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::show
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Render a saved manifest as a human report (`show`):
+    grouped by source, sizes humanized, pending updates
+    highlighted.
+
+  Security / Safety Notes:
+    Pure formatting over a parsed manifest; escape sequences
+    are only emitted when color is enabled.
+
+  Dependencies:
+    clap for the sort and color selectors.
+
+  Operational Scope:
+    Used by the `show` subcommand on manifests from this or
+    other machines; never queries pacman or the AUR.
+
+  Revision History:
+    2026-10-18 COD  Introduced the human manifest report.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Same grouping order as the display report
+    - Color is opt-in by terminal detection and NO_COLOR
+============================================================*/
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+
+use clap::ValueEnum;
+
+use crate::manifest::{ManifestDocument, ManifestEntry, PackageSource};
+use crate::space::format_bytes;
+
+const BOLD_GREEN: &str = "1;32";
+const YELLOW: &str = "33";
+const DIM: &str = "2";
+
+/// Order of packages within each source group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ShowSort {
+    Name,
+    /// Largest download first; packages without one follow by name.
+    DownloadSize,
+    /// Largest installed footprint first.
+    InstalledSize,
+}
+
+/// When to emit ANSI color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Only on a terminal, and not when `NO_COLOR` is set.
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::io::stdout().is_terminal()
+                    && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ShowOptions {
+    pub only_updates: bool,
+    pub sort: ShowSort,
+    pub color: bool,
+}
+
+/// The whole report, newline-terminated.
+pub fn render(document: &ManifestDocument, options: ShowOptions) -> String {
+    let paint = |text: &str, code: &str| {
+        if options.color {
+            format!("\x1b[{code}m{text}\x1b[0m")
+        } else {
+            text.to_string()
+        }
+    };
+    let metadata = &document.metadata;
+    let download: u64 = document
+        .packages
+        .values()
+        .filter(|entry| entry.update_available)
+        .filter_map(|entry| entry.download_size_selected)
+        .sum();
+    let mut out = format!(
+        "Manifest generated {} by {}\n",
+        metadata.generated_at, metadata.generated_by
+    );
+    out.push_str(&format!(
+        "{} package(s): pacman={} aur={} local={} unknown={}; {} to download\n",
+        metadata.total_packages,
+        metadata.pacman_packages,
+        metadata.aur_packages,
+        metadata.local_packages,
+        metadata.unknown_packages,
+        format_bytes(download)
+    ));
+    let updates = format!("{} update(s) available", metadata.updates_available);
+    if metadata.updates_available > 0 {
+        out.push_str(&paint(&updates, BOLD_GREEN));
+    } else {
+        out.push_str(&updates);
+    }
+    out.push('\n');
+    if metadata.truncated {
+        out.push_str(&paint("truncated: --max-runtime expired mid-run\n", YELLOW));
+    }
+    if metadata.limited {
+        out.push_str(&paint(
+            &format!(
+                "limited: {} of {} package(s) listed\n",
+                metadata.total_packages, metadata.total_available
+            ),
+            YELLOW,
+        ));
+    }
+
+    let mut grouped: BTreeMap<PackageSource, Vec<(&str, &ManifestEntry)>> = BTreeMap::new();
+    for (name, entry) in &document.packages {
+        if options.only_updates && !entry.update_available {
+            continue;
+        }
+        grouped
+            .entry(entry.source)
+            .or_default()
+            .push((name.as_str(), entry));
+    }
+    for (source, mut entries) in grouped {
+        match options.sort {
+            ShowSort::Name => {}
+            ShowSort::DownloadSize => {
+                entries.sort_by_key(|(_, entry)| Reverse(entry.download_size_selected))
+            }
+            ShowSort::InstalledSize => {
+                entries.sort_by_key(|(_, entry)| Reverse(entry.installed_size))
+            }
+        }
+        let pending = entries.iter().filter(|(_, e)| e.update_available).count();
+        out.push_str(&format!(
+            "\n{} — {} package(s), {pending} update(s)\n",
+            source.as_str(),
+            entries.len()
+        ));
+        let rows: Vec<(&str, String, &ManifestEntry)> = entries
+            .into_iter()
+            .map(|(name, entry)| (name, versions(entry), entry))
+            .collect();
+        let name_width = rows.iter().map(|(name, ..)| name.len()).max().unwrap_or(0);
+        let version_width = rows.iter().map(|(_, v, _)| v.len()).max().unwrap_or(0);
+        for (name, versions, entry) in rows {
+            let marker = if entry.update_available { '↑' } else { ' ' };
+            let line = format!(
+                "  {marker} {name:<name_width$}  {versions:<version_width$}  {}",
+                sizes(entry)
+            );
+            let line = line.trim_end();
+            if entry.update_available {
+                out.push_str(&paint(line, BOLD_GREEN));
+            } else {
+                out.push_str(line);
+            }
+            out.push('\n');
+            for note in &entry.notes {
+                out.push_str(&paint(&format!("      · {note}"), DIM));
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// `installed`, or `installed -> candidate` (with a rebuild marker) for pending updates.
+fn versions(entry: &ManifestEntry) -> String {
+    match entry
+        .newer_version
+        .as_deref()
+        .filter(|_| entry.update_available)
+    {
+        Some(candidate) if entry.pkgrel_only => {
+            format!("{} -> {candidate} (rebuild)", entry.installed_version)
+        }
+        Some(candidate) => format!("{} -> {candidate}", entry.installed_version),
+        None => entry.installed_version.clone(),
+    }
+}
+
+/// Download size of a pending update (`~` when estimated) and the installed footprint.
+fn sizes(entry: &ManifestEntry) -> String {
+    let mut parts = Vec::new();
+    if let Some(bytes) = entry
+        .download_size_selected
+        .filter(|_| entry.update_available)
+    {
+        let approx = if entry.download_size_estimated {
+            "~"
+        } else {
+            ""
+        };
+        parts.push(format!("download {approx}{}", format_bytes(bytes)));
+    }
+    if let Some(bytes) = entry.installed_size {
+        parts.push(format!("{} installed", format_bytes(bytes)));
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures::{document, entry};
+
+    fn sample() -> ManifestDocument {
+        let mut bash = entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1"));
+        bash.download_size_selected = Some(1_800_000);
+        bash.installed_size = Some(9_000_000);
+        let mut zlib = entry("1.3-1", PackageSource::Pacman, Some("1.3-1"));
+        zlib.installed_size = Some(400_000);
+        let mut paru = entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1"));
+        paru.download_size_selected = Some(3_000);
+        paru.download_size_estimated = true;
+        let mut ghost = entry("1.0-1", PackageSource::Unknown, None);
+        ghost.notes.push("not found in AUR".into());
+        document(vec![
+            ("bash", bash),
+            ("zlib", zlib),
+            ("paru", paru),
+            ("ghost", ghost),
+        ])
+    }
+
+    fn options(only_updates: bool, sort: ShowSort, color: bool) -> ShowOptions {
+        ShowOptions {
+            only_updates,
+            sort,
+            color,
+        }
+    }
+
+    #[test]
+    fn report_groups_by_source_and_humanizes_sizes() {
+        let text = render(&sample(), options(false, ShowSort::Name, false));
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Manifest generated 2026-10-18T00:00:00Z by synsyu_core"
+        );
+        assert!(lines.contains(&"2 update(s) available"), "{text}");
+        assert!(
+            lines.contains(&"pacman — 2 package(s), 1 update(s)"),
+            "{text}"
+        );
+        assert!(
+            lines.contains(&"  ↑ bash  5.2.32-1 -> 5.2.37-1  download 1.7 MiB, 8.6 MiB installed")
+        );
+        assert!(lines.contains(&"    zlib  1.3-1                 391 KiB installed"));
+        assert!(lines.contains(&"  ↑ paru  2.0.3-1 -> 2.0.4-1  download ~2.9 KiB"));
+        assert!(lines.contains(&"      · not found in AUR"));
+        let pacman = text.find("\npacman").unwrap();
+        let aur = text.find("\naur").unwrap();
+        let unknown = text.find("\nunknown").unwrap();
+        assert!(pacman < aur && aur < unknown);
+        assert!(!text.contains('\x1b'));
+    }
+
+    #[test]
+    fn only_updates_sorting_and_color_are_respected() {
+        let text = render(&sample(), options(true, ShowSort::Name, false));
+        assert!(!text.contains("zlib") && !text.contains("ghost"), "{text}");
+        assert!(text.contains("pacman — 1 package(s), 1 update(s)"));
+        assert!(!text.contains("\nunknown"));
+
+        let text = render(&sample(), options(false, ShowSort::InstalledSize, false));
+        assert!(text.find("bash").unwrap() < text.find("zlib").unwrap());
+        let mut doc = sample();
+        doc.packages.get_mut("zlib").unwrap().installed_size = Some(90_000_000);
+        let text = render(&doc, options(false, ShowSort::InstalledSize, false));
+        assert!(text.find("zlib").unwrap() < text.find("bash").unwrap());
+
+        let text = render(&sample(), options(false, ShowSort::Name, true));
+        assert!(text.contains("\x1b[1;32m  ↑ paru"), "{text}");
+        assert!(text.contains("    zlib"));
+        assert!(!text.contains("\x1b[1;32m    zlib"));
+    }
+}
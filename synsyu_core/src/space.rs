@@ -112,6 +112,7 @@ pub fn format_bytes(bytes: u64) -> String {
 }
 
 /// Validate that sufficient space exists; returns a descriptive error message on failure.
+#[allow(dead_code)]
 pub fn ensure_capacity(
     report: &SpaceReport,
     required_bytes: u64,
@@ -0,0 +1,264 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::split
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Split a manifest into one document per package source
+    plus an index (`--split-by-source`).
+
+  Security / Safety Notes:
+    Parts are written with the same private permissions as
+    the main manifest.
+
+  Dependencies:
+    serde for the index structure.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core after the manifest is built, for
+    systems where a single manifest grows unwieldy.
+
+  Revision History:
+    2026-10-18 COD  Introduced per-source manifest parts.
+    2026-10-18 COD  Honoured --canonical for every written part.
+    2026-10-18 COD  Parts count updates from the entries alone.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Every part is a complete, valid ManifestDocument
+    - Index totals are derived from the parts themselves
+============================================================*/
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::manifest::{
+    write_manifest, write_manifest_canonical, Applications, ManifestDocument, ManifestFormat,
+    PackageGroup, PackageSource, SymlinkGuard, NOTE_TIMED_OUT,
+};
+
+/// File name of the split index.
+pub const INDEX_FILE: &str = "index.json";
+
+/// `index.json`: one line per part plus totals across all parts.
+#[derive(Debug, Serialize)]
+pub struct SplitIndex {
+    pub generated_at: String,
+    pub generated_by: String,
+    pub total_packages: usize,
+    pub updates_available: usize,
+    pub parts: Vec<SplitPart>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct SplitPart {
+    pub source: PackageSource,
+    pub file: String,
+    pub total_packages: usize,
+    pub updates_available: usize,
+    pub download_size_exact: u64,
+    pub download_size_estimated: u64,
+}
+
+/// The manifest restricted to `source`, with metadata recomputed for that subset.
+///
+/// Application state describes the whole system, so parts leave it out.
+pub fn part(document: &ManifestDocument, source: PackageSource) -> ManifestDocument {
+    let packages: BTreeMap<_, _> = document
+        .packages
+        .iter()
+        .filter(|(_, entry)| entry.source == source)
+        .map(|(name, entry)| (name.clone(), entry.clone()))
+        .collect();
+    let mut metadata = document.metadata.clone();
+    let count = |wanted: PackageSource| if wanted == source { packages.len() } else { 0 };
+    metadata.total_packages = packages.len();
+    metadata.pacman_packages = count(PackageSource::Pacman);
+    metadata.aur_packages = count(PackageSource::Aur);
+    metadata.local_packages = count(PackageSource::Local);
+    metadata.unknown_packages = count(PackageSource::Unknown);
+    // `--ignore-pkgrel-only` already withdrew rebuilds from the entries;
+    // `pkgrel_only_ignored` carries over with the rest of the metadata.
+    metadata.updates_available = packages.values().filter(|e| e.update_available).count();
+    metadata.pkgrel_only_updates = packages.values().filter(|e| e.pkgrel_only).count();
+    metadata.timed_out_count = packages
+        .values()
+        .filter(|e| e.notes.iter().any(|note| note.starts_with(NOTE_TIMED_OUT)))
+        .count();
+    metadata.reboot_required = packages.values().any(|e| e.reboot_required);
+    metadata.apps_flatpak = None;
+    metadata.apps_fwupd = None;
+    metadata.application_state = None;
+
+    let packages_by_source = if packages.is_empty() {
+        Vec::new()
+    } else {
+        vec![PackageGroup {
+            source,
+            count: packages.len(),
+            packages: packages.keys().cloned().collect(),
+        }]
+    };
+    let mut part = ManifestDocument {
+        metadata,
+        packages,
+        packages_by_source,
+        applications: Applications::default(),
+    };
+    part.refresh_updates_by_repo();
+    part.refresh_size_totals();
+    part
+}
+
+/// Build every part (empty sources included) and the index describing them.
+pub fn split(document: &ManifestDocument) -> (Vec<(String, ManifestDocument)>, SplitIndex) {
+    let parts: Vec<(String, ManifestDocument)> = PackageSource::ALL
+        .into_iter()
+        .map(|source| (format!("{}.json", source.as_str()), part(document, source)))
+        .collect();
+    let entries: Vec<SplitPart> = parts
+        .iter()
+        .zip(PackageSource::ALL)
+        .map(|((file, doc), source)| SplitPart {
+            source,
+            file: file.clone(),
+            total_packages: doc.metadata.total_packages,
+            updates_available: doc.metadata.updates_available,
+            download_size_exact: doc.metadata.download_size_exact,
+            download_size_estimated: doc.metadata.download_size_estimated,
+        })
+        .collect();
+    let index = SplitIndex {
+        generated_at: document.metadata.generated_at.clone(),
+        generated_by: document.metadata.generated_by.clone(),
+        total_packages: entries.iter().map(|p| p.total_packages).sum(),
+        updates_available: entries.iter().map(|p| p.updates_available).sum(),
+        parts: entries,
+    };
+    (parts, index)
+}
+
+/// Write `<source>.json` for every source and `index.json` into `dir`.
+pub fn write_split(
+    document: &ManifestDocument,
+    dir: &Path,
+    canonical: bool,
+    guard: &SymlinkGuard,
+) -> Result<SplitIndex> {
+    let (parts, index) = split(document);
+    for (file, part) in &parts {
+        write_part(part, &dir.join(file), canonical, guard)?;
+    }
+    write_part(&index, &dir.join(INDEX_FILE), canonical, guard)?;
+    Ok(index)
+}
+
+fn write_part<T: Serialize>(
+    value: &T,
+    path: &Path,
+    canonical: bool,
+    guard: &SymlinkGuard,
+) -> Result<()> {
+    if canonical {
+        write_manifest_canonical(value, path, ManifestFormat::Json, guard)
+    } else {
+        write_manifest(value, path, ManifestFormat::Json, guard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures::{document, entry};
+
+    fn sample() -> ManifestDocument {
+        let mut bash = entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1"));
+        bash.download_size_selected = Some(1_000);
+        let mut paru = entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1"));
+        paru.download_size_selected = Some(300);
+        paru.download_size_estimated = true;
+        document(vec![
+            ("bash", bash),
+            (
+                "glibc",
+                entry("2.40-1", PackageSource::Pacman, Some("2.40-1")),
+            ),
+            ("paru", paru),
+            ("custom", entry("0.1-1", PackageSource::Local, None)),
+        ])
+    }
+
+    #[test]
+    fn each_part_holds_only_its_source() {
+        let doc = sample();
+        let (parts, index) = split(&doc);
+        let files: Vec<&str> = parts.iter().map(|(file, _)| file.as_str()).collect();
+        assert_eq!(
+            files,
+            ["pacman.json", "aur.json", "local.json", "unknown.json"]
+        );
+
+        for ((_, part), source) in parts.iter().zip(PackageSource::ALL) {
+            assert!(part.packages.values().all(|e| e.source == source));
+            assert_eq!(part.metadata.total_packages, part.packages.len());
+        }
+        let pacman = &parts[0].1;
+        assert_eq!(
+            pacman.packages.keys().collect::<Vec<_>>(),
+            ["bash", "glibc"]
+        );
+        assert_eq!(pacman.metadata.pacman_packages, 2);
+        assert_eq!(pacman.metadata.aur_packages, 0);
+        assert_eq!(pacman.metadata.updates_available, 1);
+        assert_eq!(pacman.metadata.download_size_exact, 1_000);
+        assert_eq!(parts[1].1.metadata.download_size_estimated, 300);
+        assert!(parts[3].1.packages.is_empty());
+        assert!(parts[3].1.packages_by_source.is_empty());
+
+        assert_eq!(index.total_packages, doc.metadata.total_packages);
+        assert_eq!(index.updates_available, doc.metadata.updates_available);
+        assert_eq!(
+            index
+                .parts
+                .iter()
+                .map(|p| p.total_packages)
+                .collect::<Vec<_>>(),
+            [2, 1, 1, 0]
+        );
+    }
+
+    #[test]
+    fn ignored_pkgrel_updates_stay_ignored_per_part() {
+        let mut doc = document(vec![
+            ("zlib", entry("1.3-1", PackageSource::Pacman, Some("1.3-2"))),
+            (
+                "curl",
+                entry("8.9-1", PackageSource::Pacman, Some("8.10-1")),
+            ),
+        ]);
+        doc.ignore_pkgrel_only();
+        let (parts, index) = split(&doc);
+        assert_eq!(index.updates_available, 1);
+        assert_eq!(index.updates_available, doc.metadata.updates_available);
+        let (_, pacman) = &parts[0];
+        assert!(pacman.metadata.pkgrel_only_ignored);
+        assert_eq!(pacman.metadata.pkgrel_only_updates, 0);
+    }
+
+    #[test]
+    fn write_split_produces_parseable_files() {
+        let dir = std::env::temp_dir().join(format!("synsyu-split-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        write_split(&sample(), &dir, false, &SymlinkGuard::default()).unwrap();
+        let index: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(dir.join(INDEX_FILE)).unwrap()).unwrap();
+        assert_eq!(index["total_packages"], 4);
+        let aur: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(dir.join("aur.json")).unwrap()).unwrap();
+        assert_eq!(aur["metadata"]["aur_packages"], 1);
+        assert!(aur["packages"]["paru"].is_object());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,229 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::stability
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Fold release age, AUR votes and popularity, the AUR
+    out-of-date flag and maintainer presence into one verdict
+    per pending update (`stable` plus `stability_reasons`).
+
+  Security / Safety Notes:
+    Pure evaluation over resolved manifest entries; no I/O
+    performed in this module.
+
+  Dependencies:
+    chrono for candidate ages.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core after resolution with the
+    `[stability]` thresholds; `--only-stable` then withdraws
+    the updates that fail.
+
+  Revision History:
+    2026-10-18 COD  Introduced the composite stability policy.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Every failing factor is reported, not just the first
+    - Unknown release dates fail an active age window
+============================================================*/
+
+use chrono::{DateTime, Utc};
+
+use crate::config::StabilityConfig;
+use crate::manifest::{ManifestDocument, ManifestEntry, PackageSource};
+
+/// Set `stable` and `stability_reasons` on every entry; returns how many pending updates fail.
+///
+/// Failing updates also get an `unstable: ...` note but stay pending.
+pub fn assess_stability(
+    document: &mut ManifestDocument,
+    policy: &StabilityConfig,
+    now: DateTime<Utc>,
+) -> usize {
+    let mut unstable = 0;
+    for entry in document.packages.values_mut() {
+        entry.stability_reasons = if entry.update_available {
+            stability_reasons(entry, policy, now)
+        } else {
+            Vec::new()
+        };
+        entry.stable = entry.stability_reasons.is_empty();
+        if !entry.stable {
+            entry
+                .notes
+                .push(format!("unstable: {}", entry.stability_reasons.join("; ")));
+            unstable += 1;
+        }
+    }
+    unstable
+}
+
+/// Every factor on which `entry`'s candidate falls short of `policy`.
+fn stability_reasons(
+    entry: &ManifestEntry,
+    policy: &StabilityConfig,
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let mut reasons = Vec::new();
+    if policy.min_age_days > 0 {
+        match entry.candidate_released_at {
+            Some(released) => {
+                let age = (now - released).num_days().max(0);
+                if age.unsigned_abs() < policy.min_age_days {
+                    reasons.push(format!(
+                        "released {age} day(s) ago, inside the {}-day window",
+                        policy.min_age_days
+                    ));
+                }
+            }
+            None => reasons.push("release date unknown".to_string()),
+        }
+    }
+    if entry.candidate_source != Some(PackageSource::Aur) {
+        return reasons;
+    }
+    let votes = entry.aur_votes.unwrap_or(0);
+    if votes < policy.min_aur_votes {
+        reasons.push(format!("AUR votes {votes} below {}", policy.min_aur_votes));
+    }
+    let popularity = entry.aur_popularity.unwrap_or(0.0);
+    if popularity < policy.min_aur_popularity {
+        reasons.push(format!(
+            "AUR popularity {popularity:.2} below {}",
+            policy.min_aur_popularity
+        ));
+    }
+    if let Some(flagged) = entry
+        .flagged_out_of_date
+        .filter(|_| policy.reject_out_of_date)
+    {
+        reasons.push(format!(
+            "flagged out of date since {}",
+            flagged.format("%Y-%m-%d")
+        ));
+    }
+    if policy.require_maintainer && entry.aur_orphaned {
+        reasons.push("AUR package has no maintainer".to_string());
+    }
+    reasons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures::{document, entry};
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-10-18T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn days_ago(days: i64) -> Option<DateTime<Utc>> {
+        Some(now() - chrono::Duration::days(days))
+    }
+
+    /// A well-established AUR update that passes every check of `strict()`.
+    fn healthy_aur() -> ManifestEntry {
+        let mut healthy = entry("2.0.3-1", PackageSource::Aur, Some("2.0.4-1"));
+        healthy.candidate_released_at = days_ago(30);
+        healthy.aur_votes = Some(1_200);
+        healthy.aur_popularity = Some(28.4);
+        healthy.aur_maintainer = Some("Morganamilo".into());
+        healthy
+    }
+
+    fn strict() -> StabilityConfig {
+        StabilityConfig {
+            min_age_days: 7,
+            min_aur_votes: 10,
+            min_aur_popularity: 0.5,
+            ..StabilityConfig::default()
+        }
+    }
+
+    fn verdict(candidate: ManifestEntry, policy: &StabilityConfig) -> (bool, Vec<String>) {
+        let mut doc = document(vec![("pkg", candidate)]);
+        assess_stability(&mut doc, policy, now());
+        let entry = &doc.packages["pkg"];
+        (entry.stable, entry.stability_reasons.clone())
+    }
+
+    #[test]
+    fn each_factor_alone_makes_an_update_unstable() {
+        assert_eq!(verdict(healthy_aur(), &strict()), (true, Vec::new()));
+
+        let mut young = healthy_aur();
+        young.candidate_released_at = days_ago(2);
+        let mut undated = healthy_aur();
+        undated.candidate_released_at = None;
+        let mut unvoted = healthy_aur();
+        unvoted.aur_votes = Some(3);
+        let mut unpopular = healthy_aur();
+        unpopular.aur_popularity = Some(0.12);
+        let mut flagged = healthy_aur();
+        flagged.flagged_out_of_date = days_ago(17);
+        let mut orphaned = healthy_aur();
+        orphaned.aur_maintainer = None;
+        orphaned.aur_orphaned = true;
+
+        for (candidate, reason) in [
+            (young, "released 2 day(s) ago, inside the 7-day window"),
+            (undated, "release date unknown"),
+            (unvoted, "AUR votes 3 below 10"),
+            (unpopular, "AUR popularity 0.12 below 0.5"),
+            (flagged, "flagged out of date since 2026-10-01"),
+            (orphaned, "AUR package has no maintainer"),
+        ] {
+            assert_eq!(
+                verdict(candidate, &strict()),
+                (false, vec![reason.to_string()])
+            );
+        }
+    }
+
+    #[test]
+    fn failures_accumulate_are_noted_and_respect_the_policy() {
+        let mut risky = healthy_aur();
+        risky.candidate_released_at = days_ago(1);
+        risky.flagged_out_of_date = days_ago(1);
+        risky.aur_orphaned = true;
+        let mut doc = document(vec![
+            ("risky", risky.clone()),
+            ("healthy", healthy_aur()),
+            ("current", entry("1.0-1", PackageSource::Aur, Some("1.0-1"))),
+        ]);
+        assert_eq!(assess_stability(&mut doc, &strict(), now()), 1);
+        let entry = &doc.packages["risky"];
+        assert_eq!(entry.stability_reasons.len(), 3);
+        assert!(entry.update_available, "noted, not suppressed");
+        assert!(entry
+            .notes
+            .iter()
+            .any(|note| note.starts_with("unstable: ")));
+        assert!(doc.packages["current"].stable);
+
+        doc.skip_unstable();
+        assert!(!doc.packages["risky"].update_available);
+        assert!(doc.packages["healthy"].update_available);
+        assert_eq!(doc.metadata.updates_available, 1);
+
+        // Disabled checks do not count against the update.
+        let lenient = StabilityConfig {
+            reject_out_of_date: false,
+            require_maintainer: false,
+            ..StabilityConfig::default()
+        };
+        assert_eq!(verdict(risky, &lenient), (true, Vec::new()));
+    }
+
+    #[test]
+    fn repo_updates_are_judged_on_age_alone() {
+        let mut repo = entry("5.2.32-1", PackageSource::Pacman, Some("5.2.37-1"));
+        repo.candidate_released_at = days_ago(10);
+        assert_eq!(verdict(repo.clone(), &strict()), (true, Vec::new()));
+        repo.candidate_released_at = days_ago(3);
+        assert!(!verdict(repo, &strict()).0);
+    }
+}
@@ -0,0 +1,164 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::sync_db
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Read package file names from pacman's sync databases, so
+    download URLs name the file the repository actually ships.
+
+  Security / Safety Notes:
+    Read-only; only the `desc` entries of wanted packages are
+    read into memory.
+
+  Dependencies:
+    tar and flate2 for the gzip-compressed database archives.
+
+  Operational Scope:
+    Used by `mirror::annotate_download_urls` for pending repo
+    updates.
+
+  Revision History:
+    2026-10-18 COD  Introduced %FILENAME% lookup in sync databases.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - File names come from the database, never from a guess
+    - Unsupported database formats are errors, not fallbacks
+============================================================*/
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+
+/// `%FILENAME%` of the `wanted` packages (name to version) in the sync
+/// database at `path`, keyed by name.
+///
+/// A package whose database entry has another version is left out. The
+/// database may be a plain or gzip-compressed tar; other compressions fail
+/// with `ErrorKind::Unsupported`.
+pub fn read_filenames(
+    path: &Path,
+    wanted: &HashMap<&str, &str>,
+) -> io::Result<HashMap<String, String>> {
+    let mut file = File::open(path)?;
+    let mut magic = [0u8; 4];
+    let read = file.read(&mut magic)?;
+    file.seek(SeekFrom::Start(0))?;
+    let reader: Box<dyn Read> = match &magic[..read] {
+        [0x1f, 0x8b, ..] => Box::new(GzDecoder::new(BufReader::new(file))),
+        [0x28, 0xb5, 0x2f, 0xfd] | [0xfd, b'7', b'z', b'X'] | [b'B', b'Z', b'h', _] => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "only plain and gzip-compressed sync databases are read",
+            ))
+        }
+        _ => Box::new(BufReader::new(file)),
+    };
+
+    // Entries are `<name>-<version>/desc`, so the wanted ones are known up front.
+    let descs: HashMap<String, &str> = wanted
+        .iter()
+        .map(|(name, version)| (format!("{name}-{version}/desc"), *name))
+        .collect();
+    let mut found = HashMap::new();
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let Some(name) = descs.get(entry.path()?.to_string_lossy().as_ref()).copied() else {
+            continue;
+        };
+        let mut desc = String::new();
+        entry.read_to_string(&mut desc)?;
+        if let Some(filename) = desc_field(&desc, "FILENAME") {
+            found.insert(name.to_string(), filename.to_string());
+        }
+        if found.len() == descs.len() {
+            break;
+        }
+    }
+    Ok(found)
+}
+
+/// First value line under `%KEY%` in a `desc` file.
+fn desc_field<'a>(desc: &'a str, key: &str) -> Option<&'a str> {
+    let header = format!("%{key}%");
+    let mut lines = desc.lines();
+    lines.find(|line| line.trim() == header)?;
+    lines
+        .next()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    /// Sync database archive holding a `desc` per `(name, version, filename)`.
+    pub fn database(packages: &[(&str, &str, &str)], gzip: bool) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, version, filename) in packages {
+            let desc =
+                format!("%FILENAME%\n{filename}\n\n%NAME%\n{name}\n\n%VERSION%\n{version}\n\n");
+            let mut header = tar::Header::new_gnu();
+            header.set_size(desc.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(
+                    &mut header,
+                    format!("{name}-{version}/desc"),
+                    desc.as_bytes(),
+                )
+                .unwrap();
+        }
+        let tar = builder.into_inner().unwrap();
+        if !gzip {
+            return tar;
+        }
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        std::io::Write::write_all(&mut encoder, &tar).unwrap();
+        encoder.finish().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filenames_come_from_matching_desc_entries() {
+        let dir = std::env::temp_dir().join(format!("synsyu-syncdb-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let packages = [
+            ("bash", "5.2.037-1", "bash-5.2.037-1-x86_64.pkg.tar.zst"),
+            ("gnupg", "1:2.4.1-2", "gnupg-1:2.4.1-2-x86_64.pkg.tar.xz"),
+            ("zsh", "5.9-5", "zsh-5.9-5-x86_64.pkg.tar.zst"),
+        ];
+        let wanted = HashMap::from([
+            ("bash", "5.2.037-1"),
+            ("gnupg", "1:2.4.1-2"),
+            ("zsh", "5.9-6"),
+        ]);
+        for gzip in [true, false] {
+            let path = dir.join("core.db");
+            std::fs::write(&path, fixtures::database(&packages, gzip)).unwrap();
+            let found = read_filenames(&path, &wanted).unwrap();
+            assert_eq!(found["bash"], "bash-5.2.037-1-x86_64.pkg.tar.zst");
+            assert_eq!(found["gnupg"], "gnupg-1:2.4.1-2-x86_64.pkg.tar.xz");
+            // The database carries another zsh than the candidate.
+            assert!(!found.contains_key("zsh"));
+        }
+
+        let zstd = dir.join("extra.db");
+        std::fs::write(&zstd, [0x28, 0xb5, 0x2f, 0xfd, 0, 0]).unwrap();
+        let err = read_filenames(&zstd, &wanted).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
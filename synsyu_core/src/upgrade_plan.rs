@@ -0,0 +1,355 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::upgrade_plan
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Turn the pending updates of a manifest into an ordered
+    upgrade plan (`--plan`) for change-management tickets:
+    dependencies first, with per-step and total sizes.
+
+  Security / Safety Notes:
+    Pure transformation over manifest and `pacman -Qi` data;
+    the caller writes the result atomically.
+
+  Dependencies:
+    cascade for the reverse-dependency graph.
+
+  Operational Scope:
+    Invoked by Syn-Syu-Core after the manifest is built. Not
+    to be confused with the `plan` subcommand, which queries
+    the live update tools.
+
+  Revision History:
+    2026-10-18 COD  Introduced the ordered upgrade plan.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Deterministic order: dependencies first, then by name
+    - Unknown sizes are counted, never guessed
+============================================================*/
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::cascade::{affected_dependents, reverse_dependencies};
+use crate::manifest::{ManifestDocument, PackageSource};
+use crate::pacman::InstalledPackage;
+use crate::report::DisplaySize;
+use crate::space::format_bytes;
+
+/// Bumped whenever a plan field changes meaning or disappears.
+pub const UPGRADE_PLAN_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+pub struct UpgradePlan {
+    pub schema_version: u32,
+    pub generated_at: String,
+    /// Updates in application order.
+    pub steps: Vec<PlanStep>,
+    pub totals: PlanTotals,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlanStep {
+    /// 1-based position in the plan.
+    pub step: usize,
+    pub package: String,
+    pub from: String,
+    pub to: String,
+    pub source: PackageSource,
+    pub repository: String,
+    /// Planned updates this one depends on, directly or through other installed packages.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    pub download_size: Option<DisplaySize>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub download_size_estimated: bool,
+    /// Installed size after minus before; absent when either side is unknown.
+    pub footprint_change: Option<SignedSize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlanTotals {
+    pub steps: usize,
+    /// Sum of every known download size, estimated ones included.
+    pub download_size: DisplaySize,
+    /// Part of `download_size` that was estimated.
+    pub download_size_estimated: DisplaySize,
+    pub unknown_download_sizes: usize,
+    pub footprint_change: SignedSize,
+    pub unknown_footprint_changes: usize,
+}
+
+/// Signed byte count paired with its IEC rendering (`+1.5 MiB`, `-200 KiB`).
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct SignedSize {
+    pub bytes: i64,
+    pub human: String,
+}
+
+impl SignedSize {
+    fn new(bytes: i64) -> Self {
+        let sign = match bytes.signum() {
+            1 => "+",
+            -1 => "-",
+            _ => "",
+        };
+        Self {
+            bytes,
+            human: format!("{sign}{}", format_bytes(bytes.unsigned_abs())),
+        }
+    }
+}
+
+/// Order the pending updates so every package follows the planned updates it depends on.
+///
+/// Dependencies are followed through installed packages that are not updated
+/// themselves. Ties, and the members of a dependency cycle, go by name.
+pub fn upgrade_plan(document: &ManifestDocument, installed: &[InstalledPackage]) -> UpgradePlan {
+    let pending: BTreeSet<&str> = document
+        .packages
+        .iter()
+        .filter(|(_, entry)| entry.update_available)
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    let graph = reverse_dependencies(installed);
+    let mut depends_on: BTreeMap<&str, BTreeSet<&str>> = pending
+        .iter()
+        .map(|&name| (name, BTreeSet::new()))
+        .collect();
+    for &name in &pending {
+        for dependent in affected_dependents(&graph, name) {
+            if let Some(deps) = depends_on.get_mut(dependent.as_str()) {
+                deps.insert(name);
+            }
+        }
+    }
+
+    let order = topological_order(&depends_on);
+    let mut totals = PlanTotals {
+        steps: order.len(),
+        download_size: DisplaySize::new(0),
+        download_size_estimated: DisplaySize::new(0),
+        unknown_download_sizes: 0,
+        footprint_change: SignedSize::new(0),
+        unknown_footprint_changes: 0,
+    };
+    let (mut download, mut estimated, mut footprint) = (0_u64, 0_u64, 0_i64);
+    let steps = order
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let entry = &document.packages[name];
+            match entry.download_size_selected {
+                Some(bytes) => {
+                    download += bytes;
+                    if entry.download_size_estimated {
+                        estimated += bytes;
+                    }
+                }
+                None => totals.unknown_download_sizes += 1,
+            }
+            let change = entry
+                .installed_size
+                .zip(entry.installed_size_selected)
+                .map(|(before, after)| after as i64 - before as i64);
+            match change {
+                Some(delta) => footprint += delta,
+                None => totals.unknown_footprint_changes += 1,
+            }
+            PlanStep {
+                step: index + 1,
+                package: name.to_string(),
+                from: entry.installed_version.clone(),
+                to: entry
+                    .newer_version
+                    .clone()
+                    .or_else(|| entry.available_version.clone())
+                    .unwrap_or_default(),
+                source: entry.candidate_source.unwrap_or(entry.source),
+                repository: entry.update_repo(),
+                depends_on: depends_on[name].iter().map(|dep| dep.to_string()).collect(),
+                download_size: entry.download_size_selected.map(DisplaySize::new),
+                download_size_estimated: entry.download_size_estimated,
+                footprint_change: change.map(SignedSize::new),
+            }
+        })
+        .collect();
+    totals.download_size = DisplaySize::new(download);
+    totals.download_size_estimated = DisplaySize::new(estimated);
+    totals.footprint_change = SignedSize::new(footprint);
+
+    UpgradePlan {
+        schema_version: UPGRADE_PLAN_SCHEMA_VERSION,
+        generated_at: document.metadata.generated_at.clone(),
+        steps,
+        totals,
+    }
+}
+
+/// Kahn's algorithm, always releasing the alphabetically first ready package.
+///
+/// A cycle leaves no package ready; the first waiting package by name is then released.
+fn topological_order<'a>(depends_on: &BTreeMap<&'a str, BTreeSet<&'a str>>) -> Vec<&'a str> {
+    let mut waiting: BTreeMap<&str, BTreeSet<&str>> = depends_on.clone();
+    let mut order = Vec::with_capacity(waiting.len());
+    while !waiting.is_empty() {
+        let next = waiting
+            .iter()
+            .find(|(_, deps)| deps.is_empty())
+            .map(|(&name, _)| name)
+            .unwrap_or_else(|| *waiting.keys().next().expect("waiting is not empty"));
+        waiting.remove(next);
+        for deps in waiting.values_mut() {
+            deps.remove(next);
+        }
+        order.push(next);
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::fixtures::{document, entry};
+
+    fn package(name: &str, required_by: &[&str]) -> InstalledPackage {
+        InstalledPackage {
+            name: name.into(),
+            version: "1.0-1".into(),
+            repository: Some("core".into()),
+            installed_size: None,
+            install_date: None,
+            validated_by: None,
+            package_hash: None,
+            required_by: required_by.iter().map(|s| s.to_string()).collect(),
+            provides: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn sized(
+        installed: &str,
+        source: PackageSource,
+        candidate: &str,
+        download: Option<u64>,
+        sizes: Option<(u64, u64)>,
+    ) -> crate::manifest::ManifestEntry {
+        let mut entry = entry(installed, source, Some(candidate));
+        entry.download_size_selected = download;
+        if let Some((before, after)) = sizes {
+            entry.installed_size = Some(before);
+            entry.installed_size_selected = Some(after);
+        }
+        entry
+    }
+
+    #[test]
+    fn steps_follow_dependencies_and_totals_add_up() {
+        // zlib <- openssl (not updated) <- curl <- pacman; glibc <- zlib.
+        let installed = vec![
+            package("glibc", &["zlib", "bash"]),
+            package("zlib", &["openssl"]),
+            package("openssl", &["curl"]),
+            package("curl", &["pacman"]),
+            package("pacman", &[]),
+            package("bash", &[]),
+            package("paru", &[]),
+        ];
+        let mut paru = sized(
+            "2.0.3-1",
+            PackageSource::Aur,
+            "2.0.4-1",
+            Some(300),
+            Some((9_000, 8_000)),
+        );
+        paru.download_size_estimated = true;
+        let doc = document(vec![
+            (
+                "pacman",
+                sized(
+                    "7.0.0-1",
+                    PackageSource::Pacman,
+                    "7.0.0-2",
+                    Some(900),
+                    Some((4_000, 4_500)),
+                ),
+            ),
+            (
+                "curl",
+                sized("8.9-1", PackageSource::Pacman, "8.10-1", Some(1_000), None),
+            ),
+            (
+                "zlib",
+                sized(
+                    "1.3-1",
+                    PackageSource::Pacman,
+                    "1.3.1-1",
+                    None,
+                    Some((100, 150)),
+                ),
+            ),
+            (
+                "glibc",
+                sized(
+                    "2.40-1",
+                    PackageSource::Pacman,
+                    "2.40-2",
+                    Some(10_000),
+                    Some((50_000, 51_000)),
+                ),
+            ),
+            ("paru", paru),
+            (
+                "openssl",
+                entry("3.3.2-1", PackageSource::Pacman, Some("3.3.2-1")),
+            ),
+        ]);
+
+        let plan = upgrade_plan(&doc, &installed);
+        let order: Vec<&str> = plan.steps.iter().map(|s| s.package.as_str()).collect();
+        assert_eq!(order, ["glibc", "paru", "zlib", "curl", "pacman"]);
+        let position = |name: &str| order.iter().position(|&p| p == name).unwrap();
+        for step in &plan.steps {
+            for dep in &step.depends_on {
+                assert!(position(dep) < position(&step.package), "{step:?}");
+            }
+        }
+        let curl = &plan.steps[3];
+        assert_eq!(curl.step, 4);
+        assert_eq!(curl.depends_on, ["glibc", "zlib"]);
+        assert_eq!((curl.from.as_str(), curl.to.as_str()), ("8.9-1", "8.10-1"));
+        assert_eq!(curl.footprint_change, None);
+
+        let totals = &plan.totals;
+        assert_eq!(totals.steps, 5);
+        assert_eq!(totals.download_size.bytes, 12_200);
+        assert_eq!(totals.download_size_estimated.bytes, 300);
+        assert_eq!(totals.unknown_download_sizes, 1);
+        // +500 (pacman) +50 (zlib) +1000 (glibc) -1000 (paru)
+        assert_eq!(totals.footprint_change, SignedSize::new(550));
+        assert_eq!(totals.footprint_change.human, "+550 B");
+        assert_eq!(totals.unknown_footprint_changes, 1);
+    }
+
+    #[test]
+    fn dependency_cycles_still_yield_every_update() {
+        let installed = vec![
+            package("a", &["b"]),
+            package("b", &["a", "c"]),
+            package("c", &[]),
+        ];
+        let doc = document(vec![
+            ("c", entry("1-1", PackageSource::Pacman, Some("2-1"))),
+            ("b", entry("1-1", PackageSource::Pacman, Some("2-1"))),
+            ("a", entry("1-1", PackageSource::Pacman, Some("2-1"))),
+        ]);
+        let plan = upgrade_plan(&doc, &installed);
+        let order: Vec<&str> = plan.steps.iter().map(|s| s.package.as_str()).collect();
+        assert_eq!(order, ["a", "b", "c"]);
+        assert_eq!(SignedSize::new(-2048).human, "-2 KiB");
+        assert_eq!(SignedSize::new(0).human, "0 B");
+    }
+}
@@ -0,0 +1,243 @@
+/*============================================================
+  Synavera Project: Syn-Syu
+  Module: synsyu_core::vercmp
+  Etiquette: Synavera Script Etiquette — Rust Profile v1.1.1
+  ------------------------------------------------------------
+  Purpose:
+    Order package versions exactly as pacman's `vercmp` does
+    (libalpm `alpm_pkg_vercmp`), without spawning a process
+    per comparison.
+
+  Security / Safety Notes:
+    Pure byte-level comparison; no allocation beyond the
+    inputs and no external commands.
+
+  Dependencies:
+    std only.
+
+  Operational Scope:
+    Default comparator behind pacman::compare_versions; the
+    `vercmp` binary remains available via --vercmp-external.
+
+  Revision History:
+    2026-10-18 COD  Ported libalpm version comparison to Rust.
+  ------------------------------------------------------------
+  SSE Principles Observed:
+    - Faithful port: quirks of the C code are kept, not fixed
+    - Table-tested against pacman's own vercmp cases
+============================================================*/
+
+use std::cmp::Ordering;
+
+/// Compare `[epoch:]version[-release]` strings like `vercmp local remote`.
+///
+/// A missing epoch is `0`; the release is only compared when both sides carry one.
+pub fn vercmp_native(local: &str, remote: &str) -> Ordering {
+    if local == remote {
+        return Ordering::Equal;
+    }
+    let (epoch1, version1, release1) = parse_evr(local);
+    let (epoch2, version2, release2) = parse_evr(remote);
+    rpmvercmp(epoch1, epoch2)
+        .then_with(|| rpmvercmp(version1, version2))
+        .then_with(|| match (release1, release2) {
+            (Some(release1), Some(release2)) => rpmvercmp(release1, release2),
+            _ => Ordering::Equal,
+        })
+}
+
+/// Split epoch, version and release the way libalpm's `parseEVR` does.
+fn parse_evr(evr: &str) -> (&str, &str, Option<&str>) {
+    let digits = evr.bytes().take_while(u8::is_ascii_digit).count();
+    let (epoch, rest) = match evr[digits..].strip_prefix(':') {
+        Some(rest) if digits == 0 => ("0", rest),
+        Some(rest) => (&evr[..digits], rest),
+        None => ("0", evr),
+    };
+    match rest.rfind('-') {
+        Some(dash) => (epoch, &rest[..dash], Some(&rest[dash + 1..])),
+        None => (epoch, rest, None),
+    }
+}
+
+/// Segment-wise comparison of one epoch, version or release (libalpm `rpmvercmp`).
+///
+/// Runs of ASCII letters or digits are compared pairwise; anything else separates
+/// them. Numbers compare by value and beat letters; a longer separator run wins;
+/// a trailing letter segment loses to nothing, any other leftover wins.
+fn rpmvercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    // `one`/`two` start the current segment, `end1`/`end2` end the previous one.
+    let (mut one, mut two) = (0, 0);
+    let (mut end1, mut end2) = (0, 0);
+    while one < a.len() && two < b.len() {
+        while one < a.len() && !a[one].is_ascii_alphanumeric() {
+            one += 1;
+        }
+        while two < b.len() && !b[two].is_ascii_alphanumeric() {
+            two += 1;
+        }
+        if one == a.len() || two == b.len() {
+            break;
+        }
+        if one - end1 != two - end2 {
+            return (one - end1).cmp(&(two - end2));
+        }
+
+        let numeric = a[one].is_ascii_digit();
+        let in_segment = |byte: &u8| {
+            if numeric {
+                byte.is_ascii_digit()
+            } else {
+                byte.is_ascii_alphabetic()
+            }
+        };
+        end1 = one + a[one..].iter().take_while(|byte| in_segment(byte)).count();
+        end2 = two + b[two..].iter().take_while(|byte| in_segment(byte)).count();
+        // Segments of different kinds: numbers are newer than letters.
+        if two == end2 {
+            return if numeric {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+
+        let (mut seg1, mut seg2) = (&a[one..end1], &b[two..end2]);
+        if numeric {
+            seg1 = trim_leading_zeros(seg1);
+            seg2 = trim_leading_zeros(seg2);
+            match seg1.len().cmp(&seg2.len()) {
+                Ordering::Equal => {}
+                longer_wins => return longer_wins,
+            }
+        }
+        match seg1.cmp(seg2) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+        one = end1;
+        two = end2;
+    }
+
+    let (rest1, rest2) = (&a[one..], &b[two..]);
+    if rest1.is_empty() && rest2.is_empty() {
+        return Ordering::Equal;
+    }
+    let alpha = |rest: &[u8]| rest.first().is_some_and(u8::is_ascii_alphabetic);
+    if (rest1.is_empty() && !alpha(rest2)) || alpha(rest1) {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
+}
+
+fn trim_leading_zeros(digits: &[u8]) -> &[u8] {
+    let zeros = digits.iter().take_while(|&&byte| byte == b'0').count();
+    &digits[zeros..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cases from pacman's `test/util/vercmptest.sh` (expected `vercmp a b` output).
+    const PACMAN_CASES: &[(&str, &str, i8)] = &[
+        // all similar length, no pkgrel
+        ("1.5.0", "1.5.0", 0),
+        ("1.5.1", "1.5.0", 1),
+        // mixed length
+        ("1.5.1", "1.5", 1),
+        // with pkgrel, simple
+        ("1.5.0-1", "1.5.0-1", 0),
+        ("1.5.0-1", "1.5.0-2", -1),
+        ("1.5.0-1", "1.5.1-1", -1),
+        ("1.5.0-2", "1.5.1-1", -1),
+        // with pkgrel, mixed lengths
+        ("1.5-1", "1.5.1-1", -1),
+        ("1.5-2", "1.5.1-1", -1),
+        ("1.5-2", "1.5.1-2", -1),
+        // mixed pkgrel inclusion
+        ("1.5", "1.5-1", 0),
+        ("1.5-1", "1.5", 0),
+        ("1.1-1", "1.1", 0),
+        ("1.0-1", "1.1", -1),
+        ("1.1-1", "1.0", 1),
+        // alphanumeric versions
+        ("1.5b-1", "1.5-1", -1),
+        ("1.5b", "1.5", -1),
+        ("1.5b-1", "1.5", -1),
+        ("1.5b", "1.5.1", -1),
+        // from the manpage
+        ("1.0a", "1.0alpha", -1),
+        ("1.0alpha", "1.0b", -1),
+        ("1.0b", "1.0beta", -1),
+        ("1.0beta", "1.0rc", -1),
+        ("1.0rc", "1.0", -1),
+        // going crazy? alpha-dotted versions
+        ("1.5.a", "1.5", 1),
+        ("1.5.b", "1.5.a", 1),
+        ("1.5.1", "1.5.b", 1),
+        // alpha dots and dashes
+        ("1.5.b-1", "1.5.b", 0),
+        ("1.5-1", "1.5.b", -1),
+        // same/similar content, differing separators
+        ("2.0", "2_0", 0),
+        ("2.0_a", "2_0.a", 0),
+        ("2.0a", "2.0.a", -1),
+        ("2___a", "2_a", 1),
+        // epoch included version comparisons
+        ("0:1.0", "0:1.0", 0),
+        ("0:1.0", "0:1.1", -1),
+        ("1:1.0", "0:1.0", 1),
+        ("1:1.0", "0:1.1", 1),
+        ("1:1.0", "2:1.1", -1),
+        // epoch + sometimes present pkgrel
+        ("1:1.0", "0:1.0-1", 1),
+        ("1:1.0-1", "0:1.1-1", 1),
+        // epoch included on one version
+        ("0:1.0", "1.0", 0),
+        ("0:1.1", "1.0", 1),
+        ("0:1.1", "1.1", 0),
+        ("1.0", "0:1.0", 0),
+        ("1.1", "0:1.0", 1),
+        ("1.1", "0:1.1", 0),
+        ("1:1.0", "1.0", 1),
+        ("1:1.1", "1.1", 1),
+        ("1.0", "1:1.0", -1),
+        ("1.1", "1:1.1", -1),
+        ("1.1", "1:1.0", -1),
+        ("1.0", "1:1.1", -1),
+    ];
+
+    #[test]
+    fn matches_pacman_vercmp_cases_both_ways() {
+        for &(a, b, expected) in PACMAN_CASES {
+            let expected = expected.cmp(&0);
+            assert_eq!(vercmp_native(a, b), expected, "vercmp {a} {b}");
+            assert_eq!(vercmp_native(b, a), expected.reverse(), "vercmp {b} {a}");
+        }
+    }
+
+    #[test]
+    fn separators_tildes_and_leading_zeros() {
+        // Trailing separators: a leftover separator outranks nothing.
+        assert_eq!(vercmp_native("1.0.", "1.0"), Ordering::Greater);
+        assert_eq!(vercmp_native("1.0.", "1.0_"), Ordering::Equal);
+        // Unlike RPM, `~` is an ordinary separator, not a pre-release marker.
+        assert_eq!(vercmp_native("1.0~rc1", "1.0"), Ordering::Greater);
+        assert_eq!(vercmp_native("1.0~rc1", "1.0.rc1"), Ordering::Equal);
+        assert_eq!(vercmp_native("1.0~rc1", "1.0rc1"), Ordering::Greater);
+        // Numbers compare by value; leading zeros are ignored.
+        assert_eq!(vercmp_native("1.010", "1.9"), Ordering::Greater);
+        assert_eq!(vercmp_native("1.001", "1.1"), Ordering::Equal);
+        // An empty epoch is zero; a larger epoch beats any version.
+        assert_eq!(vercmp_native(":2.0", "2.0"), Ordering::Equal);
+        assert_eq!(vercmp_native("2:0.1-1", "1:99.0-1"), Ordering::Greater);
+        // Only the last dash starts the release.
+        assert_eq!(parse_evr("1:2.0-beta-3"), ("1", "2.0-beta", Some("3")));
+    }
+}